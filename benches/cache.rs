@@ -0,0 +1,127 @@
+// compares the sharded options cache with a cache behind a single lock when many threads miss the
+// cache at once, such as after it is cleared on reload. creating options waits as if it read a
+// file, so the benchmark shows contention rather than cpu time, even on a single core. it uses only
+// the standard library so that it runs on stable; run it with:
+//
+// cargo bench --bench cache --features async
+
+use options::{OptionsCache, OptionsMonitorCache, Ref};
+use std::collections::HashMap;
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const THREADS: usize = 8;
+const NAMES: usize = 64;
+const ROUNDS: usize = 20;
+const CREATION: Duration = Duration::from_micros(250);
+
+#[derive(Default)]
+struct TenantOptions {
+    name: String,
+}
+
+// the cache before it was sharded, which holds its only lock while options are created
+#[derive(Default)]
+struct SingleLockCache<T> {
+    cache: Mutex<HashMap<String, Ref<T>>>,
+}
+
+impl<T: Send + Sync> OptionsMonitorCache<T> for SingleLockCache<T> {
+    fn get_or_add(&self, name: Option<&str>, create_options: &dyn Fn(Option<&str>) -> T) -> Ref<T> {
+        self.cache
+            .lock()
+            .unwrap()
+            .entry(name.unwrap_or_default().to_owned())
+            .or_insert_with(|| Ref::new(create_options(name)))
+            .clone()
+    }
+
+    fn try_add(&self, name: Option<&str>, options: T) -> bool {
+        let mut cache = self.cache.lock().unwrap();
+        let key = name.unwrap_or_default();
+
+        if cache.contains_key(key) {
+            false
+        } else {
+            cache.insert(key.to_owned(), Ref::new(options));
+            true
+        }
+    }
+
+    fn try_remove(&self, name: Option<&str>) -> bool {
+        self.cache
+            .lock()
+            .unwrap()
+            .remove(name.unwrap_or_default())
+            .is_some()
+    }
+
+    fn clear(&self) {
+        self.cache.lock().unwrap().clear()
+    }
+}
+
+fn create(name: Option<&str>) -> TenantOptions {
+    thread::sleep(CREATION);
+
+    TenantOptions {
+        name: name.unwrap_or_default().to_owned(),
+    }
+}
+
+fn measure(name: &str, cache: Arc<dyn OptionsMonitorCache<TenantOptions>>) -> Duration {
+    let names: Arc<Vec<String>> = Arc::new((0..NAMES).map(|i| format!("Tenant{}", i)).collect());
+    let barrier = Arc::new(Barrier::new(THREADS + 1));
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..ROUNDS {
+        cache.clear();
+
+        let workers: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                let (cache, names, barrier) = (cache.clone(), names.clone(), barrier.clone());
+
+                thread::spawn(move || {
+                    barrier.wait();
+
+                    // every thread asks for every name, starting at a different one
+                    for i in 0..NAMES {
+                        let name = &names[(i + thread * NAMES / THREADS) % NAMES];
+                        let options = cache.get_or_add(Some(name), &create);
+                        assert_eq!(&options.name, name);
+                    }
+                })
+            })
+            .collect();
+
+        barrier.wait();
+
+        let started = Instant::now();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        elapsed += started.elapsed();
+    }
+
+    let elapsed = elapsed / ROUNDS as u32;
+
+    println!(
+        "{:<12} {} threads x {} names: {:>10.2?} per round",
+        name, THREADS, NAMES, elapsed
+    );
+
+    elapsed
+}
+
+fn main() {
+    let single = measure("single lock", Arc::new(SingleLockCache::default()));
+    let sharded = measure("sharded", Arc::new(OptionsCache::default()));
+
+    println!(
+        "the sharded cache is {:.1}x faster",
+        single.as_secs_f64() / sharded.as_secs_f64()
+    );
+}
@@ -0,0 +1,105 @@
+// compares registering and resolving configuration closures with configuration functions by time
+// and by the number of allocations. the benchmark uses only the standard library so that it runs
+// on stable; run it with:
+//
+// cargo bench --bench configure
+
+use di::ServiceCollection;
+use options::{ext::*, Options};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+const CONFIGURATIONS: usize = 50;
+const ITERATIONS: usize = 1_000;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[derive(Default)]
+struct PoolOptions {
+    size: usize,
+}
+
+fn grow(options: &mut PoolOptions) {
+    options.size += 1;
+}
+
+fn closures() -> ServiceCollection {
+    let mut services = ServiceCollection::new();
+    let mut builder = services.add_options::<PoolOptions>();
+
+    for _ in 0..CONFIGURATIONS {
+        builder = builder.configure(|o| o.size += 1);
+    }
+
+    drop(builder);
+    services
+}
+
+fn functions() -> ServiceCollection {
+    let mut services = ServiceCollection::new();
+    let mut builder = services.add_options::<PoolOptions>();
+
+    for _ in 0..CONFIGURATIONS {
+        builder = builder.configure_fn(grow);
+    }
+
+    drop(builder);
+    services
+}
+
+fn measure(name: &str, register: fn() -> ServiceCollection) {
+    let mut registration = (Duration::ZERO, 0);
+    let mut resolution = (Duration::ZERO, 0);
+
+    for _ in 0..ITERATIONS {
+        let (started, allocated) = (Instant::now(), allocations());
+        let services = register();
+
+        registration.0 += started.elapsed();
+        registration.1 += allocations() - allocated;
+
+        let provider = services.build_provider().unwrap();
+        let (started, allocated) = (Instant::now(), allocations());
+        let options = provider.get_required::<dyn Options<PoolOptions>>();
+        let size = options.value().size;
+
+        resolution.0 += started.elapsed();
+        resolution.1 += allocations() - allocated;
+        assert_eq!(size, CONFIGURATIONS);
+    }
+
+    println!(
+        "{:<12} register: {:>10.2?} {:>5} allocations  resolve: {:>10.2?} {:>5} allocations",
+        name,
+        registration.0 / ITERATIONS as u32,
+        registration.1 / ITERATIONS,
+        resolution.0 / ITERATIONS as u32,
+        resolution.1 / ITERATIONS
+    );
+}
+
+fn main() {
+    measure("configure", closures);
+    measure("configure_fn", functions);
+}
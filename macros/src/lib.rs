@@ -0,0 +1,424 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, ExprLit, Fields, Ident, Lit,
+    LitStr, Meta, MetaNameValue, Path, Result,
+};
+
+/// Derives the `RegisterOptions` trait for a configuration options struct.
+///
+/// # Remarks
+///
+/// The generated `add_to` function registers the options and any of the configuration hooks
+/// declared with the `options` attribute:
+///
+/// * `configurer = Type` - registers a `ConfigureOptions` created using `Default`
+/// * `post_configurer = Type` - registers a `PostConfigureOptions` created using `Default`
+/// * `validator = Type` - registers a `ValidateOptions` created using `Default`
+/// * `name = "..."` - registers the options with the specified name
+/// * `section = "..."` - binds the options to the configuration section with the specified key,
+///   which requires the **cfg** feature
+/// * `discover` - declares the options for registration by `add_discovered_options`, which
+///   requires the **discovery** feature
+/// * `dump` - includes the options in the `OptionsDump`, which requires the **dump** feature and
+///   a `RedactedSerialize` implementation
+/// * `catalog` - includes the fields, defaults, and validations of the options in the
+///   `options_catalog`, which requires the **dump** feature and a `RedactedSerialize`
+///   implementation
+/// * `describe` - includes the descriptions of the fields of the options in the `options_catalog`,
+///   which requires the **dump** feature and a `DescribeOptions` implementation
+/// * `sensitive` - implements `SensitiveOptions` and marks the options as sensitive so that none of
+///   their values are included in diagnostics
+#[proc_macro_derive(Options, attributes(options))]
+pub fn derive_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives the `RedactedDebug` trait for a configuration options struct.
+///
+/// # Remarks
+///
+/// Fields marked with `#[options(secret)]` are formatted as `***`.
+#[proc_macro_derive(RedactedDebug, attributes(options))]
+pub fn derive_redacted_debug(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_redacted_debug(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives the `RedactedSerialize` trait for a configuration options struct.
+///
+/// # Remarks
+///
+/// Fields marked with `#[options(secret)]` are serialized as `"***"`. All other fields must
+/// implement `serde::Serialize`.
+#[proc_macro_derive(RedactedSerialize, attributes(options))]
+pub fn derive_redacted_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_redacted_serialize(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives the `DescribeOptions` trait for a configuration options struct.
+///
+/// # Remarks
+///
+/// Each field is described by `#[options(doc = "...")]` or, otherwise, its doc comment. An example
+/// value can be specified with `#[options(example = "...")]`.
+#[proc_macro_derive(DescribeOptions, attributes(options))]
+pub fn derive_describe_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_describe_options(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct OptionsAttributes {
+    configurers: Vec<Path>,
+    post_configurers: Vec<Path>,
+    validators: Vec<Path>,
+    name: Option<LitStr>,
+    section: Option<LitStr>,
+    discover: bool,
+    dump: bool,
+    catalog: bool,
+    describe: bool,
+    sensitive: bool,
+}
+
+impl OptionsAttributes {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut attributes = Self::default();
+
+        for attribute in input.attrs.iter().filter(|a| a.path().is_ident("options")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("configurer") {
+                    attributes.configurers.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("post_configurer") {
+                    attributes.post_configurers.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("validator") {
+                    attributes.validators.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("name") {
+                    attributes.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("section") {
+                    attributes.section = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("discover") {
+                    attributes.discover = true;
+                } else if meta.path.is_ident("dump") {
+                    attributes.dump = true;
+                } else if meta.path.is_ident("catalog") {
+                    attributes.catalog = true;
+                } else if meta.path.is_ident("describe") {
+                    attributes.describe = true;
+                } else if meta.path.is_ident("sensitive") {
+                    attributes.sensitive = true;
+                } else {
+                    return Err(meta.error("unsupported options attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let attributes = OptionsAttributes::parse(&input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let configurers = &attributes.configurers;
+    let post_configurers = &attributes.post_configurers;
+    let validators = &attributes.validators;
+    let register = match (&attributes.name, &attributes.section) {
+        (Some(name), Some(section)) => quote! {
+            ::options::ext::OptionsConfigurationServiceExtensions::add_named_options_from_config::<Self>(
+                services, #name, #section,
+            )
+        },
+        (None, Some(section)) => quote! {
+            ::options::ext::OptionsConfigurationServiceExtensions::add_options_from_config::<Self>(
+                services, #section,
+            )
+        },
+        (Some(name), None) => quote! {
+            ::options::ext::OptionsServiceExtensions::add_named_options::<Self>(services, #name)
+        },
+        (None, None) => quote! {
+            ::options::ext::OptionsServiceExtensions::add_options::<Self>(services)
+        },
+    };
+    let dump = if attributes.dump {
+        quote! { .add_to_dump() }
+    } else {
+        TokenStream2::new()
+    };
+    let catalog = if attributes.catalog {
+        quote! { .add_to_catalog() }
+    } else {
+        TokenStream2::new()
+    };
+    let describe = if attributes.describe {
+        quote! { .describe() }
+    } else {
+        TokenStream2::new()
+    };
+    let (sensitive, marker) = if attributes.sensitive {
+        (
+            quote! { .sensitive() },
+            quote! {
+                impl #impl_generics ::options::SensitiveOptions for #ident #ty_generics #where_clause {}
+            },
+        )
+    } else {
+        (TokenStream2::new(), TokenStream2::new())
+    };
+    let name = optional(&attributes.name);
+    let section = optional(&attributes.section);
+    let discover = if attributes.discover {
+        if !input.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &input.generics,
+                "generic options cannot be discovered",
+            ));
+        }
+
+        quote! { ::options::register_options!(@derived #ident); }
+    } else {
+        TokenStream2::new()
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::options::RegisterOptions for #ident #ty_generics #where_clause {
+            fn add_to(services: &mut ::options::__private::ServiceCollection) {
+                let _ = #register
+                    #(.add_configurer::<#configurers>())*
+                    #(.add_post_configurer::<#post_configurers>())*
+                    #(.add_validator::<#validators>())*
+                    #sensitive
+                    #dump
+                    #catalog
+                    #describe;
+            }
+
+            fn name() -> ::std::option::Option<&'static str> {
+                #name
+            }
+
+            fn section() -> ::std::option::Option<&'static str> {
+                #section
+            }
+        }
+
+        #marker
+
+        #discover
+    })
+}
+
+fn optional(value: &Option<LitStr>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { ::std::option::Option::Some(#value) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+struct OptionsField<'a> {
+    ident: &'a Ident,
+    secret: bool,
+    doc: Option<String>,
+    example: Option<LitStr>,
+}
+
+fn options_fields<'a>(input: &'a DeriveInput, kind: &str) -> Result<Vec<OptionsField<'a>>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            Fields::Unit => return Ok(Vec::new()),
+            Fields::Unnamed(_) => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    format!("{} options must have named fields", kind),
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                format!("{} options must be a struct", kind),
+            ))
+        }
+    };
+    let mut options = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let mut secret = false;
+        let mut doc = None;
+        let mut example = None;
+
+        for attribute in field.attrs.iter().filter(|a| a.path().is_ident("options")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("secret") {
+                    secret = true;
+                } else if meta.path.is_ident("doc") {
+                    doc = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("example") {
+                    example = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported options field attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        options.push(OptionsField {
+            ident: field.ident.as_ref().unwrap(),
+            secret,
+            doc: doc.or_else(|| doc_comment(&field.attrs)),
+            example,
+        });
+    }
+
+    Ok(options)
+}
+
+// each line of a doc comment is a separate #[doc = "..."] attribute
+fn doc_comment(attributes: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attributes
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) => Some(line.value().trim().to_owned()),
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+fn secrets(fields: &[OptionsField<'_>]) -> TokenStream2 {
+    // secrets are never read by the generated code, so acknowledge them
+    // to avoid dead code warnings in the deriving crate
+    let secrets = fields.iter().filter(|f| f.secret).map(|f| f.ident);
+    quote! { let _ = (#(&self.#secrets,)*); }
+}
+
+fn expand_redacted_debug(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = options_fields(&input, "redacted")?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let name = ident.to_string();
+    let secrets = secrets(&fields);
+    let fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        let name = ident.to_string();
+
+        if field.secret {
+            quote! { .field(#name, &::std::format_args!("{}", ::options::REDACTED)) }
+        } else {
+            quote! { .field(#name, &self.#ident) }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::options::RedactedDebug for #ident #ty_generics #where_clause {
+            fn fmt_redacted(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #secrets
+                formatter.debug_struct(#name) #(#fields)* .finish()
+            }
+        }
+    })
+}
+
+fn expand_redacted_serialize(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = options_fields(&input, "redacted")?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let name = ident.to_string();
+    let count = fields.len();
+    let secrets = secrets(&fields);
+    let fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        let name = ident.to_string();
+
+        if field.secret {
+            quote! { state.serialize_field(#name, ::options::REDACTED)?; }
+        } else {
+            quote! { state.serialize_field(#name, &self.#ident)?; }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::options::RedactedSerialize for #ident #ty_generics #where_clause {
+            fn serialize_redacted<__S>(&self, serializer: __S) -> ::std::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::options::__private::serde::Serializer,
+            {
+                use ::options::__private::serde::ser::SerializeStruct;
+
+                #secrets
+                let mut state = serializer.serialize_struct(#name, #count)?;
+                #(#fields)*
+                state.end()
+            }
+        }
+    })
+}
+
+fn expand_describe_options(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = options_fields(&input, "described")?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fields = fields.iter().map(|field| {
+        let name = field.ident.to_string();
+        let doc = field
+            .doc
+            .as_ref()
+            .map(|doc| quote! { .with_description(#doc) });
+        let example = field
+            .example
+            .as_ref()
+            .map(|example| quote! { .with_example(#example) });
+
+        quote! { ::options::FieldDescription::new(#name) #doc #example }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::options::DescribeOptions for #ident #ty_generics #where_clause {
+            fn describe() -> ::std::vec::Vec<::options::FieldDescription> {
+                ::std::vec![#(#fields),*]
+            }
+        }
+    })
+}
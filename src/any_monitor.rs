@@ -0,0 +1,195 @@
+use crate::monitor::alive;
+use crate::sync::RwLock;
+use std::any::{type_name, TypeId};
+use std::sync::{Arc, Weak};
+use std::time::SystemTime;
+
+type AnyCallback = dyn Fn(&AnyChangeEvent) + Send + Sync;
+
+/// Represents a change to monitored [`Options`](crate::Options) of any type.
+#[derive(Clone, Debug)]
+pub struct AnyChangeEvent {
+    type_id: TypeId,
+    type_name: &'static str,
+    name: Option<String>,
+    source: Option<String>,
+    generation: u64,
+    timestamp: SystemTime,
+}
+
+impl AnyChangeEvent {
+    /// Gets the identifier of the type of options that changed.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Gets the name of the type of options that changed.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Gets the name of the options that changed, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the name of the source that signaled the change, if known.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Gets the generation of the options after the change.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Gets the time at which the change occurred.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Gets a value indicating whether the change is to the specified type of options.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+}
+
+/// Represents a subscription to the changes of an [`AnyOptionsMonitor`].
+///
+/// # Remarks
+///
+/// When the subscription is dropped, the underlying callback is unsubscribed.
+pub struct AnySubscription(#[allow(dead_code)] Arc<AnyCallback>);
+
+/// Represents a monitor for the changes to [`Options`](crate::Options) of any type.
+///
+/// # Remarks
+///
+/// Every [`DefaultOptionsMonitor`](crate::DefaultOptionsMonitor) that publishes to the monitor
+/// notifies it when its options change, which allows a single component, such as an audit log or
+/// a metrics exporter, to observe every change without registering a listener for each type of
+/// options. The events are type-erased, so they do not contain the options.
+#[derive(Clone, Default)]
+pub struct AnyOptionsMonitor {
+    listeners: Arc<RwLock<Vec<Weak<AnyCallback>>>>,
+}
+
+impl AnyOptionsMonitor {
+    /// Initializes a new monitor for options of any type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a callback function to be invoked when options of any type change.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - The callback function to invoke
+    ///
+    /// # Returns
+    ///
+    /// A change subscription. When the subscription is dropped, no further notifications will be
+    /// propagated.
+    pub fn on_change(&self, listener: Box<AnyCallback>) -> AnySubscription {
+        let mut listeners = self.listeners.write().unwrap();
+
+        listeners.retain(|l| l.strong_count() > 0);
+
+        let listener: Arc<AnyCallback> = Arc::from(listener);
+
+        listeners.push(Arc::downgrade(&listener));
+        AnySubscription(listener)
+    }
+
+    pub(crate) fn publish<T: 'static>(
+        &self,
+        name: Option<&str>,
+        source: Option<&str>,
+        generation: u64,
+    ) {
+        let listeners = alive(&self.listeners);
+
+        if listeners.is_empty() {
+            return;
+        }
+
+        let event = AnyChangeEvent {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            name: name.map(|n| n.to_owned()),
+            source: source.map(|s| s.to_owned()),
+            generation,
+            timestamp: SystemTime::now(),
+        };
+
+        for listener in listeners {
+            listener(&event);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct CacheOptions;
+
+    #[derive(Default)]
+    struct QueueOptions;
+
+    #[test]
+    fn on_change_should_observe_changes_to_every_type_of_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_options::<CacheOptions>();
+        services.add_named_options::<QueueOptions>("Orders");
+
+        let provider = services.build_provider().unwrap();
+        let any = provider.get_required::<AnyOptionsMonitor>();
+        let cache = provider.get_required::<dyn OptionsMonitor<CacheOptions>>();
+        let queue = provider.get_required::<dyn OptionsMonitor<QueueOptions>>();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let observed = events.clone();
+        let subscription = any.on_change(Box::new(move |e| {
+            observed.lock().unwrap().push((
+                e.is::<CacheOptions>(),
+                e.type_name().rsplit("::").next().unwrap().to_owned(),
+                e.name().map(|n| n.to_owned()),
+                e.generation(),
+            ))
+        }));
+
+        // act
+        cache.reload(None);
+        queue.reload(Some("Orders"));
+        queue.reload(Some("Orders"));
+        drop(subscription);
+        cache.reload(None);
+
+        // assert
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (true, "CacheOptions".to_owned(), None, 1),
+                (
+                    false,
+                    "QueueOptions".to_owned(),
+                    Some("Orders".to_owned()),
+                    1
+                ),
+                (
+                    false,
+                    "QueueOptions".to_owned(),
+                    Some("Orders".to_owned()),
+                    2
+                ),
+            ]
+        );
+    }
+}
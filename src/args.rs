@@ -0,0 +1,17 @@
+/// Defines the behavior of parsed command-line arguments that are applied to configuration
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// This trait is independent of any particular argument parser. It is typically implemented by a
+/// type derived using a parser such as `clap`, where each argument that was specified overrides
+/// the corresponding option. When the **clap** feature is enabled, such a type can be parsed and
+/// applied to options in a single call with `apply_cli`.
+pub trait ApplyArgs<T> {
+    /// Applies the arguments to the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to apply the arguments to
+    fn apply_to(&self, options: &mut T);
+}
@@ -0,0 +1,636 @@
+use crate::monitor::{alive, subscribe, Callback, EventCallback, Generations};
+use crate::sync::{Mutex, RwLock};
+use crate::token::{register_next, TokenRegistration};
+use crate::{
+    meter, otel, trace, ChangeEvent, OptionsChangeTokenSource, OptionsFactory, OptionsMonitor,
+    OptionsMonitorCache, OptionsNameComparer, Ref, Subscription, Value,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll, Waker};
+
+#[cfg(feature = "tokio")]
+use crate::{broadcast::Broadcast, OptionsMonitorExt};
+
+#[cfg(feature = "tokio")]
+use tokio::sync::broadcast::{error::RecvError, Receiver, Sender};
+
+/// Represents the type alias for a task run by an [`OptionsRuntime`].
+pub type OptionsTask = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Defines the behavior of the runtime used to perform asynchronous options work.
+///
+/// # Remarks
+///
+/// This trait is implemented for any function which accepts an [`OptionsTask`], which allows a
+/// handle to any asynchronous runtime to be used. For example, `move |task| { handle.spawn(task); }`
+/// uses a Tokio runtime handle.
+pub trait OptionsRuntime: Send + Sync {
+    /// Spawns the specified task on the runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The [task](OptionsTask) to spawn
+    fn spawn(&self, task: OptionsTask);
+}
+
+impl<F: Fn(OptionsTask) + Send + Sync> OptionsRuntime for F {
+    fn spawn(&self, task: OptionsTask) {
+        (self)(task)
+    }
+}
+
+/// Represents an asynchronous implementation for notifications when option instances change.
+///
+/// # Remarks
+///
+/// Unlike [`DefaultOptionsMonitor`](crate::DefaultOptionsMonitor), options are rebuilt and callbacks
+/// are dispatched as tasks on an [`OptionsRuntime`] rather than on the thread which signaled the
+/// change. Changes can also be awaited using [`changed`](AsyncOptionsMonitor::changed).
+pub struct AsyncOptionsMonitor<T: Value> {
+    tracker: Arc<AsyncChangeTracker<T>>,
+    _watches: Vec<Arc<AsyncWatch<T>>>,
+}
+
+impl<T: Value + 'static> AsyncOptionsMonitor<T> {
+    /// Initializes a new asynchronous options monitor.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The [cache](crate::OptionsMonitorCache) used for monitored options
+    /// * `sources` - The [source tokens](crate::OptionsChangeTokenSource) used to track option changes
+    /// * `factory` - The [factory](crate::OptionsFactory) used to create new options
+    /// * `runtime` - The [runtime](OptionsRuntime) used to rebuild options and dispatch callbacks
+    pub fn new(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+        runtime: Ref<dyn OptionsRuntime>,
+    ) -> Self {
+        Self::with_comparer(cache, sources, factory, runtime, Default::default())
+    }
+
+    pub(crate) fn with_comparer(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+        runtime: Ref<dyn OptionsRuntime>,
+        comparer: OptionsNameComparer,
+    ) -> Self {
+        let tracker = Arc::new(AsyncChangeTracker::new(cache, factory, runtime, comparer));
+        let watches = sources
+            .into_iter()
+            .map(|source| AsyncWatch::new(source, tracker.clone()))
+            .collect();
+
+        Self {
+            tracker,
+            _watches: watches,
+        }
+    }
+
+    /// Waits for the next change to any of the monitored options.
+    ///
+    /// # Remarks
+    ///
+    /// The returned future completes after the changed options have been rebuilt. Only changes
+    /// which occur after this function is called are observed.
+    pub fn changed(&self) -> Changed<T> {
+        Changed::new(self.tracker.clone())
+    }
+
+    /// Subscribes to all subsequent changes to any of the monitored options.
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [`changed`](AsyncOptionsMonitor::changed), the returned receiver observes every change
+    /// that occurs after this function is called, including those that occur while the receiver is
+    /// not being awaited. A receiver that falls behind observes the latest change.
+    pub fn subscribe(&self) -> ChangeReceiver<T> {
+        ChangeReceiver::new(self.tracker.clone())
+    }
+
+    /// Rebuilds the monitored options each time a message is received on a `tokio` broadcast channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to rebuild
+    /// * `receiver` - The [receiver](tokio::sync::broadcast::Receiver) of the broadcast channel
+    ///
+    /// # Remarks
+    ///
+    /// The receiver is awaited by a task spawned on the [runtime](OptionsRuntime) until the channel
+    /// is closed or the monitor is dropped. Messages which were missed because the receiver fell
+    /// behind are processed as a single change. This is typically used with the receiver of a
+    /// [`ConfigurationReloadBroadcast`](crate::ConfigurationReloadBroadcast).
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub fn watch_broadcast<M>(&self, name: Option<&str>, mut receiver: Receiver<M>)
+    where
+        M: Clone + Send + 'static,
+    {
+        let tracker = Arc::downgrade(&self.tracker);
+        let name = name.map(|n| n.to_owned());
+
+        self.tracker.runtime.spawn(Box::pin(async move {
+            while let Ok(_) | Err(RecvError::Lagged(_)) = receiver.recv().await {
+                match tracker.upgrade() {
+                    Some(tracker) => tracker.rebuild(name.clone(), Some("broadcast".into())),
+                    _ => break,
+                }
+            }
+        }));
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl<T: Value + 'static> OptionsMonitorExt<T> for AsyncOptionsMonitor<T> {
+    fn broadcast(&self) -> Sender<ChangeEvent<T>> {
+        self.tracker.broadcast.sender()
+    }
+}
+
+impl<T: Value + 'static> OptionsMonitor<T> for AsyncOptionsMonitor<T> {
+    fn get(&self, name: Option<&str>) -> Ref<T> {
+        self.tracker.get(name)
+    }
+
+    fn on_change(&self, listener: Box<Callback<T>>) -> Subscription<T> {
+        self.tracker.add(listener)
+    }
+
+    fn on_change_event(&self, listener: Box<EventCallback<T>>) -> Subscription<T> {
+        subscribe(&self.tracker.events, listener)
+    }
+
+    fn reload(&self, name: Option<&str>) {
+        self.tracker.on_change(name.map(|n| n.to_owned()), None);
+    }
+
+    fn current_generation(&self, name: Option<&str>) -> u64 {
+        self.tracker.generations.get(name)
+    }
+}
+
+struct AsyncWatch<T: Value> {
+    me: Weak<Self>,
+    source: Ref<dyn OptionsChangeTokenSource<T>>,
+    tracker: Arc<AsyncChangeTracker<T>>,
+    registration: Mutex<Option<TokenRegistration>>,
+}
+
+impl<T: Value + 'static> AsyncWatch<T> {
+    fn new(
+        source: Ref<dyn OptionsChangeTokenSource<T>>,
+        tracker: Arc<AsyncChangeTracker<T>>,
+    ) -> Arc<Self> {
+        let watch = Arc::new_cyclic(|me: &Weak<Self>| Self {
+            me: me.clone(),
+            source,
+            tracker,
+            registration: Default::default(),
+        });
+
+        if watch.register() {
+            watch.changed();
+        }
+
+        watch
+    }
+
+    fn register(&self) -> bool {
+        let me = self.me.clone();
+        let (registration, missed) = register_next(&*self.source, move || {
+            if let Some(watch) = me.upgrade() {
+                watch.changed();
+            }
+        });
+
+        if registration.is_some() {
+            *self.registration.lock().unwrap() = registration;
+        }
+
+        missed
+    }
+
+    fn changed(&self) {
+        // the next token is registered before the options are rebuilt so that no change is missed
+        self.register();
+        self.tracker.on_change(
+            self.source.name().map(|n| n.to_owned()),
+            self.source.source_name().map(|n| n.to_owned()),
+        );
+    }
+}
+
+/// Represents a future which completes when monitored [`Options`](crate::Options) change.
+pub struct Changed<T: Value> {
+    tracker: Arc<AsyncChangeTracker<T>>,
+    version: u64,
+}
+
+impl<T: Value> Changed<T> {
+    fn new(tracker: Arc<AsyncChangeTracker<T>>) -> Self {
+        let version = tracker.changes.lock().unwrap().version;
+        Self { tracker, version }
+    }
+}
+
+impl<T: Value> Future for Changed<T> {
+    type Output = ChangeEvent<T>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        self.tracker
+            .poll_change(self.version, context)
+            .map(|(_, change)| change)
+    }
+}
+
+/// Represents a receiver of changes to monitored [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Receivers do not register a callback with the monitor, which makes it inexpensive for many
+/// tasks to observe the same changes. A cloned receiver observes the same changes as the receiver
+/// it was cloned from, starting with the first change that neither has received.
+pub struct ChangeReceiver<T: Value> {
+    tracker: Arc<AsyncChangeTracker<T>>,
+    version: u64,
+}
+
+impl<T: Value> ChangeReceiver<T> {
+    fn new(tracker: Arc<AsyncChangeTracker<T>>) -> Self {
+        let version = tracker.changes.lock().unwrap().version;
+        Self { tracker, version }
+    }
+
+    /// Waits for the next change that has not been received.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+}
+
+impl<T: Value> Clone for ChangeReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tracker: self.tracker.clone(),
+            version: self.version,
+        }
+    }
+}
+
+/// Represents a future which completes when a [receiver](ChangeReceiver) observes the next change.
+pub struct Recv<'a, T: Value> {
+    receiver: &'a mut ChangeReceiver<T>,
+}
+
+impl<T: Value> Future for Recv<'_, T> {
+    type Output = ChangeEvent<T>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = &mut *self.receiver;
+
+        receiver
+            .tracker
+            .poll_change(receiver.version, context)
+            .map(|(version, change)| {
+                receiver.version = version;
+                change
+            })
+    }
+}
+
+struct Changes<T: Value> {
+    version: u64,
+    latest: Option<ChangeEvent<T>>,
+    wakers: Vec<Waker>,
+}
+
+impl<T: Value> Default for Changes<T> {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            latest: None,
+            wakers: Vec::new(),
+        }
+    }
+}
+
+struct AsyncChangeTracker<T: Value> {
+    cache: Ref<dyn OptionsMonitorCache<T>>,
+    factory: Ref<dyn OptionsFactory<T>>,
+    runtime: Ref<dyn OptionsRuntime>,
+    listeners: RwLock<Vec<Weak<Callback<T>>>>,
+    events: RwLock<Vec<Weak<EventCallback<T>>>>,
+    changes: Mutex<Changes<T>>,
+    generations: Generations,
+    #[cfg(feature = "tokio")]
+    broadcast: Broadcast<T>,
+}
+
+impl<T: Value + 'static> AsyncChangeTracker<T> {
+    fn new(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+        runtime: Ref<dyn OptionsRuntime>,
+        comparer: OptionsNameComparer,
+    ) -> Self {
+        Self {
+            cache,
+            factory,
+            runtime,
+            listeners: Default::default(),
+            events: Default::default(),
+            changes: Default::default(),
+            generations: Generations::new(comparer),
+            #[cfg(feature = "tokio")]
+            broadcast: Default::default(),
+        }
+    }
+
+    fn on_change(self: &Arc<Self>, name: Option<String>, source: Option<String>) {
+        let tracker = self.clone();
+        self.runtime
+            .spawn(Box::pin(async move { tracker.rebuild(name, source) }));
+    }
+
+    fn rebuild(&self, name: Option<String>, source: Option<String>) {
+        let _span = trace::changing::<T>(name.as_deref(), source.as_deref());
+
+        meter::reloaded::<T>(name.as_deref());
+
+        // capture the callbacks before they are dispatched so that a callback
+        // which registers another callback cannot deadlock
+        let callbacks = alive(&self.listeners);
+        let events = alive(&self.events);
+        let previous = self.cache.try_get(name.as_deref());
+
+        trace::changed::<T>(name.as_deref());
+        self.cache.try_remove(name.as_deref());
+
+        let value = self.get(name.as_deref());
+        let generation = self.generations.next(name.as_deref());
+
+        otel::rebuilt::<T>(name.as_deref(), source.as_deref(), otel::SUCCEEDED);
+        let mut event = ChangeEvent::new(name.clone(), value.clone()).with_generation(generation);
+
+        if let Some(previous) = previous {
+            event = event.with_previous(previous);
+        }
+
+        if let Some(source) = source {
+            event = event.with_source(source);
+        }
+
+        for callback in callbacks {
+            let name = name.clone();
+            let value = value.clone();
+            self.runtime
+                .spawn(Box::pin(async move { callback(name.as_deref(), value) }));
+        }
+
+        #[cfg(feature = "tokio")]
+        if let Some(sender) = self.broadcast.active() {
+            // there is an error only when every receiver was dropped since it was checked
+            let _ = sender.send(event.clone());
+        }
+
+        for callback in events {
+            let event = event.clone();
+            self.runtime
+                .spawn(Box::pin(async move { callback(&event) }));
+        }
+
+        let wakers = {
+            let mut changes = self.changes.lock().unwrap();
+            changes.version += 1;
+            changes.latest = Some(event);
+            std::mem::take(&mut changes.wakers)
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: Value> AsyncChangeTracker<T> {
+    fn poll_change(&self, version: u64, context: &mut Context<'_>) -> Poll<(u64, ChangeEvent<T>)> {
+        let mut changes = self.changes.lock().unwrap();
+
+        if changes.version > version {
+            if let Some(change) = &changes.latest {
+                return Poll::Ready((changes.version, change.clone()));
+            }
+        }
+
+        if !changes.wakers.iter().any(|w| w.will_wake(context.waker())) {
+            changes.wakers.push(context.waker().clone());
+        }
+
+        Poll::Pending
+    }
+
+    fn get(&self, name: Option<&str>) -> Ref<T> {
+        self.cache
+            .get_or_add(name, &|n| self.factory.create(n).unwrap())
+    }
+
+    fn add(&self, listener: Box<Callback<T>>) -> Subscription<T> {
+        let mut listeners = self.listeners.write().unwrap();
+
+        for i in (0..listeners.len()).rev() {
+            if listeners[i].upgrade().is_none() {
+                listeners.remove(i);
+            }
+        }
+
+        let source: Arc<Callback<T>> = Arc::from(listener);
+
+        listeners.push(Arc::downgrade(&source));
+        Subscription::new(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::mpsc::channel;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+    use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default)]
+    struct Config {
+        retries: u8,
+    }
+
+    #[derive(Default)]
+    struct ConfigSetup {
+        counter: AtomicU8,
+    }
+
+    impl ConfigureOptions<Config> for ConfigSetup {
+        fn configure(&self, _name: Option<&str>, options: &mut Config) {
+            options.retries = self.counter.fetch_add(1, Ordering::SeqCst) + 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct ConfigSource {
+        token: SharedChangeToken<SingleChangeToken>,
+    }
+
+    impl OptionsChangeTokenSource<Config> for ConfigSource {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.token.clone())
+        }
+    }
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    fn new_monitor(source: Ref<ConfigSource>) -> AsyncOptionsMonitor<Config> {
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(ConfigSetup::default());
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let runtime: Ref<dyn OptionsRuntime> = Ref::new(|task: OptionsTask| {
+            thread::spawn(move || block_on(task));
+        });
+
+        AsyncOptionsMonitor::new(cache, vec![source], factory, runtime)
+    }
+
+    #[test]
+    fn changed_should_complete_when_source_changes() {
+        // arrange
+        let source = Ref::new(ConfigSource::default());
+        let monitor = new_monitor(source.clone());
+        let initial = monitor.current_value().retries;
+        let changed = monitor.changed();
+
+        // act
+        source.token.notify();
+
+        let change = block_on(changed);
+
+        // assert
+        assert_eq!(initial, 1);
+        assert_eq!(change.name(), None);
+        assert_eq!(change.value().retries, 2);
+        assert_eq!(change.generation(), 1);
+        assert_eq!(monitor.current_value().retries, 2);
+        assert_eq!(monitor.current_generation(None), 1);
+    }
+
+    #[test]
+    fn receiver_should_observe_changes_which_occur_before_it_is_awaited() {
+        // arrange
+        let source = Ref::new(ConfigSource::default());
+        let monitor = new_monitor(source.clone());
+        let mut receiver = monitor.subscribe();
+        let _ = monitor.current_value();
+        let rebuilt = monitor.changed();
+
+        source.token.notify();
+        let _ = block_on(rebuilt);
+
+        // act
+        let change = block_on(receiver.recv());
+
+        // assert
+        assert_eq!(change.value().retries, 2);
+    }
+
+    #[test]
+    fn cloned_receivers_should_observe_the_same_change() {
+        // arrange
+        let source = Ref::new(ConfigSource::default());
+        let monitor = new_monitor(source.clone());
+        let mut receiver = monitor.subscribe();
+        let mut clone = receiver.clone();
+        let _ = monitor.current_value();
+
+        source.token.notify();
+
+        // act
+        let first = block_on(receiver.recv());
+        let second = block_on(clone.recv());
+
+        // assert
+        assert!(Ref::ptr_eq(&first.value(), &second.value()));
+    }
+
+    #[test]
+    fn on_change_should_dispatch_callback_as_task() {
+        // arrange
+        let source = Ref::new(ConfigSource::default());
+        let monitor = new_monitor(source.clone());
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let caller = thread::current().id();
+        let _subscription = monitor.on_change(Box::new(move |_, options: Ref<Config>| {
+            let same_thread = thread::current().id() == caller;
+            sender
+                .lock()
+                .unwrap()
+                .send((options.retries, same_thread))
+                .unwrap();
+        }));
+
+        // act
+        source.token.notify();
+
+        let (retries, same_thread) = receiver.recv().unwrap();
+
+        // assert
+        assert_eq!(retries, 1);
+        assert!(!same_thread);
+    }
+
+    #[test]
+    fn reload_should_rebuild_options() {
+        // arrange
+        let source = Ref::new(ConfigSource::default());
+        let monitor = new_monitor(source);
+        let initial = monitor.current_value().retries;
+        let changed = monitor.changed();
+
+        // act
+        monitor.reload(None);
+
+        let change = block_on(changed);
+
+        // assert
+        assert_eq!(initial, 1);
+        assert_eq!(change.value().retries, 2);
+        assert_eq!(change.source(), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{OptionsCache, OptionsFactory, OptionsMonitorCache, Ref, ValidateOptionsResult, Value};
+use std::cell::RefCell;
+use std::future::{ready, Future};
+use std::pin::Pin;
+
+/// Represents the type alias for a future returned by asynchronous options.
+pub type OptionsFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Defines the behavior of an object that asynchronously creates configuration [`Options`](crate::Options).
+pub trait AsyncOptionsFactory<T: Value>: Send + Sync {
+    /// Creates and returns new configuration options.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the configuration options to create
+    fn create<'a>(
+        &'a self,
+        name: Option<&'a str>,
+    ) -> OptionsFuture<'a, Result<T, ValidateOptionsResult>>;
+}
+
+/// Represents the default factory used to asynchronously create configuration [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Options are created by the underlying [`OptionsFactory`](crate::OptionsFactory) and the returned
+/// future is always immediately ready.
+pub struct DefaultAsyncOptionsFactory<T: Value> {
+    factory: Ref<dyn OptionsFactory<T>>,
+}
+
+impl<T: Value> DefaultAsyncOptionsFactory<T> {
+    /// Initializes a new asynchronous options factory.
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - The [factory](crate::OptionsFactory) used to create new options
+    pub fn new(factory: Ref<dyn OptionsFactory<T>>) -> Self {
+        Self { factory }
+    }
+}
+
+impl<T: Value> AsyncOptionsFactory<T> for DefaultAsyncOptionsFactory<T> {
+    fn create<'a>(
+        &'a self,
+        name: Option<&'a str>,
+    ) -> OptionsFuture<'a, Result<T, ValidateOptionsResult>> {
+        Box::pin(ready(self.factory.create(name)))
+    }
+}
+
+/// Defines the behavior to asynchronously retrieve configured options.
+pub trait AsyncOptions<T: Value>: Send + Sync {
+    /// Gets the configured value.
+    fn value(&self) -> OptionsFuture<'_, Ref<T>>;
+}
+
+/// Defines the behavior for an asynchronous snapshot of configuration [`Options`](crate::Options).
+pub trait AsyncOptionsSnapshot<T: Value>: Send + Sync {
+    /// Gets the configuration options with the specified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to retrieve
+    fn get<'a>(&'a self, name: Option<&'a str>) -> OptionsFuture<'a, Ref<T>>;
+}
+
+/// Represents an object that manages [`AsyncOptions`] and [asynchronous option snapshots](AsyncOptionsSnapshot).
+pub struct AsyncOptionsManager<T: Value> {
+    factory: Ref<dyn AsyncOptionsFactory<T>>,
+    cache: OptionsCache<T>,
+}
+
+impl<T: Value> AsyncOptionsManager<T> {
+    /// Initializes a new asynchronous options manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - The [factory](AsyncOptionsFactory) used to create new options.
+    pub fn new(factory: Ref<dyn AsyncOptionsFactory<T>>) -> Self {
+        Self {
+            factory,
+            cache: Default::default(),
+        }
+    }
+}
+
+impl<T: Value> AsyncOptions<T> for AsyncOptionsManager<T> {
+    fn value(&self) -> OptionsFuture<'_, Ref<T>> {
+        self.get(None)
+    }
+}
+
+impl<T: Value> AsyncOptionsSnapshot<T> for AsyncOptionsManager<T> {
+    fn get<'a>(&'a self, name: Option<&'a str>) -> OptionsFuture<'a, Ref<T>> {
+        Box::pin(async move {
+            if let Some(options) = self.cache.get(name) {
+                return options;
+            }
+
+            // the cache cannot be locked while options are created so concurrent
+            // callers may race to create them; the first to finish wins
+            let options = RefCell::new(Some(self.factory.create(name).await.unwrap()));
+            self.cache
+                .get_or_add(name, &|_| options.borrow_mut().take().unwrap())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    #[derive(Default)]
+    struct Config {
+        retries: u8,
+    }
+
+    #[derive(Default)]
+    struct ConfigFactory {
+        created: AtomicU8,
+    }
+
+    impl AsyncOptionsFactory<Config> for ConfigFactory {
+        fn create<'a>(
+            &'a self,
+            _name: Option<&'a str>,
+        ) -> OptionsFuture<'a, Result<Config, ValidateOptionsResult>> {
+            Box::pin(async move {
+                Yield::default().await;
+                let retries = self.created.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(Config { retries })
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct Yield(bool);
+
+    impl Future for Yield {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                context.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn value_should_create_options_asynchronously() {
+        // arrange
+        let manager = AsyncOptionsManager::new(Ref::new(ConfigFactory::default()));
+
+        // act
+        let options = block_on(manager.value());
+
+        // assert
+        assert_eq!(options.retries, 1);
+    }
+
+    #[test]
+    fn get_should_return_cached_options() {
+        // arrange
+        let manager = AsyncOptionsManager::new(Ref::new(ConfigFactory::default()));
+        let first = block_on(manager.get(Some("Test")));
+
+        // act
+        let second = block_on(manager.get(Some("Test")));
+        let other = block_on(manager.get(None));
+
+        // assert
+        assert!(Ref::ptr_eq(&first, &second));
+        assert_eq!(other.retries, 2);
+    }
+}
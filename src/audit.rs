@@ -0,0 +1,190 @@
+use crate::{OptionsFactory, ValidateOptionsResult, Value};
+
+/// Represents the result of creating a single configuration [`Options`](crate::Options) instance
+/// in an [`OptionsFactoryAudit`].
+pub struct OptionsAuditEntry {
+    options_type: &'static str,
+    name: Option<String>,
+    result: ValidateOptionsResult,
+}
+
+impl OptionsAuditEntry {
+    /// Gets the name of the type of options.
+    pub fn options_type(&self) -> &str {
+        self.options_type
+    }
+
+    /// Gets the name of the options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the result of creating the options.
+    pub fn result(&self) -> &ValidateOptionsResult {
+        &self.result
+    }
+}
+
+/// Represents an audit that creates every known instance of configuration
+/// [`Options`](crate::Options) without using them.
+///
+/// # Remarks
+///
+/// Options are normally created when they are first used, which means a misconfiguration might not
+/// be discovered until long after an application has started and each problem is only discovered
+/// one at a time. An audit creates each instance with its [factory](crate::OptionsFactory) and
+/// collects every failure into a single report.
+#[derive(Default)]
+pub struct OptionsFactoryAudit {
+    entries: Vec<OptionsAuditEntry>,
+}
+
+impl OptionsFactoryAudit {
+    /// Initializes a new options factory audit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the options with each of the specified names.
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - The [factory](crate::OptionsFactory) used to create the options
+    /// * `names` - The names of the options to create
+    pub fn audit<T: Value>(
+        mut self,
+        factory: &dyn OptionsFactory<T>,
+        names: &[Option<&str>],
+    ) -> Self {
+        for name in names {
+            self.entries.push(OptionsAuditEntry {
+                options_type: std::any::type_name::<T>(),
+                name: name.map(|n| n.to_owned()),
+                result: match factory.create(*name) {
+                    Ok(_) => ValidateOptionsResult::success(),
+                    Err(failure) => failure,
+                },
+            });
+        }
+
+        self
+    }
+
+    /// Creates the options with each of the names they are registered with.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The [provider](di::ServiceProvider) used to resolve the factory and names
+    ///
+    /// # Remarks
+    ///
+    /// The names are those of every [registration](crate::OptionsRegistration) of the options,
+    /// such as those added by [`add_options`](crate::ext::OptionsServiceExtensions::add_options)
+    /// and [`add_named_options`](crate::ext::OptionsServiceExtensions::add_named_options).
+    #[cfg(feature = "di")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "di")))]
+    pub fn audit_registered<T: Value + 'static>(self, provider: &di::ServiceProvider) -> Self {
+        let options_type = di::Type::of::<T>();
+        let comparer = crate::OptionsNameComparer::default();
+        let mut names = Vec::<Option<String>>::new();
+
+        for registration in provider.get_all::<crate::OptionsRegistration>() {
+            if *registration.options_type() != options_type {
+                continue;
+            }
+
+            let name = registration.name();
+
+            if !names.iter().any(|n| match (n.as_deref(), name) {
+                (Some(known), Some(name)) => comparer.equals(known, name),
+                (known, name) => known == name,
+            }) {
+                names.push(name.map(|n| n.to_owned()));
+            }
+        }
+
+        let names: Vec<_> = names.iter().map(Option::as_deref).collect();
+        let factory = crate::factory::NamedOptionsFactory::<T>::resolve(provider);
+
+        self.audit(&*factory, &names)
+    }
+
+    /// Gets the audited options in the order they were created.
+    pub fn entries(&self) -> &[OptionsAuditEntry] {
+        &self.entries
+    }
+
+    /// Gets a value indicating whether all of the options were created successfully.
+    pub fn succeeded(&self) -> bool {
+        self.entries.iter().all(|e| !e.result.failed())
+    }
+
+    /// Converts the audit into a result that contains every failure.
+    pub fn into_result(self) -> Result<(), ValidateOptionsResult> {
+        let failures: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.result.failed())
+            .map(|e| match e.name() {
+                Some(name) => format!(
+                    "'{}' with the name '{}': {}",
+                    e.options_type,
+                    name,
+                    e.result.failure_message()
+                ),
+                _ => format!("'{}': {}", e.options_type, e.result.failure_message()),
+            })
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidateOptionsResult::fail_many(failures.iter()))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct DbOptions {
+        host: String,
+        pool_size: usize,
+    }
+
+    #[test]
+    fn audit_registered_should_report_every_failure() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<DbOptions>()
+            .configure(|o| o.host = "localhost".into())
+            .validate(|o| o.pool_size > 0, "A pool size is required.");
+        services
+            .add_named_options::<DbOptions>("Primary")
+            .configure(|o| o.pool_size = 10);
+        services
+            .add_named_options::<DbOptions>("primary")
+            .validate(|o| o.pool_size < 10, "The pool size is too large.");
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let audit = OptionsFactoryAudit::new().audit_registered::<DbOptions>(&provider);
+
+        // assert
+        let entries = audit.entries();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name(), None);
+        assert_eq!(entries[1].name(), Some("Primary"));
+        assert!(!audit.succeeded());
+        assert_eq!(audit.into_result().unwrap_err().failures().len(), 2);
+    }
+}
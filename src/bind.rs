@@ -0,0 +1,115 @@
+use config::{
+    ext::{from_config, Error},
+    Configuration,
+};
+use serde::de::{DeserializeSeed, Deserializer, Error as _};
+use serde::Deserialize;
+use std::any::Any;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+/// Defines the behavior of [`Options`](crate::Options) that bind themselves to configuration.
+///
+/// # Remarks
+///
+/// This is an alternative to `serde` deserialization for types that cannot implement it, such as
+/// foreign types or types that must uphold invariants. Options that implement this trait are
+/// registered using
+/// [`apply_bindable_config`](crate::ext::OptionsConfigurationServiceExtensions::apply_bindable_config)
+/// and are reloaded when the configuration changes, just like deserialized options.
+pub trait BindOptions {
+    /// Binds the options to the specified configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) to bind to
+    fn bind(&mut self, configuration: &dyn Configuration) -> Result<(), Error>;
+}
+
+thread_local! {
+    static SEED: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+}
+
+// the configuration deserializer is not public, so the seed is handed to a surrogate type that
+// the configuration is deserialized into
+struct Seeded<S, T> {
+    value: T,
+    _seed: PhantomData<S>,
+}
+
+impl<'de, S, T> Deserialize<'de> for Seeded<S, T>
+where
+    S: for<'a> DeserializeSeed<'a, Value = T> + 'static,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let seed = SEED
+            .with(|seed| seed.borrow_mut().take())
+            .and_then(|seed| seed.downcast::<S>().ok())
+            .ok_or_else(|| D::Error::custom("the deserialization seed is unavailable"))?;
+
+        seed.deserialize(deserializer).map(|value| Self {
+            value,
+            _seed: PhantomData,
+        })
+    }
+}
+
+pub(crate) fn from_config_seeded<S, T>(
+    configuration: &dyn Configuration,
+    seed: S,
+) -> Result<T, Error>
+where
+    S: for<'de> DeserializeSeed<'de, Value = T> + 'static,
+{
+    SEED.with(|slot| *slot.borrow_mut() = Some(Box::new(seed)));
+
+    let result = from_config::<Seeded<S, T>>(configuration).map(|seeded| seeded.value);
+
+    SEED.with(|slot| slot.borrow_mut().take());
+    result
+}
+
+#[cfg(feature = "path-to-error")]
+thread_local! {
+    static PATH: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// the failing path is recorded while the configuration is deserialized because the error of the
+// surrogate must be the error of the configuration deserializer
+#[cfg(feature = "path-to-error")]
+struct Traced<T>(T);
+
+#[cfg(feature = "path-to-error")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Traced<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_path_to_error::deserialize(deserializer)
+            .map(Self)
+            .map_err(|error| {
+                let path = error
+                    .path()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(config::ConfigurationPath::key_delimiter());
+
+                PATH.with(|slot| *slot.borrow_mut() = Some(path));
+                error.into_inner()
+            })
+    }
+}
+
+// deserializes a configuration once and, if it fails, provides the path of the value that failed
+// relative to the configuration
+#[cfg(feature = "path-to-error")]
+pub(crate) fn from_config_traced<T>(
+    configuration: &dyn Configuration,
+) -> Result<T, (Error, Option<String>)>
+where
+    T: serde::de::DeserializeOwned,
+{
+    PATH.with(|slot| slot.borrow_mut().take());
+
+    from_config::<Traced<T>>(configuration)
+        .map(|traced| traced.0)
+        .map_err(|error| (error, PATH.with(|slot| slot.borrow_mut().take())))
+}
@@ -0,0 +1,274 @@
+use crate::{ChangeEvent, Value};
+use std::sync::Mutex;
+use tokio::sync::broadcast::{self, Sender};
+
+#[cfg(feature = "cfg")]
+use crate::token::{register_next, TokenRegistration};
+
+#[cfg(feature = "cfg")]
+use crate::{ext::ConfigurationChangeTokenSource, Ref};
+
+#[cfg(feature = "cfg")]
+use config::Configuration;
+
+#[cfg(feature = "cfg")]
+use std::sync::{Arc, Weak};
+
+// the number of changes retained for a receiver that falls behind. changes are infrequent and a
+// lagging receiver only needs the latest options, so the buffer is kept small
+const CAPACITY: usize = 16;
+
+/// Defines extension methods for monitors of [`Options`](crate::Options).
+pub trait OptionsMonitorExt<T: Value> {
+    /// Gets the sender which broadcasts every change to the monitored options.
+    ///
+    /// # Remarks
+    ///
+    /// The sender is created when it is first requested and is kept alive by the monitor, so
+    /// [subscribing](tokio::sync::broadcast::Sender::subscribe) to it is inexpensive and does not
+    /// register a callback with the monitor. A change is only broadcast while there is at least one
+    /// receiver. A receiver which falls behind by more than 16 changes observes
+    /// [`Lagged`](tokio::sync::broadcast::error::RecvError::Lagged) and resumes with the oldest
+    /// retained change.
+    fn broadcast(&self) -> Sender<ChangeEvent<T>>;
+}
+
+// the broadcast of changes kept alive by a monitor
+pub(crate) struct Broadcast<T: Value>(Mutex<Option<Sender<ChangeEvent<T>>>>);
+
+impl<T: Value> Broadcast<T> {
+    pub(crate) fn sender(&self) -> Sender<ChangeEvent<T>> {
+        self.0
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| broadcast::channel(CAPACITY).0)
+            .clone()
+    }
+
+    // the sender, if any receiver would observe a change
+    pub(crate) fn active(&self) -> Option<Sender<ChangeEvent<T>>> {
+        self.0
+            .lock()
+            .unwrap()
+            .clone()
+            .filter(|sender| sender.receiver_count() > 0)
+    }
+}
+
+impl<T: Value> Default for Broadcast<T> {
+    fn default() -> Self {
+        Self(Mutex::new(None))
+    }
+}
+
+/// Represents the broadcast of the reloads of a [configuration](config::Configuration).
+///
+/// # Remarks
+///
+/// The reload token of the configuration is republished on a `tokio` broadcast channel for as long
+/// as the broadcast is alive, which allows a task to await a reload rather than blocking a thread.
+/// A receiver can also be [watched](crate::AsyncOptionsMonitor::watch_broadcast) by an
+/// [`AsyncOptionsMonitor`](crate::AsyncOptionsMonitor) to rebuild options on each reload.
+#[cfg(feature = "cfg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+pub struct ConfigurationReloadBroadcast {
+    relay: Arc<Relay>,
+}
+
+#[cfg(feature = "cfg")]
+impl ConfigurationReloadBroadcast {
+    /// Initializes a new configuration reload broadcast.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) to broadcast the reloads of
+    pub fn new(configuration: Ref<dyn Configuration>) -> Self {
+        Self {
+            relay: Relay::new(ConfigurationChangeTokenSource::new(None, configuration)),
+        }
+    }
+
+    /// Subscribes to all subsequent reloads of the configuration.
+    pub fn subscribe(&self) -> broadcast::Receiver<()> {
+        self.relay.sender.subscribe()
+    }
+
+    /// Gets the sender which broadcasts the reloads of the configuration.
+    pub fn sender(&self) -> Sender<()> {
+        self.relay.sender.clone()
+    }
+}
+
+#[cfg(feature = "cfg")]
+struct Relay {
+    me: Weak<Self>,
+    source: ConfigurationChangeTokenSource<()>,
+    sender: Sender<()>,
+    registration: Mutex<Option<TokenRegistration>>,
+}
+
+#[cfg(feature = "cfg")]
+impl Relay {
+    fn new(source: ConfigurationChangeTokenSource<()>) -> Arc<Self> {
+        let relay = Arc::new_cyclic(|me: &Weak<Self>| Self {
+            me: me.clone(),
+            source,
+            sender: broadcast::channel(CAPACITY).0,
+            registration: Default::default(),
+        });
+
+        if relay.register() {
+            relay.reloaded();
+        }
+
+        relay
+    }
+
+    fn register(&self) -> bool {
+        let me = self.me.clone();
+        let (registration, missed) = register_next(&self.source, move || {
+            if let Some(relay) = me.upgrade() {
+                relay.reloaded();
+            }
+        });
+
+        if registration.is_some() {
+            *self.registration.lock().unwrap() = registration;
+        }
+
+        missed
+    }
+
+    fn reloaded(&self) {
+        // the next token is registered before the reload is published so that no reload is missed
+        self.register();
+
+        // there is an error only when there are no receivers, which is not a failure
+        let _ = self.sender.send(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+    use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default)]
+    struct TestOptions {
+        value: usize,
+    }
+
+    struct TokenSource(SharedChangeToken<SingleChangeToken>);
+
+    impl OptionsChangeTokenSource<TestOptions> for TokenSource {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.0.clone())
+        }
+    }
+
+    fn new_factory() -> Ref<dyn OptionsFactory<TestOptions>> {
+        let configure: Ref<dyn ConfigureOptions<TestOptions>> =
+            Ref::new(configure(|_, o: &mut TestOptions| o.value = 42));
+        Ref::new(DefaultOptionsFactory::new(vec![configure], vec![], vec![]))
+    }
+
+    #[test]
+    fn broadcast_should_publish_change_to_every_receiver() {
+        // arrange
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let source: Ref<dyn OptionsChangeTokenSource<TestOptions>> =
+            Ref::new(TokenSource(token.clone()));
+        let monitor = DefaultOptionsMonitor::new(
+            Ref::new(OptionsCache::default()),
+            vec![source],
+            new_factory(),
+        );
+        let sender = monitor.broadcast();
+        let mut first = sender.subscribe();
+        let mut second = monitor.broadcast().subscribe();
+
+        // act
+        token.notify();
+
+        // assert
+        assert_eq!(first.try_recv().unwrap().value().value, 42);
+        assert_eq!(second.try_recv().unwrap().value().value, 42);
+        assert_eq!(sender.receiver_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn async_monitor_should_rebuild_options_when_watched_broadcast_is_received() {
+        // arrange
+        let handle = tokio::runtime::Handle::current();
+        let runtime: Ref<dyn OptionsRuntime> = Ref::new(move |task| {
+            handle.spawn(task);
+        });
+        let monitor = AsyncOptionsMonitor::new(
+            Ref::new(OptionsCache::default()),
+            vec![],
+            new_factory(),
+            runtime,
+        );
+        let (sender, receiver) = tokio::sync::broadcast::channel(1);
+        let mut changes = monitor.broadcast().subscribe();
+
+        monitor.watch_broadcast(None, receiver);
+
+        // act
+        sender.send(()).unwrap();
+
+        // assert
+        let change = changes.recv().await.unwrap();
+        assert_eq!(change.source(), Some("broadcast"));
+        assert_eq!(change.value().value, 42);
+    }
+
+    #[cfg(feature = "cfg")]
+    #[test]
+    fn configuration_reload_should_be_broadcast() {
+        // arrange
+        use config::{
+            ConfigurationBuilder, ConfigurationProvider, ConfigurationSource,
+            DefaultConfigurationBuilder,
+        };
+
+        #[derive(Clone, Default)]
+        struct ReloadableSource(SharedChangeToken<SingleChangeToken>);
+
+        impl ConfigurationSource for ReloadableSource {
+            fn build(&self, _builder: &dyn ConfigurationBuilder) -> Box<dyn ConfigurationProvider> {
+                Box::new(self.clone())
+            }
+        }
+
+        impl ConfigurationProvider for ReloadableSource {
+            fn get(&self, _key: &str) -> Option<config::Value> {
+                None
+            }
+
+            fn reload_token(&self) -> Box<dyn ChangeToken> {
+                Box::new(self.0.clone())
+            }
+
+            fn child_keys(&self, _earlier_keys: &mut Vec<String>, _parent_path: Option<&str>) {}
+        }
+
+        let source = ReloadableSource::default();
+        let mut builder = DefaultConfigurationBuilder::new();
+
+        builder.add(Box::new(source.clone()));
+
+        let config: Ref<dyn Configuration> = Ref::from(builder.build().unwrap().as_config());
+        let broadcast = ConfigurationReloadBroadcast::new(config);
+        let mut receiver = broadcast.subscribe();
+
+        // act
+        source.0.notify();
+
+        // assert
+        assert!(receiver.try_recv().is_ok());
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -1,16 +1,91 @@
-use crate::{ConfigureOptions, PostConfigureOptions, ValidateOptions, ValidateOptionsResult};
-use di::{singleton_factory, transient_factory, ServiceCollection, Ref};
+use crate::{
+    cache::{CacheComparer, CacheMeasure},
+    ext::OptionsServiceExtensions,
+    fallback::FallbackPolicy,
+    history::HistoryChangeTokenSource,
+    inherit::Inherits,
+    messages::messages,
+    monitor::{ErrorHandler, MonitorPolicy},
+    redact::SensitiveMarker,
+    tenant::TenantConfigure,
+    token::FnChangeTokenSource,
+    watchdog::TimeoutHandler,
+    ApplyArgs, ConfigureOptions, MeasureOptions, Options, OptionsChangeTokenSource, OptionsHistory,
+    OptionsManager, OptionsMonitor, OptionsMonitorCache, OptionsNameComparer, OptionsRegistration,
+    OptionsRole, OptionsSnapshot, PathExpansion, PostConfigureOptions, ReloadTransaction,
+    RetryPolicy, SecretMapping, SecretSource, SensitiveOptions, TenantContext, TenantOptions,
+    ValidateOptions, ValidateOptionsResult, ValidationRule, Value,
+};
+use di::{
+    exactly_one, exactly_one_with_key, existing, existing_as_self, scoped, singleton_factory,
+    singleton_with_key, transient, transient_factory, Ref, ServiceCollection, ServiceProvider,
+    Type,
+};
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
-use std::{marker::PhantomData, rc::Rc};
+use std::sync::Arc;
+use std::time::Duration;
+use tokens::ChangeToken;
+
+#[cfg(feature = "async")]
+use crate::{factory::NamedOptionsFactory, AsyncOptionsMonitor, OptionsFactory, OptionsRuntime};
+
+#[cfg(feature = "async")]
+use di::{singleton, singleton_as_self, zero_or_more, zero_or_one};
+
+#[cfg(feature = "dump")]
+use crate::{
+    bundle::OptionsImporter,
+    catalog::{OptionsDescriber, OptionsDocumentation},
+    dump::OptionsDumper,
+    DescribeOptions, OptionsDump, RedactedSerialize,
+};
+
+#[cfg(feature = "cfg")]
+use crate::{
+    deprecate::Deprecation, migrate::_Migrate, ConfigurationMigration, DeprecatedKey,
+    MigrateOptions,
+};
+
+#[cfg(feature = "diff")]
+use crate::{diff::DiffObserver, FieldChange, OptionsCreateObserver};
+
+#[cfg(feature = "fs-watch")]
+use crate::{
+    file_watch::{DirectoryWatch, FileWatch},
+    DirectoryChangeTokenSource, FileChangeTokenSource,
+};
+
+#[cfg(feature = "k8s")]
+use crate::MountChangeTokenSource;
+
+#[cfg(feature = "env")]
+use crate::EnvironmentChangeTokenSource;
+
+#[cfg(all(unix, feature = "signal"))]
+use crate::{signal::SignalWatch, ReloadSignal, SignalChangeTokenSource};
+
+#[cfg(feature = "http-push")]
+use crate::{HttpPushTokenSource, HttpPushTrigger};
+
+#[cfg(feature = "bus")]
+use crate::{bus::BusWatch, BusChangeTokenSource, BusSubscriber};
+
+#[cfg(feature = "json-schema")]
+use crate::JsonSchema;
+
+#[cfg(feature = "writable")]
+use crate::{writable::StoreConfigure, OptionsStore, WritableOptions};
 
 /// Represents a builder used to configure [`Options`](crate::Options).
 pub struct OptionsBuilder<'a, T: 'static> {
-    name: Option<String>,
+    names: Arc<[String]>,
+    comparer: OptionsNameComparer,
     services: &'a mut ServiceCollection,
     _marker: PhantomData<T>,
 }
 
-impl<'a, T: 'static> OptionsBuilder<'a, T> {
+impl<'a, T: Value + 'static> OptionsBuilder<'a, T> {
     /// Initializes a new options builder.
     ///
     /// # Arguments
@@ -18,16 +93,49 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `services` - The associated [collection of services](di::ServiceCollection)
     /// * `name` - The optional name associated with the options
     pub fn new(services: &'a mut ServiceCollection, name: Option<&str>) -> Self {
+        Self::with_names(services, name)
+    }
+
+    /// Initializes a new options builder for multiple names.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The associated [collection of services](di::ServiceCollection)
+    /// * `names` - The names associated with the options
+    ///
+    /// # Remarks
+    ///
+    /// Every configuration registered by the builder applies to all of the specified names. If no
+    /// names are specified, the configuration applies to all options.
+    pub fn with_names<I, S>(services: &'a mut ServiceCollection, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
         Self {
-            name: name.map(|s| s.to_owned()),
+            names: names
+                .into_iter()
+                .map(|s| s.as_ref().to_owned())
+                .collect::<Vec<_>>()
+                .into(),
+            comparer: Default::default(),
             services,
             _marker: PhantomData,
         }
     }
 
     /// Gets the name of the options
+    ///
+    /// # Remarks
+    ///
+    /// If the builder is associated with multiple names, this is the first name.
     pub fn name(&self) -> Option<&str> {
-        self.name.as_deref()
+        self.names.first().map(String::as_str)
+    }
+
+    /// Gets all of the names associated with the options
+    pub fn names(&self) -> &[String] {
+        &self.names
     }
 
     /// Gets the associated [collection of services](di::ServiceCollection)
@@ -35,6 +143,53 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
         self.services
     }
 
+    /// Sets the comparer used to match the options name for subsequent registrations.
+    ///
+    /// # Arguments
+    ///
+    /// * `comparer` - The [comparer](crate::OptionsNameComparer) used to match names
+    ///
+    /// # Remarks
+    ///
+    /// The cache of the options uses the last comparer that is set so that names which match are
+    /// always served the same instance.
+    pub fn name_comparer(mut self, comparer: OptionsNameComparer) -> Self {
+        self.comparer = comparer;
+        self.services
+            .add(existing_as_self(CacheComparer::<T>::new(comparer)));
+        self
+    }
+
+    /// Registers another type of [`Options`](crate::Options) and returns its builder.
+    ///
+    /// # Remarks
+    ///
+    /// This function continues a fluent chain with a related options type and is equivalent to
+    /// calling [`add_options`](crate::ext::OptionsServiceExtensions::add_options) on the associated
+    /// [collection of services](di::ServiceCollection).
+    pub fn and_options<U>(self) -> OptionsBuilder<'a, U>
+    where
+        U: Value + Default + 'static,
+    {
+        self.services.add_options::<U>()
+    }
+
+    /// Registers another type of named [`Options`](crate::Options) and returns its builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name associated with the options
+    pub fn and_named_options<U>(self, name: impl AsRef<str>) -> OptionsBuilder<'a, U>
+    where
+        U: Value + Default + 'static,
+    {
+        self.services.add_named_options::<U>(name)
+    }
+
+    fn matcher(&self) -> NameMatcher {
+        NameMatcher::new(self.names.clone(), self.comparer)
+    }
+
     /// Registers an action used to configure a particular type of [`Options`](crate::Options).
     ///
     /// # Arguments
@@ -42,9 +197,47 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn configure<F>(self, setup: F) -> Self
     where
-        F: Fn(&mut T) + 'static,
+        F: Fn(&mut T) + Value + 'static,
+    {
+        let configure = _Configure::new(self.matcher(), setup);
+        let action: Ref<dyn ConfigureOptions<T>> = Ref::new(configure);
+        let descriptor = singleton_factory(move |_| action.clone());
+        self.services.add(descriptor);
+        self
+    }
+
+    /// Registers a function used to configure a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The configuration function
+    ///
+    /// # Remarks
+    ///
+    /// A function cannot capture any state, so it is registered as an existing instance rather than
+    /// through a factory. Resolving it neither invokes a factory nor allocates, which makes it
+    /// cheaper to resolve than the equivalent closure used with [`configure`](Self::configure).
+    pub fn configure_fn(self, setup: fn(&mut T)) -> Self {
+        let configure = Box::new(_ConfigureFn::new(self.matcher(), setup));
+        let descriptor = existing::<dyn ConfigureOptions<T>, _ConfigureFn<T>>(configure);
+        self.services.add(descriptor);
+        self
+    }
+
+    /// Registers a fallible action used to configure a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The configuration action
+    ///
+    /// # Remarks
+    ///
+    /// Any failure returned by the action is reported as an error when the options are created.
+    pub fn configure_result<F>(self, setup: F) -> Self
+    where
+        F: Fn(&mut T) -> Result<(), String> + Value + 'static,
     {
-        let configure = _Configure::new(self.name.clone(), setup);
+        let configure = _TryConfigure::new(self.matcher(), setup);
         let action: Ref<dyn ConfigureOptions<T>> = Ref::new(configure);
         let descriptor = singleton_factory(move |_| action.clone());
         self.services.add(descriptor);
@@ -58,20 +251,71 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn configure1<F, D>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D>) + 'static,
-        D: 'static,
+        F: Fn(&mut T, Ref<D>) + Value + 'static,
+        D: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn ConfigureOptions<T>, _Configure1<T, F, D>>()
+                .depends_on(exactly_one::<D>())
+                .from(move |sp| {
+                    Ref::new(_Configure1::new(
+                        name.clone(),
+                        sp.get_required::<D>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn ConfigureOptions<T>> = Ref::new(_Configure1::new(
-                name.clone(),
-                sp.get_required::<D>(),
-                action.clone(),
-            ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Configure,
+            &self.names,
+            vec![Type::of::<D>()],
+        );
+
+        self
+    }
+
+    /// Registers an action used to configure a particular type of [`Options`](crate::Options) with a single,
+    /// keyed dependency.
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The configuration action
+    ///
+    /// # Remarks
+    ///
+    /// The dependency is resolved as the service of type `D` registered with the key `K`.
+    pub fn configure1_keyed<K, D, F>(self, setup: F) -> Self
+    where
+        F: Fn(&mut T, Ref<D>) + Value + 'static,
+        K: 'static,
+        D: Value + 'static,
+    {
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn ConfigureOptions<T>, _Configure1<T, F, D>>()
+                .depends_on(exactly_one_with_key::<K, D>())
+                .from(move |sp| {
+                    Ref::new(_Configure1::new(
+                        name.clone(),
+                        sp.get_required_by_key::<K, D>().into(),
+                        action.clone(),
+                    ))
+                }),
+        );
+
+        register::<T>(
+            self.services,
+            OptionsRole::Configure,
+            &self.names,
+            vec![Type::keyed::<K, D>()],
+        );
 
         self
     }
@@ -83,22 +327,33 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn configure2<F, D1, D2>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D1>, Ref<D2>) + 'static,
-        D1: 'static,
-        D2: 'static,
+        F: Fn(&mut T, Ref<D1>, Ref<D2>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn ConfigureOptions<T>, _Configure2<T, F, D1, D2>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .from(move |sp| {
+                    Ref::new(_Configure2::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn ConfigureOptions<T>> = Ref::new(_Configure2::new(
-                name.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                action.clone(),
-            ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Configure,
+            &self.names,
+            vec![Type::of::<D1>(), Type::of::<D2>()],
+        );
 
         self
     }
@@ -110,24 +365,36 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn configure3<F, D1, D2, D3>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>) + 'static,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
+        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn ConfigureOptions<T>, _Configure3<T, F, D1, D2, D3>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .from(move |sp| {
+                    Ref::new(_Configure3::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn ConfigureOptions<T>> = Ref::new(_Configure3::new(
-                name.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                sp.get_required::<D3>(),
-                action.clone(),
-            ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Configure,
+            &self.names,
+            vec![Type::of::<D1>(), Type::of::<D2>(), Type::of::<D3>()],
+        );
 
         self
     }
@@ -139,26 +406,44 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn configure4<F, D1, D2, D3, D4>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>) + 'static,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
-        D4: 'static,
+        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
+        D4: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn ConfigureOptions<T>, _Configure4<T, F, D1, D2, D3, D4>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .depends_on(exactly_one::<D4>())
+                .from(move |sp| {
+                    Ref::new(_Configure4::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        sp.get_required::<D4>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn ConfigureOptions<T>> = Ref::new(_Configure4::new(
-                name.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                sp.get_required::<D3>(),
-                sp.get_required::<D4>(),
-                action.clone(),
-            ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Configure,
+            &self.names,
+            vec![
+                Type::of::<D1>(),
+                Type::of::<D2>(),
+                Type::of::<D3>(),
+                Type::of::<D4>(),
+            ],
+        );
 
         self
     }
@@ -170,39 +455,140 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn configure5<F, D1, D2, D3, D4, D5>(self, setup: F) -> Self
     where
-        F: Fn(
-                &mut T,
-                Ref<D1>,
-                Ref<D2>,
-                Ref<D3>,
-                Ref<D4>,
-                Ref<D5>,
-            ) + 'static,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
-        D4: 'static,
-        D5: 'static,
-    {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>, Ref<D5>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
+        D4: Value + 'static,
+        D5: Value + 'static,
+    {
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn ConfigureOptions<T>, _Configure5<T, F, D1, D2, D3, D4, D5>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .depends_on(exactly_one::<D4>())
+                .depends_on(exactly_one::<D5>())
+                .from(move |sp| {
+                    Ref::new(_Configure5::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        sp.get_required::<D4>(),
+                        sp.get_required::<D5>(),
+                        action.clone(),
+                    ))
+                }),
+        );
+
+        register::<T>(
+            self.services,
+            OptionsRole::Configure,
+            &self.names,
+            vec![
+                Type::of::<D1>(),
+                Type::of::<D2>(),
+                Type::of::<D3>(),
+                Type::of::<D4>(),
+                Type::of::<D5>(),
+            ],
+        );
+
+        self
+    }
+
+    /// Registers a type used to configure a particular type of [`Options`](crate::Options).
+    ///
+    /// # Remarks
+    ///
+    /// The configurer is created using its [`Default`] implementation and is only applied to the
+    /// options matching the name of the builder.
+    pub fn add_configurer<C>(self) -> Self
+    where
+        C: ConfigureOptions<T> + Default + 'static,
+    {
+        self.add_configurer_with(|_| C::default())
+    }
+
+    /// Registers a type used to configure a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - The function used to create the configurer
+    ///
+    /// # Remarks
+    ///
+    /// The configurer is created from the [`ServiceProvider`](di::ServiceProvider) each time the
+    /// options are created and is only applied to the options matching the name of the builder.
+    pub fn add_configurer_with<C, F>(self, factory: F) -> Self
+    where
+        C: ConfigureOptions<T> + 'static,
+        F: Fn(&ServiceProvider) -> C + Value + 'static,
+    {
+        let name = self.matcher();
 
         self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn ConfigureOptions<T>> = Ref::new(_Configure5::new(
-                name.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                sp.get_required::<D3>(),
-                sp.get_required::<D4>(),
-                sp.get_required::<D5>(),
-                action.clone(),
-            ));
+            let config: Ref<dyn ConfigureOptions<T>> =
+                Ref::new(_Configurer::new(name.clone(), factory(sp)));
             config
         }));
 
         self
     }
 
+    /// Registers an action used to configure a particular type of [`Options`](crate::Options) for
+    /// each tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The configuration action, which receives the identifier of the tenant
+    ///
+    /// # Remarks
+    ///
+    /// The action is only applied to the options of a tenant, which are resolved by
+    /// [`TenantOptions`](crate::TenantOptions), regardless of the names of the builder.
+    pub fn configure_tenant<F>(self, setup: F) -> Self
+    where
+        F: Fn(&str, &mut T) + Value + 'static,
+    {
+        let action: Ref<dyn ConfigureOptions<T>> = Ref::new(TenantConfigure::new(setup));
+        self.services
+            .add(singleton_factory(move |_| action.clone()));
+        self
+    }
+
+    /// Registers [`TenantOptions`](crate::TenantOptions) for a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Remarks
+    ///
+    /// A scoped [`TenantContext`](crate::TenantContext) is registered if one has not been
+    /// registered. The options of each tenant are named using
+    /// [`TenantContext::options_name`](crate::TenantContext::options_name).
+    pub fn per_tenant(self) -> Self {
+        self.services
+            .try_add(
+                scoped::<TenantContext, TenantContext>()
+                    .from(|_| Ref::new(TenantContext::default())),
+            )
+            .try_add(
+                scoped::<TenantOptions<T>, TenantOptions<T>>()
+                    .depends_on(exactly_one::<TenantContext>())
+                    .depends_on(exactly_one::<dyn OptionsMonitor<T>>())
+                    .from(|sp| {
+                        Ref::new(TenantOptions::new(
+                            sp.get_required::<TenantContext>(),
+                            sp.get_required::<dyn OptionsMonitor<T>>(),
+                        ))
+                    }),
+            );
+        self
+    }
+
     /// Registers an action used to configure a particular type of [`Options`](crate::Options).
     ///
     /// # Arguments
@@ -210,9 +596,47 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn post_configure<F>(self, setup: F) -> Self
     where
-        F: Fn(&mut T) + 'static,
+        F: Fn(&mut T) + Value + 'static,
+    {
+        let configure = _Configure::new(self.matcher(), setup);
+        let action: Ref<dyn PostConfigureOptions<T>> = Ref::new(configure);
+        let descriptor = singleton_factory(move |_| action.clone());
+        self.services.add(descriptor);
+        self
+    }
+
+    /// Registers a function used to post-configure a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The post-configuration function
+    ///
+    /// # Remarks
+    ///
+    /// A function cannot capture any state, so it is registered as an existing instance rather than
+    /// through a factory. Resolving it neither invokes a factory nor allocates, which makes it
+    /// cheaper to resolve than the equivalent closure used with [`post_configure`](Self::post_configure).
+    pub fn post_configure_fn(self, setup: fn(&mut T)) -> Self {
+        let configure = Box::new(_ConfigureFn::new(self.matcher(), setup));
+        let descriptor = existing::<dyn PostConfigureOptions<T>, _ConfigureFn<T>>(configure);
+        self.services.add(descriptor);
+        self
+    }
+
+    /// Registers a fallible action used to post-configure a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The configuration action
+    ///
+    /// # Remarks
+    ///
+    /// Any failure returned by the action is reported as an error when the options are created.
+    pub fn post_configure_result<F>(self, setup: F) -> Self
+    where
+        F: Fn(&mut T) -> Result<(), String> + Value + 'static,
     {
-        let configure = _Configure::new(self.name.clone(), setup);
+        let configure = _TryConfigure::new(self.matcher(), setup);
         let action: Ref<dyn PostConfigureOptions<T>> = Ref::new(configure);
         let descriptor = singleton_factory(move |_| action.clone());
         self.services.add(descriptor);
@@ -226,18 +650,71 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn post_configure1<F, D>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D>) + 'static,
-        D: 'static,
+        F: Fn(&mut T, Ref<D>) + Value + 'static,
+        D: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn PostConfigureOptions<T>, _Configure1<T, F, D>>()
+                .depends_on(exactly_one::<D>())
+                .from(move |sp| {
+                    Ref::new(_Configure1::new(
+                        name.clone(),
+                        sp.get_required::<D>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn PostConfigureOptions<T>> = Ref::new(
-                _Configure1::new(name.clone(), sp.get_required::<D>(), action.clone()),
-            );
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::PostConfigure,
+            &self.names,
+            vec![Type::of::<D>()],
+        );
+
+        self
+    }
+
+    /// Registers an action used to post-configure a particular type of [`Options`](crate::Options) with a single,
+    /// keyed dependency.
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The configuration action
+    ///
+    /// # Remarks
+    ///
+    /// The dependency is resolved as the service of type `D` registered with the key `K`.
+    pub fn post_configure1_keyed<K, D, F>(self, setup: F) -> Self
+    where
+        F: Fn(&mut T, Ref<D>) + Value + 'static,
+        K: 'static,
+        D: Value + 'static,
+    {
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn PostConfigureOptions<T>, _Configure1<T, F, D>>()
+                .depends_on(exactly_one_with_key::<K, D>())
+                .from(move |sp| {
+                    Ref::new(_Configure1::new(
+                        name.clone(),
+                        sp.get_required_by_key::<K, D>().into(),
+                        action.clone(),
+                    ))
+                }),
+        );
+
+        register::<T>(
+            self.services,
+            OptionsRole::PostConfigure,
+            &self.names,
+            vec![Type::keyed::<K, D>()],
+        );
 
         self
     }
@@ -249,23 +726,33 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn post_configure2<F, D1, D2>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D1>, Ref<D2>) + 'static,
-        D1: 'static,
-        D2: 'static,
+        F: Fn(&mut T, Ref<D1>, Ref<D2>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn PostConfigureOptions<T>, _Configure2<T, F, D1, D2>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .from(move |sp| {
+                    Ref::new(_Configure2::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn PostConfigureOptions<T>> =
-                Ref::new(_Configure2::new(
-                    name.clone(),
-                    sp.get_required::<D1>(),
-                    sp.get_required::<D2>(),
-                    action.clone(),
-                ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::PostConfigure,
+            &self.names,
+            vec![Type::of::<D1>(), Type::of::<D2>()],
+        );
 
         self
     }
@@ -277,25 +764,36 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn post_configure3<F, D1, D2, D3>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>) + 'static,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
+        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn PostConfigureOptions<T>, _Configure3<T, F, D1, D2, D3>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .from(move |sp| {
+                    Ref::new(_Configure3::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn PostConfigureOptions<T>> =
-                Ref::new(_Configure3::new(
-                    name.clone(),
-                    sp.get_required::<D1>(),
-                    sp.get_required::<D2>(),
-                    sp.get_required::<D3>(),
-                    action.clone(),
-                ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::PostConfigure,
+            &self.names,
+            vec![Type::of::<D1>(), Type::of::<D2>(), Type::of::<D3>()],
+        );
 
         self
     }
@@ -307,27 +805,44 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn post_configure4<F, D1, D2, D3, D4>(self, setup: F) -> Self
     where
-        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>) + 'static,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
-        D4: 'static,
+        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
+        D4: Value + 'static,
     {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn PostConfigureOptions<T>, _Configure4<T, F, D1, D2, D3, D4>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .depends_on(exactly_one::<D4>())
+                .from(move |sp| {
+                    Ref::new(_Configure4::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        sp.get_required::<D4>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn PostConfigureOptions<T>> =
-                Ref::new(_Configure4::new(
-                    name.clone(),
-                    sp.get_required::<D1>(),
-                    sp.get_required::<D2>(),
-                    sp.get_required::<D3>(),
-                    sp.get_required::<D4>(),
-                    action.clone(),
-                ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::PostConfigure,
+            &self.names,
+            vec![
+                Type::of::<D1>(),
+                Type::of::<D2>(),
+                Type::of::<D3>(),
+                Type::of::<D4>(),
+            ],
+        );
 
         self
     }
@@ -339,91 +854,1307 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `setup` - The configuration action
     pub fn post_configure5<F, D1, D2, D3, D4, D5>(self, setup: F) -> Self
     where
-        F: Fn(
-                &mut T,
-                Ref<D1>,
-                Ref<D2>,
-                Ref<D3>,
-                Ref<D4>,
-                Ref<D5>,
-            ) + 'static,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
-        D4: 'static,
-        D5: 'static,
-    {
-        let action = Rc::new(setup);
-        let name = self.name.clone();
+        F: Fn(&mut T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>, Ref<D5>) + Value + 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
+        D4: Value + 'static,
+        D5: Value + 'static,
+    {
+        let action = Ref::new(setup);
+        let name = self.matcher();
+
+        self.services.add(
+            transient::<dyn PostConfigureOptions<T>, _Configure5<T, F, D1, D2, D3, D4, D5>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .depends_on(exactly_one::<D4>())
+                .depends_on(exactly_one::<D5>())
+                .from(move |sp| {
+                    Ref::new(_Configure5::new(
+                        name.clone(),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        sp.get_required::<D4>(),
+                        sp.get_required::<D5>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let config: Ref<dyn PostConfigureOptions<T>> =
-                Ref::new(_Configure5::new(
-                    name.clone(),
-                    sp.get_required::<D1>(),
-                    sp.get_required::<D2>(),
-                    sp.get_required::<D3>(),
-                    sp.get_required::<D4>(),
-                    sp.get_required::<D5>(),
-                    action.clone(),
-                ));
-            config
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::PostConfigure,
+            &self.names,
+            vec![
+                Type::of::<D1>(),
+                Type::of::<D2>(),
+                Type::of::<D3>(),
+                Type::of::<D4>(),
+                Type::of::<D5>(),
+            ],
+        );
 
         self
     }
 
-    /// Registers an action used to validate a particular type of [`Options`](crate::Options).
+    /// Registers a type used to post-configure a particular type of [`Options`](crate::Options).
     ///
-    /// # Arguments
+    /// # Remarks
     ///
-    /// * `action` - The validation action
-    /// * `failure_message` - The message used when validation fails
-    pub fn validate<F, M>(self, action: F, failure_message: M) -> Self
+    /// The post-configurer is created using its [`Default`] implementation and is only applied to the
+    /// options matching the name of the builder.
+    pub fn add_post_configurer<C>(self) -> Self
     where
-        F: Fn(&T) -> bool + 'static,
-        M: AsRef<str>,
+        C: PostConfigureOptions<T> + Default + 'static,
     {
-        let validate = _Validate::new(
-            self.name.clone(),
-            message_or_default(failure_message),
-            action,
-        );
-        let action: Ref<dyn ValidateOptions<T>> = Ref::new(validate);
-        let descriptor = transient_factory(move |_| action.clone());
-        self.services.add(descriptor);
-        self
+        self.add_post_configurer_with(|_| C::default())
     }
 
-    /// Registers an action used to validate a particular type of [`Options`](crate::Options) with a single dependency.
+    /// Registers a type used to post-configure a particular type of [`Options`](crate::Options).
     ///
     /// # Arguments
     ///
-    /// * `action` - The validation action
-    /// * `failure_message` - The message used when validation fails
-    pub fn validate1<F, M, D>(self, action: F, failure_message: M) -> Self
+    /// * `factory` - The function used to create the post-configurer
+    ///
+    /// # Remarks
+    ///
+    /// The post-configurer is created from the [`ServiceProvider`](di::ServiceProvider) each time the
+    /// options are created and is only applied to the options matching the name of the builder.
+    pub fn add_post_configurer_with<C, F>(self, factory: F) -> Self
     where
-        F: Fn(&T, Ref<D>) -> bool + 'static,
-        M: AsRef<str>,
-        D: 'static,
+        C: PostConfigureOptions<T> + 'static,
+        F: Fn(&ServiceProvider) -> C + Value + 'static,
     {
-        let action = Rc::new(action);
-        let name = self.name.clone();
-        let failure_message = message_or_default(failure_message);
+        let name = self.matcher();
 
         self.services.add(transient_factory(move |sp| {
-            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate1::new(
-                name.clone(),
-                failure_message.clone(),
-                sp.get_required::<D>(),
-                action.clone(),
-            ));
+            let config: Ref<dyn PostConfigureOptions<T>> =
+                Ref::new(_Configurer::new(name.clone(), factory(sp)));
+            config
+        }));
+
+        self
+    }
+
+    /// Registers parsed command-line arguments that are applied to a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The [arguments](crate::ApplyArgs) to apply
+    ///
+    /// # Remarks
+    ///
+    /// The arguments are applied as a post-configuration, which runs after the options have been
+    /// bound to configuration. This gives command-line arguments precedence over any other source.
+    pub fn apply_args<A>(self, args: A) -> Self
+    where
+        A: ApplyArgs<T> + Value + 'static,
+    {
+        self.post_configure(move |options| args.apply_to(options))
+    }
+
+    /// Registers paths that are expanded in a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `expansion` - The [expansion](crate::PathExpansion) of the paths in the options
+    ///
+    /// # Remarks
+    ///
+    /// The paths are expanded as a post-configuration, which runs after the options have been
+    /// bound to configuration.
+    pub fn expand_paths(self, expansion: PathExpansion<T>) -> Self {
+        self.post_configure(move |options| expansion.expand(options))
+    }
+
+    /// Registers secrets that are applied to a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [source](crate::SecretSource) of the secrets
+    /// * `mapping` - The [mapping](crate::SecretMapping) of secrets to the fields of the options
+    ///
+    /// # Remarks
+    ///
+    /// The secrets are retrieved each time the options are created and are applied in the order
+    /// the configuration is registered. When the source signals that secrets have been rotated, the
+    /// options associated with each name of the builder are reloaded.
+    pub fn configure_secrets<S>(self, source: S, mapping: SecretMapping<T>) -> Self
+    where
+        S: SecretSource + 'static,
+    {
+        let source = Ref::new(source);
+        let secrets = source.clone();
+
+        self.configure(move |options| mapping.apply(&*secrets, options))
+            .watch_fn(move || source.token())
+    }
+
+    /// Registers a store used to load and persist a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The [store](crate::OptionsStore) of the options
+    ///
+    /// # Remarks
+    ///
+    /// The persisted options are loaded in the order the configuration is registered. Changes are
+    /// made through the registered [`WritableOptions`](crate::WritableOptions), which persists them
+    /// to the store and reloads the options associated with each name of the builder.
+    #[cfg(feature = "writable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "writable")))]
+    pub fn writable<S>(self, store: S) -> Self
+    where
+        T: Value,
+        S: OptionsStore<T> + 'static,
+    {
+        let store: Ref<dyn OptionsStore<T>> = Ref::new(store);
+        let token = std::sync::Arc::new(std::sync::Mutex::new(Default::default()));
+        let load = store.clone();
+        let watch = token.clone();
+
+        self.services.try_add(singleton_factory(move |sp| {
+            Ref::new(WritableOptions::new(
+                crate::factory::NamedOptionsFactory::resolve(sp),
+                sp.get_all::<dyn ValidateOptions<T>>().collect(),
+                store.clone(),
+                token.clone(),
+            ))
+        }));
+
+        self.add_configurer_with(move |_| StoreConfigure::new(load.clone()))
+            .watch_fn(move || Box::new(watch.lock().unwrap().clone()))
+    }
+
+    /// Registers a type used to validate a particular type of [`Options`](crate::Options).
+    ///
+    /// # Remarks
+    ///
+    /// The validator is created using its [`Default`] implementation and is only applied to the
+    /// options matching the name of the builder.
+    pub fn add_validator<V>(self) -> Self
+    where
+        V: ValidateOptions<T> + Default + 'static,
+    {
+        self.add_validator_with(|_| V::default())
+    }
+
+    /// Registers a type used to validate a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - The function used to create the validator
+    ///
+    /// # Remarks
+    ///
+    /// The validator is created from the [`ServiceProvider`](di::ServiceProvider) each time the
+    /// options are created and is only applied to the options matching the name of the builder.
+    pub fn add_validator_with<V, F>(self, factory: F) -> Self
+    where
+        V: ValidateOptions<T> + 'static,
+        F: Fn(&ServiceProvider) -> V + Value + 'static,
+    {
+        let name = self.matcher();
+
+        self.services.add(transient_factory(move |sp| {
+            let validate: Ref<dyn ValidateOptions<T>> =
+                Ref::new(_Configurer::new(name.clone(), factory(sp)));
+            validate
+        }));
+
+        self
+    }
+
+    /// Sets the policy used to resolve the names of a particular type of [`Options`](crate::Options)
+    /// that were never configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The optional name of the options to fall back to before the default name
+    ///
+    /// # Remarks
+    ///
+    /// A name is configured when it is associated with any registration for the options, such as
+    /// [`add_named_options`](crate::ext::OptionsServiceExtensions::add_named_options). Requesting
+    /// the options with a name that was never configured creates the options for the parent name,
+    /// if it was configured, or the default name rather than an instance that is only configured by
+    /// the actions that apply to every name. The policy is only honored by the
+    /// [`DefaultOptionsFactory`](crate::DefaultOptionsFactory).
+    pub fn fallback_to(self, parent: Option<&str>) -> Self {
+        self.services.add(existing_as_self(FallbackPolicy::<T>::new(
+            parent,
+            self.comparer,
+        )));
+        self
+    }
+
+    /// Declares that the named [`Options`](crate::Options) of the builder inherit from other named
+    /// options.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The name of the options to inherit from
+    ///
+    /// # Remarks
+    ///
+    /// The options are configured in layers. The configurations of the parent, including those that
+    /// apply to every name, are applied first and then the configurations of the named options.
+    /// The same layering applies to post-configurations and validations. A parent can inherit from
+    /// another parent. This method has no effect if the builder has no names. The inheritance is
+    /// only honored by the [`DefaultOptionsFactory`](crate::DefaultOptionsFactory).
+    pub fn inherits(self, parent: &str) -> Self {
+        for name in self.names.iter() {
+            self.services.add(existing_as_self(Inherits::<T>::new(
+                name,
+                parent,
+                self.comparer,
+            )));
+        }
+
+        self
+    }
+
+    /// Registers the options as [`Options`](crate::Options) keyed by the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// The keyed [`Options`](crate::Options) resolve the instance matching the name of the builder,
+    /// which allows a service to depend on a particular named instance rather than resolving an
+    /// [`OptionsSnapshot`](crate::OptionsSnapshot) by name. When the builder has several names, a
+    /// keyed instance is registered for each name in order, so every instance is resolved by
+    /// [`get_all_by_key`](di::ServiceProvider::get_all_by_key) and the instance of the last name
+    /// is resolved by [`get_by_key`](di::ServiceProvider::get_by_key).
+    pub fn as_keyed<K: 'static>(self) -> Self
+    where
+        T: Value,
+    {
+        let names: Vec<_> = if self.names.is_empty() {
+            vec![None]
+        } else {
+            self.names.iter().cloned().map(Some).collect()
+        };
+
+        for name in names {
+            self.services.add(
+                singleton_with_key::<K, dyn Options<T>, _NamedOptions<T>>()
+                    .depends_on(exactly_one::<OptionsManager<T>>())
+                    .from(move |sp| {
+                        Ref::new(_NamedOptions::new(
+                            name.clone(),
+                            sp.get_required::<OptionsManager<T>>(),
+                        ))
+                    }),
+            );
+        }
+
+        self
+    }
+
+    /// Registers an action used to migrate the configuration bound to a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `setup` - The migration action
+    ///
+    /// # Remarks
+    ///
+    /// Migrations run in the order they are registered before the options are bound to
+    /// configuration and are only applied to the options matching the name of the builder.
+    #[cfg(feature = "cfg")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+    pub fn migrate<F>(self, setup: F) -> Self
+    where
+        F: Fn(&mut ConfigurationMigration) + Value + 'static,
+    {
+        let name = self.matcher();
+        let migration: Ref<dyn MigrateOptions<T>> = Ref::new(_Migrate::new(
+            move |n: Option<&str>, configuration: &mut ConfigurationMigration| {
+                if name.matches(n) {
+                    setup(configuration)
+                }
+            },
+        ));
+
+        self.services
+            .add(singleton_factory(move |_| migration.clone()));
+        self
+    }
+
+    /// Registers a deprecated key in the configuration bound to a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The [deprecated key](crate::DeprecatedKey)
+    ///
+    /// # Remarks
+    ///
+    /// A warning is emitted whenever the options are bound to a configuration that contains the key.
+    /// The deprecation applies to every name of the options bound to configuration.
+    #[cfg(feature = "cfg")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+    pub fn deprecate(self, key: DeprecatedKey) -> Self {
+        self.services
+            .add(existing_as_self(Deprecation::<T>::new(key)));
+        self
+    }
+
+    /// Marks a particular type of [`Options`](crate::Options) as [sensitive](crate::SensitiveOptions).
+    ///
+    /// # Remarks
+    ///
+    /// The values of sensitive options, including the messages of failed validations, are never
+    /// included in the log events, [`OptionsDump`](crate::OptionsDump), catalog, or support bundles
+    /// produced by this crate. Only the names of their fields are reported. The mark applies to
+    /// every name of the options.
+    pub fn sensitive(self) -> Self
+    where
+        T: SensitiveOptions,
+    {
+        self.services
+            .try_add(existing_as_self(SensitiveMarker::<T>::new()));
+        self
+    }
+
+    /// Measures the cached instances of a particular type of [`Options`](crate::Options).
+    ///
+    /// # Remarks
+    ///
+    /// The [approximate size](crate::MeasureOptions::approx_size) of every cached instance is
+    /// included in the [statistics](crate::OptionsMonitorCache::statistics) of the cache.
+    pub fn measure(self) -> Self
+    where
+        T: MeasureOptions,
+    {
+        self.services
+            .try_add(existing_as_self(CacheMeasure::<T>::new()));
+        self
+    }
+
+    /// Includes a particular type of [`Options`](crate::Options) in the [`OptionsDump`](crate::OptionsDump).
+    ///
+    /// # Remarks
+    ///
+    /// The current value of the options associated with each name of the builder is serialized
+    /// with all of its secrets masked.
+    #[cfg(feature = "dump")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+    pub fn add_to_dump(self) -> Self
+    where
+        T: RedactedSerialize,
+    {
+        if self.names.is_empty() {
+            self.services
+                .add(existing_as_self(OptionsDumper::new::<T>(None)));
+        } else {
+            for name in self.names.iter() {
+                self.services
+                    .add(existing_as_self(OptionsDumper::new::<T>(Some(name))));
+            }
+        }
+
+        self.services
+            .try_add(transient_factory(|sp| Ref::new(OptionsDump::new(sp))));
+        self
+    }
+
+    /// Includes a particular type of [`Options`](crate::Options) in support bundles.
+    ///
+    /// # Remarks
+    ///
+    /// The options associated with each name of the builder are included in the
+    /// [`OptionsDump`](crate::OptionsDump) and [exported](crate::export_snapshot) with all of their
+    /// secrets masked. They can also be [imported](crate::import_snapshot) from a bundle.
+    #[cfg(feature = "dump")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+    pub fn add_to_bundle(self) -> Self
+    where
+        T: RedactedSerialize + serde::de::DeserializeOwned,
+    {
+        if self.names.is_empty() {
+            self.services
+                .add(existing_as_self(OptionsImporter::new::<T>(None)));
+        } else {
+            for name in self.names.iter() {
+                self.services
+                    .add(existing_as_self(OptionsImporter::new::<T>(Some(name))));
+            }
+        }
+
+        self.add_to_dump()
+    }
+
+    /// Includes the fields, defaults, and validations of a particular type of [`Options`](crate::Options)
+    /// in the [`options_catalog`](crate::options_catalog).
+    ///
+    /// # Remarks
+    ///
+    /// The defaults are created using the [`Default`] implementation of the options and are
+    /// serialized with all of their secrets masked.
+    #[cfg(feature = "dump")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+    pub fn add_to_catalog(self) -> Self
+    where
+        T: Default + RedactedSerialize,
+    {
+        self.services
+            .add(existing_as_self(OptionsDescriber::new::<T>()));
+        self
+    }
+
+    /// Includes the descriptions of the fields of a particular type of [`Options`](crate::Options)
+    /// in the [catalog](crate::options_catalog).
+    ///
+    /// # Remarks
+    ///
+    /// The descriptions are provided by the [`DescribeOptions`](crate::DescribeOptions)
+    /// implementation of the options and apply to every name of the options.
+    #[cfg(feature = "dump")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+    pub fn describe(self) -> Self
+    where
+        T: DescribeOptions,
+    {
+        self.services
+            .add(existing_as_self(OptionsDocumentation::new::<T>()));
+        self
+    }
+
+    /// Retains the history of a particular type of [`Options`](crate::Options) so that they can be
+    /// rolled back.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of versions retained for each name
+    ///
+    /// # Remarks
+    ///
+    /// The [`OptionsHistory`](crate::OptionsHistory) replaces the
+    /// [`OptionsMonitorCache`](crate::OptionsMonitorCache) of the options. Only the options
+    /// associated with the names of the builder can be rolled back. The capacity of the first
+    /// history registered for the options is used.
+    pub fn keep_history(self, capacity: usize) -> Self
+    where
+        T: Value,
+    {
+        let history = Type::of::<OptionsHistory<T>>();
+
+        if !self.services.iter().any(|d| d.service_type() == history) {
+            let cache = Type::of::<dyn OptionsMonitorCache<T>>();
+
+            for i in (0..self.services.len()).rev() {
+                if self.services[i].service_type() == cache {
+                    self.services.remove(i);
+                }
+            }
+
+            self.services
+                .add(singleton_factory(move |sp| {
+                    let comparer = CacheComparer::<T>::resolve(sp);
+                    Ref::new(OptionsHistory::<T>::with_comparer(capacity, comparer))
+                }))
+                .add(singleton_factory(|sp| {
+                    let cache: Ref<dyn OptionsMonitorCache<T>> =
+                        sp.get_required::<OptionsHistory<T>>();
+                    cache
+                }));
+        }
+
+        let names: Vec<_> = if self.names.is_empty() {
+            vec![None]
+        } else {
+            self.names.iter().cloned().map(Some).collect()
+        };
+
+        for name in names {
+            self.services.add(singleton_factory(move |sp| {
+                let source: Ref<dyn OptionsChangeTokenSource<T>> =
+                    Ref::new(HistoryChangeTokenSource::new(
+                        name.as_deref(),
+                        sp.get_required::<OptionsHistory<T>>(),
+                    ));
+                source
+            }));
+        }
+
+        self
+    }
+
+    /// Suppresses changes to a particular type of [`Options`](crate::Options) that do not change
+    /// the options.
+    ///
+    /// # Remarks
+    ///
+    /// When a change is signaled, the [`OptionsMonitor`](crate::OptionsMonitor) creates the options
+    /// again and compares them to the cached options. If they are equal, the cached options are
+    /// kept and listeners are not notified, which prevents downstream work when the options are
+    /// reloaded without changing. The comparison applies to every name of the options.
+    pub fn suppress_unchanged(self) -> Self
+    where
+        T: Value + PartialEq + 'static,
+    {
+        self.services
+            .add(existing_as_self(MonitorPolicy::SuppressUnchanged(T::eq)));
+        self
+    }
+
+    /// Retries reloading a particular type of [`Options`](crate::Options) that failed to reload.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The [policy](crate::RetryPolicy) used to retry
+    /// * `on_error` - The function invoked with the failure when every retry failed
+    ///
+    /// # Remarks
+    ///
+    /// When a change is signaled and the options cannot be created, such as when a file is only
+    /// partially written, the [`OptionsMonitor`](crate::OptionsMonitor) keeps the current options
+    /// and tries again after the delay of the policy rather than waiting for the next change. The
+    /// [`AsyncOptionsMonitor`](crate::AsyncOptionsMonitor) does not retry.
+    pub fn retry_on_failure<F>(self, policy: RetryPolicy, on_error: F) -> Self
+    where
+        T: Value,
+        F: Fn(Option<&str>, &ValidateOptionsResult) + Send + Sync + 'static,
+    {
+        let on_error: Arc<ErrorHandler> = Arc::new(on_error);
+        self.services
+            .add(existing_as_self(MonitorPolicy::<T>::Retry(
+                policy, on_error,
+            )));
+        self
+    }
+
+    /// Enlists a particular type of [`Options`](crate::Options) in a transaction that reloads them
+    /// together with other options.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The [transaction](crate::ReloadTransaction) to enlist in
+    ///
+    /// # Remarks
+    ///
+    /// The options of each name of the builder, or the default name if the builder has no names,
+    /// are enlisted when the [`OptionsMonitor`](crate::OptionsMonitor) is created. Committing the
+    /// transaction replaces the options of every participant before any listener is notified. The
+    /// [`AsyncOptionsMonitor`](crate::AsyncOptionsMonitor) is not enlisted.
+    pub fn reload_in(self, transaction: &ReloadTransaction) -> Self
+    where
+        T: Value,
+    {
+        let names = if self.names.is_empty() {
+            vec![None]
+        } else {
+            self.names.iter().map(|n| Some(n.clone())).collect()
+        };
+
+        self.services
+            .add(existing_as_self(MonitorPolicy::<T>::Enlist(
+                transaction.clone(),
+                names,
+            )));
+        self
+    }
+
+    /// Reports the listeners of a particular type of [`Options`](crate::Options) that take longer
+    /// than a timeout to return.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The duration after which a listener is considered hung
+    /// * `on_timeout` - The function invoked with the name of the options and the elapsed time
+    ///
+    /// # Remarks
+    ///
+    /// A listener of the [`OptionsMonitor`](crate::OptionsMonitor) that exceeds the timeout is
+    /// reported while it is still running, so a listener that never returns cannot stall the
+    /// reload pipeline invisibly. The listener is not interrupted. When the **tracing** feature is
+    /// enabled, the listener is also logged as a warning.
+    pub fn listener_timeout<F>(self, timeout: Duration, on_timeout: F) -> Self
+    where
+        T: Value,
+        F: Fn(Option<&str>, Duration) + Send + Sync + 'static,
+    {
+        let on_timeout: Arc<TimeoutHandler> = Arc::new(on_timeout);
+        self.services
+            .add(existing_as_self(MonitorPolicy::<T>::Timeout(
+                timeout, on_timeout,
+            )));
+        self
+    }
+
+    /// Sets how the listeners of a particular type of [`Options`](crate::Options) are invoked.
+    ///
+    /// # Arguments
+    ///
+    /// * `dispatch` - The [dispatch](crate::ListenerDispatch) used to invoke listeners
+    ///
+    /// # Remarks
+    ///
+    /// By default, the listeners of the [`OptionsMonitor`](crate::OptionsMonitor) are invoked
+    /// sequentially on the thread that signaled the change, which is typically the thread of a
+    /// change token, so a slow listener delays every other listener and the next change.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn dispatch_listeners(self, dispatch: crate::ListenerDispatch) -> Self
+    where
+        T: Value,
+    {
+        self.services
+            .add(existing_as_self(MonitorPolicy::<T>::Dispatch(dispatch)));
+        self
+    }
+
+    /// Registers a source of change tokens used to reload a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [source](crate::OptionsChangeTokenSource) of change tokens
+    pub fn watch<S>(self, source: S) -> Self
+    where
+        T: Value,
+        S: OptionsChangeTokenSource<T> + 'static,
+    {
+        let source: Ref<dyn OptionsChangeTokenSource<T>> = Ref::new(source);
+        self.services
+            .add(singleton_factory(move |_| source.clone()));
+        self
+    }
+
+    /// Registers a function that produces change tokens used to reload a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The function used to create a [change token](tokens::ChangeToken)
+    ///
+    /// # Remarks
+    ///
+    /// When a change is signaled, the options associated with each name of the builder are reloaded.
+    pub fn watch_fn<F>(self, token: F) -> Self
+    where
+        T: Value,
+        F: Fn() -> Box<dyn ChangeToken> + Value + 'static,
+    {
+        let token = Ref::new(token);
+
+        if self.names.is_empty() {
+            let source = FnChangeTokenSource::new(None, token);
+            self.watch(source)
+        } else {
+            let names = self.names.clone();
+            names.iter().cloned().fold(self, |builder, name| {
+                builder.watch(FnChangeTokenSource::new(Some(name), token.clone()))
+            })
+        }
+    }
+
+    /// Registers a callback that receives the fields that changed when a particular type of
+    /// [`Options`](crate::Options) is reloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The function that receives the name of the options and their [changes](crate::FieldChange)
+    ///
+    /// # Remarks
+    ///
+    /// Each instance created for the names of the builder is compared to the previous instance
+    /// with the same name using [`options_diff`](crate::options_diff). The callback is only
+    /// invoked when at least one field changed. The paths of the changed fields are also emitted
+    /// as an event when the **tracing** feature is enabled, but their values are only provided to
+    /// the callback.
+    #[cfg(feature = "diff")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diff")))]
+    pub fn on_change_diff<F>(self, callback: F) -> Self
+    where
+        T: Value + serde::Serialize,
+        F: Fn(Option<&str>, &[FieldChange]) + Send + Sync + 'static,
+    {
+        let name = self.matcher();
+        let observer: Ref<dyn OptionsCreateObserver<T>> = Ref::new(DiffObserver::new(
+            self.comparer,
+            move |n: Option<&str>, changes: &[FieldChange]| {
+                if name.matches(n) {
+                    callback(n, changes)
+                }
+            },
+        ));
+
+        self.services
+            .add(singleton_factory(move |_| observer.clone()));
+        self
+    }
+
+    /// Registers files used to reload a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The paths of the files to watch
+    ///
+    /// # Remarks
+    ///
+    /// When any of the files change, the options associated with each name of the builder are
+    /// reloaded. This is useful when options embed data derived from files, such as certificates,
+    /// that are not bound through configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the files cannot be watched.
+    #[cfg(feature = "fs-watch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fs-watch")))]
+    pub fn watch_files<I, P>(self, paths: I) -> Self
+    where
+        T: Value + 'static,
+        I: IntoIterator<Item = P>,
+        P: AsRef<std::path::Path>,
+    {
+        let watch = std::sync::Arc::new(FileWatch::new(paths));
+
+        if self.names.is_empty() {
+            let source = FileChangeTokenSource::with_watch(None, watch);
+            self.watch(source)
+        } else {
+            let names = self.names.clone();
+            names.iter().cloned().fold(self, |builder, name| {
+                builder.watch(FileChangeTokenSource::with_watch(
+                    Some(&name),
+                    watch.clone(),
+                ))
+            })
+        }
+    }
+
+    /// Registers a directory of configuration fragments used to reload a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the directory to watch, such as `conf.d`
+    ///
+    /// # Remarks
+    ///
+    /// A file in the directory is the fragment of the options whose name matches the file name
+    /// without its extension, using the [comparer](OptionsBuilder::name_comparer) of the builder.
+    /// When a fragment changes, only the options with the matching name are reloaded. If the
+    /// builder is not associated with any names, the unnamed options are reloaded when any file in
+    /// the directory changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be watched.
+    #[cfg(feature = "fs-watch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fs-watch")))]
+    pub fn watch_directory<P>(self, path: P) -> Self
+    where
+        T: Value + 'static,
+        P: AsRef<std::path::Path>,
+    {
+        let watch = std::sync::Arc::new(DirectoryWatch::new(path, self.comparer));
+
+        if self.names.is_empty() {
+            let source = DirectoryChangeTokenSource::with_watch(None, watch);
+            self.watch(source)
+        } else {
+            let names = self.names.clone();
+            names.iter().fold(self, |builder, name| {
+                builder.watch(DirectoryChangeTokenSource::with_watch(
+                    Some(name),
+                    watch.clone(),
+                ))
+            })
+        }
+    }
+
+    /// Registers mounted Kubernetes ConfigMap or Secret folders used to reload a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The paths of the mounted folders to watch
+    ///
+    /// # Remarks
+    ///
+    /// When the content of any of the folders is updated, the options associated with each name
+    /// of the builder are reloaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the folders cannot be watched.
+    #[cfg(feature = "k8s")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "k8s")))]
+    pub fn watch_mounts<I, P>(self, paths: I) -> Self
+    where
+        T: Value + 'static,
+        I: IntoIterator<Item = P>,
+        P: AsRef<std::path::Path>,
+    {
+        let watch = std::sync::Arc::new(FileWatch::folders(paths));
+
+        if self.names.is_empty() {
+            let source = MountChangeTokenSource::with_watch(None, watch);
+            self.watch(source)
+        } else {
+            let names = self.names.clone();
+            names.iter().cloned().fold(self, |builder, name| {
+                builder.watch(MountChangeTokenSource::with_watch(
+                    Some(&name),
+                    watch.clone(),
+                ))
+            })
+        }
+    }
+
+    /// Registers environment variables used to reload a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the environment variables to watch
+    ///
+    /// # Remarks
+    ///
+    /// The options are only reloaded when the value of a variable that starts with the prefix
+    /// differs from when it was last read. Changes are only detected when
+    /// [`refresh_env`](crate::refresh_env) is called.
+    #[cfg(feature = "env")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+    pub fn reload_on_env(self, prefix: &str) -> Self
+    where
+        T: Value + 'static,
+    {
+        self.watch(EnvironmentChangeTokenSource::variables(prefix))
+    }
+
+    /// Registers environment variables which are polled to reload a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the environment variables to poll
+    /// * `interval` - The interval at which changes are polled
+    ///
+    /// # Remarks
+    ///
+    /// The options are only reloaded when the value of a variable that starts with the prefix
+    /// differs from when it was last read. Changes are also detected when
+    /// [`refresh_env`](crate::refresh_env) is called.
+    #[cfg(all(feature = "env", feature = "async"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "env", feature = "async"))))]
+    pub fn poll_env(self, prefix: &str, interval: std::time::Duration) -> Self
+    where
+        T: Value + 'static,
+    {
+        self.watch(EnvironmentChangeTokenSource::polling(prefix, interval))
+    }
+
+    /// Registers Unix signals used to reload a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `signals` - The [signals](crate::ReloadSignal) to watch
+    ///
+    /// # Remarks
+    ///
+    /// When the process receives any of the signals, the options associated with each name of the
+    /// builder are reloaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signal handler cannot be installed.
+    #[cfg(all(unix, feature = "signal"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "signal"))))]
+    pub fn reload_on_signals(self, signals: &[ReloadSignal]) -> Self
+    where
+        T: Value + 'static,
+    {
+        let watch = SignalWatch::new(signals);
+
+        if self.names.is_empty() {
+            let source = SignalChangeTokenSource::with_watch(None, watch);
+            self.watch(source)
+        } else {
+            let names = self.names.clone();
+            names.iter().cloned().fold(self, |builder, name| {
+                builder.watch(SignalChangeTokenSource::with_watch(
+                    Some(&name),
+                    watch.clone(),
+                ))
+            })
+        }
+    }
+
+    /// Reloads a particular type of [`Options`](crate::Options) when the process receives the
+    /// hangup signal (`SIGHUP`).
+    ///
+    /// # Remarks
+    ///
+    /// This follows the convention used by daemons to re-read their configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signal handler cannot be installed.
+    #[cfg(all(unix, feature = "signal"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "signal"))))]
+    pub fn reload_on_sighup(self) -> Self
+    where
+        T: Value + 'static,
+    {
+        self.reload_on_signals(&[ReloadSignal::Hangup])
+    }
+
+    /// Reloads a particular type of [`Options`](crate::Options) when a change is pushed over HTTP.
+    ///
+    /// # Arguments
+    ///
+    /// * `trigger` - The [trigger](crate::HttpPushTrigger) changes are pushed to
+    ///
+    /// # Remarks
+    ///
+    /// When a change is pushed, the options associated with each name of the builder are reloaded.
+    /// This complements polling by allowing an external configuration service to announce a change
+    /// as soon as it happens.
+    #[cfg(feature = "http-push")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http-push")))]
+    pub fn reload_on_push(self, trigger: &HttpPushTrigger) -> Self
+    where
+        T: Value + 'static,
+    {
+        if self.names.is_empty() {
+            self.watch(HttpPushTokenSource::new(None, trigger))
+        } else {
+            let names = self.names.clone();
+            names.iter().fold(self, |builder, name| {
+                builder.watch(HttpPushTokenSource::new(Some(name), trigger))
+            })
+        }
+    }
+
+    /// Reloads a particular type of [`Options`](crate::Options) when a message is published on a
+    /// topic of a message bus.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The [subscriber](crate::BusSubscriber) to the message bus
+    /// * `topic` - The topic changes are published on
+    ///
+    /// # Remarks
+    ///
+    /// When a message is published, the options associated with each name of the builder are
+    /// reloaded. The payload of the message is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the topic cannot be subscribed to.
+    #[cfg(feature = "bus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bus")))]
+    pub fn reload_on_bus(self, subscriber: &dyn BusSubscriber, topic: &str) -> Self
+    where
+        T: Value + 'static,
+    {
+        let watch = subscribe(subscriber, topic);
+        self.watch_bus(watch)
+    }
+
+    /// Configures and reloads a particular type of [`Options`](crate::Options) from the messages
+    /// published on a topic of a message bus.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The [subscriber](crate::BusSubscriber) to the message bus
+    /// * `topic` - The topic changes are published on
+    /// * `setup` - The function that applies the payload of the last message to the options
+    ///
+    /// # Remarks
+    ///
+    /// When a message is published, the options associated with each name of the builder are
+    /// reloaded and configured with the payload of the message. The payload is applied in the
+    /// order this method is called relative to other configuration actions. Nothing is applied
+    /// until the first message is received.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the topic cannot be subscribed to.
+    #[cfg(feature = "bus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bus")))]
+    pub fn configure_from_bus<F>(
+        self,
+        subscriber: &dyn BusSubscriber,
+        topic: &str,
+        setup: F,
+    ) -> Self
+    where
+        T: Value + 'static,
+        F: Fn(&mut T, &[u8]) + Value + 'static,
+    {
+        let watch = subscribe(subscriber, topic);
+        let payload = watch.clone();
+
+        self.configure(move |options| {
+            if let Some(payload) = payload.payload() {
+                setup(options, &payload)
+            }
+        })
+        .watch_bus(watch)
+    }
+
+    #[cfg(feature = "bus")]
+    fn watch_bus(self, watch: Arc<BusWatch>) -> Self
+    where
+        T: Value + 'static,
+    {
+        if self.names.is_empty() {
+            self.watch(BusChangeTokenSource::with_watch(None, watch))
+        } else {
+            let names = self.names.clone();
+            names.iter().fold(self, |builder, name| {
+                builder.watch(BusChangeTokenSource::with_watch(Some(name), watch.clone()))
+            })
+        }
+    }
+
+    /// Monitors a particular type of [`Options`](crate::Options) using an [`AsyncOptionsMonitor`](crate::AsyncOptionsMonitor).
+    ///
+    /// # Arguments
+    ///
+    /// * `runtime` - The [runtime](crate::OptionsRuntime) used to rebuild options and dispatch callbacks
+    ///
+    /// # Remarks
+    ///
+    /// The asynchronous monitor replaces the registered [`OptionsMonitor`](crate::OptionsMonitor) and
+    /// can also be resolved directly to await changes.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn async_monitor<R>(self, runtime: R) -> Self
+    where
+        R: OptionsRuntime + 'static,
+    {
+        let runtime: Ref<dyn OptionsRuntime> = Ref::new(runtime);
+
+        self.services
+            .add(
+                singleton_as_self::<AsyncOptionsMonitor<T>>()
+                    .depends_on(exactly_one::<dyn OptionsMonitorCache<T>>())
+                    .depends_on(zero_or_more::<dyn OptionsChangeTokenSource<T>>())
+                    .depends_on(zero_or_one::<dyn OptionsFactory<T>>())
+                    .depends_on(zero_or_more::<NamedOptionsFactory<T>>())
+                    .depends_on(zero_or_more::<CacheComparer<T>>())
+                    .from(move |sp| {
+                        Ref::new(AsyncOptionsMonitor::with_comparer(
+                            sp.get_required::<dyn OptionsMonitorCache<T>>(),
+                            sp.get_all::<dyn OptionsChangeTokenSource<T>>().collect(),
+                            NamedOptionsFactory::resolve(sp),
+                            runtime.clone(),
+                            CacheComparer::<T>::resolve(sp),
+                        ))
+                    }),
+            )
+            .replace(
+                singleton::<dyn OptionsMonitor<T>, AsyncOptionsMonitor<T>>()
+                    .depends_on(exactly_one::<AsyncOptionsMonitor<T>>())
+                    .from(|sp| sp.get_required::<AsyncOptionsMonitor<T>>()),
+            );
+        self
+    }
+
+    /// Registers an action used to validate a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The validation action
+    /// * `failure_message` - The message used when validation fails
+    pub fn validate<F, M>(self, action: F, failure_message: M) -> Self
+    where
+        F: Fn(&T) -> bool + Value + 'static,
+        M: AsRef<str>,
+    {
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(transient_factory(move |sp| {
+            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate::new(
+                name.clone(),
+                message_or_default(sp, &failure_message),
+                action.clone(),
+            ));
+            validate
+        }));
+
+        self
+    }
+
+    /// Registers an action used to require a setting of a particular type of
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action that determines whether the setting is present
+    /// * `field` - The name of the required setting
+    ///
+    /// # Remarks
+    ///
+    /// This is most useful for optional fields that must be configured in some environments,
+    /// such as `require(|o| o.api_key.is_some(), "api_key")`. The failure message is provided by
+    /// [`OptionsMessages::missing_setting`](crate::OptionsMessages::missing_setting), which is
+    /// "missing required setting `field`" by default.
+    pub fn require<F>(self, action: F, field: &str) -> Self
+    where
+        F: Fn(&T) -> bool + Value + 'static,
+    {
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let field = field.to_owned();
+
+        self.services.add(transient_factory(move |sp| {
+            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate::new(
+                name.clone(),
+                messages(sp).missing_setting(&field),
+                action.clone(),
+            ));
             validate
         }));
 
         self
     }
 
+    /// Registers a rule used to validate a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The [validation rule](crate::ValidationRule)
+    ///
+    /// # Remarks
+    ///
+    /// The rule is only applied to the options matching the name of the builder. Ready-made rules
+    /// are provided by the [`validators`](crate::validators) module.
+    pub fn validate_with_rule<R>(self, rule: R) -> Self
+    where
+        R: ValidationRule<T> + 'static,
+    {
+        let validate = _ValidateRule::new(self.matcher(), rule);
+        let action: Ref<dyn ValidateOptions<T>> = Ref::new(validate);
+        let descriptor = transient_factory(move |_| action.clone());
+        self.services.add(descriptor);
+        self
+    }
+
+    /// Registers a JSON Schema used to validate a particular type of [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The JSON text of the schema or the path of a schema file
+    ///
+    /// # Remarks
+    ///
+    /// A schema that begins with `{` is the JSON text of the schema; otherwise, the schema is
+    /// loaded from a file with the specified path. The options are serialized to JSON each time
+    /// they are validated. Only a subset of JSON Schema is supported; refer to
+    /// [`JsonSchema`](crate::JsonSchema) for more information. An error is returned if the schema
+    /// cannot be read, is not a valid JSON Schema, or uses a keyword which is not supported.
+    #[cfg(feature = "json-schema")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+    pub fn validate_json_schema<S: AsRef<str>>(self, schema: S) -> Result<Self, String>
+    where
+        T: serde::Serialize,
+    {
+        let schema = schema.as_ref();
+        let schema = if schema.trim_start().starts_with('{') {
+            JsonSchema::parse(schema)
+        } else {
+            JsonSchema::from_file(schema)
+        }?;
+
+        Ok(self.validate_with_rule(schema))
+    }
+
+    /// Registers an action used to validate a particular type of [`Options`](crate::Options) with a single dependency.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The validation action
+    /// * `failure_message` - The message used when validation fails
+    pub fn validate1<F, M, D>(self, action: F, failure_message: M) -> Self
+    where
+        F: Fn(&T, Ref<D>) -> bool + Value + 'static,
+        M: AsRef<str>,
+        D: Value + 'static,
+    {
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(
+            transient::<dyn ValidateOptions<T>, _Validate1<T, F, D>>()
+                .depends_on(exactly_one::<D>())
+                .from(move |sp| {
+                    Ref::new(_Validate1::new(
+                        name.clone(),
+                        message_or_default(sp, &failure_message),
+                        sp.get_required::<D>(),
+                        action.clone(),
+                    ))
+                }),
+        );
+
+        register::<T>(
+            self.services,
+            OptionsRole::Validate,
+            &self.names,
+            vec![Type::of::<D>()],
+        );
+
+        self
+    }
+
+    /// Registers an action used to validate a particular type of [`Options`](crate::Options) with a single,
+    /// keyed dependency.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The validation action
+    /// * `failure_message` - The message used when validation fails
+    ///
+    /// # Remarks
+    ///
+    /// The dependency is resolved as the service of type `D` registered with the key `K`.
+    pub fn validate1_keyed<K, D, F, M>(self, action: F, failure_message: M) -> Self
+    where
+        F: Fn(&T, Ref<D>) -> bool + Value + 'static,
+        M: AsRef<str>,
+        K: 'static,
+        D: Value + 'static,
+    {
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(
+            transient::<dyn ValidateOptions<T>, _Validate1<T, F, D>>()
+                .depends_on(exactly_one_with_key::<K, D>())
+                .from(move |sp| {
+                    Ref::new(_Validate1::new(
+                        name.clone(),
+                        message_or_default(sp, &failure_message),
+                        sp.get_required_by_key::<K, D>().into(),
+                        action.clone(),
+                    ))
+                }),
+        );
+
+        register::<T>(
+            self.services,
+            OptionsRole::Validate,
+            &self.names,
+            vec![Type::keyed::<K, D>()],
+        );
+
+        self
+    }
+
     /// Registers an action used to validate a particular type of [`Options`](crate::Options) with two dependencies.
     ///
     /// # Arguments
@@ -432,25 +2163,36 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `failure_message` - The message used when validation fails
     pub fn validate2<F, M, D1, D2>(self, action: F, failure_message: M) -> Self
     where
-        F: Fn(&T, Ref<D1>, Ref<D2>) -> bool + 'static,
+        F: Fn(&T, Ref<D1>, Ref<D2>) -> bool + Value + 'static,
         M: AsRef<str>,
-        D1: 'static,
-        D2: 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
     {
-        let action = Rc::new(action);
-        let name = self.name.clone();
-        let failure_message = message_or_default(failure_message);
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(
+            transient::<dyn ValidateOptions<T>, _Validate2<T, F, D1, D2>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .from(move |sp| {
+                    Ref::new(_Validate2::new(
+                        name.clone(),
+                        message_or_default(sp, &failure_message),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate2::new(
-                name.clone(),
-                failure_message.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                action.clone(),
-            ));
-            validate
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Validate,
+            &self.names,
+            vec![Type::of::<D1>(), Type::of::<D2>()],
+        );
 
         self
     }
@@ -463,27 +2205,39 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `failure_message` - The message used when validation fails
     pub fn validate3<F, M, D1, D2, D3>(self, action: F, failure_message: M) -> Self
     where
-        F: Fn(&T, Ref<D1>, Ref<D2>, Ref<D3>) -> bool + 'static,
+        F: Fn(&T, Ref<D1>, Ref<D2>, Ref<D3>) -> bool + Value + 'static,
         M: AsRef<str>,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
     {
-        let action = Rc::new(action);
-        let name = self.name.clone();
-        let failure_message = message_or_default(failure_message);
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(
+            transient::<dyn ValidateOptions<T>, _Validate3<T, F, D1, D2, D3>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .from(move |sp| {
+                    Ref::new(_Validate3::new(
+                        name.clone(),
+                        message_or_default(sp, &failure_message),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate3::new(
-                name.clone(),
-                failure_message.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                sp.get_required::<D3>(),
-                action.clone(),
-            ));
-            validate
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Validate,
+            &self.names,
+            vec![Type::of::<D1>(), Type::of::<D2>(), Type::of::<D3>()],
+        );
 
         self
     }
@@ -496,29 +2250,47 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `failure_message` - The message used when validation fails
     pub fn validate4<F, M, D1, D2, D3, D4>(self, action: F, failure_message: M) -> Self
     where
-        F: Fn(&T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>) -> bool + 'static,
+        F: Fn(&T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>) -> bool + Value + 'static,
         M: AsRef<str>,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
-        D4: 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
+        D4: Value + 'static,
     {
-        let action = Rc::new(action);
-        let name = self.name.clone();
-        let failure_message = message_or_default(failure_message);
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(
+            transient::<dyn ValidateOptions<T>, _Validate4<T, F, D1, D2, D3, D4>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .depends_on(exactly_one::<D4>())
+                .from(move |sp| {
+                    Ref::new(_Validate4::new(
+                        name.clone(),
+                        message_or_default(sp, &failure_message),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        sp.get_required::<D4>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate4::new(
-                name.clone(),
-                failure_message.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                sp.get_required::<D3>(),
-                sp.get_required::<D4>(),
-                action.clone(),
-            ));
-            validate
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Validate,
+            &self.names,
+            vec![
+                Type::of::<D1>(),
+                Type::of::<D2>(),
+                Type::of::<D3>(),
+                Type::of::<D4>(),
+            ],
+        );
 
         self
     }
@@ -531,64 +2303,110 @@ impl<'a, T: 'static> OptionsBuilder<'a, T> {
     /// * `failure_message` - The message used when validation fails
     pub fn validate5<F, M, D1, D2, D3, D4, D5>(self, action: F, failure_message: M) -> Self
     where
-        F: Fn(
-                &T,
-                Ref<D1>,
-                Ref<D2>,
-                Ref<D3>,
-                Ref<D4>,
-                Ref<D5>,
-            ) -> bool
-            + 'static,
+        F: Fn(&T, Ref<D1>, Ref<D2>, Ref<D3>, Ref<D4>, Ref<D5>) -> bool + Value + 'static,
         M: AsRef<str>,
-        D1: 'static,
-        D2: 'static,
-        D3: 'static,
-        D4: 'static,
-        D5: 'static,
+        D1: Value + 'static,
+        D2: Value + 'static,
+        D3: Value + 'static,
+        D4: Value + 'static,
+        D5: Value + 'static,
     {
-        let action = Rc::new(action);
-        let name = self.name.clone();
-        let failure_message = message_or_default(failure_message);
+        let action = Ref::new(action);
+        let name = self.matcher();
+        let failure_message = failure_message.as_ref().to_owned();
+
+        self.services.add(
+            transient::<dyn ValidateOptions<T>, _Validate5<T, F, D1, D2, D3, D4, D5>>()
+                .depends_on(exactly_one::<D1>())
+                .depends_on(exactly_one::<D2>())
+                .depends_on(exactly_one::<D3>())
+                .depends_on(exactly_one::<D4>())
+                .depends_on(exactly_one::<D5>())
+                .from(move |sp| {
+                    Ref::new(_Validate5::new(
+                        name.clone(),
+                        message_or_default(sp, &failure_message),
+                        sp.get_required::<D1>(),
+                        sp.get_required::<D2>(),
+                        sp.get_required::<D3>(),
+                        sp.get_required::<D4>(),
+                        sp.get_required::<D5>(),
+                        action.clone(),
+                    ))
+                }),
+        );
 
-        self.services.add(transient_factory(move |sp| {
-            let validate: Ref<dyn ValidateOptions<T>> = Ref::new(_Validate5::new(
-                name.clone(),
-                failure_message.clone(),
-                sp.get_required::<D1>(),
-                sp.get_required::<D2>(),
-                sp.get_required::<D3>(),
-                sp.get_required::<D4>(),
-                sp.get_required::<D5>(),
-                action.clone(),
-            ));
-            validate
-        }));
+        register::<T>(
+            self.services,
+            OptionsRole::Validate,
+            &self.names,
+            vec![
+                Type::of::<D1>(),
+                Type::of::<D2>(),
+                Type::of::<D3>(),
+                Type::of::<D4>(),
+                Type::of::<D5>(),
+            ],
+        );
 
         self
     }
 }
 
-fn names_equal(name: Option<&str>, other_name: Option<&str>) -> bool {
-    let matches_all = name.is_none();
+fn register<T: 'static>(
+    services: &mut ServiceCollection,
+    role: OptionsRole,
+    names: &[String],
+    dependencies: Vec<Type>,
+) {
+    let registration = |name: Option<&str>| {
+        dependencies.iter().cloned().fold(
+            OptionsRegistration::new::<T>(name).for_role(role),
+            OptionsRegistration::depends_on,
+        )
+    };
+
+    if names.is_empty() {
+        services.add(existing_as_self(registration(None)));
+    } else {
+        for name in names {
+            services.add(existing_as_self(registration(Some(name))));
+        }
+    }
+}
+
+#[derive(Clone)]
+// names are shared by every wrapper created by the same builder rather than copied
+struct NameMatcher {
+    names: Arc<[String]>,
+    comparer: OptionsNameComparer,
+}
 
-    if matches_all || name == other_name {
-        return true;
-    } else if other_name.is_none() {
-        return false;
+impl NameMatcher {
+    fn new(names: Arc<[String]>, comparer: OptionsNameComparer) -> Self {
+        Self { names, comparer }
     }
 
-    let name1 = name.unwrap();
-    let name2 = other_name.unwrap();
+    fn is_every_name(&self) -> bool {
+        self.names.is_empty()
+    }
 
-    (name1.len() == name2.len())
-        && ((name1.to_uppercase() == name2.to_uppercase())
-            || (name1.to_lowercase() == name2.to_lowercase()))
+    fn matches(&self, other_name: Option<&str>) -> bool {
+        if self.names.is_empty() {
+            true
+        } else if let Some(other_name) = other_name {
+            self.names
+                .iter()
+                .any(|name| self.comparer.equals(name, other_name))
+        } else {
+            false
+        }
+    }
 }
 
-impl<'a, T> Into<&'a mut ServiceCollection> for OptionsBuilder<'a, T> {
-    fn into(self) -> &'a mut ServiceCollection {
-        self.services
+impl<'a, T> From<OptionsBuilder<'a, T>> for &'a mut ServiceCollection {
+    fn from(builder: OptionsBuilder<'a, T>) -> Self {
+        builder.services
     }
 }
 
@@ -606,11 +2424,130 @@ impl<'a, T> DerefMut for OptionsBuilder<'a, T> {
     }
 }
 
+struct _Configurer<C> {
+    name: NameMatcher,
+    configurer: C,
+}
+
+impl<C> _Configurer<C> {
+    fn new(name: NameMatcher, configurer: C) -> Self {
+        Self { name, configurer }
+    }
+}
+
+impl<TOptions, C: ConfigureOptions<TOptions>> ConfigureOptions<TOptions> for _Configurer<C> {
+    fn configure(&self, name: Option<&str>, options: &mut TOptions) {
+        if self.name.matches(name) {
+            self.configurer.configure(name, options)
+        }
+    }
+
+    fn try_configure(&self, name: Option<&str>, options: &mut TOptions) -> Result<(), String> {
+        if self.name.matches(name) {
+            self.configurer.try_configure(name, options)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+impl<TOptions, C: PostConfigureOptions<TOptions>> PostConfigureOptions<TOptions>
+    for _Configurer<C>
+{
+    fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
+        if self.name.matches(name) {
+            self.configurer.post_configure(name, options)
+        }
+    }
+
+    fn try_post_configure(&self, name: Option<&str>, options: &mut TOptions) -> Result<(), String> {
+        if self.name.matches(name) {
+            self.configurer.try_post_configure(name, options)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+impl<TOptions, C: ValidateOptions<TOptions>> ValidateOptions<TOptions> for _Configurer<C> {
+    fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
+        if self.name.matches(name) {
+            self.configurer.validate(name, options)
+        } else {
+            ValidateOptionsResult::skip()
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+struct _NamedOptions<T: Value> {
+    name: Option<String>,
+    manager: Ref<OptionsManager<T>>,
+}
+
+impl<T: Value> _NamedOptions<T> {
+    fn new(name: Option<String>, manager: Ref<OptionsManager<T>>) -> Self {
+        Self { name, manager }
+    }
+}
+
+impl<T: Value> Options<T> for _NamedOptions<T> {
+    fn value(&self) -> Ref<T> {
+        self.manager.get(self.name.as_deref())
+    }
+}
+
+struct _ConfigureFn<TOptions> {
+    name: NameMatcher,
+    action: fn(&mut TOptions),
+}
+
+impl<TOptions> _ConfigureFn<TOptions> {
+    fn new(name: NameMatcher, action: fn(&mut TOptions)) -> Self {
+        Self { name, action }
+    }
+}
+
+impl<TOptions: Value> ConfigureOptions<TOptions> for _ConfigureFn<TOptions> {
+    fn configure(&self, name: Option<&str>, options: &mut TOptions) {
+        if self.name.matches(name) {
+            (self.action)(options)
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+impl<TOptions: Value> PostConfigureOptions<TOptions> for _ConfigureFn<TOptions> {
+    fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
+        if self.name.matches(name) {
+            (self.action)(options)
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
 struct _Configure<TOptions, TAction>
 where
     TAction: Fn(&mut TOptions),
 {
-    name: Option<String>,
+    name: NameMatcher,
     action: TAction,
     _marker: PhantomData<TOptions>,
 }
@@ -619,7 +2556,7 @@ impl<TOptions, TAction> _Configure<TOptions, TAction>
 where
     TAction: Fn(&mut TOptions),
 {
-    fn new(name: Option<String>, action: TAction) -> Self {
+    fn new(name: NameMatcher, action: TAction) -> Self {
         Self {
             name,
             action,
@@ -630,32 +2567,108 @@ where
 
 impl<TOptions, TAction> ConfigureOptions<TOptions> for _Configure<TOptions, TAction>
 where
-    TAction: Fn(&mut TOptions),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions) + Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(options)
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 impl<TOptions, TAction> PostConfigureOptions<TOptions> for _Configure<TOptions, TAction>
 where
-    TAction: Fn(&mut TOptions),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions) + Value,
+{
+    fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
+        if self.name.matches(name) {
+            (self.action)(options)
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+struct _TryConfigure<TOptions, TAction>
+where
+    TAction: Fn(&mut TOptions) -> Result<(), String>,
+{
+    name: NameMatcher,
+    action: TAction,
+    _marker: PhantomData<TOptions>,
+}
+
+impl<TOptions, TAction> _TryConfigure<TOptions, TAction>
+where
+    TAction: Fn(&mut TOptions) -> Result<(), String>,
+{
+    fn new(name: NameMatcher, action: TAction) -> Self {
+        Self {
+            name,
+            action,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<TOptions, TAction> ConfigureOptions<TOptions> for _TryConfigure<TOptions, TAction>
+where
+    TOptions: Value,
+    TAction: Fn(&mut TOptions) -> Result<(), String> + Value,
+{
+    fn configure(&self, name: Option<&str>, options: &mut TOptions) {
+        let _ = self.try_configure(name, options);
+    }
+
+    fn try_configure(&self, name: Option<&str>, options: &mut TOptions) -> Result<(), String> {
+        if self.name.matches(name) {
+            (self.action)(options)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+impl<TOptions, TAction> PostConfigureOptions<TOptions> for _TryConfigure<TOptions, TAction>
+where
+    TOptions: Value,
+    TAction: Fn(&mut TOptions) -> Result<(), String> + Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        let _ = self.try_post_configure(name, options);
+    }
+
+    fn try_post_configure(&self, name: Option<&str>, options: &mut TOptions) -> Result<(), String> {
+        if self.name.matches(name) {
             (self.action)(options)
+        } else {
+            Ok(())
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 struct _Configure1<TOptions, TAction, TDep>
 where
     TAction: Fn(&mut TOptions, Ref<TDep>),
 {
-    name: Option<String>,
-    action: Rc<TAction>,
+    name: NameMatcher,
+    action: Ref<TAction>,
     dependency: Ref<TDep>,
     _marker: PhantomData<TOptions>,
 }
@@ -664,7 +2677,7 @@ impl<TOptions, TAction, TDep> _Configure1<TOptions, TAction, TDep>
 where
     TAction: Fn(&mut TOptions, Ref<TDep>),
 {
-    fn new(name: Option<String>, dependency: Ref<TDep>, action: Rc<TAction>) -> Self {
+    fn new(name: NameMatcher, dependency: Ref<TDep>, action: Ref<TAction>) -> Self {
         Self {
             name,
             action,
@@ -676,33 +2689,45 @@ where
 
 impl<TOptions, TAction, TDep> ConfigureOptions<TOptions> for _Configure1<TOptions, TAction, TDep>
 where
-    TAction: Fn(&mut TOptions, Ref<TDep>),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep>) + Value,
+    TDep: Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(options, self.dependency.clone())
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 impl<TOptions, TAction, TDep> PostConfigureOptions<TOptions>
     for _Configure1<TOptions, TAction, TDep>
 where
-    TAction: Fn(&mut TOptions, Ref<TDep>),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep>) + Value,
+    TDep: Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(options, self.dependency.clone())
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 struct _Configure2<TOptions, TAction, TDep1, TDep2>
 where
     TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>),
 {
-    name: Option<String>,
-    action: Rc<TAction>,
+    name: NameMatcher,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     _marker: PhantomData<TOptions>,
@@ -713,10 +2738,10 @@ where
     TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>),
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -731,33 +2756,47 @@ where
 impl<TOptions, TAction, TDep1, TDep2> ConfigureOptions<TOptions>
     for _Configure2<TOptions, TAction, TDep1, TDep2>
 where
-    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>) + Value,
+    TDep1: Value,
+    TDep2: Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(options, self.dependency1.clone(), self.dependency2.clone())
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 impl<TOptions, TAction, TDep1, TDep2> PostConfigureOptions<TOptions>
     for _Configure2<TOptions, TAction, TDep1, TDep2>
 where
-    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>) + Value,
+    TDep1: Value,
+    TDep2: Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(options, self.dependency1.clone(), self.dependency2.clone())
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 struct _Configure3<TOptions, TAction, TDep1, TDep2, TDep3>
 where
     TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>),
 {
-    name: Option<String>,
-    action: Rc<TAction>,
+    name: NameMatcher,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     dependency3: Ref<TDep3>,
@@ -769,11 +2808,11 @@ where
     TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>),
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
         dependency3: Ref<TDep3>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -789,10 +2828,14 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3> ConfigureOptions<TOptions>
     for _Configure3<TOptions, TAction, TDep1, TDep2, TDep3>
 where
-    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>) + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -801,15 +2844,23 @@ where
             )
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 impl<TOptions, TAction, TDep1, TDep2, TDep3> PostConfigureOptions<TOptions>
     for _Configure3<TOptions, TAction, TDep1, TDep2, TDep3>
 where
-    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>) + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -818,20 +2869,18 @@ where
             )
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 struct _Configure4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ),
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>),
 {
-    name: Option<String>,
-    action: Rc<TAction>,
+    name: NameMatcher,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     dependency3: Ref<TDep3>,
@@ -842,21 +2891,15 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
     _Configure4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ),
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>),
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
         dependency3: Ref<TDep3>,
         dependency4: Ref<TDep4>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -873,16 +2916,15 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4> ConfigureOptions<TOptions>
     for _Configure4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>) + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
+    TDep4: Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -892,21 +2934,24 @@ where
             )
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4> PostConfigureOptions<TOptions>
     for _Configure4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>) + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
+    TDep4: Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -916,21 +2961,18 @@ where
             )
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 struct _Configure5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ),
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>),
 {
-    name: Option<String>,
-    action: Rc<TAction>,
+    name: NameMatcher,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     dependency3: Ref<TDep3>,
@@ -942,23 +2984,16 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
     _Configure5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ),
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>),
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
         dependency3: Ref<TDep3>,
         dependency4: Ref<TDep4>,
         dependency5: Ref<TDep5>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -976,17 +3011,16 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5> ConfigureOptions<TOptions>
     for _Configure5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>) + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
+    TDep4: Value,
+    TDep5: Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -997,22 +3031,25 @@ where
             )
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5> PostConfigureOptions<TOptions>
     for _Configure5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &mut TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ),
+    TOptions: Value,
+    TAction: Fn(&mut TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>) + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
+    TDep4: Value,
+    TDep5: Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -1023,15 +3060,27 @@ where
             )
         }
     }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
 }
 
-fn message_or_default<T: AsRef<str>>(message: T) -> String {
-    let msg = message.as_ref();
+#[cfg(feature = "bus")]
+fn subscribe(subscriber: &dyn BusSubscriber, topic: &str) -> Arc<BusWatch> {
+    BusWatch::subscribe(subscriber, topic).unwrap_or_else(|error| {
+        panic!(
+            "The topic '{}' could not be subscribed to: {}",
+            topic, error
+        )
+    })
+}
 
-    if msg.is_empty() {
-        String::from("A validation error has occurred.")
+fn message_or_default(provider: &ServiceProvider, message: &str) -> String {
+    if message.is_empty() {
+        messages(provider).validation_failed()
     } else {
-        String::from(msg)
+        String::from(message)
     }
 }
 
@@ -1039,9 +3088,9 @@ struct _Validate<TOptions, TAction>
 where
     TAction: Fn(&TOptions) -> bool,
 {
-    name: Option<String>,
+    name: NameMatcher,
     failure_message: String,
-    action: TAction,
+    action: Ref<TAction>,
     _marker: PhantomData<TOptions>,
 }
 
@@ -1049,7 +3098,7 @@ impl<TOptions, TAction> _Validate<TOptions, TAction>
 where
     TAction: Fn(&TOptions) -> bool,
 {
-    fn new(name: Option<String>, failure_message: String, action: TAction) -> Self {
+    fn new(name: NameMatcher, failure_message: String, action: Ref<TAction>) -> Self {
         Self {
             name,
             failure_message,
@@ -1061,18 +3110,57 @@ where
 
 impl<TOptions, TAction> ValidateOptions<TOptions> for _Validate<TOptions, TAction>
 where
-    TAction: Fn(&TOptions) -> bool,
+    TOptions: Value,
+    TAction: Fn(&TOptions) -> bool + Value,
 {
     fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             if (self.action)(options) {
-                return ValidateOptionsResult::success();
+                ValidateOptionsResult::success()
             } else {
-                return ValidateOptionsResult::fail(&self.failure_message);
+                ValidateOptionsResult::fail(&self.failure_message)
             }
+        } else {
+            ValidateOptionsResult::skip()
+        }
+    }
+
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
+    }
+}
+
+struct _ValidateRule<TOptions, TRule> {
+    name: NameMatcher,
+    rule: TRule,
+    _marker: PhantomData<TOptions>,
+}
+
+impl<TOptions, TRule> _ValidateRule<TOptions, TRule> {
+    fn new(name: NameMatcher, rule: TRule) -> Self {
+        Self {
+            name,
+            rule,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<TOptions, TRule> ValidateOptions<TOptions> for _ValidateRule<TOptions, TRule>
+where
+    TOptions: Value,
+    TRule: ValidationRule<TOptions>,
+{
+    fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
+        if self.name.matches(name) {
+            self.rule.check(options)
+        } else {
+            ValidateOptionsResult::skip()
         }
+    }
 
-        return ValidateOptionsResult::skip();
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
     }
 }
 
@@ -1080,9 +3168,9 @@ struct _Validate1<TOptions, TAction, TDep>
 where
     TAction: Fn(&TOptions, Ref<TDep>) -> bool,
 {
-    name: Option<String>,
+    name: NameMatcher,
     failure_message: String,
-    action: Rc<TAction>,
+    action: Ref<TAction>,
     dependency1: Ref<TDep>,
     _marker: PhantomData<TOptions>,
 }
@@ -1092,10 +3180,10 @@ where
     TAction: Fn(&TOptions, Ref<TDep>) -> bool,
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         failure_message: String,
         dependency1: Ref<TDep>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -1109,18 +3197,24 @@ where
 
 impl<TOptions, TAction, TDep> ValidateOptions<TOptions> for _Validate1<TOptions, TAction, TDep>
 where
-    TAction: Fn(&TOptions, Ref<TDep>) -> bool,
+    TOptions: Value,
+    TAction: Fn(&TOptions, Ref<TDep>) -> bool + Value,
+    TDep: Value,
 {
     fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             if (self.action)(options, self.dependency1.clone()) {
-                return ValidateOptionsResult::success();
+                ValidateOptionsResult::success()
             } else {
-                return ValidateOptionsResult::fail(&self.failure_message);
+                ValidateOptionsResult::fail(&self.failure_message)
             }
+        } else {
+            ValidateOptionsResult::skip()
         }
+    }
 
-        return ValidateOptionsResult::skip();
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
     }
 }
 
@@ -1128,9 +3222,9 @@ struct _Validate2<TOptions, TAction, TDep1, TDep2>
 where
     TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>) -> bool,
 {
-    name: Option<String>,
+    name: NameMatcher,
     failure_message: String,
-    action: Rc<TAction>,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     _marker: PhantomData<TOptions>,
@@ -1141,11 +3235,11 @@ where
     TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>) -> bool,
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         failure_message: String,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -1161,18 +3255,25 @@ where
 impl<TOptions, TAction, TDep1, TDep2> ValidateOptions<TOptions>
     for _Validate2<TOptions, TAction, TDep1, TDep2>
 where
-    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>) -> bool,
+    TOptions: Value,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>) -> bool + Value,
+    TDep1: Value,
+    TDep2: Value,
 {
     fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             if (self.action)(options, self.dependency1.clone(), self.dependency2.clone()) {
-                return ValidateOptionsResult::success();
+                ValidateOptionsResult::success()
             } else {
-                return ValidateOptionsResult::fail(&self.failure_message);
+                ValidateOptionsResult::fail(&self.failure_message)
             }
+        } else {
+            ValidateOptionsResult::skip()
         }
+    }
 
-        return ValidateOptionsResult::skip();
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
     }
 }
 
@@ -1180,9 +3281,9 @@ struct _Validate3<TOptions, TAction, TDep1, TDep2, TDep3>
 where
     TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>) -> bool,
 {
-    name: Option<String>,
+    name: NameMatcher,
     failure_message: String,
-    action: Rc<TAction>,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     dependency3: Ref<TDep3>,
@@ -1194,12 +3295,12 @@ where
     TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>) -> bool,
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         failure_message: String,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
         dependency3: Ref<TDep3>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -1216,39 +3317,41 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3> ValidateOptions<TOptions>
     for _Validate3<TOptions, TAction, TDep1, TDep2, TDep3>
 where
-    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>) -> bool,
+    TOptions: Value,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>) -> bool + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
 {
     fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             if (self.action)(
                 options,
                 self.dependency1.clone(),
                 self.dependency2.clone(),
                 self.dependency3.clone(),
             ) {
-                return ValidateOptionsResult::success();
+                ValidateOptionsResult::success()
             } else {
-                return ValidateOptionsResult::fail(&self.failure_message);
+                ValidateOptionsResult::fail(&self.failure_message)
             }
+        } else {
+            ValidateOptionsResult::skip()
         }
+    }
 
-        return ValidateOptionsResult::skip();
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
     }
 }
 
 struct _Validate4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ) -> bool,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>) -> bool,
 {
-    name: Option<String>,
+    name: NameMatcher,
     failure_message: String,
-    action: Rc<TAction>,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     dependency3: Ref<TDep3>,
@@ -1259,22 +3362,16 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
     _Validate4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ) -> bool,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>) -> bool,
 {
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         failure_message: String,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
         dependency3: Ref<TDep3>,
         dependency4: Ref<TDep4>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -1292,16 +3389,15 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4> ValidateOptions<TOptions>
     for _Validate4<TOptions, TAction, TDep1, TDep2, TDep3, TDep4>
 where
-    TAction: Fn(
-        &TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-    ) -> bool,
+    TOptions: Value,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>) -> bool + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
+    TDep4: Value,
 {
     fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             if (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -1309,30 +3405,27 @@ where
                 self.dependency3.clone(),
                 self.dependency4.clone(),
             ) {
-                return ValidateOptionsResult::success();
+                ValidateOptionsResult::success()
             } else {
-                return ValidateOptionsResult::fail(&self.failure_message);
+                ValidateOptionsResult::fail(&self.failure_message)
             }
+        } else {
+            ValidateOptionsResult::skip()
         }
+    }
 
-        return ValidateOptionsResult::skip();
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
     }
 }
 
 struct _Validate5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ) -> bool,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>) -> bool,
 {
-    name: Option<String>,
+    name: NameMatcher,
     failure_message: String,
-    action: Rc<TAction>,
+    action: Ref<TAction>,
     dependency1: Ref<TDep1>,
     dependency2: Ref<TDep2>,
     dependency3: Ref<TDep3>,
@@ -1344,24 +3437,18 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
     _Validate5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ) -> bool,
+    TAction: Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>) -> bool,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
-        name: Option<String>,
+        name: NameMatcher,
         failure_message: String,
         dependency1: Ref<TDep1>,
         dependency2: Ref<TDep2>,
         dependency3: Ref<TDep3>,
         dependency4: Ref<TDep4>,
         dependency5: Ref<TDep5>,
-        action: Rc<TAction>,
+        action: Ref<TAction>,
     ) -> Self {
         Self {
             name,
@@ -1380,17 +3467,17 @@ where
 impl<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5> ValidateOptions<TOptions>
     for _Validate5<TOptions, TAction, TDep1, TDep2, TDep3, TDep4, TDep5>
 where
-    TAction: Fn(
-        &TOptions,
-        Ref<TDep1>,
-        Ref<TDep2>,
-        Ref<TDep3>,
-        Ref<TDep4>,
-        Ref<TDep5>,
-    ) -> bool,
+    TOptions: Value,
+    TAction:
+        Fn(&TOptions, Ref<TDep1>, Ref<TDep2>, Ref<TDep3>, Ref<TDep4>, Ref<TDep5>) -> bool + Value,
+    TDep1: Value,
+    TDep2: Value,
+    TDep3: Value,
+    TDep4: Value,
+    TDep5: Value,
 {
     fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
-        if names_equal(self.name.as_deref(), name) {
+        if self.name.matches(name) {
             if (self.action)(
                 options,
                 self.dependency1.clone(),
@@ -1399,12 +3486,264 @@ where
                 self.dependency4.clone(),
                 self.dependency5.clone(),
             ) {
-                return ValidateOptionsResult::success();
+                ValidateOptionsResult::success()
             } else {
-                return ValidateOptionsResult::fail(&self.failure_message);
+                ValidateOptionsResult::fail(&self.failure_message)
             }
+        } else {
+            ValidateOptionsResult::skip()
         }
+    }
 
-        return ValidateOptionsResult::skip();
+    fn applies_to_every_name(&self) -> bool {
+        self.name.is_every_name()
     }
 }
+
+// the functions and types for more than five dependencies are identical aside
+// from their arity so they are generated rather than written out by hand
+macro_rules! configure_with_dependencies {
+    ($count:literal, $configure:ident, $post_configure:ident, $validate:ident,
+     $configure_type:ident, $validate_type:ident; $($dep:ident $index:tt),+) => {
+        impl<'a, T: Value + 'static> OptionsBuilder<'a, T> {
+            #[doc = concat!("Registers an action used to configure a particular type of [`Options`](crate::Options) with ", $count, " dependencies.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `setup` - The configuration action
+            pub fn $configure<F, $($dep),+>(self, setup: F) -> Self
+            where
+                F: Fn(&mut T, $(Ref<$dep>),+) + Value + 'static,
+                $($dep: Value + 'static),+
+            {
+                let action = Ref::new(setup);
+                let name = self.matcher();
+
+                self.services.add(
+                    transient::<dyn ConfigureOptions<T>, $configure_type<T, F, $($dep),+>>()
+                        $(.depends_on(exactly_one::<$dep>()))+
+                        .from(move |sp| {
+                            Ref::new($configure_type::new(
+                                name.clone(),
+                                ($(sp.get_required::<$dep>(),)+),
+                                action.clone(),
+                            ))
+                        }),
+                );
+
+                register::<T>(
+                    self.services,
+                    OptionsRole::Configure,
+                    &self.names,
+                    vec![$(Type::of::<$dep>()),+],
+                );
+
+                self
+            }
+
+            #[doc = concat!("Registers an action used to post-configure a particular type of [`Options`](crate::Options) with ", $count, " dependencies.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `setup` - The configuration action
+            pub fn $post_configure<F, $($dep),+>(self, setup: F) -> Self
+            where
+                F: Fn(&mut T, $(Ref<$dep>),+) + Value + 'static,
+                $($dep: Value + 'static),+
+            {
+                let action = Ref::new(setup);
+                let name = self.matcher();
+
+                self.services.add(
+                    transient::<dyn PostConfigureOptions<T>, $configure_type<T, F, $($dep),+>>()
+                        $(.depends_on(exactly_one::<$dep>()))+
+                        .from(move |sp| {
+                            Ref::new($configure_type::new(
+                                name.clone(),
+                                ($(sp.get_required::<$dep>(),)+),
+                                action.clone(),
+                            ))
+                        }),
+                );
+
+                register::<T>(
+                    self.services,
+                    OptionsRole::PostConfigure,
+                    &self.names,
+                    vec![$(Type::of::<$dep>()),+],
+                );
+
+                self
+            }
+
+            #[doc = concat!("Registers an action used to validate a particular type of [`Options`](crate::Options) with ", $count, " dependencies.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `action` - The validation action
+            /// * `failure_message` - The message used when validation fails
+            pub fn $validate<F, M, $($dep),+>(self, action: F, failure_message: M) -> Self
+            where
+                F: Fn(&T, $(Ref<$dep>),+) -> bool + Value + 'static,
+                M: AsRef<str>,
+                $($dep: Value + 'static),+
+            {
+                let action = Ref::new(action);
+                let name = self.matcher();
+                let failure_message = failure_message.as_ref().to_owned();
+
+                self.services.add(
+                    transient::<dyn ValidateOptions<T>, $validate_type<T, F, $($dep),+>>()
+                        $(.depends_on(exactly_one::<$dep>()))+
+                        .from(move |sp| {
+                            Ref::new($validate_type::new(
+                                name.clone(),
+                                message_or_default(sp, &failure_message),
+                                ($(sp.get_required::<$dep>(),)+),
+                                action.clone(),
+                            ))
+                        }),
+                );
+
+                register::<T>(
+                    self.services,
+                    OptionsRole::Validate,
+                    &self.names,
+                    vec![$(Type::of::<$dep>()),+],
+                );
+
+                self
+            }
+        }
+
+        struct $configure_type<TOptions, TAction, $($dep),+>
+        where
+            TAction: Fn(&mut TOptions, $(Ref<$dep>),+),
+        {
+            name: NameMatcher,
+            action: Ref<TAction>,
+            dependencies: ($(Ref<$dep>,)+),
+            _marker: PhantomData<TOptions>,
+        }
+
+        impl<TOptions, TAction, $($dep),+> $configure_type<TOptions, TAction, $($dep),+>
+        where
+            TAction: Fn(&mut TOptions, $(Ref<$dep>),+),
+        {
+            fn new(name: NameMatcher, dependencies: ($(Ref<$dep>,)+), action: Ref<TAction>) -> Self {
+                Self {
+                    name,
+                    action,
+                    dependencies,
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<TOptions, TAction, $($dep),+> ConfigureOptions<TOptions>
+            for $configure_type<TOptions, TAction, $($dep),+>
+        where
+            TOptions: Value,
+            TAction: Fn(&mut TOptions, $(Ref<$dep>),+) + Value,
+            $($dep: Value,)+
+        {
+            fn configure(&self, name: Option<&str>, options: &mut TOptions) {
+                if self.name.matches(name) {
+                    (self.action)(options, $(self.dependencies.$index.clone()),+)
+                }
+            }
+
+            fn applies_to_every_name(&self) -> bool {
+                self.name.is_every_name()
+            }
+        }
+
+        impl<TOptions, TAction, $($dep),+> PostConfigureOptions<TOptions>
+            for $configure_type<TOptions, TAction, $($dep),+>
+        where
+            TOptions: Value,
+            TAction: Fn(&mut TOptions, $(Ref<$dep>),+) + Value,
+            $($dep: Value,)+
+        {
+            fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
+                if self.name.matches(name) {
+                    (self.action)(options, $(self.dependencies.$index.clone()),+)
+                }
+            }
+
+            fn applies_to_every_name(&self) -> bool {
+                self.name.is_every_name()
+            }
+        }
+
+        struct $validate_type<TOptions, TAction, $($dep),+>
+        where
+            TAction: Fn(&TOptions, $(Ref<$dep>),+) -> bool,
+        {
+            name: NameMatcher,
+            failure_message: String,
+            action: Ref<TAction>,
+            dependencies: ($(Ref<$dep>,)+),
+            _marker: PhantomData<TOptions>,
+        }
+
+        impl<TOptions, TAction, $($dep),+> $validate_type<TOptions, TAction, $($dep),+>
+        where
+            TAction: Fn(&TOptions, $(Ref<$dep>),+) -> bool,
+        {
+            fn new(
+                name: NameMatcher,
+                failure_message: String,
+                dependencies: ($(Ref<$dep>,)+),
+                action: Ref<TAction>,
+            ) -> Self {
+                Self {
+                    name,
+                    failure_message,
+                    action,
+                    dependencies,
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<TOptions, TAction, $($dep),+> ValidateOptions<TOptions>
+            for $validate_type<TOptions, TAction, $($dep),+>
+        where
+            TOptions: Value,
+            TAction: Fn(&TOptions, $(Ref<$dep>),+) -> bool + Value,
+            $($dep: Value,)+
+        {
+            fn validate(&self, name: Option<&str>, options: &TOptions) -> ValidateOptionsResult {
+                if self.name.matches(name) {
+                    if (self.action)(options, $(self.dependencies.$index.clone()),+) {
+                        ValidateOptionsResult::success()
+                    } else {
+                        ValidateOptionsResult::fail(&self.failure_message)
+                    }
+                } else {
+                    ValidateOptionsResult::skip()
+                }
+            }
+
+            fn applies_to_every_name(&self) -> bool {
+                self.name.is_every_name()
+            }
+        }
+    };
+}
+
+configure_with_dependencies!("six", configure6, post_configure6, validate6, _Configure6, _Validate6;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5);
+configure_with_dependencies!("seven", configure7, post_configure7, validate7, _Configure7, _Validate7;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5, D7 6);
+configure_with_dependencies!("eight", configure8, post_configure8, validate8, _Configure8, _Validate8;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5, D7 6, D8 7);
+configure_with_dependencies!("nine", configure9, post_configure9, validate9, _Configure9, _Validate9;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5, D7 6, D8 7, D9 8);
+configure_with_dependencies!("ten", configure10, post_configure10, validate10, _Configure10, _Validate10;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5, D7 6, D8 7, D9 8, D10 9);
+configure_with_dependencies!("eleven", configure11, post_configure11, validate11, _Configure11, _Validate11;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5, D7 6, D8 7, D9 8, D10 9, D11 10);
+configure_with_dependencies!("twelve", configure12, post_configure12, validate12, _Configure12, _Validate12;
+    D1 0, D2 1, D3 2, D4 3, D5 4, D6 5, D7 6, D8 7, D9 8, D10 9, D11 10, D12 11);
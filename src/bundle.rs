@@ -0,0 +1,303 @@
+use crate::{OptionsDump, OptionsMonitorCache, OptionsNameComparer, ValidateOptionsResult, Value};
+use di::ServiceProvider;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde_json::{Error, Value as Json};
+
+/// Represents a support bundle of the effective configuration [`Options`](crate::Options) of an
+/// application.
+///
+/// # Remarks
+///
+/// A bundle is created with [`export_snapshot`] and applied with [`import_snapshot`], which makes
+/// it possible to reproduce the effective configuration of one process in another. Secrets are
+/// masked when a bundle is exported and are imported as their masked values.
+pub struct OptionsBundle {
+    entries: Vec<OptionsBundleEntry>,
+}
+
+/// Represents the serialized value of a single configuration [`Options`](crate::Options) instance
+/// in an [`OptionsBundle`].
+pub struct OptionsBundleEntry {
+    options_type: String,
+    name: Option<String>,
+    value: Json,
+}
+
+impl OptionsBundleEntry {
+    /// Gets the name of the type of options.
+    pub fn options_type(&self) -> &str {
+        &self.options_type
+    }
+
+    /// Gets the name of the options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the serialized value of the options.
+    pub fn value(&self) -> &Json {
+        &self.value
+    }
+}
+
+impl OptionsBundle {
+    /// Gets the bundled options.
+    pub fn entries(&self) -> &[OptionsBundleEntry] {
+        &self.entries
+    }
+
+    /// Serializes the bundle to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Serializes the bundle to pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Deserializes a bundle from JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON produced by [`to_json`](OptionsBundle::to_json)
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let entries = match serde_json::from_str::<Json>(json)? {
+            Json::Array(entries) => entries,
+            _ => return Err(Error::custom("an options bundle must be a sequence")),
+        };
+
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                let options_type = match entry.get("type") {
+                    Some(Json::String(options_type)) => options_type.clone(),
+                    _ => return Err(Error::missing_field("type")),
+                };
+                let name = match entry.get("name") {
+                    Some(Json::String(name)) => Some(name.clone()),
+                    Some(Json::Null) | None => None,
+                    _ => return Err(Error::custom("the name of options must be a string")),
+                };
+                let value = match entry.get_mut("value") {
+                    Some(value) => value.take(),
+                    None => return Err(Error::missing_field("value")),
+                };
+
+                Ok(OptionsBundleEntry {
+                    options_type,
+                    name,
+                    value,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(|entries| Self { entries })
+    }
+}
+
+impl Serialize for OptionsBundle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.entries.len()))?;
+
+        for entry in &self.entries {
+            seq.serialize_element(entry)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl Serialize for OptionsBundleEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OptionsBundleEntry", 3)?;
+        state.serialize_field("type", &self.options_type)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
+
+pub(crate) struct OptionsImporter {
+    options_type: &'static str,
+    name: Option<String>,
+    import: fn(&ServiceProvider, Option<&str>, &Json) -> Result<(), String>,
+}
+
+impl OptionsImporter {
+    pub(crate) fn new<T>(name: Option<&str>) -> Self
+    where
+        T: Value + DeserializeOwned + 'static,
+    {
+        Self {
+            options_type: std::any::type_name::<T>(),
+            name: name.map(|s| s.to_owned()),
+            import: import::<T>,
+        }
+    }
+}
+
+fn import<T>(provider: &ServiceProvider, name: Option<&str>, value: &Json) -> Result<(), String>
+where
+    T: Value + DeserializeOwned + 'static,
+{
+    let options: T = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+    let cache = provider.get_required::<dyn OptionsMonitorCache<T>>();
+
+    cache.try_remove(name);
+    cache.try_add(name, options);
+    Ok(())
+}
+
+/// Exports the effective configuration [`Options`](crate::Options) to a support bundle.
+///
+/// # Arguments
+///
+/// * `provider` - The [provider](di::ServiceProvider) used to resolve the current options
+///
+/// # Remarks
+///
+/// The bundle contains the options included in the [`OptionsDump`](crate::OptionsDump) with all of
+/// their secrets masked.
+pub fn export_snapshot(provider: &ServiceProvider) -> OptionsBundle {
+    let entries = OptionsDump::new(provider)
+        .entries()
+        .iter()
+        .map(|entry| OptionsBundleEntry {
+            options_type: entry.options_type().to_owned(),
+            name: entry.name().map(|n| n.to_owned()),
+            value: entry.value().clone(),
+        })
+        .collect();
+
+    OptionsBundle { entries }
+}
+
+/// Imports the configuration [`Options`](crate::Options) in a support bundle.
+///
+/// # Arguments
+///
+/// * `provider` - The [provider](di::ServiceProvider) whose options are replaced
+/// * `bundle` - The [bundle](OptionsBundle) to import
+///
+/// # Remarks
+///
+/// Each bundled instance replaces the instance held by the [`OptionsMonitor`](crate::OptionsMonitor)
+/// until the options are reloaded. Only options added with
+/// [`add_to_bundle`](crate::OptionsBuilder::add_to_bundle) can be imported. Every other instance in
+/// the bundle is reported as a failure, but does not prevent the remaining instances from being
+/// imported.
+pub fn import_snapshot(
+    provider: &ServiceProvider,
+    bundle: &OptionsBundle,
+) -> Result<(), ValidateOptionsResult> {
+    let comparer = OptionsNameComparer::default();
+    let importers: Vec<_> = provider.get_all::<OptionsImporter>().collect();
+    let mut failures = Vec::new();
+
+    for entry in &bundle.entries {
+        let name = entry.name();
+        let importer = importers.iter().find(|i| {
+            i.options_type == entry.options_type
+                && match (i.name.as_deref(), name) {
+                    (Some(expected), Some(actual)) => comparer.equals(expected, actual),
+                    (expected, actual) => expected == actual,
+                }
+        });
+        let result = match importer {
+            Some(importer) => (importer.import)(provider, name, &entry.value),
+            None => Err("the options cannot be imported".to_owned()),
+        };
+
+        if let Err(error) = result {
+            failures.push(match name {
+                Some(name) => format!(
+                    "'{}' with the name '{}': {}",
+                    entry.options_type, name, error
+                ),
+                _ => format!("'{}': {}", entry.options_type, error),
+            });
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidateOptionsResult::fail_many(failures.iter()))
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor, RedactedSerialize};
+    use di::ServiceCollection;
+    use serde::Deserialize;
+
+    #[derive(Default, Deserialize, RedactedSerialize)]
+    struct DbOptions {
+        host: String,
+        #[options(secret)]
+        password: String,
+    }
+
+    #[derive(Default, RedactedSerialize)]
+    struct CacheOptions {
+        ttl: u32,
+    }
+
+    #[test]
+    fn import_snapshot_should_reproduce_exported_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_options::<DbOptions>("Primary")
+            .configure(|o| {
+                o.host = "db.contoso.com".into();
+                o.password = "p@ssw0rd".into();
+            })
+            .add_to_bundle();
+        services
+            .add_options::<CacheOptions>()
+            .configure(|o| o.ttl = 60)
+            .add_to_dump();
+
+        let json = export_snapshot(&services.build_provider().unwrap()).to_json();
+        let bundle = OptionsBundle::from_json(&json).unwrap();
+        let provider = ServiceCollection::new()
+            .add_named_options::<DbOptions>("Primary")
+            .configure(|o| o.host = "localhost".into())
+            .add_to_bundle()
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = import_snapshot(&provider, &bundle);
+
+        // assert
+        let monitor = provider.get_required::<dyn OptionsMonitor<DbOptions>>();
+        let options = monitor.get(Some("Primary"));
+
+        assert_eq!(bundle.entries().len(), 2);
+        assert_eq!(&options.host, "db.contoso.com");
+        assert_eq!(&options.password, "***");
+        assert!(result
+            .unwrap_err()
+            .failure_message()
+            .contains("CacheOptions"));
+    }
+
+    #[test]
+    fn from_json_should_reject_invalid_bundle() {
+        // arrange
+        let json = r#"[{ "name": null, "value": {} }]"#;
+
+        // act
+        let result = OptionsBundle::from_json(json);
+
+        // assert
+        assert!(result.is_err());
+    }
+}
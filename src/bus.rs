@@ -0,0 +1,222 @@
+use crate::{OptionsChangeTokenSource, Value};
+use std::io::Result;
+use std::marker::PhantomData;
+use std::mem::take;
+use std::sync::{Arc, Mutex};
+use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+/// Represents the function that handles the payload of a message received from a message bus.
+pub type BusHandler = Box<dyn Fn(&[u8]) + Send + Sync>;
+
+/// Defines the behavior of a subscriber to a message bus.
+///
+/// # Remarks
+///
+/// A subscriber adapts a message bus, such as NATS or Kafka, so that a message published on a
+/// topic notifies a [`BusChangeTokenSource`]. The handler may be invoked from any thread.
+pub trait BusSubscriber {
+    /// Subscribes to the messages published on a topic.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The topic to subscribe to
+    /// * `handler` - The [handler](BusHandler) invoked with the payload of each message
+    ///
+    /// # Remarks
+    ///
+    /// The handler must not be invoked after the returned [subscription](BusSubscription) is
+    /// dropped.
+    fn subscribe(&self, topic: &str, handler: BusHandler) -> Result<BusSubscription>;
+}
+
+/// Represents a subscription to a topic on a message bus.
+///
+/// # Remarks
+///
+/// The subscription is cancelled when it is dropped.
+pub struct BusSubscription(Mutex<Option<Box<dyn FnOnce() + Send>>>);
+
+impl BusSubscription {
+    /// Initializes a new message bus subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `unsubscribe` - The function called to cancel the subscription
+    pub fn new<F: FnOnce() + Send + 'static>(unsubscribe: F) -> Self {
+        Self(Mutex::new(Some(Box::new(unsubscribe))))
+    }
+}
+
+impl Drop for BusSubscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.0.lock().unwrap().take() {
+            (unsubscribe)()
+        }
+    }
+}
+
+pub(crate) struct BusWatch {
+    token: Mutex<SharedChangeToken<SingleChangeToken>>,
+    payload: Mutex<Option<Vec<u8>>>,
+    subscription: Mutex<Option<BusSubscription>>,
+}
+
+impl BusWatch {
+    pub(crate) fn subscribe(subscriber: &dyn BusSubscriber, topic: &str) -> Result<Arc<Self>> {
+        let watch = Arc::new(Self {
+            token: Default::default(),
+            payload: Default::default(),
+            subscription: Default::default(),
+        });
+        let weak = Arc::downgrade(&watch);
+        let subscription = subscriber.subscribe(
+            topic,
+            Box::new(move |payload| {
+                if let Some(watch) = weak.upgrade() {
+                    watch.notify(payload);
+                }
+            }),
+        )?;
+
+        *watch.subscription.lock().unwrap() = Some(subscription);
+        Ok(watch)
+    }
+
+    pub(crate) fn payload(&self) -> Option<Vec<u8>> {
+        self.payload.lock().unwrap().clone()
+    }
+
+    fn notify(&self, payload: &[u8]) {
+        // the payload must be available before listeners are notified
+        *self.payload.lock().unwrap() = Some(payload.to_vec());
+
+        // a token only signals once so it's replaced before it's signaled
+        let token = take(&mut *self.token.lock().unwrap());
+        token.notify();
+    }
+}
+
+/// Represents a change token source for [`Options`](crate::Options) that are notified when a
+/// message is published on a topic of a message bus.
+pub struct BusChangeTokenSource<T: Value> {
+    name: Option<String>,
+    watch: Arc<BusWatch>,
+    _data: PhantomData<T>,
+}
+
+impl<T: Value> BusChangeTokenSource<T> {
+    /// Initializes a new message bus change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being watched
+    /// * `subscriber` - The [subscriber](BusSubscriber) to the message bus
+    /// * `topic` - The topic changes are published on
+    pub fn new(name: Option<&str>, subscriber: &dyn BusSubscriber, topic: &str) -> Result<Self> {
+        Ok(Self::with_watch(
+            name,
+            BusWatch::subscribe(subscriber, topic)?,
+        ))
+    }
+
+    pub(crate) fn with_watch(name: Option<&str>, watch: Arc<BusWatch>) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            watch,
+            _data: PhantomData,
+        }
+    }
+
+    /// Gets the payload of the last message received, if any.
+    pub fn payload(&self) -> Option<Vec<u8>> {
+        self.watch.payload()
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for BusChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(self.watch.token.lock().unwrap().clone())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("bus")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct Config {
+        level: String,
+    }
+
+    #[derive(Default)]
+    struct MemoryBus(Mutex<Vec<(String, Arc<BusHandler>)>>);
+
+    impl MemoryBus {
+        fn publish(&self, topic: &str, payload: &str) {
+            for (_, handler) in self.0.lock().unwrap().iter().filter(|s| s.0 == topic) {
+                (handler)(payload.as_bytes());
+            }
+        }
+    }
+
+    impl BusSubscriber for MemoryBus {
+        fn subscribe(&self, topic: &str, handler: BusHandler) -> Result<BusSubscription> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((topic.to_owned(), Arc::new(handler)));
+            Ok(BusSubscription::new(|| {}))
+        }
+    }
+
+    #[test]
+    fn token_should_change_when_message_is_published() {
+        // arrange
+        let bus = MemoryBus::default();
+        let source = BusChangeTokenSource::<Config>::new(None, &bus, "config.changed").unwrap();
+        let token = source.token();
+
+        // act
+        bus.publish("config.other", "ignored");
+        bus.publish("config.changed", "debug");
+
+        // assert
+        assert!(token.changed());
+        assert!(!source.token().changed());
+        assert_eq!(source.payload().unwrap(), b"debug");
+    }
+
+    #[test]
+    fn configure_from_bus_should_reload_options_with_payload() {
+        // arrange
+        let bus = MemoryBus::default();
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .configure(|o| o.level = "info".into())
+            .configure_from_bus(&bus, "config.changed", |o, payload| {
+                o.level = String::from_utf8_lossy(payload).into_owned()
+            })
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let original = monitor.current_value().level.clone();
+
+        // act
+        bus.publish("config.changed", "debug");
+
+        // assert
+        assert_eq!(original, "info");
+        assert_eq!(monitor.current_value().level, "debug");
+    }
+}
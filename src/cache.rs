@@ -1,6 +1,17 @@
-use crate::{Ref, Value};
+use crate::sync::Mutex;
+use crate::{trace, OptionsNameComparer, Ref, ValidateOptionsResult, Value};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+// the number of independently locked shards of a cache, which is enough to keep the creation of
+// options with different names from contending without making an empty cache expensive
+const SHARDS: usize = 16;
+
+type Shard<T> = Mutex<HashMap<Arc<str>, Ref<T>>>;
 
 /// Defines the behavior of an [`Options`](crate::Options) monitor cache.
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
@@ -13,6 +24,25 @@ pub trait OptionsMonitorCache<T: Value> {
     /// * `create_options` - The function used to create options when added
     fn get_or_add(&self, name: Option<&str>, create_options: &dyn Fn(Option<&str>) -> T) -> Ref<T>;
 
+    /// Gets or attempts to add options with the specified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    /// * `create_options` - The function used to create options when added
+    ///
+    /// # Remarks
+    ///
+    /// If the options cannot be created, the failure is returned and nothing is added to the cache.
+    fn try_get_or_add(
+        &self,
+        name: Option<&str>,
+        create_options: &dyn Fn(Option<&str>) -> Result<T, ValidateOptionsResult>,
+    ) -> Result<Ref<T>, ValidateOptionsResult> {
+        let options = RefCell::new(Some(create_options(name)?));
+        Ok(self.get_or_add(name, &|_| options.borrow_mut().take().unwrap()))
+    }
+
     /// Attempts to add options with the specified name.
     ///
     /// # Arguments
@@ -21,6 +51,23 @@ pub trait OptionsMonitorCache<T: Value> {
     /// * `options` - The options to add
     fn try_add(&self, name: Option<&str>, options: T) -> bool;
 
+    /// Attempts to get cached options with the specified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [`get_or_add`](OptionsMonitorCache::get_or_add), options are never created, which
+    /// allows diagnostics and change notifications to compare cached options without forcing them
+    /// to be built. The default implementation returns `None`, which indicates that cached options
+    /// cannot be retrieved without potentially creating them.
+    fn try_get(&self, name: Option<&str>) -> Option<Ref<T>> {
+        let _ = name;
+        None
+    }
+
     /// Attempts to remove options with the specified name.
     ///
     /// # Arguments
@@ -28,55 +75,407 @@ pub trait OptionsMonitorCache<T: Value> {
     /// * `name` - The optional name of the options
     fn try_remove(&self, name: Option<&str>) -> bool;
 
+    /// Attempts to replace the options with the specified name, adding them if none are cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    /// * `options` - The options to replace the cached options with
+    ///
+    /// # Remarks
+    ///
+    /// A caller retrieving the options while they are replaced observes either the cached options
+    /// or their replacement, never neither. The default implementation removes and then adds the
+    /// options, which is not atomic. `false` is returned if other options are cached instead.
+    fn try_replace(&self, name: Option<&str>, options: T) -> bool {
+        self.try_remove(name);
+        self.try_add(name, options)
+    }
+
     /// Clears all options from the cache.
     fn clear(&self);
+
+    /// Gets the statistics of the cache.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation returns empty statistics, which indicates that the cache does not
+    /// collect statistics.
+    fn statistics(&self) -> OptionsCacheStatistics {
+        OptionsCacheStatistics::default()
+    }
+}
+
+/// Defines the behavior of [`Options`](crate::Options) that can measure their approximate size.
+///
+/// # Remarks
+///
+/// The size is consulted by the [statistics](OptionsMonitorCache::statistics) of an
+/// [`OptionsCache`] created with [`with_measure`](OptionsCache::with_measure), which allows
+/// operators of systems with many named options to see how much memory the cached options consume.
+pub trait MeasureOptions {
+    /// Gets the approximate size of the options, in bytes, including any memory they own.
+    fn approx_size(&self) -> usize;
+}
+
+/// Represents the statistics of an [`Options`](crate::Options) cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OptionsCacheStatistics {
+    count: usize,
+    approx_size: usize,
+}
+
+impl OptionsCacheStatistics {
+    /// Initializes new options cache statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of cached options
+    /// * `approx_size` - The approximate size of the cached options, in bytes
+    pub fn new(count: usize, approx_size: usize) -> Self {
+        Self { count, approx_size }
+    }
+
+    /// Gets the number of cached options.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Gets the approximate size of the cached options and their names, in bytes.
+    pub fn approx_size(&self) -> usize {
+        self.approx_size
+    }
 }
 
 /// Represents a cache for configured options.
+///
+/// # Remarks
+///
+/// Names are normalized with the configured [comparer](crate::OptionsNameComparer) and a name is
+/// only allocated the first time options with the name are added. Names are distributed across
+/// shards by their hash and each shard has its own lock, so creating options with one name rarely
+/// waits for options with another name to be created, such as when many names miss the cache after
+/// it is cleared. [Clearing](OptionsMonitorCache::clear) the cache and collecting its
+/// [statistics](OptionsMonitorCache::statistics) visit one shard at a time.
 pub struct OptionsCache<T> {
-    cache: Mutex<HashMap<String, Ref<T>>>,
+    comparer: OptionsNameComparer,
+    shards: Box<[Shard<T>]>,
+    measure: Option<fn(&T) -> usize>,
 }
 
-impl<T> Default for OptionsCache<T> {
-    fn default() -> Self {
+impl<T> OptionsCache<T> {
+    /// Initializes a new options cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `comparer` - The [comparer](crate::OptionsNameComparer) used to normalize cached names
+    pub fn with_comparer(comparer: OptionsNameComparer) -> Self {
         Self {
-            cache: Default::default(),
+            comparer,
+            shards: (0..SHARDS).map(|_| Default::default()).collect(),
+            measure: None,
+        }
+    }
+
+    /// Measures the cached options when the [statistics](OptionsMonitorCache::statistics) of the
+    /// cache are collected.
+    ///
+    /// # Remarks
+    ///
+    /// Unless the options are measured, their approximate size is only the size of their type.
+    pub fn with_measure(mut self) -> Self
+    where
+        T: MeasureOptions,
+    {
+        self.measure = Some(T::approx_size);
+        self
+    }
+
+    pub(crate) fn get(&self, name: Option<&str>) -> Option<Ref<T>> {
+        let key = self.key(name);
+        self.shard(&key).lock().unwrap().get(key.as_ref()).cloned()
+    }
+
+    // returns a value indicating whether options were replaced rather than added
+    pub(crate) fn replace(&self, name: Option<&str>, options: Ref<T>) -> bool {
+        let key = self.key(name);
+        let mut cache = self.shard(&key).lock().unwrap();
+        cache.insert(Arc::from(key.as_ref()), options).is_some()
+    }
+
+    fn key<'a>(&self, name: Option<&'a str>) -> Cow<'a, str> {
+        self.comparer.normalize(name.unwrap_or_default())
+    }
+
+    fn shard(&self, key: &str) -> &Shard<T> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+// the function used by the cache for a particular type of options to measure them
+#[cfg(feature = "di")]
+pub(crate) struct CacheMeasure<T>(fn(&T) -> usize);
+
+#[cfg(feature = "di")]
+impl<T: MeasureOptions> CacheMeasure<T> {
+    pub(crate) fn new() -> Self {
+        Self(T::approx_size)
+    }
+}
+
+#[cfg(feature = "di")]
+impl<T> CacheMeasure<T> {
+    pub(crate) fn apply(provider: &di::ServiceProvider, cache: OptionsCache<T>) -> OptionsCache<T>
+    where
+        T: 'static,
+    {
+        match provider.get::<Self>() {
+            Some(measure) => OptionsCache {
+                measure: Some(measure.0),
+                ..cache
+            },
+            _ => cache,
         }
     }
 }
 
-unsafe impl<T: Send + Sync> Send for OptionsCache<T> {}
-unsafe impl<T: Send + Sync> Sync for OptionsCache<T> {}
+// the comparer of the cache for a particular type of options, which must match the comparer used
+// to match the names of the options so that names which match are served the same instance
+#[cfg(feature = "di")]
+pub(crate) struct CacheComparer<T>(OptionsNameComparer, std::marker::PhantomData<fn() -> T>);
+
+#[cfg(feature = "di")]
+impl<T> CacheComparer<T> {
+    pub(crate) fn new(comparer: OptionsNameComparer) -> Self {
+        Self(comparer, std::marker::PhantomData)
+    }
+
+    pub(crate) fn resolve(provider: &di::ServiceProvider) -> OptionsNameComparer
+    where
+        T: 'static,
+    {
+        provider
+            .get_all::<Self>()
+            .last()
+            .map(|c| c.0)
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Default for OptionsCache<T> {
+    fn default() -> Self {
+        Self::with_comparer(OptionsNameComparer::default())
+    }
+}
 
 impl<T: Value> OptionsMonitorCache<T> for OptionsCache<T> {
     fn get_or_add(&self, name: Option<&str>, create_options: &dyn Fn(Option<&str>) -> T) -> Ref<T> {
-        let key = name.unwrap_or_default().to_string();
-        self.cache
-            .lock()
-            .unwrap()
-            .entry(key)
-            .or_insert_with(|| Ref::new(create_options(name)))
-            .clone()
+        let key = self.key(name);
+        let mut cache = self.shard(&key).lock().unwrap();
+
+        if let Some(options) = cache.get(key.as_ref()) {
+            options.clone()
+        } else {
+            let options = Ref::new(create_options(name));
+            cache.insert(Arc::from(key.as_ref()), options.clone());
+            options
+        }
+    }
+
+    fn try_get_or_add(
+        &self,
+        name: Option<&str>,
+        create_options: &dyn Fn(Option<&str>) -> Result<T, ValidateOptionsResult>,
+    ) -> Result<Ref<T>, ValidateOptionsResult> {
+        let key = self.key(name);
+        let mut cache = self.shard(&key).lock().unwrap();
+
+        if let Some(options) = cache.get(key.as_ref()) {
+            Ok(options.clone())
+        } else {
+            let options = Ref::new(create_options(name)?);
+            cache.insert(Arc::from(key.as_ref()), options.clone());
+            Ok(options)
+        }
     }
 
     fn try_add(&self, name: Option<&str>, options: T) -> bool {
-        let key = name.unwrap_or_default();
-        let mut cache = self.cache.lock().unwrap();
+        let key = self.key(name);
+        let mut cache = self.shard(&key).lock().unwrap();
 
-        if cache.contains_key(key) {
+        if cache.contains_key(key.as_ref()) {
             false
         } else {
-            cache.insert(key.to_owned(), Ref::new(options));
+            cache.insert(Arc::from(key.as_ref()), Ref::new(options));
             true
         }
     }
 
+    fn try_get(&self, name: Option<&str>) -> Option<Ref<T>> {
+        self.get(name)
+    }
+
     fn try_remove(&self, name: Option<&str>) -> bool {
-        let key = name.unwrap_or_default();
-        self.cache.lock().unwrap().remove(key).is_some()
+        let key = self.key(name);
+        let removed = self
+            .shard(&key)
+            .lock()
+            .unwrap()
+            .remove(key.as_ref())
+            .is_some();
+
+        if removed {
+            trace::invalidated::<T>(name);
+        }
+
+        removed
+    }
+
+    fn try_replace(&self, name: Option<&str>, options: T) -> bool {
+        if self.replace(name, Ref::new(options)) {
+            trace::invalidated::<T>(name);
+        }
+
+        true
     }
 
     fn clear(&self) {
-        self.cache.lock().unwrap().clear()
+        for shard in self.shards.iter() {
+            shard.lock().unwrap().clear();
+        }
+
+        trace::cleared::<T>();
+    }
+
+    fn statistics(&self) -> OptionsCacheStatistics {
+        let mut count = 0;
+        let mut approx_size = 0;
+
+        for shard in self.shards.iter() {
+            let cache = shard.lock().unwrap();
+
+            count += cache.len();
+            approx_size += cache
+                .iter()
+                .map(|(name, options)| {
+                    let size = match self.measure {
+                        Some(measure) => measure(options),
+                        _ => std::mem::size_of::<T>(),
+                    };
+                    name.len() + size
+                })
+                .sum::<usize>();
+        }
+
+        OptionsCacheStatistics::new(count, approx_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn try_get_should_not_create_options() {
+        // arrange
+        let cache = OptionsCache::<String>::default();
+
+        cache.get_or_add(Some("Cached"), &|_| "cached".into());
+
+        // act
+        let cached = cache.try_get(Some("Cached"));
+        let missing = cache.try_get(Some("Missing"));
+
+        // assert
+        assert_eq!(cached.as_deref().map(String::as_str), Some("cached"));
+        assert!(missing.is_none());
+        assert!(!cache.try_remove(Some("Missing")));
+    }
+
+    #[test]
+    fn try_replace_should_replace_or_add_options() {
+        // arrange
+        let cache = OptionsCache::<String>::default();
+
+        cache.get_or_add(Some("Cached"), &|_| "cached".into());
+
+        // act
+        let replaced = cache.try_replace(Some("cached"), "replaced".into());
+        let added = cache.try_replace(Some("Missing"), "added".into());
+
+        // assert
+        assert!(replaced && added);
+        assert_eq!(cache.try_get(Some("Cached")).unwrap().as_str(), "replaced");
+        assert_eq!(cache.try_get(Some("Missing")).unwrap().as_str(), "added");
+        assert_eq!(cache.statistics().count(), 2);
+    }
+
+    #[test]
+    fn default_cache_should_match_names_like_the_default_comparer() {
+        // arrange
+        let cache = OptionsCache::<String>::default();
+
+        // act
+        let first = cache.get_or_add(Some("Test"), &|_| "first".into());
+        let second = cache.get_or_add(Some("test"), &|_| "second".into());
+
+        // assert
+        assert!(Ref::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn get_or_add_should_not_wait_for_options_with_a_name_in_another_shard() {
+        // arrange
+        let cache = OptionsCache::<String>::default();
+        let other = (0..)
+            .map(|i| format!("Tenant{}", i))
+            .find(|n| !std::ptr::eq(cache.shard("Outer"), cache.shard(n)))
+            .unwrap();
+
+        // act
+        let outer = cache.get_or_add(Some("Outer"), &|_| {
+            // a single lock for every name would deadlock here
+            let inner = cache.get_or_add(Some(&other), &|_| "inner".into());
+            format!("outer+{}", inner)
+        });
+
+        // assert
+        assert_eq!(outer.as_str(), "outer+inner");
+        assert_eq!(cache.statistics().count(), 2);
+        cache.clear();
+        assert_eq!(cache.statistics().count(), 0);
+    }
+
+    struct TenantOptions {
+        name: String,
+    }
+
+    impl MeasureOptions for TenantOptions {
+        fn approx_size(&self) -> usize {
+            std::mem::size_of::<Self>() + self.name.capacity()
+        }
+    }
+
+    #[test]
+    fn statistics_should_measure_cached_options() {
+        // arrange
+        let cache = OptionsCache::<TenantOptions>::default().with_measure();
+        let size = std::mem::size_of::<TenantOptions>();
+
+        // act
+        for tenant in ["contoso", "fabrikam"] {
+            cache.get_or_add(Some(tenant), &|n| TenantOptions {
+                name: n.unwrap().to_owned(),
+            });
+        }
+
+        // assert
+        assert_eq!(
+            cache.statistics(),
+            OptionsCacheStatistics::new(2, (size + 7 + 7) + (size + 8 + 8))
+        );
     }
 }
@@ -0,0 +1,395 @@
+use crate::{
+    redact::{mask_values, SensitiveMarker},
+    verify::{instance_of, registrations},
+    DescribeOptions, FieldDescription, OptionsNameComparer, Redacted, RedactedSerialize,
+    ValidateOptions, Value,
+};
+use di::{ServiceCollection, Type};
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde_json::Value as Json;
+
+/// Represents a machine-readable catalog of the configuration [`Options`](crate::Options)
+/// registered in a [collection of services](di::ServiceCollection).
+///
+/// # Remarks
+///
+/// The catalog is created using [`options_catalog`] and serializes to JSON, which can be used to
+/// generate documentation and configuration templates.
+pub struct OptionsCatalog {
+    entries: Vec<OptionsCatalogEntry>,
+}
+
+/// Represents a single named instance of configuration [`Options`](crate::Options) in an
+/// [`OptionsCatalog`].
+pub struct OptionsCatalogEntry {
+    options_type: String,
+    name: Option<String>,
+    section: Option<String>,
+    defaults: Option<Json>,
+    validators: Option<usize>,
+    descriptions: Vec<FieldDescription>,
+}
+
+impl OptionsCatalogEntry {
+    /// Gets the name of the type of options.
+    pub fn options_type(&self) -> &str {
+        &self.options_type
+    }
+
+    /// Gets the name of the options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the key of the configuration section the options are bound to, if any.
+    ///
+    /// # Remarks
+    ///
+    /// An empty key indicates that the options are bound to the root of the configuration.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Gets the names of the fields of the options.
+    ///
+    /// # Remarks
+    ///
+    /// Fields are only known for options added with
+    /// [`add_to_catalog`](crate::OptionsBuilder::add_to_catalog).
+    pub fn fields(&self) -> Vec<&str> {
+        match &self.defaults {
+            Some(Json::Object(map)) => map.keys().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Gets the default values of the options with all of their secrets masked, if known.
+    ///
+    /// # Remarks
+    ///
+    /// Defaults are only known for options added with
+    /// [`add_to_catalog`](crate::OptionsBuilder::add_to_catalog).
+    pub fn defaults(&self) -> Option<&Json> {
+        self.defaults.as_ref()
+    }
+
+    /// Gets the number of [validations](crate::ValidateOptions) registered for the type of
+    /// options, if known.
+    ///
+    /// # Remarks
+    ///
+    /// Validations are only known for options added with
+    /// [`add_to_catalog`](crate::OptionsBuilder::add_to_catalog).
+    pub fn validators(&self) -> Option<usize> {
+        self.validators
+    }
+
+    /// Gets the descriptions of the fields of the options.
+    ///
+    /// # Remarks
+    ///
+    /// Descriptions are only known for options added with
+    /// [`describe`](crate::OptionsBuilder::describe).
+    pub fn descriptions(&self) -> &[FieldDescription] {
+        &self.descriptions
+    }
+}
+
+impl OptionsCatalog {
+    /// Gets the cataloged options in the order they were registered.
+    pub fn entries(&self) -> &[OptionsCatalogEntry] {
+        &self.entries
+    }
+
+    /// Serializes the catalog to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Serializes the catalog to pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+impl Serialize for OptionsCatalog {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.entries.len()))?;
+
+        for entry in &self.entries {
+            seq.serialize_element(entry)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl Serialize for OptionsCatalogEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OptionsCatalogEntry", 7)?;
+        state.serialize_field("type", &self.options_type)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("section", &self.section)?;
+        state.serialize_field("fields", &self.fields())?;
+        state.serialize_field("defaults", &self.defaults)?;
+        state.serialize_field("validators", &self.validators)?;
+        state.serialize_field("descriptions", &Descriptions(&self.descriptions))?;
+        state.end()
+    }
+}
+
+struct Descriptions<'a>(&'a [FieldDescription]);
+
+impl Serialize for Descriptions<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+
+        for field in self.0 {
+            seq.serialize_element(&Description(field))?;
+        }
+
+        seq.end()
+    }
+}
+
+struct Description<'a>(&'a FieldDescription);
+
+impl Serialize for Description<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("FieldDescription", 3)?;
+        state.serialize_field("name", self.0.name())?;
+        state.serialize_field("description", &self.0.description())?;
+        state.serialize_field("example", &self.0.example())?;
+        state.end()
+    }
+}
+
+pub(crate) struct OptionsDescriber {
+    options_type: Type,
+    defaults: fn(&ServiceCollection) -> Json,
+    validators: fn(&ServiceCollection) -> usize,
+}
+
+impl OptionsDescriber {
+    pub(crate) fn new<T>() -> Self
+    where
+        T: Value + Default + RedactedSerialize + 'static,
+    {
+        Self {
+            options_type: Type::of::<T>(),
+            defaults: defaults::<T>,
+            validators: validators::<T>,
+        }
+    }
+}
+
+pub(crate) struct OptionsDocumentation {
+    options_type: Type,
+    describe: fn() -> Vec<FieldDescription>,
+}
+
+impl OptionsDocumentation {
+    pub(crate) fn new<T: DescribeOptions + 'static>() -> Self {
+        Self {
+            options_type: Type::of::<T>(),
+            describe: T::describe,
+        }
+    }
+}
+
+fn defaults<T: Default + RedactedSerialize + 'static>(services: &ServiceCollection) -> Json {
+    let sensitive = Type::of::<SensitiveMarker<T>>();
+    let value = serde_json::to_value(Redacted::new(&T::default()))
+        .unwrap_or_else(|e| Json::String(e.to_string()));
+
+    if services.iter().any(|d| d.service_type() == sensitive) {
+        mask_values(value)
+    } else {
+        value
+    }
+}
+
+fn validators<T: Value + 'static>(services: &ServiceCollection) -> usize {
+    let validate = Type::of::<dyn ValidateOptions<T>>();
+    services
+        .iter()
+        .filter(|d| d.service_type() == validate)
+        .count()
+}
+
+/// Creates a catalog of the configuration [`Options`](crate::Options) registered in a
+/// [collection of services](di::ServiceCollection).
+///
+/// # Arguments
+///
+/// * `services` - The [collection of services](di::ServiceCollection) to catalog
+///
+/// # Remarks
+///
+/// Every registered type and name of options is cataloged along with the configuration section it
+/// is bound to. The fields, default values, and number of validations are included for options
+/// added with [`add_to_catalog`](crate::OptionsBuilder::add_to_catalog) and the descriptions of
+/// the fields are included for options added with [`describe`](crate::OptionsBuilder::describe).
+pub fn options_catalog(services: &ServiceCollection) -> OptionsCatalog {
+    let comparer = OptionsNameComparer::default();
+    let describers: Vec<_> = services
+        .iter()
+        .filter_map(instance_of::<OptionsDescriber>)
+        .collect();
+    let documentation: Vec<_> = services
+        .iter()
+        .filter_map(instance_of::<OptionsDocumentation>)
+        .collect();
+    let mut keys = Vec::<(Type, String)>::new();
+    let mut entries = Vec::<OptionsCatalogEntry>::new();
+
+    for registration in registrations(services) {
+        let options_type = registration.options_type();
+        let key = (
+            options_type.clone(),
+            comparer
+                .normalize(registration.name().unwrap_or_default())
+                .into_owned(),
+        );
+
+        if let Some(index) = keys.iter().position(|k| k == &key) {
+            let entry = &mut entries[index];
+
+            if entry.section.is_none() {
+                entry.section = registration.binding_key().map(|k| k.to_owned());
+            }
+
+            continue;
+        }
+
+        let describer = describers.iter().find(|d| &d.options_type == options_type);
+        let descriptions = documentation
+            .iter()
+            .find(|d| &d.options_type == options_type)
+            .map(|d| (d.describe)())
+            .unwrap_or_default();
+
+        keys.push(key);
+        entries.push(OptionsCatalogEntry {
+            options_type: options_type.name().to_owned(),
+            name: registration.name().map(|n| n.to_owned()),
+            section: registration.binding_key().map(|k| k.to_owned()),
+            defaults: describer.map(|d| (d.defaults)(services)),
+            validators: describer.map(|d| (d.validators)(services)),
+            descriptions,
+        });
+    }
+
+    OptionsCatalog { entries }
+}
+
+#[cfg(all(test, feature = "cfg", feature = "derive"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, RegisterOptions};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, RedactedSerialize)]
+    struct DbOptions {
+        host: String,
+        port: u16,
+        #[options(secret)]
+        password: String,
+    }
+
+    impl Default for DbOptions {
+        fn default() -> Self {
+            Self {
+                host: "localhost".into(),
+                port: 5432,
+                password: "changeit".into(),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct CacheOptions;
+
+    #[derive(Default, crate::DescribeOptions, crate::Options, RedactedSerialize)]
+    #[options(name = "Derived", catalog, describe)]
+    struct DerivedOptions {
+        /// Indicates whether the feature
+        /// is enabled.
+        enabled: bool,
+        #[options(doc = "The number of retries.", example = "3")]
+        retries: u8,
+    }
+
+    #[test]
+    fn options_catalog_should_describe_registered_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_options_from_config::<DbOptions>("Primary", "Db:Primary")
+            .validate(|o| !o.host.is_empty(), "A host is required.")
+            .add_to_catalog();
+        services.add_options::<CacheOptions>();
+
+        // act
+        let catalog = options_catalog(&services);
+        let json: Json = serde_json::from_str(&catalog.to_json()).unwrap();
+
+        // assert
+        assert_eq!(catalog.entries().len(), 2);
+        assert!(catalog.entries()[0].options_type().ends_with("DbOptions"));
+        assert_eq!(catalog.entries()[0].name(), Some("Primary"));
+        assert_eq!(catalog.entries()[0].section(), Some("Db:Primary"));
+        assert_eq!(
+            catalog.entries()[0].fields(),
+            vec!["host", "password", "port"]
+        );
+        assert_eq!(catalog.entries()[0].validators(), Some(1));
+        assert_eq!(
+            json[0]["defaults"],
+            serde_json::json!({ "host": "localhost", "port": 5432, "password": "***" })
+        );
+        assert!(catalog.entries()[1]
+            .options_type()
+            .ends_with("CacheOptions"));
+        assert_eq!(json[1]["section"], Json::Null);
+        assert_eq!(json[1]["defaults"], Json::Null);
+    }
+
+    #[test]
+    fn derived_options_should_be_included_in_catalog() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        DerivedOptions::add_to(&mut services);
+
+        // act
+        let catalog = options_catalog(&services);
+        let json: Json = serde_json::from_str(&catalog.to_json()).unwrap();
+
+        // assert
+        assert_eq!(catalog.entries().len(), 1);
+        assert_eq!(catalog.entries()[0].name(), Some("Derived"));
+        assert_eq!(catalog.entries()[0].fields(), vec!["enabled", "retries"]);
+        assert_eq!(catalog.entries()[0].validators(), Some(0));
+        assert_eq!(
+            catalog.entries()[0].descriptions(),
+            &[
+                FieldDescription::new("enabled")
+                    .with_description("Indicates whether the feature is enabled."),
+                FieldDescription::new("retries")
+                    .with_description("The number of retries.")
+                    .with_example("3"),
+            ]
+        );
+        assert_eq!(
+            json[0]["descriptions"][1],
+            serde_json::json!({
+                "name": "retries",
+                "description": "The number of retries.",
+                "example": "3"
+            })
+        );
+    }
+}
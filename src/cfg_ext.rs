@@ -1,9 +1,20 @@
-use crate::{ext::*, *};
+#[cfg(feature = "path-to-error")]
+use crate::bind::from_config_traced;
+
+use crate::{
+    bind::from_config_seeded, cli::CommandLineSection, ext::*, lint::OptionsLinter,
+    migrate::OptionsBinder, *,
+};
 use config::ext::*;
-use config::Configuration;
-use di::{existing, Ref, ServiceCollection};
-use serde::de::DeserializeOwned;
+use config::{Configuration, ConfigurationIterator, ConfigurationPath, ConfigurationSection};
+use di::{exactly_one, existing, existing_as_self, singleton, Ref, ServiceCollection, Type};
+use serde::de::{DeserializeOwned, DeserializeSeed};
+use std::borrow::Borrow;
 use std::marker::PhantomData;
+use std::ops::Deref;
+
+#[cfg(not(feature = "path-to-error"))]
+use std::sync::Mutex;
 use tokens::ChangeToken;
 
 /// Represents a change token for monitored [`Options`](crate::Options) that are
@@ -14,9 +25,6 @@ pub struct ConfigurationChangeTokenSource<T: Value> {
     _data: PhantomData<T>,
 }
 
-unsafe impl<T: Send + Sync> Send for ConfigurationChangeTokenSource<T> {}
-unsafe impl<T: Send + Sync> Sync for ConfigurationChangeTokenSource<T> {}
-
 impl<T: Value> ConfigurationChangeTokenSource<T> {
     /// Initializes a new configuration change token source.
     ///
@@ -41,6 +49,334 @@ impl<T: Value> OptionsChangeTokenSource<T> for ConfigurationChangeTokenSource<T>
     fn name(&self) -> Option<&str> {
         self.name.as_deref()
     }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("configuration")
+    }
+}
+
+fn source_of(configuration: &Ref<dyn Configuration>) -> usize {
+    Ref::as_ptr(configuration) as *const () as usize
+}
+
+// the registered configuration has no instance until the provider is built
+const REGISTERED_CONFIGURATION: usize = 0;
+
+// a section whose values replace the values they share with a base section
+#[derive(Clone)]
+pub(crate) struct OverlaySection {
+    base: Ref<dyn ConfigurationSection>,
+    overlay: Ref<dyn ConfigurationSection>,
+}
+
+impl OverlaySection {
+    pub(crate) fn new(
+        base: Box<dyn ConfigurationSection>,
+        overlay: Box<dyn ConfigurationSection>,
+    ) -> Self {
+        Self {
+            base: base.into(),
+            overlay: overlay.into(),
+        }
+    }
+}
+
+impl Configuration for OverlaySection {
+    fn get(&self, key: &str) -> Option<config::Value> {
+        self.overlay.get(key).or_else(|| self.base.get(key))
+    }
+
+    fn section(&self, key: &str) -> Box<dyn ConfigurationSection> {
+        Box::new(Self::new(self.base.section(key), self.overlay.section(key)))
+    }
+
+    fn children(&self) -> Vec<Box<dyn ConfigurationSection>> {
+        let mut keys: Vec<String> = Vec::new();
+
+        for child in self.base.children().iter().chain(&self.overlay.children()) {
+            if !keys.iter().any(|k| k.eq_ignore_ascii_case(child.key())) {
+                keys.push(child.key().to_owned());
+            }
+        }
+
+        keys.iter().map(|key| self.section(key)).collect()
+    }
+
+    fn reload_token(&self) -> Box<dyn ChangeToken> {
+        self.base.reload_token()
+    }
+
+    fn as_section(&self) -> Option<&dyn ConfigurationSection> {
+        Some(self)
+    }
+
+    fn iter(
+        &self,
+        path: Option<ConfigurationPath>,
+    ) -> Box<dyn Iterator<Item = (String, config::Value)>> {
+        Box::new(ConfigurationIterator::new(
+            self,
+            path.unwrap_or(ConfigurationPath::Absolute),
+        ))
+    }
+}
+
+impl ConfigurationSection for OverlaySection {
+    fn key(&self) -> &str {
+        self.base.key()
+    }
+
+    fn path(&self) -> &str {
+        self.base.path()
+    }
+
+    fn value(&self) -> config::Value {
+        let value = self.overlay.value();
+
+        if value.is_empty() {
+            self.base.value()
+        } else {
+            value
+        }
+    }
+
+    fn as_config(&self) -> Box<dyn Configuration> {
+        Box::new(self.clone())
+    }
+}
+
+impl<'a> AsRef<dyn Configuration + 'a> for OverlaySection {
+    fn as_ref(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+impl<'a> Borrow<dyn Configuration + 'a> for OverlaySection {
+    fn borrow(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+impl Deref for OverlaySection {
+    type Target = dyn Configuration;
+
+    fn deref(&self) -> &Self::Target {
+        self
+    }
+}
+
+// a configuration that remembers the path of the last value read while it's bound
+#[cfg(not(feature = "path-to-error"))]
+struct TrackedConfiguration<'a> {
+    configuration: &'a dyn Configuration,
+    last: Ref<Mutex<String>>,
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl<'a> TrackedConfiguration<'a> {
+    fn new(configuration: &'a dyn Configuration) -> Self {
+        Self {
+            configuration,
+            last: Ref::new(Mutex::new(String::new())),
+        }
+    }
+
+    fn last_path(&self) -> String {
+        self.last.lock().unwrap().clone()
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl Configuration for TrackedConfiguration<'_> {
+    fn get(&self, key: &str) -> Option<config::Value> {
+        self.configuration.get(key)
+    }
+
+    fn section(&self, key: &str) -> Box<dyn ConfigurationSection> {
+        Box::new(TrackedSection::new(
+            self.configuration.section(key),
+            self.last.clone(),
+        ))
+    }
+
+    fn children(&self) -> Vec<Box<dyn ConfigurationSection>> {
+        self.configuration
+            .children()
+            .into_iter()
+            .map(|child| {
+                Box::new(TrackedSection::new(child, self.last.clone()))
+                    as Box<dyn ConfigurationSection>
+            })
+            .collect()
+    }
+
+    fn reload_token(&self) -> Box<dyn ChangeToken> {
+        self.configuration.reload_token()
+    }
+
+    fn iter(
+        &self,
+        path: Option<ConfigurationPath>,
+    ) -> Box<dyn Iterator<Item = (String, config::Value)>> {
+        self.configuration.iter(path)
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+#[derive(Clone)]
+struct TrackedSection {
+    section: Ref<dyn ConfigurationSection>,
+    last: Ref<Mutex<String>>,
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl TrackedSection {
+    fn new(section: Box<dyn ConfigurationSection>, last: Ref<Mutex<String>>) -> Self {
+        Self {
+            section: section.into(),
+            last,
+        }
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl Configuration for TrackedSection {
+    fn get(&self, key: &str) -> Option<config::Value> {
+        self.section.get(key)
+    }
+
+    fn section(&self, key: &str) -> Box<dyn ConfigurationSection> {
+        Box::new(Self::new(self.section.section(key), self.last.clone()))
+    }
+
+    fn children(&self) -> Vec<Box<dyn ConfigurationSection>> {
+        self.section
+            .children()
+            .into_iter()
+            .map(|child| {
+                Box::new(Self::new(child, self.last.clone())) as Box<dyn ConfigurationSection>
+            })
+            .collect()
+    }
+
+    fn reload_token(&self) -> Box<dyn ChangeToken> {
+        self.section.reload_token()
+    }
+
+    fn as_section(&self) -> Option<&dyn ConfigurationSection> {
+        Some(self)
+    }
+
+    fn iter(
+        &self,
+        path: Option<ConfigurationPath>,
+    ) -> Box<dyn Iterator<Item = (String, config::Value)>> {
+        self.section.iter(path)
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl ConfigurationSection for TrackedSection {
+    fn key(&self) -> &str {
+        self.section.key()
+    }
+
+    fn path(&self) -> &str {
+        self.section.path()
+    }
+
+    fn value(&self) -> config::Value {
+        *self.last.lock().unwrap() = self.section.path().to_owned();
+        self.section.value()
+    }
+
+    fn as_config(&self) -> Box<dyn Configuration> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl<'a> AsRef<dyn Configuration + 'a> for TrackedSection {
+    fn as_ref(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl<'a> Borrow<dyn Configuration + 'a> for TrackedSection {
+    fn borrow(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+impl Deref for TrackedSection {
+    type Target = dyn Configuration;
+
+    fn deref(&self) -> &Self::Target {
+        self
+    }
+}
+
+// binds options to configuration; a value that cannot be deserialized, such as one that doesn't
+// match any variant of an enumeration, is reported with the path of its configuration key, which
+// the binder doesn't otherwise provide
+#[cfg(not(feature = "path-to-error"))]
+pub(crate) fn bind_options<T>(
+    configuration: &dyn Configuration,
+    options: &mut T,
+    messages: &dyn OptionsMessages,
+) -> Result<(), Error>
+where
+    T: DeserializeOwned,
+{
+    let error = match bind_config(configuration, options) {
+        Err(Error::Custom(message)) => message,
+        result => return result,
+    };
+
+    // binding again is only required to determine where the failure occurred, which is only
+    // approximated by the last value read
+    let tracked = TrackedConfiguration::new(configuration);
+
+    match from_config::<T>(&tracked) {
+        Err(_) if !tracked.last_path().is_empty() => Err(Error::Custom(
+            messages.invalid_value(&tracked.last_path(), &error),
+        )),
+        _ => Err(Error::Custom(error)),
+    }
+}
+
+// binds options to configuration in a single pass; the path of a value that cannot be deserialized
+// is the path of the value that failed, including a structure that rejects its own fields
+#[cfg(feature = "path-to-error")]
+pub(crate) fn bind_options<T>(
+    configuration: &dyn Configuration,
+    options: &mut T,
+    messages: &dyn OptionsMessages,
+) -> Result<(), Error>
+where
+    T: DeserializeOwned,
+{
+    let (message, path) = match from_config_traced::<T>(configuration) {
+        Ok(value) => {
+            *options = value;
+            return Ok(());
+        }
+        Err((Error::Custom(message), Some(path))) => (message, path),
+        Err((error, _)) => return Err(error),
+    };
+    let path = match configuration.as_section().map(|section| section.path()) {
+        Some(parent) if path.is_empty() => parent.to_owned(),
+        Some(parent) => ConfigurationPath::combine(&[parent, &path]),
+        _ => path,
+    };
+
+    if path.is_empty() {
+        Err(Error::Custom(message))
+    } else {
+        Err(Error::Custom(messages.invalid_value(&path, &message)))
+    }
 }
 
 /// Defines extension methods for the [`ServiceCollection`](di::ServiceCollection) struct.
@@ -50,61 +386,554 @@ pub trait OptionsConfigurationServiceExtensions {
     /// # Arguments
     ///
     /// * `configuration` - The [configuration](config::Configuration) applied to the options
-    fn apply_config<T>(&mut self, configuration: Ref<dyn Configuration>) -> OptionsBuilder<T>
+    fn apply_config<T>(&mut self, configuration: Ref<dyn Configuration>) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers an options type that will have all of its associated services registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    fn apply_config_at<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        key: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers an options type bound to a section of the configuration overlaid by the section for
+    /// an environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    /// * `base_key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    /// * `env` - The name of the environment, such as `Production`
+    ///
+    /// # Remarks
+    ///
+    /// The options are bound to `base_key` overlaid by `base_key:{env}`, if it exists, so that the
+    /// values for the environment replace the values they share with the base section. Both sections
+    /// are part of the same [configuration](config::Configuration), so the options are reloaded when
+    /// either of them changes.
+    fn apply_config_for_env<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        base_key: impl AsRef<str>,
+        env: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers an options type using the name and configuration section it declares.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    ///
+    /// # Remarks
+    ///
+    /// The [name](crate::RegisterOptions::name) and [section](crate::RegisterOptions::section) are
+    /// typically declared using `#[options(name = "...")]` and `#[options(section = "...")]`. If no
+    /// section is declared, the entire [configuration](config::Configuration) is applied.
+    fn apply_declared_config<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: RegisterOptions + Value + Default + DeserializeOwned + 'static;
+
+    /// Registers a named options type bound to a section of command line configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    /// * `name` - The name associated with the options
+    ///
+    /// # Remarks
+    ///
+    /// Arguments are bound regardless of how they are cased, so `--servers:primary:max-connections`
+    /// binds to the `MaxConnections` key of the section. The command line can only express a list
+    /// as a comma-separated value, such as `--servers:primary:hosts=a,b`, or as indexed arguments,
+    /// such as those produced by [`index_repeated_args`](crate::index_repeated_args). A value is
+    /// bound to a list field as its comma-separated parts and to any other field as is. Switch
+    /// mappings can target the section with the full key, such as `("-h", "Servers:Primary:Hosts")`.
+    fn apply_cli_config_at<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        key: impl AsRef<str>,
+        name: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers an options type bound to the registered [configuration](config::Configuration).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    ///
+    /// # Remarks
+    ///
+    /// The [configuration](config::Configuration) is resolved from the
+    /// [`ServiceProvider`](di::ServiceProvider) when the options are created and the options are
+    /// reloaded whenever the configuration changes.
+    fn add_options_from_config<T>(&mut self, key: impl AsRef<str>) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers a named options type bound to the registered [configuration](config::Configuration).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name associated with the options
+    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    ///
+    /// # Remarks
+    ///
+    /// The [configuration](config::Configuration) is resolved from the
+    /// [`ServiceProvider`](di::ServiceProvider) when the options are created and the options are
+    /// reloaded whenever the configuration changes.
+    fn add_named_options_from_config<T>(
+        &mut self,
+        name: impl AsRef<str>,
+        key: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers an options type that binds itself to configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    ///
+    /// # Remarks
+    ///
+    /// The options are bound using [`BindOptions`](crate::BindOptions) rather than deserialized,
+    /// so [migrations](crate::MigrateOptions) and [deprecated keys](crate::DeprecatedKey) do not
+    /// apply.
+    fn apply_bindable_config<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: BindOptions + Value + Default + 'static;
+
+    /// Registers an options type that binds itself to a section of configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    ///
+    /// # Remarks
+    ///
+    /// The options are bound using [`BindOptions`](crate::BindOptions) rather than deserialized,
+    /// so [migrations](crate::MigrateOptions) and [deprecated keys](crate::DeprecatedKey) do not
+    /// apply.
+    fn apply_bindable_config_at<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        key: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: BindOptions + Value + Default + 'static;
+
+    /// Registers an options type that is deserialized from configuration with external context.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    /// * `seed_factory` - The function used to create the [seed](serde::de::DeserializeSeed) each
+    ///   time the options are bound
+    ///
+    /// # Remarks
+    ///
+    /// A seed carries the context that plain deserialization cannot express, such as a registry
+    /// or an interner. The options are replaced rather than bound in place, so
+    /// [migrations](crate::MigrateOptions) and [deprecated keys](crate::DeprecatedKey) do not
+    /// apply.
+    fn apply_config_seeded<T, S, F>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        seed_factory: F,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + 'static,
+        S: for<'de> DeserializeSeed<'de, Value = T> + 'static,
+        F: Fn() -> S + Value + 'static;
+
+    /// Registers an options type that is deserialized from a section of configuration with
+    /// external context.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The [configuration](config::Configuration) applied to the options
+    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
+    /// * `seed_factory` - The function used to create the [seed](serde::de::DeserializeSeed) each
+    ///   time the options are bound
+    ///
+    /// # Remarks
+    ///
+    /// A seed carries the context that plain deserialization cannot express, such as a registry
+    /// or an interner. The options are replaced rather than bound in place, so
+    /// [migrations](crate::MigrateOptions) and [deprecated keys](crate::DeprecatedKey) do not
+    /// apply.
+    fn apply_config_seeded_at<T, S, F>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        key: impl AsRef<str>,
+        seed_factory: F,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + 'static,
+        S: for<'de> DeserializeSeed<'de, Value = T> + 'static,
+        F: Fn() -> S + Value + 'static;
+}
+
+fn _watch_config<'a, T>(
+    services: &'a mut ServiceCollection,
+    configuration: &Ref<dyn Configuration>,
+    name: Option<&str>,
+    key: Option<&str>,
+) -> OptionsBuilder<'a, T>
+where
+    T: Value + Default + 'static,
+{
+    let source = Box::new(ConfigurationChangeTokenSource::<T>::new(
+        name,
+        configuration.clone(),
+    ));
+    let descriptor =
+        existing::<dyn OptionsChangeTokenSource<T>, ConfigurationChangeTokenSource<T>>(source);
+    let registration = OptionsRegistration::new::<T>(name)
+        .for_role(OptionsRole::Configure)
+        .bound_to(source_of(configuration), key.unwrap_or_default());
+
+    services
+        .add(descriptor)
+        .add(existing_as_self(registration))
+        .add_named_options_many(name)
+}
+
+fn _apply_config<'a, T>(
+    services: &'a mut ServiceCollection,
+    configuration: Ref<dyn Configuration>,
+    name: Option<&str>,
+    key: Option<&str>,
+) -> OptionsBuilder<'a, T>
+where
+    T: Value + Default + DeserializeOwned + 'static,
+{
+    services.add(existing_as_self(OptionsLinter::new::<T>(name, key)));
+
+    let builder = _watch_config(services, &configuration, name, key);
+    let key = key.map(|k| k.to_owned());
+
+    builder.add_configurer_with(move |sp| {
+        let configuration = configuration.clone();
+        let key = key.clone();
+        let binder = OptionsBinder::<T>::new(sp);
+
+        configure(move |name, options: &mut T| {
+            if let Some(key) = &key {
+                let section = configuration.section(key);
+
+                if section.exists() {
+                    bind(&binder, section.deref(), name, options)
+                }
+            } else {
+                bind(&binder, configuration.as_ref(), name, options)
+            }
+        })
+    })
+}
+
+fn _apply_custom_config<'a, T, F>(
+    services: &'a mut ServiceCollection,
+    configuration: Ref<dyn Configuration>,
+    name: Option<&str>,
+    key: Option<&str>,
+    bind: F,
+) -> OptionsBuilder<'a, T>
+where
+    T: Value + Default + 'static,
+    F: Fn(&dyn Configuration, &mut T) -> Result<(), Error> + Value + 'static,
+{
+    let key = key.map(|k| k.to_owned());
+    let bind = Ref::new(bind);
+
+    _watch_config(services, &configuration, name, key.as_deref()).add_configurer_with(move |_| {
+        let configuration = configuration.clone();
+        let key = key.clone();
+        let bind = bind.clone();
+
+        configure(move |_, options: &mut T| {
+            let result = if let Some(key) = &key {
+                let section = configuration.section(key);
+
+                if section.exists() {
+                    bind(section.deref(), options)
+                } else {
+                    Ok(())
+                }
+            } else {
+                bind(configuration.as_ref(), options)
+            };
+
+            if let Err(error) = result {
+                panic!("{}", error)
+            }
+        })
+    })
+}
+
+fn bind_self<T: BindOptions>(
+    configuration: &dyn Configuration,
+    options: &mut T,
+) -> Result<(), Error> {
+    options.bind(configuration)
+}
+
+fn _apply_seeded_config<'a, T, S, F>(
+    services: &'a mut ServiceCollection,
+    configuration: Ref<dyn Configuration>,
+    name: Option<&str>,
+    key: Option<&str>,
+    seed_factory: F,
+) -> OptionsBuilder<'a, T>
+where
+    T: Value + Default + 'static,
+    S: for<'de> DeserializeSeed<'de, Value = T> + 'static,
+    F: Fn() -> S + Value + 'static,
+{
+    let bind = move |config: &dyn Configuration, options: &mut T| {
+        *options = from_config_seeded(config, seed_factory())?;
+        Ok(())
+    };
+
+    _apply_custom_config(services, configuration, name, key, bind)
+}
+
+fn bind<T>(
+    binder: &OptionsBinder<T>,
+    configuration: &dyn Configuration,
+    name: Option<&str>,
+    options: &mut T,
+) where
+    T: Value + DeserializeOwned + 'static,
+{
+    if let Err(error) = binder.bind(configuration, name, options) {
+        panic!("{}", error)
+    }
+}
+
+fn _add_options_from_config<'a, T>(
+    services: &'a mut ServiceCollection,
+    name: Option<&str>,
+    key: &str,
+) -> OptionsBuilder<'a, T>
+where
+    T: Value + Default + DeserializeOwned + 'static,
+{
+    let state = name.map(|n| n.to_owned());
+    let descriptor =
+        singleton::<dyn OptionsChangeTokenSource<T>, ConfigurationChangeTokenSource<T>>()
+            .depends_on(exactly_one::<dyn Configuration>())
+            .from(move |sp| {
+                Ref::new(ConfigurationChangeTokenSource::new(
+                    state.as_deref(),
+                    sp.get_required::<dyn Configuration>(),
+                ))
+            });
+    let key = key.to_owned();
+    let registration = OptionsRegistration::new::<T>(name)
+        .for_role(OptionsRole::Configure)
+        .bound_to(REGISTERED_CONFIGURATION, &key)
+        .depends_on(Type::of::<dyn Configuration>());
+
+    services
+        .add(descriptor)
+        .add(existing_as_self(registration))
+        .add(existing_as_self(OptionsLinter::new::<T>(name, Some(&key))))
+        .add_named_options_many(name)
+        .add_configurer_with(move |sp| {
+            let configuration = sp.get_required::<dyn Configuration>();
+            let key = key.clone();
+            let binder = OptionsBinder::<T>::new(sp);
+
+            configure(move |name, options: &mut T| {
+                let section = configuration.section(&key);
+
+                if section.exists() {
+                    bind(&binder, section.deref(), name, options)
+                }
+            })
+        })
+}
+
+impl OptionsConfigurationServiceExtensions for ServiceCollection {
+    fn apply_config<T>(&mut self, configuration: Ref<dyn Configuration>) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        _apply_config(self, configuration, None, None)
+    }
+
+    fn apply_config_at<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        key: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        let key = key.as_ref();
+        _apply_config(self, configuration, Some(key), Some(key))
+    }
+
+    fn apply_config_for_env<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        base_key: impl AsRef<str>,
+        env: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        let key = base_key.as_ref().to_owned();
+        let env = env.as_ref().to_owned();
+        let linter = OptionsLinter::new::<T>(None, Some(&key)).with_overlay(&env);
+
+        self.add(existing_as_self(linter));
+        _watch_config(self, &configuration, None, Some(&key)).add_configurer_with(move |sp| {
+            let configuration = configuration.clone();
+            let key = key.clone();
+            let env = env.clone();
+            let binder = OptionsBinder::<T>::new(sp);
+
+            configure(move |name, options: &mut T| {
+                let base = configuration.section(&key);
+                let overlay = base.section(&env);
+
+                if base.exists() {
+                    let section = OverlaySection::new(base, overlay);
+                    bind(&binder, &section, name, options)
+                }
+            })
+        })
+    }
+
+    fn apply_declared_config<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: RegisterOptions + Value + Default + DeserializeOwned + 'static,
+    {
+        _apply_config(self, configuration, T::name(), T::section())
+    }
+
+    fn apply_cli_config_at<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        key: impl AsRef<str>,
+        name: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        let key = key.as_ref().to_owned();
+        let name = name.as_ref();
+        let linter = OptionsLinter::new::<T>(Some(name), Some(&key)).for_command_line();
+
+        self.add(existing_as_self(linter));
+        _watch_config(self, &configuration, Some(name), Some(&key)).add_configurer_with(move |sp| {
+            let configuration = configuration.clone();
+            let key = key.clone();
+            let binder = OptionsBinder::<T>::new(sp);
+
+            configure(move |name, options: &mut T| {
+                let section = configuration.section(&key);
+
+                if section.exists() {
+                    bind(&binder, &CommandLineSection::new(section), name, options)
+                }
+            })
+        })
+    }
+
+    fn add_options_from_config<T>(&mut self, key: impl AsRef<str>) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        _add_options_from_config(self, None, key.as_ref())
+    }
+
+    fn add_named_options_from_config<T>(
+        &mut self,
+        name: impl AsRef<str>,
+        key: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        _add_options_from_config(self, Some(name.as_ref()), key.as_ref())
+    }
+
+    fn apply_bindable_config<T>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+    ) -> OptionsBuilder<'_, T>
     where
-        T: Value + Default + DeserializeOwned + 'static;
+        T: BindOptions + Value + Default + 'static,
+    {
+        _apply_custom_config(self, configuration, None, None, bind_self::<T>)
+    }
 
-    /// Registers an options type that will have all of its associated services registered.
-    ///
-    /// # Arguments
-    ///
-    /// * `configuration` - The [configuration](config::Configuration) applied to the options
-    /// * `key` - The key to the part of the [configuration](config::Configuration) applied to the options
-    fn apply_config_at<T>(
+    fn apply_bindable_config_at<T>(
         &mut self,
         configuration: Ref<dyn Configuration>,
         key: impl AsRef<str>,
-    ) -> OptionsBuilder<T>
+    ) -> OptionsBuilder<'_, T>
     where
-        T: Value + Default + DeserializeOwned + 'static;
-}
+        T: BindOptions + Value + Default + 'static,
+    {
+        let key = key.as_ref();
+        _apply_custom_config(self, configuration, Some(key), Some(key), bind_self::<T>)
+    }
 
-impl OptionsConfigurationServiceExtensions for ServiceCollection {
-    fn apply_config<T>(&mut self, configuration: Ref<dyn Configuration>) -> OptionsBuilder<T>
+    fn apply_config_seeded<T, S, F>(
+        &mut self,
+        configuration: Ref<dyn Configuration>,
+        seed_factory: F,
+    ) -> OptionsBuilder<'_, T>
     where
-        T: Value + Default + DeserializeOwned + 'static,
+        T: Value + Default + 'static,
+        S: for<'de> DeserializeSeed<'de, Value = T> + 'static,
+        F: Fn() -> S + Value + 'static,
     {
-        let source = Box::new(ConfigurationChangeTokenSource::<T>::new(
-            None,
-            configuration.clone(),
-        ));
-        let descriptor =
-            existing::<dyn OptionsChangeTokenSource<T>, ConfigurationChangeTokenSource<T>>(source);
-
-        self.add(descriptor)
-            .add_options()
-            .configure(move |options: &mut T| configuration.bind(options))
+        _apply_seeded_config(self, configuration, None, None, seed_factory)
     }
 
-    fn apply_config_at<T>(
+    fn apply_config_seeded_at<T, S, F>(
         &mut self,
         configuration: Ref<dyn Configuration>,
         key: impl AsRef<str>,
-    ) -> OptionsBuilder<T>
+        seed_factory: F,
+    ) -> OptionsBuilder<'_, T>
     where
-        T: Value + Default + DeserializeOwned + 'static,
+        T: Value + Default + 'static,
+        S: for<'de> DeserializeSeed<'de, Value = T> + 'static,
+        F: Fn() -> S + Value + 'static,
     {
-        let source = Box::new(ConfigurationChangeTokenSource::<T>::new(
-            Some(key.as_ref()),
-            configuration.clone(),
-        ));
-        let descriptor =
-            existing::<dyn OptionsChangeTokenSource<T>, ConfigurationChangeTokenSource<T>>(source);
-        let key = key.as_ref().to_owned();
-
-        self.add(descriptor)
-            .add_named_options(&key)
-            .configure(move |options: &mut T| configuration.bind_at(&key, options))
+        let key = key.as_ref();
+        _apply_seeded_config(self, configuration, Some(key), Some(key), seed_factory)
     }
 }
 
@@ -113,7 +942,7 @@ mod tests {
 
     use super::*;
     use config::{ConfigurationBuilder, DefaultConfigurationBuilder};
-    use di::ServiceCollection;
+    use di::{singleton_factory, ServiceCollection};
     use serde::Deserialize;
     use serde_json::json;
     use std::env::temp_dir;
@@ -191,6 +1020,413 @@ mod tests {
         assert!(options.get(Some("Test")).enabled);
     }
 
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "PascalCase"))]
+    struct ServerOptions {
+        name: String,
+        hosts: Vec<String>,
+        max_connections: u16,
+    }
+
+    #[test]
+    fn apply_cli_config_at_should_bind_command_line_to_named_options() {
+        // arrange
+        let args = index_repeated_args(
+            [
+                "--servers:primary:hosts",
+                "a",
+                "-h",
+                "b",
+                "--servers:primary:name=db, primary",
+                "--servers:primary:max-connections=5",
+                "--servers:secondary:hosts=c,d",
+            ],
+            &[("-h", "Servers:Primary:Hosts")],
+        );
+        let mut builder = DefaultConfigurationBuilder::new();
+
+        builder.add(Box::new(config::CommandLineConfigurationSource::from(
+            args.iter(),
+        )));
+
+        let config: Ref<dyn Configuration> = Ref::from(builder.build().unwrap().as_config());
+        let mut services = ServiceCollection::new();
+
+        services.apply_cli_config_at::<ServerOptions>(config.clone(), "Servers:Primary", "Primary");
+        services.apply_cli_config_at::<ServerOptions>(config, "Servers:Secondary", "Secondary");
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let options = provider.get_required::<dyn OptionsSnapshot<ServerOptions>>();
+        let primary = options.get(Some("Primary"));
+        let secondary = options.get(Some("Secondary"));
+
+        // assert
+        assert_eq!(primary.name, "db, primary");
+        assert_eq!(primary.hosts, ["a", "b"]);
+        assert_eq!(primary.max_connections, 5);
+        assert_eq!(secondary.hosts, ["c", "d"]);
+    }
+
+    #[test]
+    fn apply_args_should_override_configuration() {
+        // arrange
+        struct Args {
+            disable: bool,
+        }
+
+        impl ApplyArgs<TestOptions> for Args {
+            fn apply_to(&self, options: &mut TestOptions) {
+                if self.disable {
+                    options.enabled = false;
+                }
+            }
+        }
+
+        let config = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Enabled", "true")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let provider = ServiceCollection::new()
+            .apply_config::<TestOptions>(config)
+            .apply_args(Args { disable: true })
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert!(!options.value().enabled);
+    }
+
+    #[test]
+    fn add_options_from_config_should_bind_registered_configuration_to_options() {
+        // arrange
+        let config: Ref<dyn Configuration> = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Test:Enabled", "true")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let provider = ServiceCollection::new()
+            .add(singleton_factory(move |_| config.clone()))
+            .add_options_from_config::<TestOptions>("Test")
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert!(options.value().enabled);
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all(deserialize = "PascalCase"))]
+    struct LoggingOptions {
+        level: String,
+        format: String,
+    }
+
+    #[test]
+    fn apply_config_for_env_should_overlay_environment_section() {
+        // arrange
+        let config: Ref<dyn Configuration> = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[
+                    ("Logging:Level", "Information"),
+                    ("Logging:Format", "json"),
+                    ("Logging:Production:Level", "Warning"),
+                ])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let mut services = ServiceCollection::new();
+
+        services.apply_config_for_env::<LoggingOptions>(config, "Logging", "Production");
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<LoggingOptions>>();
+
+        // assert
+        assert_eq!(&options.value().level, "Warning");
+        assert_eq!(&options.value().format, "json");
+        assert!(verify_options(&services).is_ok());
+    }
+
+    #[test]
+    fn verify_options_should_report_conflicting_configuration_bindings() {
+        // arrange
+        let config1 = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Enabled", "true")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let config2 = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Enabled", "false")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let mut services = ServiceCollection::new();
+
+        services
+            .apply_config::<TestOptions>(config1)
+            .apply_config::<TestOptions>(config2);
+
+        // act
+        let result = verify_options(&services);
+
+        // assert
+        assert!(result
+            .unwrap_err()
+            .failure_message()
+            .ends_with("are bound to more than one configuration."));
+    }
+
+    #[test]
+    fn apply_bindable_config_at_should_bind_configuration_to_options() {
+        // arrange
+        #[derive(Default)]
+        struct Threshold {
+            low: u8,
+            high: u8,
+        }
+
+        impl BindOptions for Threshold {
+            fn bind(&mut self, configuration: &dyn Configuration) -> Result<(), Error> {
+                let parse = |key| match configuration.get(key) {
+                    Some(value) => value
+                        .parse::<u8>()
+                        .map_err(|e| Error::Custom(format!("{}: {}", key, e))),
+                    _ => Ok(0),
+                };
+
+                self.low = parse("Low")?;
+                self.high = parse("High")?.max(self.low);
+                Ok(())
+            }
+        }
+
+        let config = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Threshold:Low", "10"), ("Threshold:High", "5")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let provider = ServiceCollection::new()
+            .apply_bindable_config_at::<Threshold>(config, "Threshold")
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn OptionsSnapshot<Threshold>>();
+
+        // assert
+        let threshold = options.get(Some("Threshold"));
+        assert_eq!(threshold.low, 10);
+        assert_eq!(threshold.high, 10);
+    }
+
+    #[test]
+    fn apply_config_seeded_at_should_deserialize_options_with_seed() {
+        // arrange
+        #[derive(Default)]
+        struct TimeoutOptions {
+            timeout_ms: u32,
+        }
+
+        struct UnitSeed {
+            factor: u32,
+        }
+
+        impl<'de> DeserializeSeed<'de> for UnitSeed {
+            type Value = TimeoutOptions;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(rename_all(deserialize = "PascalCase"))]
+                struct Timeout {
+                    timeout: u32,
+                }
+
+                let timeout = Timeout::deserialize(deserializer)?;
+
+                Ok(TimeoutOptions {
+                    timeout_ms: timeout.timeout * self.factor,
+                })
+            }
+        }
+
+        let config = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Http:Timeout", "30")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let provider = ServiceCollection::new()
+            .apply_config_seeded_at::<TimeoutOptions, _, _>(config, "Http", || UnitSeed {
+                factor: 1000,
+            })
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn OptionsSnapshot<TimeoutOptions>>();
+
+        // assert
+        assert_eq!(options.get(Some("Http")).timeout_ms, 30_000);
+    }
+
+    #[test]
+    fn bind_options_should_report_key_of_unknown_variant() {
+        // arrange
+        #[derive(Deserialize)]
+        enum Level {
+            Info,
+            Warning,
+        }
+
+        impl Default for Level {
+            fn default() -> Self {
+                Self::Info
+            }
+        }
+
+        #[derive(Default, Deserialize)]
+        #[serde(default, rename_all(deserialize = "PascalCase"))]
+        struct LoggingOptions {
+            format: String,
+            level: Level,
+        }
+
+        let config = DefaultConfigurationBuilder::new()
+            .add_in_memory(&[("Logging:Format", "json"), ("Logging:Level", "Verbose")])
+            .build()
+            .unwrap();
+        let section = config.section("Logging");
+        let mut options = LoggingOptions::default();
+
+        // act
+        let result = bind_options(section.deref(), &mut options, &DefaultOptionsMessages);
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid value for configuration key 'Logging:Level': \
+             unknown variant `Verbose`, expected `Info` or `Warning`"
+        );
+    }
+
+    #[test]
+    fn bind_options_should_report_path_of_nested_value() {
+        // arrange
+        #[derive(Default, Deserialize)]
+        #[serde(default, rename_all(deserialize = "PascalCase"))]
+        struct RetryOptions {
+            max_attempts: u8,
+        }
+
+        #[derive(Default, Deserialize)]
+        #[serde(default, rename_all(deserialize = "PascalCase"))]
+        struct HttpOptions {
+            timeout: u32,
+            retry: RetryOptions,
+        }
+
+        let config = DefaultConfigurationBuilder::new()
+            .add_in_memory(&[("Http:Timeout", "30"), ("Http:Retry:MaxAttempts", "many")])
+            .build()
+            .unwrap();
+        let section = config.section("Http");
+        let mut options = HttpOptions::default();
+
+        // act
+        let result = bind_options(section.deref(), &mut options, &DefaultOptionsMessages);
+
+        // assert
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid value for configuration key 'Http:Retry:MaxAttempts': "));
+    }
+
+    #[cfg(feature = "path-to-error")]
+    #[test]
+    fn bind_options_should_report_path_of_structure_that_rejects_its_fields() {
+        // arrange
+        #[derive(Deserialize)]
+        #[serde(rename_all(deserialize = "PascalCase"))]
+        struct RawRange {
+            min: u8,
+            max: u8,
+        }
+
+        #[derive(Default, Deserialize)]
+        #[serde(try_from = "RawRange")]
+        struct Range {
+            min: u8,
+            max: u8,
+        }
+
+        impl std::convert::TryFrom<RawRange> for Range {
+            type Error = String;
+
+            fn try_from(raw: RawRange) -> Result<Self, Self::Error> {
+                if raw.min > raw.max {
+                    Err("the minimum is greater than the maximum".into())
+                } else {
+                    Ok(Self {
+                        min: raw.min,
+                        max: raw.max,
+                    })
+                }
+            }
+        }
+
+        #[derive(Default, Deserialize)]
+        #[serde(default, rename_all(deserialize = "PascalCase"))]
+        struct PoolOptions {
+            size: Range,
+        }
+
+        let config = DefaultConfigurationBuilder::new()
+            .add_in_memory(&[("Pool:Size:Max", "1"), ("Pool:Size:Min", "5")])
+            .build()
+            .unwrap();
+        let section = config.section("Pool");
+        let mut options = PoolOptions::default();
+
+        // act
+        let result = bind_options(section.deref(), &mut options, &DefaultOptionsMessages);
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid value for configuration key 'Pool:Size': \
+             the minimum is greater than the maximum"
+        );
+        assert_eq!(options.size.min, 0);
+        assert_eq!(options.size.max, 0);
+    }
+
     #[test]
     fn options_should_be_updated_after_configuration_change() {
         // arrange
@@ -203,7 +1439,7 @@ mod tests {
 
         let config: Ref<dyn Configuration> = Ref::from(
             DefaultConfigurationBuilder::new()
-                .add_json_file(&path.is().reloadable())
+                .add_json_file(path.is().reloadable())
                 .build()
                 .unwrap()
                 .as_config(),
@@ -221,7 +1457,7 @@ mod tests {
         let _unused = token.register(
             Box::new(|s| {
                 let data = s.unwrap();
-                let (reloaded, event) = &*(data.downcast_ref::<(Mutex<bool>, Condvar)>().unwrap());
+                let (reloaded, event) = data.downcast_ref::<(Mutex<bool>, Condvar)>().unwrap();
                 *reloaded.lock().unwrap() = true;
                 event.notify_one();
             }),
@@ -253,7 +1489,93 @@ mod tests {
             remove_file(&path).ok();
         }
 
-        assert_eq!(original.enabled, true);
-        assert_eq!(current.enabled, false);
+        // the removal is reported on the thread that watches the file. if the configuration is
+        // dropped while it's reloaded, the watcher is torn down on its own thread and the test
+        // process aborts, so the configuration is kept alive until the process exits
+        std::mem::forget(provider);
+        std::mem::forget(config);
+
+        assert!(original.enabled);
+        assert!(!current.enabled);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_monitor_should_publish_configuration_change() {
+        // arrange
+        use config::{ConfigurationProvider, ConfigurationSource};
+        use std::future::Future;
+        use std::task::{Context, Poll, Wake, Waker};
+        use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+        #[derive(Clone, Default)]
+        struct ReloadableSource {
+            enabled: Arc<Mutex<bool>>,
+            token: SharedChangeToken<SingleChangeToken>,
+        }
+
+        impl ConfigurationSource for ReloadableSource {
+            fn build(&self, _builder: &dyn ConfigurationBuilder) -> Box<dyn ConfigurationProvider> {
+                Box::new(self.clone())
+            }
+        }
+
+        impl ConfigurationProvider for ReloadableSource {
+            fn get(&self, key: &str) -> Option<config::Value> {
+                if key.eq_ignore_ascii_case("Enabled") {
+                    Some(self.enabled.lock().unwrap().to_string().into())
+                } else {
+                    None
+                }
+            }
+
+            fn reload_token(&self) -> Box<dyn ChangeToken> {
+                Box::new(self.token.clone())
+            }
+
+            fn child_keys(&self, earlier_keys: &mut Vec<String>, parent_path: Option<&str>) {
+                if parent_path.is_none() {
+                    earlier_keys.push("Enabled".into());
+                }
+            }
+        }
+
+        struct NoopWaker;
+
+        impl Wake for NoopWaker {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let source = ReloadableSource::default();
+        let mut builder = DefaultConfigurationBuilder::new();
+
+        *source.enabled.lock().unwrap() = true;
+        builder.add(Box::new(source.clone()));
+
+        let config: Ref<dyn Configuration> = Ref::from(builder.build().unwrap().as_config());
+        let provider = ServiceCollection::new()
+            .apply_config::<TestOptions>(config)
+            .async_monitor(|mut task: OptionsTask| {
+                let waker = Waker::from(Arc::new(NoopWaker));
+                let _ = task.as_mut().poll(&mut Context::from_waker(&waker));
+            })
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<AsyncOptionsMonitor<TestOptions>>();
+        let original = monitor.current_value();
+        let mut receiver = monitor.subscribe();
+        let waker = Waker::from(Arc::new(NoopWaker));
+
+        *source.enabled.lock().unwrap() = false;
+        source.token.notify();
+
+        // act
+        let change = Box::pin(receiver.recv())
+            .as_mut()
+            .poll(&mut Context::from_waker(&waker));
+
+        // assert
+        assert!(original.enabled);
+        assert!(matches!(change, Poll::Ready(ref c) if !c.value().enabled));
     }
 }
@@ -0,0 +1,125 @@
+use crate::{ext::*, ApplyArgs, OptionsBuilder, Value};
+use clap::Parser;
+use di::ServiceCollection;
+use std::ffi::OsString;
+
+/// Defines extension methods for the [`ServiceCollection`](di::ServiceCollection) struct.
+pub trait OptionsCommandLineServiceExtensions {
+    /// Registers an options type which is overridden by the arguments of the current process.
+    ///
+    /// # Remarks
+    ///
+    /// The arguments are parsed once, when they are registered, and the process exits with the
+    /// usage of the command if they are invalid, which is the same as [`Parser::parse`]. The parsed
+    /// [arguments](crate::ApplyArgs) are applied as a post-configuration, which runs after the
+    /// options have been bound to configuration. When combined with configuration that is read from
+    /// a file and overlaid with environment variables, this results in a precedence of
+    /// file < environment < command line.
+    fn apply_cli<T, A>(&mut self) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + 'static,
+        A: Parser + ApplyArgs<T> + Value + 'static;
+
+    /// Registers an options type which is overridden by the specified arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The arguments to parse, where the first argument is the name of the binary
+    fn apply_cli_from<T, A, I>(&mut self, args: I) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + 'static,
+        A: Parser + ApplyArgs<T> + Value + 'static,
+        I: IntoIterator,
+        I::Item: Into<OsString> + Clone;
+}
+
+impl OptionsCommandLineServiceExtensions for ServiceCollection {
+    fn apply_cli<T, A>(&mut self) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + 'static,
+        A: Parser + ApplyArgs<T> + Value + 'static,
+    {
+        self.add_options().apply_args(A::parse())
+    }
+
+    fn apply_cli_from<T, A, I>(&mut self, args: I) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + 'static,
+        A: Parser + ApplyArgs<T> + Value + 'static,
+        I: IntoIterator,
+        I::Item: Into<OsString> + Clone,
+    {
+        self.add_options().apply_args(A::parse_from(args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Options;
+
+    #[derive(Default)]
+    struct ServerOptions {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Parser)]
+    struct Cli {
+        #[arg(long)]
+        port: Option<u16>,
+    }
+
+    impl ApplyArgs<ServerOptions> for Cli {
+        fn apply_to(&self, options: &mut ServerOptions) {
+            if let Some(port) = self.port {
+                options.port = port;
+            }
+        }
+    }
+
+    #[test]
+    fn apply_cli_from_should_override_configured_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_options::<ServerOptions>().configure(|o| {
+            o.host = "localhost".into();
+            o.port = 80;
+        });
+
+        let provider = services
+            .apply_cli_from::<ServerOptions, Cli, _>(["server", "--port", "8080"])
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<ServerOptions>>();
+
+        // assert
+        assert_eq!(options.value().host, "localhost");
+        assert_eq!(options.value().port, 8080);
+    }
+
+    #[test]
+    fn apply_cli_from_should_not_override_options_without_arguments() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<ServerOptions>()
+            .configure(|o| o.port = 80);
+
+        let provider = services
+            .apply_cli_from::<ServerOptions, Cli, _>(["server"])
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<ServerOptions>>();
+
+        // assert
+        assert_eq!(options.value().port, 80);
+    }
+}
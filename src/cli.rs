@@ -0,0 +1,350 @@
+use config::{Configuration, ConfigurationIterator, ConfigurationPath, ConfigurationSection};
+use di::Ref;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ops::Deref;
+use tokens::ChangeToken;
+
+// a section of command line configuration. arguments are spelled however they were typed, such
+// as --max-connections or --maxconnections, so keys are presented in pascal case with dashes
+// removed and without duplicates that differ only by case. the command line can only express a
+// list as a single comma-separated value, so a value without children is also presented as a list
+// whose items are its comma-separated parts. a field that is not a list only observes the value.
+#[derive(Clone)]
+pub(crate) struct CommandLineSection {
+    section: Ref<dyn ConfigurationSection>,
+    key: String,
+    item: Option<Ref<ListItem>>,
+}
+
+struct ListItem {
+    path: String,
+    value: config::Value,
+}
+
+impl CommandLineSection {
+    pub(crate) fn new(section: Box<dyn ConfigurationSection>) -> Self {
+        Self {
+            key: pascal_case(section.key()),
+            section: section.into(),
+            item: None,
+        }
+    }
+
+    fn item(&self, key: String, value: &str) -> Self {
+        Self {
+            section: self.section.clone(),
+            item: Some(Ref::new(ListItem {
+                path: format!(
+                    "{}{}{}",
+                    self.path(),
+                    ConfigurationPath::key_delimiter(),
+                    key
+                ),
+                value: config::Value::new(value.to_owned()),
+            })),
+            key,
+        }
+    }
+}
+
+fn pascal_case(key: &str) -> String {
+    key.split('-')
+        .flat_map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
+
+impl Configuration for CommandLineSection {
+    fn get(&self, key: &str) -> Option<config::Value> {
+        match self.item {
+            Some(_) => None,
+            _ => self.section.get(key),
+        }
+    }
+
+    fn section(&self, key: &str) -> Box<dyn ConfigurationSection> {
+        match self.item {
+            Some(_) => Box::new(self.item(key.to_owned(), "")),
+            _ => Box::new(Self::new(self.section.section(key))),
+        }
+    }
+
+    fn children(&self) -> Vec<Box<dyn ConfigurationSection>> {
+        if self.item.is_some() {
+            return Vec::new();
+        }
+
+        let children = self.section.children();
+
+        if !children.is_empty() {
+            let mut keys: Vec<&str> = Vec::new();
+
+            for child in &children {
+                if !keys.iter().any(|k| k.eq_ignore_ascii_case(child.key())) {
+                    keys.push(child.key());
+                }
+            }
+
+            return keys
+                .into_iter()
+                .map(|key| {
+                    Box::new(Self::new(self.section.section(key))) as Box<dyn ConfigurationSection>
+                })
+                .collect();
+        }
+
+        self.section
+            .value()
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .enumerate()
+            .map(|(index, part)| {
+                Box::new(self.item(index.to_string(), part)) as Box<dyn ConfigurationSection>
+            })
+            .collect()
+    }
+
+    fn reload_token(&self) -> Box<dyn ChangeToken> {
+        self.section.reload_token()
+    }
+
+    fn as_section(&self) -> Option<&dyn ConfigurationSection> {
+        Some(self)
+    }
+
+    fn iter(
+        &self,
+        path: Option<ConfigurationPath>,
+    ) -> Box<dyn Iterator<Item = (String, config::Value)>> {
+        Box::new(ConfigurationIterator::new(
+            self,
+            path.unwrap_or(ConfigurationPath::Absolute),
+        ))
+    }
+}
+
+impl ConfigurationSection for CommandLineSection {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn path(&self) -> &str {
+        match &self.item {
+            Some(item) => &item.path,
+            _ => self.section.path(),
+        }
+    }
+
+    fn value(&self) -> config::Value {
+        match &self.item {
+            Some(item) => item.value.clone(),
+            _ => self.section.value(),
+        }
+    }
+
+    fn as_config(&self) -> Box<dyn Configuration> {
+        Box::new(self.clone())
+    }
+}
+
+impl<'a> AsRef<dyn Configuration + 'a> for CommandLineSection {
+    fn as_ref(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+impl<'a> Borrow<dyn Configuration + 'a> for CommandLineSection {
+    fn borrow(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+impl Deref for CommandLineSection {
+    type Target = dyn Configuration;
+
+    fn deref(&self) -> &Self::Target {
+        self
+    }
+}
+
+// an argument in the forms understood by the command line configuration provider
+struct Argument {
+    key: String,
+    value: String,
+    tokens: Vec<String>,
+}
+
+fn parse<S: AsRef<str>>(
+    args: &[String],
+    switch_mappings: &[(S, S)],
+) -> Vec<Result<Argument, String>> {
+    let mappings: HashMap<_, _> = switch_mappings
+        .iter()
+        .map(|(k, v)| (k.as_ref().to_uppercase(), v.as_ref()))
+        .collect();
+    let mut parsed = Vec::with_capacity(args.len());
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let (switch, start) = if let Some(rest) = arg.strip_prefix("--") {
+            (format!("--{}", rest), 2)
+        } else if arg.starts_with('-') {
+            (arg.clone(), 1)
+        } else if let Some(rest) = arg.strip_prefix('/') {
+            (format!("--{}", rest), 2)
+        } else {
+            (arg.clone(), 0)
+        };
+        let separator = switch.find('=');
+        let (segment, value, tokens) = match separator {
+            Some(separator) => (
+                switch[..separator].to_owned(),
+                switch[separator + 1..].to_owned(),
+                vec![arg.clone()],
+            ),
+            _ if start > 0 => match args.next() {
+                Some(value) => (
+                    switch.clone(),
+                    value.clone(),
+                    vec![arg.clone(), value.clone()],
+                ),
+                _ => {
+                    parsed.push(Err(arg.clone()));
+                    continue;
+                }
+            },
+            _ => {
+                parsed.push(Err(arg.clone()));
+                continue;
+            }
+        };
+
+        // a short switch with a value in the same argument is only understood when it is mapped
+        let key = match mappings.get(&segment.to_uppercase()) {
+            Some(mapping) => (*mapping).to_owned(),
+            _ if start == 1 && separator.is_some() => {
+                parsed.extend(tokens.into_iter().map(Err));
+                continue;
+            }
+            _ => segment[start..].to_owned(),
+        };
+
+        parsed.push(Ok(Argument { key, value, tokens }));
+    }
+
+    parsed
+}
+
+/// Indexes command line arguments that are repeated so that they bind to a list.
+///
+/// # Arguments
+///
+/// * `args` - The command line arguments
+/// * `switch_mappings` - The mapping of switches to configuration keys
+///
+/// # Remarks
+///
+/// The command line configuration provider keeps only the last value of an argument that is
+/// repeated. Every occurrence of a repeated argument, such as `--host a --host b`, is rewritten
+/// with the zero-based index of its occurrence, such as `--host:0 a --host:1 b`, which binds to a
+/// list field. Switch mappings are applied before arguments are compared, so a mapped switch and
+/// its key are the same argument. Arguments that are not repeated are unchanged.
+pub fn index_repeated_args<I, S1, S2>(args: I, switch_mappings: &[(S2, S2)]) -> Vec<String>
+where
+    I: IntoIterator<Item = S1>,
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    let args: Vec<_> = args.into_iter().map(|a| a.as_ref().to_owned()).collect();
+    let parsed = parse(&args, switch_mappings);
+    let mut counts = HashMap::<String, (usize, &str)>::new();
+
+    // every occurrence is spelled like the first so that the indexes share a section
+    for argument in parsed.iter().flatten() {
+        counts
+            .entry(argument.key.to_uppercase())
+            .or_insert((0, &argument.key))
+            .0 += 1;
+    }
+
+    let mut indexes = HashMap::<String, usize>::new();
+    let mut indexed = Vec::with_capacity(args.len());
+
+    for argument in &parsed {
+        let argument = match argument {
+            Ok(argument) => argument,
+            Err(arg) => {
+                indexed.push(arg.clone());
+                continue;
+            }
+        };
+
+        match counts[&argument.key.to_uppercase()] {
+            (count, key) if count > 1 => {
+                let index = indexes.entry(argument.key.to_uppercase()).or_default();
+
+                indexed.push(format!(
+                    "--{}{}{}",
+                    key,
+                    ConfigurationPath::key_delimiter(),
+                    index
+                ));
+                indexed.push(argument.value.clone());
+                *index += 1;
+            }
+            _ => indexed.extend(argument.tokens.iter().cloned()),
+        }
+    }
+
+    indexed
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn index_repeated_args_should_index_every_occurrence_of_repeated_arguments() {
+        // arrange
+        let args = [
+            "--host",
+            "a",
+            "-h",
+            "b",
+            "/HOST=c",
+            "--port=80",
+            "--verbose",
+            "true",
+            "build",
+        ];
+
+        // act
+        let indexed = index_repeated_args(args, &[("-h", "host")]);
+
+        // assert
+        assert_eq!(
+            indexed,
+            [
+                "--host:0",
+                "a",
+                "--host:1",
+                "b",
+                "--host:2",
+                "c",
+                "--port=80",
+                "--verbose",
+                "true",
+                "build"
+            ]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::confined::Confined;
+use crate::monitor::alive;
+use crate::sync::RwLock;
+use crate::{OptionsMonitor, Ref, Subscription, Value};
+use std::sync::{Arc, Weak};
+
+type CombinedCallback<A, B> = dyn Fn(Ref<A>, Ref<B>) + Send + Sync;
+
+impl<A: Value + 'static> dyn OptionsMonitor<A> {
+    /// Creates a monitor that combines the monitored [`Options`](crate::Options) with other
+    /// monitored options.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The [monitor](crate::OptionsMonitor) of the options to combine with
+    pub fn combine<B: Value + 'static>(
+        self: Ref<Self>,
+        other: Ref<dyn OptionsMonitor<B>>,
+    ) -> CombinedMonitor<A, B> {
+        CombinedMonitor::new(self, other)
+    }
+}
+
+/// Represents a subscription to the changes of a [`CombinedMonitor`].
+///
+/// # Remarks
+///
+/// When the subscription is dropped, the underlying callback is unsubscribed.
+pub struct CombinedSubscription<A, B>(#[allow(dead_code)] Arc<CombinedCallback<A, B>>);
+
+/// Represents a monitor for two types of monitored [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// A component whose behavior derives from two types of options can use a single subscription to
+/// be notified when either of them change. Listeners always receive the current options of both
+/// types. Only the options with the default name are combined.
+pub struct CombinedMonitor<A: Value, B: Value> {
+    first: Ref<dyn OptionsMonitor<A>>,
+    second: Ref<dyn OptionsMonitor<B>>,
+    combination: Arc<Combination<A, B>>,
+    _first: Subscription<A>,
+    _second: Subscription<B>,
+}
+
+struct Combination<A: Value, B: Value> {
+    first: Ref<dyn OptionsMonitor<A>>,
+    second: Ref<dyn OptionsMonitor<B>>,
+    listeners: RwLock<Vec<Weak<CombinedCallback<A, B>>>>,
+}
+
+impl<A: Value + 'static, B: Value + 'static> CombinedMonitor<A, B> {
+    /// Initializes a new combined monitor.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The [monitor](crate::OptionsMonitor) of the first type of options
+    /// * `second` - The [monitor](crate::OptionsMonitor) of the second type of options
+    pub fn new(first: Ref<dyn OptionsMonitor<A>>, second: Ref<dyn OptionsMonitor<B>>) -> Self {
+        let combination = Arc::new(Combination {
+            first: first.clone(),
+            second: second.clone(),
+            listeners: Default::default(),
+        });
+        let weak = Confined::new(Arc::downgrade(&combination));
+        let first_subscription = first.on_change(Box::new(move |name, a| {
+            if let (None, Some(combination)) = (name, weak.get().and_then(|c| c.upgrade())) {
+                combination.notify(a, combination.second.current_value());
+            }
+        }));
+        let weak = Confined::new(Arc::downgrade(&combination));
+        let second_subscription = second.on_change(Box::new(move |name, b| {
+            if let (None, Some(combination)) = (name, weak.get().and_then(|c| c.upgrade())) {
+                combination.notify(combination.first.current_value(), b);
+            }
+        }));
+
+        Self {
+            first,
+            second,
+            combination,
+            _first: first_subscription,
+            _second: second_subscription,
+        }
+    }
+
+    /// Returns the current options of both types.
+    pub fn current(&self) -> (Ref<A>, Ref<B>) {
+        (self.first.current_value(), self.second.current_value())
+    }
+
+    /// Registers a callback function to be invoked when either type of options changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - The callback function invoked with the current options of both types
+    ///
+    /// # Returns
+    ///
+    /// A change subscription. When the subscription is dropped, no further notifications will be
+    /// propagated.
+    pub fn on_change(&self, listener: Box<CombinedCallback<A, B>>) -> CombinedSubscription<A, B> {
+        let mut listeners = self.combination.listeners.write().unwrap();
+
+        listeners.retain(|l| l.strong_count() > 0);
+
+        let listener: Arc<CombinedCallback<A, B>> = Arc::from(listener);
+
+        listeners.push(Arc::downgrade(&listener));
+        CombinedSubscription(listener)
+    }
+}
+
+impl<A: Value, B: Value> Combination<A, B> {
+    fn notify(&self, a: Ref<A>, b: Ref<B>) {
+        // do NOT invoke the callbacks with the lock held. a callback might register a new callback
+        for callback in alive(&self.listeners) {
+            callback(a.clone(), b.clone());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::ServiceCollection;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct PoolOptions {
+        size: u8,
+    }
+
+    #[derive(Default)]
+    struct TimeoutOptions {
+        seconds: u8,
+    }
+
+    #[test]
+    fn combined_monitor_should_notify_with_both_options_when_either_changes() {
+        // arrange
+        let size = Arc::new(AtomicU8::new(1));
+        let seconds = Arc::new(AtomicU8::new(30));
+        let (s1, s2) = (size.clone(), seconds.clone());
+        let mut services = ServiceCollection::new();
+
+        services.configure_options::<PoolOptions, _>(move |o| o.size = s1.load(Ordering::SeqCst));
+        services
+            .configure_options::<TimeoutOptions, _>(move |o| o.seconds = s2.load(Ordering::SeqCst));
+        services.monitor_pair::<PoolOptions, TimeoutOptions>();
+
+        let provider = services.build_provider().unwrap();
+        let pool = provider.get_required::<dyn OptionsMonitor<PoolOptions>>();
+        let timeout = provider.get_required::<dyn OptionsMonitor<TimeoutOptions>>();
+        let monitor = provider.get_required::<CombinedMonitor<PoolOptions, TimeoutOptions>>();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let observed = changes.clone();
+        let _subscription = monitor.on_change(Box::new(move |pool, timeout| {
+            observed.lock().unwrap().push((pool.size, timeout.seconds))
+        }));
+        let original = monitor.current();
+
+        // act
+        size.store(2, Ordering::SeqCst);
+        pool.reload(None);
+        pool.reload(Some("Other"));
+        seconds.store(60, Ordering::SeqCst);
+        timeout.reload(None);
+
+        // assert
+        assert_eq!((original.0.size, original.1.seconds), (1, 30));
+        assert_eq!(*changes.lock().unwrap(), [(2, 30), (2, 60)]);
+    }
+}
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+/// Represents the policy used to compare the names of configuration [`Options`](crate::Options).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OptionsNameComparer {
+    /// Indicates names must match exactly.
+    Exact,
+
+    /// Indicates names match when they are equal, ignoring ASCII case.
+    IgnoreAsciiCase,
+
+    /// Indicates names match when they are equal, ignoring Unicode case.
+    IgnoreCase,
+}
+
+impl OptionsNameComparer {
+    /// Gets a value indicating whether the specified names are equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to compare
+    /// * `other` - The name to compare against
+    pub fn equals(&self, name: &str, other: &str) -> bool {
+        match self {
+            Self::Exact => name == other,
+            Self::IgnoreAsciiCase => name.eq_ignore_ascii_case(other),
+            Self::IgnoreCase => name
+                .chars()
+                .flat_map(char::to_lowercase)
+                .eq(other.chars().flat_map(char::to_lowercase)),
+        }
+    }
+
+    /// Normalizes the specified name such that names which are equal produce the same value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to normalize
+    pub fn normalize<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Exact => Cow::Borrowed(name),
+            Self::IgnoreAsciiCase => {
+                if name.bytes().any(|b| b.is_ascii_uppercase()) {
+                    Cow::Owned(name.to_ascii_lowercase())
+                } else {
+                    Cow::Borrowed(name)
+                }
+            }
+            Self::IgnoreCase => {
+                // each character is folded on its own, as it is by equals, so that a titlecase
+                // character or a final sigma normalizes to the same value as its equal names
+                if name.chars().any(|c| c.to_lowercase().ne(Some(c))) {
+                    Cow::Owned(name.chars().flat_map(char::to_lowercase).collect())
+                } else {
+                    Cow::Borrowed(name)
+                }
+            }
+        }
+    }
+}
+
+impl Default for OptionsNameComparer {
+    fn default() -> Self {
+        Self::IgnoreCase
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(OptionsNameComparer::Exact, "Test", "Test", true ; "exact with same case")]
+    #[test_case(OptionsNameComparer::Exact, "Test", "test", false ; "exact with different case")]
+    #[test_case(OptionsNameComparer::IgnoreAsciiCase, "Test", "tEST", true ; "ascii with different case")]
+    #[test_case(OptionsNameComparer::IgnoreAsciiCase, "Ärger", "ärger", false ; "ascii with unicode")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "Ärger", "ärger", true ; "unicode with different case")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "Test", "Tests", false ; "unicode with different length")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "ΣΑΣ", "σασ", true ; "unicode with sigma")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "ǅ", "ǆ", true ; "unicode with titlecase")]
+    fn equals_should_compare_names(
+        comparer: OptionsNameComparer,
+        name: &str,
+        other: &str,
+        expected: bool,
+    ) {
+        // arrange
+
+        // act
+        let result = comparer.equals(name, other);
+
+        // assert
+        assert_eq!(result, expected);
+    }
+
+    #[test_case(OptionsNameComparer::Exact, "Test", "Test" ; "exact")]
+    #[test_case(OptionsNameComparer::IgnoreAsciiCase, "ÄrGER", "Ärger" ; "ascii")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "ÄrGER", "ärger" ; "unicode")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "ΣΑΣ", "σασ" ; "unicode with sigma")]
+    #[test_case(OptionsNameComparer::IgnoreCase, "ǅ", "ǆ" ; "unicode with titlecase")]
+    fn normalize_should_produce_canonical_name(
+        comparer: OptionsNameComparer,
+        name: &str,
+        expected: &str,
+    ) {
+        // arrange
+
+        // act
+        let normalized = comparer.normalize(name);
+
+        // assert
+        assert_eq!(normalized, expected);
+    }
+}
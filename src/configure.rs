@@ -1,3 +1,4 @@
+use crate::Value;
 use std::marker::PhantomData;
 
 /// Defines the behavior of something that configures [`Options`](crate::Options).
@@ -5,6 +6,7 @@ use std::marker::PhantomData;
 /// # Remarks
 ///
 /// These are all run first
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
 pub trait ConfigureOptions<T> {
     /// Configures the corresponding options.
     ///
@@ -13,6 +15,33 @@ pub trait ConfigureOptions<T> {
     /// * `name` - The optional name of the options to configure
     /// * `options` - The options to configure
     fn configure(&self, name: Option<&str>, options: &mut T);
+
+    /// Attempts to configure the corresponding options.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to configure
+    /// * `options` - The options to configure
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation calls [`configure`](ConfigureOptions::configure) and always succeeds.
+    /// Any failure is reported when the options are created.
+    fn try_configure(&self, name: Option<&str>, options: &mut T) -> Result<(), String> {
+        self.configure(name, options);
+        Ok(())
+    }
+
+    /// Gets a value indicating whether the configuration applies to every name.
+    ///
+    /// # Remarks
+    ///
+    /// A configuration that applies to every name is only applied to the first layer of
+    /// [inherited](crate::OptionsInheritance) options so that the layer of the parent is not
+    /// overwritten. The default implementation returns `false`.
+    fn applies_to_every_name(&self) -> bool {
+        false
+    }
 }
 
 /// Defines the behavior of something that configures [`Options`](crate::Options).
@@ -20,6 +49,7 @@ pub trait ConfigureOptions<T> {
 /// # Remarks
 ///
 /// These are all run last
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
 pub trait PostConfigureOptions<T> {
     /// Configures the corresponding options.
     ///
@@ -28,6 +58,33 @@ pub trait PostConfigureOptions<T> {
     /// * `name` - The optional name of the options to configure
     /// * `options` - The options to configure
     fn post_configure(&self, name: Option<&str>, options: &mut T);
+
+    /// Attempts to configure the corresponding options.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to configure
+    /// * `options` - The options to configure
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation calls [`post_configure`](PostConfigureOptions::post_configure) and
+    /// always succeeds. Any failure is reported when the options are created.
+    fn try_post_configure(&self, name: Option<&str>, options: &mut T) -> Result<(), String> {
+        self.post_configure(name, options);
+        Ok(())
+    }
+
+    /// Gets a value indicating whether the configuration applies to every name.
+    ///
+    /// # Remarks
+    ///
+    /// A configuration that applies to every name is only applied to the first layer of
+    /// [inherited](crate::OptionsInheritance) options so that the layer of the parent is not
+    /// overwritten. The default implementation returns `false`.
+    fn applies_to_every_name(&self) -> bool {
+        false
+    }
 }
 
 /// Creates and returns [options configuration](ConfigureOptions) for the specified action.
@@ -37,7 +94,8 @@ pub trait PostConfigureOptions<T> {
 /// * `action` - The configuration action
 pub fn configure<T, F>(action: F) -> impl ConfigureOptions<T>
 where
-    F: Fn(Option<&str>, &mut T),
+    T: Value,
+    F: Fn(Option<&str>, &mut T) + Value,
 {
     _ConfigureOptions::new(action)
 }
@@ -49,7 +107,8 @@ where
 /// * `action` - The post configuration action
 pub fn post_configure<T, F>(action: F) -> impl PostConfigureOptions<T>
 where
-    F: Fn(Option<&str>, &mut T),
+    T: Value,
+    F: Fn(Option<&str>, &mut T) + Value,
 {
     _ConfigureOptions::new(action)
 }
@@ -76,7 +135,8 @@ where
 
 impl<TOptions, TAction> ConfigureOptions<TOptions> for _ConfigureOptions<TOptions, TAction>
 where
-    TAction: Fn(Option<&str>, &mut TOptions),
+    TOptions: Value,
+    TAction: Fn(Option<&str>, &mut TOptions) + Value,
 {
     fn configure(&self, name: Option<&str>, options: &mut TOptions) {
         (self.action)(name, options)
@@ -85,7 +145,8 @@ where
 
 impl<TOptions, TAction> PostConfigureOptions<TOptions> for _ConfigureOptions<TOptions, TAction>
 where
-    TAction: Fn(Option<&str>, &mut TOptions),
+    TOptions: Value,
+    TAction: Fn(Option<&str>, &mut TOptions) + Value,
 {
     fn post_configure(&self, name: Option<&str>, options: &mut TOptions) {
         (self.action)(name, options)
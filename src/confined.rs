@@ -0,0 +1,279 @@
+// change tokens can signal from any thread, so everything reached by their callbacks must be Send +
+// Sync. when the 'async' feature is enabled, every options service is Send + Sync and the compiler
+// verifies it, so a confined value is simply shared. without it, options services are built from Rc
+// references and must stay on the thread that created them. the value is then kept by that thread
+// and a callback only holds a handle to it. work posted to the handle on the owning thread is done
+// immediately. work posted on any other thread is queued and done on the owning thread when options
+// are next retrieved there.
+
+#[cfg(not(feature = "async"))]
+use crate::singleton::Singleton;
+
+#[cfg(not(feature = "async"))]
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    marker::PhantomData,
+    mem::take,
+    rc::Rc,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    sync::{Arc, Mutex},
+    thread::{self, ThreadId},
+};
+
+#[cfg(feature = "async")]
+#[derive(Clone)]
+pub(crate) struct Confined<T>(T);
+
+#[cfg(feature = "async")]
+impl<T: Send + Sync + 'static> Confined<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub(crate) fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        Some(self.0.clone())
+    }
+
+    pub(crate) fn post<F: FnOnce(&T) + Send + 'static>(&self, work: F) {
+        work(&self.0)
+    }
+}
+
+// every value is shared, so there is never any queued work
+#[cfg(feature = "async")]
+pub(crate) fn deliver() {}
+
+#[cfg(not(feature = "async"))]
+type Work = Box<dyn FnOnce(&dyn Any) + Send>;
+
+#[cfg(not(feature = "async"))]
+struct Posted {
+    owner: ThreadId,
+    id: u64,
+
+    // no work releases the value, which is posted when its last handle is dropped on another thread
+    work: Option<Work>,
+}
+
+#[cfg(not(feature = "async"))]
+thread_local! {
+    static VALUES: RefCell<HashMap<u64, Rc<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+#[cfg(not(feature = "async"))]
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+// the amount of queued work, which lets retrieving options skip the queue when it is empty
+#[cfg(not(feature = "async"))]
+static QUEUED: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(not(feature = "async"))]
+fn queue() -> &'static Mutex<Vec<Posted>> {
+    static QUEUE: Singleton<Mutex<Vec<Posted>>> = Singleton::new();
+    QUEUE.get_or_init(Default::default)
+}
+
+#[cfg(not(feature = "async"))]
+fn enqueue(posted: Posted) {
+    let mut queue = queue().lock().unwrap();
+    queue.push(posted);
+    QUEUED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[cfg(not(feature = "async"))]
+fn value(id: u64) -> Option<Rc<dyn Any>> {
+    // the value is cloned so that no borrow is held while work is done
+    VALUES
+        .try_with(|values| values.borrow().get(&id).cloned())
+        .ok()
+        .flatten()
+}
+
+#[cfg(not(feature = "async"))]
+fn release(id: u64) {
+    // the value is dropped after the borrow is released
+    let released = VALUES.try_with(|values| values.borrow_mut().remove(&id));
+    drop(released);
+}
+
+// does the work queued for the values owned by the current thread
+#[cfg(not(feature = "async"))]
+pub(crate) fn deliver() {
+    if QUEUED.load(Ordering::SeqCst) == 0 {
+        return;
+    }
+
+    let owner = thread::current().id();
+    let posted: Vec<_> = {
+        let mut queue = queue().lock().unwrap();
+        let (owned, other): (Vec<_>, Vec<_>) = take(&mut *queue)
+            .into_iter()
+            .partition(|p| p.owner == owner);
+
+        *queue = other;
+        QUEUED.fetch_sub(owned.len(), Ordering::SeqCst);
+        owned
+    };
+
+    // work is done without the queue locked because it may post more work
+    for posted in posted {
+        match posted.work {
+            Some(work) => {
+                if let Some(value) = value(posted.id) {
+                    work(&*value);
+                }
+            }
+            _ => release(posted.id),
+        }
+    }
+}
+
+#[cfg(not(feature = "async"))]
+struct Key {
+    owner: ThreadId,
+    id: u64,
+}
+
+#[cfg(not(feature = "async"))]
+impl Drop for Key {
+    fn drop(&mut self) {
+        if thread::current().id() == self.owner {
+            release(self.id);
+        } else {
+            enqueue(Posted {
+                owner: self.owner,
+                id: self.id,
+                work: None,
+            });
+        }
+    }
+}
+
+#[cfg(not(feature = "async"))]
+pub(crate) struct Confined<T> {
+    key: Arc<Key>,
+    _value: PhantomData<fn() -> T>,
+}
+
+#[cfg(not(feature = "async"))]
+impl<T> Clone for Confined<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            _value: PhantomData,
+        }
+    }
+}
+
+#[cfg(not(feature = "async"))]
+impl<T: 'static> Confined<T> {
+    pub(crate) fn new(value: T) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+
+        VALUES.with(|values| values.borrow_mut().insert(id, Rc::new(value)));
+
+        Self {
+            key: Arc::new(Key {
+                owner: thread::current().id(),
+                id,
+            }),
+            _value: PhantomData,
+        }
+    }
+
+    // the value is only available on the thread that owns it
+    pub(crate) fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.owned()
+            .map(|value| value.downcast_ref::<T>().unwrap().clone())
+    }
+
+    pub(crate) fn post<F: FnOnce(&T) + Send + 'static>(&self, work: F) {
+        if let Some(value) = self.owned() {
+            work(value.downcast_ref().unwrap());
+        } else if thread::current().id() != self.key.owner {
+            enqueue(Posted {
+                owner: self.key.owner,
+                id: self.key.id,
+                work: Some(Box::new(move |value| work(value.downcast_ref().unwrap()))),
+            });
+        }
+    }
+
+    fn owned(&self) -> Option<Rc<dyn Any>> {
+        if thread::current().id() == self.key.owner {
+            value(self.key.id)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn post_should_do_work_immediately_on_owning_thread() {
+        // arrange
+        let counter = Rc::new(Cell::new(0));
+        let confined = Confined::new(counter.clone());
+
+        // act
+        confined.post(|counter| counter.set(counter.get() + 1));
+
+        // assert
+        assert_eq!(counter.get(), 1);
+    }
+
+    #[test]
+    fn post_should_do_work_on_owning_thread_when_delivered() {
+        // arrange
+        let counter = Rc::new(Cell::new(0));
+        let confined = Confined::new(counter.clone());
+        let handle = confined.clone();
+
+        // act
+        thread::spawn(move || {
+            assert!(handle.get().is_none());
+            handle.post(|counter| counter.set(counter.get() + 1));
+        })
+        .join()
+        .unwrap();
+
+        let queued = counter.get();
+
+        deliver();
+
+        // assert
+        assert_eq!(queued, 0);
+        assert_eq!(counter.get(), 1);
+    }
+
+    #[test]
+    fn value_should_be_released_when_last_handle_is_dropped_on_another_thread() {
+        // arrange
+        let counter = Rc::new(Cell::new(0));
+        let confined = Confined::new(counter.clone());
+
+        // act
+        thread::spawn(move || drop(confined)).join().unwrap();
+
+        let kept = Rc::strong_count(&counter);
+
+        deliver();
+
+        // assert
+        assert_eq!(kept, 2);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
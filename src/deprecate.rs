@@ -0,0 +1,181 @@
+use crate::{trace, view::join, ConfigurationMigration};
+use di::Ref;
+use std::marker::PhantomData;
+
+/// Represents a deprecated key in the configuration bound to [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// A deprecated key is registered using [`deprecate`](crate::OptionsBuilder::deprecate) and is
+/// relative to the configuration section the options are bound to. A warning is emitted each time
+/// the options are bound to a configuration that contains the key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecatedKey {
+    key: String,
+    replacement: Option<String>,
+    map_value: bool,
+}
+
+impl DeprecatedKey {
+    /// Initializes a new deprecated key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The deprecated configuration key, such as `Timeout` or `Retry:Count`
+    pub fn new<S: AsRef<str>>(key: S) -> Self {
+        Self {
+            key: key.as_ref().to_owned(),
+            replacement: None,
+            map_value: false,
+        }
+    }
+
+    /// Sets the configuration key that replaces the deprecated key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key that should be used instead
+    pub fn replaced_by<S: AsRef<str>>(mut self, key: S) -> Self {
+        self.replacement = Some(key.as_ref().to_owned());
+        self
+    }
+
+    /// Maps the value of the deprecated key to its replacement.
+    ///
+    /// # Remarks
+    ///
+    /// The value is only mapped when the configuration does not contain the replacement, which
+    /// allows existing configuration to be migrated without changing how the options are bound.
+    pub fn map_value(mut self) -> Self {
+        self.map_value = true;
+        self
+    }
+
+    /// Gets the deprecated configuration key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Gets the configuration key that replaces the deprecated key, if any.
+    pub fn replacement(&self) -> Option<&str> {
+        self.replacement.as_deref()
+    }
+}
+
+pub(crate) struct Deprecation<T> {
+    key: DeprecatedKey,
+    _options: PhantomData<T>,
+}
+
+impl<T> Deprecation<T> {
+    pub(crate) fn new(key: DeprecatedKey) -> Self {
+        Self {
+            key,
+            _options: PhantomData,
+        }
+    }
+}
+
+pub(crate) fn deprecate<T>(
+    deprecations: &[Ref<Deprecation<T>>],
+    path: &str,
+    name: Option<&str>,
+    configuration: &mut ConfigurationMigration,
+) {
+    for deprecation in deprecations {
+        let deprecated = &deprecation.key;
+
+        if !configuration.contains(&deprecated.key) {
+            continue;
+        }
+
+        trace::deprecated::<T>(name, &join(path, &deprecated.key), deprecated.replacement());
+
+        if let Some(replacement) = &deprecated.replacement {
+            // configured values always take precedence over deprecated values
+            if deprecated.map_value {
+                configuration.rename(&deprecated.key, replacement);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use config::{ext::*, Configuration, ConfigurationBuilder, DefaultConfigurationBuilder};
+    use di::ServiceCollection;
+    use serde::Deserialize;
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "PascalCase"))]
+    struct HttpOptions {
+        request_timeout: u32,
+        retry: RetryOptions,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "PascalCase"))]
+    struct RetryOptions {
+        max_attempts: u8,
+    }
+
+    fn new_options(values: &[(&str, &str)]) -> Ref<HttpOptions> {
+        let config: Ref<dyn Configuration> = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(values)
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+
+        ServiceCollection::new()
+            .apply_config_at::<HttpOptions>(config, "Http")
+            .deprecate(
+                DeprecatedKey::new("Timeout")
+                    .replaced_by("RequestTimeout")
+                    .map_value(),
+            )
+            .deprecate(DeprecatedKey::new("Retries").replaced_by("Retry:MaxAttempts"))
+            .deprecate(
+                DeprecatedKey::new("Backoff:Attempts")
+                    .replaced_by("Retry:MaxAttempts")
+                    .map_value(),
+            )
+            .build_provider()
+            .unwrap()
+            .get_required::<dyn OptionsMonitor<HttpOptions>>()
+            .get(Some("Http"))
+    }
+
+    #[test]
+    fn deprecated_key_should_map_value_to_replacement() {
+        // arrange
+        let values = [
+            ("Http:Timeout", "30"),
+            ("Http:Retries", "5"),
+            ("Http:Backoff:Attempts", "3"),
+        ];
+
+        // act
+        let options = new_options(&values);
+
+        // assert
+        assert_eq!(options.request_timeout, 30);
+        assert_eq!(options.retry.max_attempts, 3);
+    }
+
+    #[test]
+    fn replacement_should_take_precedence_over_deprecated_key() {
+        // arrange
+        let values = [("Http:Timeout", "30"), ("Http:RequestTimeout", "60")];
+
+        // act
+        let options = new_options(&values);
+
+        // assert
+        assert_eq!(options.request_timeout, 60);
+    }
+}
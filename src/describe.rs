@@ -0,0 +1,70 @@
+/// Represents the documentation of a single field of configuration [`Options`](crate::Options).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldDescription {
+    name: String,
+    description: Option<String>,
+    example: Option<String>,
+}
+
+impl FieldDescription {
+    /// Initializes a new field description.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the description of the field.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The text explaining the purpose of the field
+    pub fn with_description<S: AsRef<str>>(mut self, description: S) -> Self {
+        self.description = Some(description.as_ref().to_owned());
+        self
+    }
+
+    /// Sets an example value of the field.
+    ///
+    /// # Arguments
+    ///
+    /// * `example` - The text of an example value
+    pub fn with_example<S: AsRef<str>>(mut self, example: S) -> Self {
+        self.example = Some(example.as_ref().to_owned());
+        self
+    }
+
+    /// Gets the name of the field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the description of the field, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Gets an example value of the field, if any.
+    pub fn example(&self) -> Option<&str> {
+        self.example.as_deref()
+    }
+}
+
+/// Defines the behavior of configuration options that describe their fields at runtime.
+///
+/// # Remarks
+///
+/// This trait is typically implemented using `#[derive(DescribeOptions)]`, which describes each
+/// field with its `#[options(doc = "...", example = "...")]` attribute or, otherwise, its doc
+/// comment. The descriptions are included in the catalog of options added with
+/// [`describe`](crate::OptionsBuilder::describe), which can be used to generate administrative
+/// user interfaces and help text for configuration.
+pub trait DescribeOptions {
+    /// Gets the descriptions of the fields in the order they are declared.
+    fn describe() -> Vec<FieldDescription>;
+}
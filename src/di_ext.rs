@@ -1,13 +1,21 @@
+use crate::cache::{CacheComparer, CacheMeasure};
+use crate::factory::NamedOptionsFactory;
+use crate::fallback::FallbackPolicy;
+use crate::inherit::Inherits;
+use crate::monitor::MonitorPolicy;
+use crate::redact::SensitiveMarker;
+use crate::warmup::OptionsWarmer;
 use crate::*;
 use di::{
-    exactly_one, scoped, singleton, singleton_as_self, transient, transient_factory, zero_or_more,
-    ServiceCollection, ServiceDescriptor, ServiceProvider,
+    exactly_one, existing_as_self, scoped, singleton, singleton_as_self, transient,
+    transient_factory, zero_or_more, zero_or_one, ServiceCollection, ServiceDescriptor,
+    ServiceProvider,
 };
 
 /// Defines extension methods for the [`ServiceCollection`](di::ServiceCollection) struct.
 pub trait OptionsServiceExtensions {
     /// Registers an options type that will have all of its associated services registered.
-    fn add_options<T: Value + Default + 'static>(&mut self) -> OptionsBuilder<T>;
+    fn add_options<T: Value + Default + 'static>(&mut self) -> OptionsBuilder<'_, T>;
 
     /// Registers an options type that will have all of its associated services registered.
     ///
@@ -17,17 +25,32 @@ pub trait OptionsServiceExtensions {
     fn add_named_options<T: Value + Default + 'static>(
         &mut self,
         name: impl AsRef<str>,
-    ) -> OptionsBuilder<T>;
+    ) -> OptionsBuilder<'_, T>;
+
+    /// Registers an options type for multiple names that will have all of its associated services registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names associated with the options
+    ///
+    /// # Remarks
+    ///
+    /// Every configuration, post-configuration, and validation registered by the returned
+    /// [builder](crate::OptionsBuilder) applies to each of the specified names.
+    fn add_named_options_many<T: Value + Default + 'static>(
+        &mut self,
+        names: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> OptionsBuilder<'_, T>;
 
     /// Registers an options type that will have all of its associated services registered.
     ///
     /// # Arguments
     ///
     /// * `factory` - The function used to create the associated options factory
-    fn add_options_with<T, F>(&mut self, factory: F) -> OptionsBuilder<T>
+    fn add_options_with<T, F>(&mut self, factory: F) -> OptionsBuilder<'_, T>
     where
         T: Value,
-        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + 'static;
+        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + Value + 'static;
 
     /// Registers an options type that will have all of its associated services registered.
     ///
@@ -35,14 +58,19 @@ pub trait OptionsServiceExtensions {
     ///
     /// * `name` - The name associated with the options
     /// * `factory` - The function used to create the associated options factory
+    ///
+    /// # Remarks
+    ///
+    /// The options factory is only used to create the options with the specified name. Options with
+    /// any other name are created by the options factory registered for the type, if any.
     fn add_named_options_with<T, F>(
         &mut self,
         name: impl AsRef<str>,
         factory: F,
-    ) -> OptionsBuilder<T>
+    ) -> OptionsBuilder<'_, T>
     where
         T: Value,
-        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + 'static;
+        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + Value + 'static;
 
     /// Registers an action used to initialize a particular type of configuration options.
     ///
@@ -52,7 +80,7 @@ pub trait OptionsServiceExtensions {
     fn configure_options<T, F>(&mut self, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static;
+        F: Fn(&mut T) + Value + 'static;
 
     /// Registers an action used to initialize a particular type of configuration options.
     ///
@@ -63,7 +91,7 @@ pub trait OptionsServiceExtensions {
     fn configure_named_options<T, F>(&mut self, name: impl AsRef<str>, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static;
+        F: Fn(&mut T) + Value + 'static;
 
     /// Registers an action used to initialize a particular type of configuration options.
     ///
@@ -73,7 +101,7 @@ pub trait OptionsServiceExtensions {
     fn post_configure_options<T, F>(&mut self, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static;
+        F: Fn(&mut T) + Value + 'static;
 
     /// Registers an action used to initialize a particular type of configuration options.
     ///
@@ -84,21 +112,50 @@ pub trait OptionsServiceExtensions {
     fn post_configure_named_options<T, F>(&mut self, name: impl AsRef<str>, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static;
+        F: Fn(&mut T) + Value + 'static;
+
+    /// Removes all of the configurations and post-configurations registered for a particular type of
+    /// configuration options.
+    fn clear_configurations<T: Value + 'static>(&mut self) -> &mut Self;
+
+    /// Replaces all of the validations registered for a particular type of configuration options.
+    ///
+    /// # Remarks
+    ///
+    /// The replacement [validation](crate::ValidateOptions) is created using its [`Default`] implementation.
+    fn replace_validation<T, V>(&mut self) -> &mut Self
+    where
+        T: Value + 'static,
+        V: ValidateOptions<T> + Default + 'static;
+
+    /// Registers a [monitor](crate::CombinedMonitor) that combines two types of configuration
+    /// options.
+    ///
+    /// # Remarks
+    ///
+    /// Both types of options must also be registered, such as with
+    /// [`add_options`](OptionsServiceExtensions::add_options).
+    fn monitor_pair<A, B>(&mut self) -> &mut Self
+    where
+        A: Value + 'static,
+        B: Value + 'static;
 }
 
-fn _add_options<'a, T: Value>(
+fn _add_options<'a, T: Value + 'static>(
     services: &'a mut ServiceCollection,
-    name: Option<&str>,
-    descriptor: ServiceDescriptor,
+    names: Vec<String>,
+    descriptor: Option<ServiceDescriptor>,
 ) -> OptionsBuilder<'a, T> {
     services
         .try_add(
             singleton_as_self::<OptionsManager<T>>()
-                .depends_on(exactly_one::<dyn OptionsFactory<T>>())
+                .depends_on(zero_or_one::<dyn OptionsFactory<T>>())
+                .depends_on(zero_or_more::<NamedOptionsFactory<T>>())
+                .depends_on(zero_or_more::<CacheComparer<T>>())
                 .from(|sp| {
-                    Ref::new(OptionsManager::new(
-                        sp.get_required::<dyn OptionsFactory<T>>(),
+                    Ref::new(OptionsManager::with_comparer(
+                        NamedOptionsFactory::resolve(sp),
+                        CacheComparer::<T>::resolve(sp),
                     ))
                 }),
         )
@@ -116,84 +173,211 @@ fn _add_options<'a, T: Value>(
             singleton::<dyn OptionsMonitor<T>, DefaultOptionsMonitor<T>>()
                 .depends_on(exactly_one::<dyn OptionsMonitorCache<T>>())
                 .depends_on(zero_or_more::<dyn OptionsChangeTokenSource<T>>())
-                .depends_on(exactly_one::<dyn OptionsFactory<T>>())
+                .depends_on(zero_or_one::<dyn OptionsFactory<T>>())
+                .depends_on(zero_or_more::<NamedOptionsFactory<T>>())
+                .depends_on(zero_or_more::<CacheComparer<T>>())
+                .depends_on(zero_or_more::<MonitorPolicy<T>>())
+                .depends_on(exactly_one::<AnyOptionsMonitor>())
+                .depends_on(exactly_one::<OptionsHealthCheck>())
+                .from(|sp| {
+                    Ref::new(
+                        DefaultOptionsMonitor::with_policies(
+                            sp.get_required::<dyn OptionsMonitorCache<T>>(),
+                            sp.get_all::<dyn OptionsChangeTokenSource<T>>().collect(),
+                            NamedOptionsFactory::resolve(sp),
+                            sp.get_all::<MonitorPolicy<T>>(),
+                            CacheComparer::<T>::resolve(sp),
+                        )
+                        .publish_to(&sp.get_required::<AnyOptionsMonitor>())
+                        .report_to(&sp.get_required::<OptionsHealthCheck>()),
+                    )
+                }),
+        )
+        .try_add(
+            singleton_as_self::<AnyOptionsMonitor>().from(|_| Ref::new(AnyOptionsMonitor::new())),
+        )
+        .try_add(
+            singleton_as_self::<OptionsHealthCheck>().from(|_| Ref::new(OptionsHealthCheck::new())),
+        )
+        .try_add(
+            singleton::<dyn OptionsMonitorCache<T>, OptionsCache<T>>()
+                .depends_on(zero_or_more::<CacheComparer<T>>())
+                .depends_on(zero_or_one::<CacheMeasure<T>>())
+                .from(|sp| {
+                    let cache = OptionsCache::with_comparer(CacheComparer::<T>::resolve(sp));
+                    Ref::new(CacheMeasure::apply(sp, cache))
+                }),
+        )
+        .try_add(
+            singleton_as_self::<OptionsService<T>>()
+                .depends_on(exactly_one::<dyn OptionsMonitor<T>>())
+                .depends_on(exactly_one::<dyn OptionsMonitorCache<T>>())
+                .depends_on(zero_or_one::<dyn OptionsFactory<T>>())
+                .depends_on(zero_or_more::<NamedOptionsFactory<T>>())
+                .depends_on(zero_or_more::<CacheComparer<T>>())
                 .from(|sp| {
-                    Ref::new(DefaultOptionsMonitor::new(
+                    Ref::new(OptionsService::new(
+                        sp.get_required::<dyn OptionsMonitor<T>>(),
                         sp.get_required::<dyn OptionsMonitorCache<T>>(),
-                        sp.get_all::<dyn OptionsChangeTokenSource<T>>().collect(),
-                        sp.get_required::<dyn OptionsFactory<T>>(),
+                        NamedOptionsFactory::resolve(sp),
+                    ))
+                }),
+        );
+
+    if let Some(descriptor) = descriptor {
+        services.try_add(descriptor);
+    }
+
+    services.add(existing_as_self(OptionsWarmer::new::<T>()));
+
+    #[cfg(feature = "async")]
+    _add_async_options::<T>(services);
+
+    if names.is_empty() {
+        services.add(existing_as_self(OptionsRegistration::new::<T>(None)));
+    } else {
+        for name in &names {
+            services.add(existing_as_self(OptionsRegistration::new::<T>(Some(name))));
+        }
+    }
+
+    OptionsBuilder::with_names(services, names)
+}
+
+#[cfg(feature = "async")]
+fn _add_async_options<T: Value + 'static>(services: &mut ServiceCollection) {
+    services
+        .try_add(
+            transient::<dyn AsyncOptionsFactory<T>, DefaultAsyncOptionsFactory<T>>()
+                .depends_on(zero_or_one::<dyn OptionsFactory<T>>())
+                .depends_on(zero_or_more::<NamedOptionsFactory<T>>())
+                .depends_on(zero_or_more::<CacheComparer<T>>())
+                .from(|sp| {
+                    Ref::new(DefaultAsyncOptionsFactory::new(
+                        NamedOptionsFactory::resolve(sp),
                     ))
                 }),
         )
-        .try_add(descriptor)
         .try_add(
-            singleton::<dyn OptionsMonitorCache<T>, OptionsCache<T>>()
-                .from(|_| Ref::new(OptionsCache::default())),
+            singleton_as_self::<AsyncOptionsManager<T>>()
+                .depends_on(exactly_one::<dyn AsyncOptionsFactory<T>>())
+                .from(|sp| {
+                    Ref::new(AsyncOptionsManager::new(
+                        sp.get_required::<dyn AsyncOptionsFactory<T>>(),
+                    ))
+                }),
+        )
+        .try_add(
+            singleton::<dyn AsyncOptions<T>, AsyncOptionsManager<T>>()
+                .depends_on(exactly_one::<AsyncOptionsManager<T>>())
+                .from(|sp| sp.get_required::<AsyncOptionsManager<T>>()),
+        )
+        .try_add(
+            scoped::<dyn AsyncOptionsSnapshot<T>, AsyncOptionsManager<T>>()
+                .depends_on(exactly_one::<AsyncOptionsManager<T>>())
+                .from(|sp| sp.get_required::<AsyncOptionsManager<T>>()),
         );
+}
+
+fn _default_factory<T: Value + Default + 'static>() -> ServiceDescriptor {
+    transient::<dyn OptionsFactory<T>, DefaultOptionsFactory<T>>()
+        .depends_on(zero_or_more::<dyn ConfigureOptions<T>>())
+        .depends_on(zero_or_more::<dyn PostConfigureOptions<T>>())
+        .depends_on(zero_or_more::<dyn ValidateOptions<T>>())
+        .depends_on(zero_or_more::<dyn OptionsCreateObserver<T>>())
+        .depends_on(zero_or_more::<FallbackPolicy<T>>())
+        .depends_on(zero_or_more::<Inherits<T>>())
+        .depends_on(zero_or_one::<SensitiveMarker<T>>())
+        .depends_on(zero_or_one::<SlowOptionsThreshold>())
+        .from(|sp| {
+            let mut factory = DefaultOptionsFactory::new(
+                sp.get_all::<dyn ConfigureOptions<T>>().collect(),
+                sp.get_all::<dyn PostConfigureOptions<T>>().collect(),
+                sp.get_all::<dyn ValidateOptions<T>>().collect(),
+            )
+            .with_observers(sp.get_all::<dyn OptionsCreateObserver<T>>().collect());
+
+            if let Some(policy) = sp.get::<FallbackPolicy<T>>() {
+                factory = factory.with_fallback(policy.fallback(sp));
+            }
+
+            let inheritance = Inherits::<T>::resolve(sp);
+
+            if !inheritance.is_empty() {
+                factory = factory.with_inheritance(inheritance);
+            }
+
+            if sp.get::<SensitiveMarker<T>>().is_some() {
+                factory = factory.sensitive();
+            }
+
+            if let Some(threshold) = sp.get::<SlowOptionsThreshold>() {
+                factory = factory.with_slow_threshold(*threshold);
+            }
 
-    OptionsBuilder::new(services, name)
+            Ref::new(factory)
+        })
 }
 
 impl OptionsServiceExtensions for ServiceCollection {
-    fn add_options<T: Value + Default + 'static>(&mut self) -> OptionsBuilder<T> {
-        let descriptor = transient::<dyn OptionsFactory<T>, DefaultOptionsFactory<T>>()
-            .depends_on(zero_or_more::<dyn ConfigureOptions<T>>())
-            .depends_on(zero_or_more::<dyn PostConfigureOptions<T>>())
-            .depends_on(zero_or_more::<dyn ValidateOptions<T>>())
-            .from(|sp| {
-                Ref::new(DefaultOptionsFactory::new(
-                    sp.get_all::<dyn ConfigureOptions<T>>().collect(),
-                    sp.get_all::<dyn PostConfigureOptions<T>>().collect(),
-                    sp.get_all::<dyn ValidateOptions<T>>().collect(),
-                ))
-            });
-
-        _add_options(self, None, descriptor)
+    fn add_options<T: Value + Default + 'static>(&mut self) -> OptionsBuilder<'_, T> {
+        let descriptor = _default_factory::<T>();
+
+        _add_options(self, Vec::new(), Some(descriptor))
     }
 
     fn add_named_options<T: Value + Default + 'static>(
         &mut self,
         name: impl AsRef<str>,
-    ) -> OptionsBuilder<T> {
-        let descriptor = transient::<dyn OptionsFactory<T>, DefaultOptionsFactory<T>>()
-            .depends_on(zero_or_more::<dyn ConfigureOptions<T>>())
-            .depends_on(zero_or_more::<dyn PostConfigureOptions<T>>())
-            .depends_on(zero_or_more::<dyn ValidateOptions<T>>())
-            .from(|sp| {
-                Ref::new(DefaultOptionsFactory::new(
-                    sp.get_all::<dyn ConfigureOptions<T>>().collect(),
-                    sp.get_all::<dyn PostConfigureOptions<T>>().collect(),
-                    sp.get_all::<dyn ValidateOptions<T>>().collect(),
-                ))
-            });
-
-        _add_options(self, Some(name.as_ref()), descriptor)
-    }
-
-    fn add_options_with<T, F>(&mut self, factory: F) -> OptionsBuilder<T>
+    ) -> OptionsBuilder<'_, T> {
+        let descriptor = _default_factory::<T>();
+
+        _add_options(self, vec![name.as_ref().to_owned()], Some(descriptor))
+    }
+
+    fn add_named_options_many<T: Value + Default + 'static>(
+        &mut self,
+        names: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> OptionsBuilder<'_, T> {
+        let descriptor = _default_factory::<T>();
+        let names = names.into_iter().map(|n| n.as_ref().to_owned()).collect();
+
+        _add_options(self, names, Some(descriptor))
+    }
+
+    fn add_options_with<T, F>(&mut self, factory: F) -> OptionsBuilder<'_, T>
     where
         T: Value,
-        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + 'static,
+        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + Value + 'static,
     {
-        _add_options(self, None, transient_factory(factory))
+        _add_options(self, Vec::new(), Some(transient_factory(factory)))
     }
 
     fn add_named_options_with<T, F>(
         &mut self,
         name: impl AsRef<str>,
         factory: F,
-    ) -> OptionsBuilder<T>
+    ) -> OptionsBuilder<'_, T>
     where
         T: Value,
-        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + 'static,
+        F: Fn(&ServiceProvider) -> Ref<dyn OptionsFactory<T>> + Value + 'static,
     {
-        _add_options(self, Some(name.as_ref()), transient_factory(factory))
+        let name = name.as_ref().to_owned();
+        let named = name.clone();
+
+        self.add(transient_factory(move |sp| {
+            Ref::new(NamedOptionsFactory::new(&named, factory(sp)))
+        }))
+        .add(existing_as_self(
+            OptionsRegistration::new::<T>(Some(&name)).for_role(OptionsRole::Factory),
+        ));
+        _add_options(self, vec![name], None)
     }
 
     fn configure_options<T, F>(&mut self, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static,
+        F: Fn(&mut T) + Value + 'static,
     {
         self.add_options().configure(setup).into()
     }
@@ -201,7 +385,7 @@ impl OptionsServiceExtensions for ServiceCollection {
     fn configure_named_options<T, F>(&mut self, name: impl AsRef<str>, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static,
+        F: Fn(&mut T) + Value + 'static,
     {
         self.add_named_options(name).configure(setup).into()
     }
@@ -209,7 +393,7 @@ impl OptionsServiceExtensions for ServiceCollection {
     fn post_configure_options<T, F>(&mut self, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static,
+        F: Fn(&mut T) + Value + 'static,
     {
         self.add_options().post_configure(setup).into()
     }
@@ -217,18 +401,57 @@ impl OptionsServiceExtensions for ServiceCollection {
     fn post_configure_named_options<T, F>(&mut self, name: impl AsRef<str>, setup: F) -> &mut Self
     where
         T: Value + Default + 'static,
-        F: Fn(&mut T) + 'static,
+        F: Fn(&mut T) + Value + 'static,
     {
         self.add_named_options(name).configure(setup).into()
     }
+
+    fn clear_configurations<T: Value + 'static>(&mut self) -> &mut Self {
+        remove_registrations::<T>(self, &[OptionsRole::Configure, OptionsRole::PostConfigure]);
+
+        #[cfg(feature = "cfg")]
+        crate::lint::remove_linters::<T>(self);
+
+        self.remove_all::<dyn ConfigureOptions<T>>()
+            .remove_all::<dyn PostConfigureOptions<T>>()
+    }
+
+    fn replace_validation<T, V>(&mut self) -> &mut Self
+    where
+        T: Value + 'static,
+        V: ValidateOptions<T> + Default + 'static,
+    {
+        remove_registrations::<T>(self, &[OptionsRole::Validate]);
+        self.remove_all::<dyn ValidateOptions<T>>()
+            .add(singleton::<dyn ValidateOptions<T>, V>().from(|_| Ref::new(V::default())))
+    }
+
+    fn monitor_pair<A, B>(&mut self) -> &mut Self
+    where
+        A: Value + 'static,
+        B: Value + 'static,
+    {
+        self.try_add(
+            singleton_as_self::<CombinedMonitor<A, B>>()
+                .depends_on(exactly_one::<dyn OptionsMonitor<A>>())
+                .depends_on(exactly_one::<dyn OptionsMonitor<B>>())
+                .from(|sp| {
+                    Ref::new(CombinedMonitor::new(
+                        sp.get_required::<dyn OptionsMonitor<A>>(),
+                        sp.get_required::<dyn OptionsMonitor<B>>(),
+                    ))
+                }),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use di::{existing_as_self, transient};
-    use std::cell::Cell;
+    use di::{existing_as_self, existing_with_key_as_self, transient};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokens::{SharedChangeToken, SingleChangeToken};
 
     #[derive(Default, Debug, PartialEq, Eq)]
     struct TestOptions {
@@ -236,6 +459,12 @@ mod tests {
         setting: usize,
     }
 
+    impl MeasureOptions for TestOptions {
+        fn approx_size(&self) -> usize {
+            64
+        }
+    }
+
     #[derive(Default)]
     struct TestValidation;
 
@@ -249,24 +478,41 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct TestConfigurer;
+
+    impl ConfigureOptions<TestOptions> for TestConfigurer {
+        fn configure(&self, _name: Option<&str>, options: &mut TestOptions) {
+            options.enabled = true;
+        }
+    }
+
+    struct TestPostConfigurer(Ref<TestService>);
+
+    impl PostConfigureOptions<TestOptions> for TestPostConfigurer {
+        fn post_configure(&self, _name: Option<&str>, options: &mut TestOptions) {
+            options.setting = self.0.next();
+        }
+    }
+
     struct TestService {
-        value: Cell<usize>,
+        value: AtomicUsize,
     }
 
     impl TestService {
         fn next(&self) -> usize {
-            self.value.replace(self.value.get() + 1)
+            self.value.fetch_add(1, Ordering::Relaxed)
         }
 
         fn calls(&self) -> usize {
-            self.value.get() - 1
+            self.value.load(Ordering::Relaxed) - 1
         }
     }
 
     impl Default for TestService {
         fn default() -> Self {
             Self {
-                value: Cell::new(1),
+                value: AtomicUsize::new(1),
             }
         }
     }
@@ -335,6 +581,37 @@ mod tests {
         assert_eq!(options.setting, 2);
     }
 
+    #[test]
+    fn get_should_apply_configuration_functions() {
+        // arrange
+        fn enable(options: &mut TestOptions) {
+            options.enabled = true;
+        }
+
+        fn double(options: &mut TestOptions) {
+            options.setting *= 2;
+        }
+
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .post_configure_fn(double)
+            .configure(|o| o.setting = 2)
+            .configure_fn(enable)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+        let named = options.get(Some("Test"));
+        let unnamed = options.get(None);
+
+        // assert
+        assert!(named.enabled);
+        assert_eq!(named.setting, 4);
+        assert!(!unnamed.enabled);
+        assert_eq!(unnamed.setting, 0);
+    }
+
     #[test]
     fn get_required_should_not_panic_when_configured_options_are_valid() {
         // arrange
@@ -345,7 +622,7 @@ mod tests {
             })
             .add(
                 transient::<dyn ValidateOptions<TestOptions>, TestValidation>()
-                    .from(|_| Ref::new(TestValidation::default())),
+                    .from(|_| Ref::new(TestValidation)),
             )
             .build_provider()
             .unwrap();
@@ -368,7 +645,7 @@ mod tests {
             })
             .add(
                 transient::<dyn ValidateOptions<TestOptions>, TestValidation>()
-                    .from(|_| Ref::new(TestValidation::default())),
+                    .from(|_| Ref::new(TestValidation)),
             )
             .build_provider()
             .unwrap();
@@ -380,6 +657,369 @@ mod tests {
         let _ = options.value();
     }
 
+    #[test]
+    fn get_should_match_named_options_ignoring_case_by_default() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .configure(|o| o.setting = 1)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+
+        // assert
+        assert_eq!(snapshot.get(Some("tEST")).setting, 1);
+    }
+
+    #[test]
+    fn get_should_match_named_options_with_configured_comparer() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .name_comparer(OptionsNameComparer::Exact)
+            .configure(|o| o.setting = 1)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+
+        // assert
+        assert_eq!(snapshot.get(Some("Test")).setting, 1);
+        assert_eq!(snapshot.get(Some("test")).setting, 0);
+    }
+
+    #[test]
+    fn get_should_cache_names_that_match() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .configure(|o| o.setting = 1)
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<TestOptions>>();
+
+        // act
+        let first = monitor.get(Some("Test"));
+        let second = monitor.get(Some("test"));
+
+        // assert
+        assert_eq!(second.setting, 1);
+        assert!(Ref::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn statistics_should_include_measured_options() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .measure()
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<TestOptions>>();
+        let cache = provider.get_required::<dyn OptionsMonitorCache<TestOptions>>();
+
+        // act
+        let _ = monitor.get(None);
+        let _ = monitor.get(Some("Tenant"));
+
+        // assert
+        assert_eq!(
+            cache.statistics(),
+            OptionsCacheStatistics::new(2, 64 + 64 + "tenant".len())
+        );
+    }
+
+    #[test]
+    fn get_should_apply_configuration_to_many_named_options() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options_many::<TestOptions>(["A", "B"])
+            .configure(|o| o.enabled = true)
+            .build_provider()
+            .unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+
+        // act
+        let a = snapshot.get(Some("A"));
+        let b = snapshot.get(Some("B"));
+        let c = snapshot.get(Some("C"));
+
+        // assert
+        assert!(a.enabled);
+        assert!(b.enabled);
+        assert!(!c.enabled);
+    }
+
+    #[test]
+    fn get_should_apply_configurer_to_matching_name() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .add_configurer::<TestConfigurer>()
+            .build_provider()
+            .unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+
+        // act
+        let matched = snapshot.get(Some("Test"));
+        let unmatched = snapshot.get(Some("Other"));
+
+        // assert
+        assert!(matched.enabled);
+        assert!(!unmatched.enabled);
+    }
+
+    #[test]
+    fn get_required_should_apply_post_configurer_created_from_provider() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .add_post_configurer_with(|sp| TestPostConfigurer(sp.get_required::<TestService>()))
+            .add(existing_as_self(TestService::default()))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert_eq!(options.value().setting, 1);
+    }
+
+    #[test]
+    fn get_should_report_failed_configuration() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure_result(|o| {
+                o.setting = "forty-two".parse().map_err(|_| "Invalid setting")?;
+                Ok(())
+            })
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<dyn OptionsFactory<TestOptions>>();
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(result.err().unwrap().failure_message(), "Invalid setting");
+    }
+
+    #[test]
+    fn monitor_should_reload_options_when_watched_token_changes() {
+        // arrange
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let producer = token.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure1(|o, d1: Ref<TestService>| o.setting = d1.next())
+            .watch_fn(move || Box::new(producer.clone()))
+            .add(existing_as_self(TestService::default()))
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<TestOptions>>();
+        let initial = monitor.current_value().setting;
+
+        // act
+        token.notify();
+
+        // assert
+        assert_eq!(initial, 1);
+        assert_eq!(monitor.current_value().setting, 2);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn monitor_should_reload_options_on_async_runtime() {
+        // arrange
+        use std::sync::Arc;
+        use std::task::{Context, Wake, Waker};
+
+        struct NoopWaker;
+
+        impl Wake for NoopWaker {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let producer = token.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure1(|o, d1: Ref<TestService>| o.setting = d1.next())
+            .watch_fn(move || Box::new(producer.clone()))
+            .async_monitor(|mut task: OptionsTask| {
+                let waker = Waker::from(Arc::new(NoopWaker));
+                let _ = task.as_mut().poll(&mut Context::from_waker(&waker));
+            })
+            .add(existing_as_self(TestService::default()))
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<TestOptions>>();
+        let initial = monitor.current_value().setting;
+
+        // act
+        token.notify();
+
+        // assert
+        assert_eq!(initial, 1);
+        assert_eq!(monitor.current_value().setting, 2);
+        assert_eq!(
+            provider
+                .get_required::<AsyncOptionsMonitor<TestOptions>>()
+                .current_value()
+                .setting,
+            2
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn add_options_should_register_async_options() {
+        // arrange
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct NoopWaker;
+
+        impl Wake for NoopWaker {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .configure(|o| o.setting = 1)
+            .build_provider()
+            .unwrap();
+        let options = provider.get_required::<dyn AsyncOptions<TestOptions>>();
+        let snapshot = provider.get_required::<dyn AsyncOptionsSnapshot<TestOptions>>();
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+
+        // act
+        let value = options.value().as_mut().poll(&mut context);
+        let named = snapshot.get(Some("Test")).as_mut().poll(&mut context);
+
+        // assert
+        assert!(matches!(value, Poll::Ready(ref v) if v.setting == 0));
+        assert!(matches!(named, Poll::Ready(ref v) if v.setting == 1));
+    }
+
+    #[test]
+    fn get_required_by_key_should_resolve_named_options() {
+        // arrange
+        struct Primary;
+
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Primary")
+            .configure(|o| o.setting = 1)
+            .as_keyed::<Primary>()
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required_by_key::<Primary, dyn Options<TestOptions>>();
+
+        // assert
+        assert_eq!(options.value().setting, 1);
+    }
+
+    #[test]
+    fn get_all_by_key_should_resolve_options_for_every_name() {
+        // arrange
+        struct Replica;
+
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_options_many::<TestOptions>(["East", "West"])
+            .configure(|o| o.enabled = true)
+            .as_keyed::<Replica>();
+        services
+            .add_named_options::<TestOptions>("West")
+            .configure(|o| o.setting = 2);
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let replicas: Vec<_> = provider
+            .get_all_by_key::<Replica, dyn Options<TestOptions>>()
+            .map(|o| (o.value().enabled, o.value().setting))
+            .collect();
+
+        // assert
+        assert_eq!(replicas, vec![(true, 0), (true, 2)]);
+    }
+
+    #[test]
+    fn clear_configurations_should_remove_registered_configurations() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<TestOptions>()
+            .configure1(|o, d1: Ref<TestService>| o.setting = d1.next())
+            .post_configure(|o| o.enabled = true);
+
+        services
+            .clear_configurations::<TestOptions>()
+            .configure_options::<TestOptions, _>(|o| o.setting = 42);
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert!(verify_options(&services).is_ok());
+        assert_eq!(
+            options.value().as_ref(),
+            &TestOptions {
+                enabled: false,
+                setting: 42
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Setting must be zero when disabled")]
+    fn replace_validation_should_substitute_registered_validations() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure(|o| o.setting = 1)
+            .validate(|_| true, "")
+            .replace_validation::<TestOptions, TestValidation>()
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        let _ = options.value();
+    }
+
+    #[test]
+    fn get_required_should_resolve_options_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options::<TestOptions>("Test")
+            .configure(|o| o.setting = 1)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service = provider.get_required::<OptionsService<TestOptions>>();
+
+        // assert
+        assert_eq!(service.get(Some("Test")).setting, 1);
+        assert_eq!(service.try_value().unwrap().setting, 0);
+    }
+
     #[test]
     fn get_required_should_configure_options_with_1_dependency() {
         // arrange
@@ -397,6 +1037,28 @@ mod tests {
         assert_eq!(options.value().setting, 1);
     }
 
+    #[test]
+    fn get_required_should_configure_options_with_keyed_dependency() {
+        // arrange
+        struct Key;
+
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure1_keyed::<Key, _, _>(|o, d1: Ref<TestService>| o.setting = d1.next())
+            .add(existing_as_self(TestService::default()))
+            .add(existing_with_key_as_self::<Key, _>(TestService {
+                value: AtomicUsize::new(10),
+            }))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert_eq!(options.value().setting, 10);
+    }
+
     #[test]
     fn get_required_should_configure_options_with_2_dependencies() {
         // arrange
@@ -488,6 +1150,40 @@ mod tests {
         assert_eq!(options.value().setting, 15);
     }
 
+    #[test]
+    fn get_required_should_configure_options_with_7_dependencies() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure7(
+                |o,
+                 d1: Ref<TestService>,
+                 d2: Ref<TestService>,
+                 d3: Ref<TestService>,
+                 d4: Ref<TestService>,
+                 d5: Ref<TestService>,
+                 d6: Ref<TestService>,
+                 d7: Ref<TestService>| {
+                    o.setting = d1.next()
+                        + d2.next()
+                        + d3.next()
+                        + d4.next()
+                        + d5.next()
+                        + d6.next()
+                        + d7.next()
+                },
+            )
+            .add(existing_as_self(TestService::default()))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert_eq!(options.value().setting, 28);
+    }
+
     #[test]
     fn get_required_should_post_configure_options_with_1_dependency() {
         // arrange
@@ -618,7 +1314,7 @@ mod tests {
         let service = provider.get_required::<TestService>();
 
         // assert
-        assert_eq!(options.value().enabled, true);
+        assert!(options.value().enabled);
         assert_eq!(service.calls(), 1);
     }
 
@@ -644,7 +1340,7 @@ mod tests {
         let service = provider.get_required::<TestService>();
 
         // assert
-        assert_eq!(options.value().enabled, true);
+        assert!(options.value().enabled);
         assert_eq!(service.calls(), 2);
     }
 
@@ -670,7 +1366,7 @@ mod tests {
         let service = provider.get_required::<TestService>();
 
         // assert
-        assert_eq!(options.value().enabled, true);
+        assert!(options.value().enabled);
         assert_eq!(service.calls(), 3);
     }
 
@@ -700,7 +1396,7 @@ mod tests {
         let service = provider.get_required::<TestService>();
 
         // assert
-        assert_eq!(options.value().enabled, true);
+        assert!(options.value().enabled);
         assert_eq!(service.calls(), 4);
     }
 
@@ -731,7 +1427,239 @@ mod tests {
         let service = provider.get_required::<TestService>();
 
         // assert
-        assert_eq!(options.value().enabled, true);
+        assert!(options.value().enabled);
         assert_eq!(service.calls(), 5);
     }
+
+    #[test]
+    fn get_required_should_validate_options_with_12_dependencies() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure(|o| o.enabled = true)
+            .validate12(
+                |o,
+                 d1: Ref<TestService>,
+                 _d2: Ref<TestService>,
+                 _d3: Ref<TestService>,
+                 _d4: Ref<TestService>,
+                 _d5: Ref<TestService>,
+                 _d6: Ref<TestService>,
+                 _d7: Ref<TestService>,
+                 _d8: Ref<TestService>,
+                 _d9: Ref<TestService>,
+                 _d10: Ref<TestService>,
+                 _d11: Ref<TestService>,
+                 d12: Ref<TestService>| {
+                    let _ = d1.next() + d12.next();
+                    o.enabled
+                },
+                "Not enabled!",
+            )
+            .add(existing_as_self(TestService::default()))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+        let service = provider.get_required::<TestService>();
+
+        // assert
+        assert!(options.value().enabled);
+        assert_eq!(service.calls(), 2);
+    }
+
+    #[test]
+    fn build_provider_should_fail_when_dependency_is_missing() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<TestOptions>()
+            .configure1(|o, d: Ref<TestService>| o.setting = d.next())
+            .post_configure1(|o, d: Ref<TestService>| o.setting += d.next())
+            .validate1(|o, _: Ref<TestService>| o.enabled, "Not enabled!");
+
+        // act
+        let result = services.build_provider();
+
+        // assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_provider_should_fail_when_dependency_is_missing_with_6_dependencies() {
+        // arrange
+        #[derive(Default)]
+        struct MissingService;
+
+        type D = Ref<TestService>;
+        type M = Ref<MissingService>;
+
+        let mut configured = ServiceCollection::new();
+        let mut post_configured = ServiceCollection::new();
+        let mut validated = ServiceCollection::new();
+
+        configured
+            .add_options::<TestOptions>()
+            .configure6(|o, d1: D, _: D, _: D, _: D, _: D, _: M| o.setting = d1.next())
+            .add(existing_as_self(TestService::default()));
+        post_configured
+            .add_options::<TestOptions>()
+            .post_configure6(|o, d1: D, _: D, _: D, _: D, _: D, _: M| o.setting = d1.next())
+            .add(existing_as_self(TestService::default()));
+        validated
+            .add_options::<TestOptions>()
+            .validate6(
+                |o, _: D, _: D, _: D, _: D, _: D, _: M| o.enabled,
+                "Not enabled!",
+            )
+            .add(existing_as_self(TestService::default()));
+
+        // act
+        let results = [
+            configured.build_provider(),
+            post_configured.build_provider(),
+            validated.build_provider(),
+        ];
+
+        // assert
+        assert!(results.iter().all(Result::is_err));
+    }
+
+    #[derive(Default)]
+    struct OtherOptions {
+        enabled: bool,
+    }
+
+    #[test]
+    fn and_options_should_register_related_options_in_one_chain() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<TestOptions>()
+            .configure(|o| o.setting = 1)
+            .and_named_options::<OtherOptions>("Other")
+            .configure(|o| o.enabled = true)
+            .and_options::<TestOptions>()
+            .configure(|o| o.enabled = true)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+        let other = provider.get_required::<dyn OptionsSnapshot<OtherOptions>>();
+
+        // assert
+        assert_eq!(
+            *options.value(),
+            TestOptions {
+                enabled: true,
+                setting: 1
+            }
+        );
+        assert!(other.get(Some("Other")).enabled);
+        assert!(!other.get(None).enabled);
+    }
+
+    struct FixedFactory(usize);
+
+    impl OptionsFactory<TestOptions> for FixedFactory {
+        fn create(&self, name: Option<&str>) -> Result<TestOptions, ValidateOptionsResult> {
+            self.create_from(name, Default::default())
+        }
+
+        fn create_from(
+            &self,
+            _name: Option<&str>,
+            mut seed: TestOptions,
+        ) -> Result<TestOptions, ValidateOptionsResult> {
+            seed.setting = self.0;
+            Ok(seed)
+        }
+    }
+
+    #[test]
+    fn get_should_create_named_options_with_name_specific_factory() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_named_options_with("Custom", |_| {
+            let factory: Ref<dyn OptionsFactory<TestOptions>> = Ref::new(FixedFactory(42));
+            factory
+        });
+        services
+            .add_options::<TestOptions>()
+            .configure(|o| o.setting = 1);
+
+        let provider = services.build_provider().unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+        let monitor = provider.get_required::<dyn OptionsMonitor<TestOptions>>();
+
+        // act
+        let custom = snapshot.get(Some("custom"));
+        let other = snapshot.get(Some("Other"));
+
+        // assert
+        assert_eq!(custom.setting, 42);
+        assert_eq!(other.setting, 1);
+        assert_eq!(monitor.get(Some("Custom")).setting, 42);
+        assert_eq!(monitor.current_value().setting, 1);
+    }
+
+    #[test]
+    fn create_from_should_route_seed_to_name_specific_factory() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_named_options_with("Custom", |_| {
+            let factory: Ref<dyn OptionsFactory<TestOptions>> = Ref::new(FixedFactory(42));
+            factory
+        });
+        services
+            .add_options::<TestOptions>()
+            .configure(|o| o.setting = 1);
+
+        let provider = services.build_provider().unwrap();
+        let factory = crate::factory::NamedOptionsFactory::<TestOptions>::resolve(&provider);
+        let seed = || TestOptions {
+            enabled: true,
+            setting: 0,
+        };
+
+        // act
+        let custom = factory.create_from(Some("Custom"), seed()).unwrap();
+        let other = factory.create_from(Some("Other"), seed()).unwrap();
+
+        // assert
+        assert!(custom.enabled);
+        assert_eq!(custom.setting, 42);
+        assert!(other.enabled);
+        assert_eq!(other.setting, 1);
+    }
+
+    #[test]
+    fn get_should_route_names_with_configured_comparer() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_named_options_with("Custom", |_| {
+            let factory: Ref<dyn OptionsFactory<TestOptions>> = Ref::new(FixedFactory(42));
+            factory
+        });
+        services
+            .add_options::<TestOptions>()
+            .name_comparer(OptionsNameComparer::Exact)
+            .configure(|o| o.setting = 1);
+
+        let provider = services.build_provider().unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TestOptions>>();
+
+        // act
+        let exact = snapshot.get(Some("Custom"));
+        let other = snapshot.get(Some("custom"));
+
+        // assert
+        assert_eq!(exact.setting, 42);
+        assert_eq!(other.setting, 1);
+    }
 }
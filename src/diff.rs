@@ -0,0 +1,262 @@
+use serde::Serialize;
+use serde_json::Value as Json;
+
+#[cfg(feature = "di")]
+use crate::{trace, OptionsCreateObserver, OptionsNameComparer, ValidateOptionsResult, Value};
+
+#[cfg(feature = "di")]
+use std::{collections::HashMap, marker::PhantomData, sync::Mutex, time::Duration};
+
+/// Represents a change to a single field of configuration [`Options`](crate::Options).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    path: String,
+    old_value: Option<Json>,
+    new_value: Option<Json>,
+}
+
+impl FieldChange {
+    /// Gets the path of the changed field.
+    ///
+    /// # Remarks
+    ///
+    /// The path uses the same `:` delimited format as configuration keys, such as `Db:Port`, and
+    /// uses the index of an element within a sequence. An empty path indicates that the options
+    /// themselves are not a structure and were changed entirely.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Gets the value of the field before the change, if the field existed.
+    pub fn old_value(&self) -> Option<&Json> {
+        self.old_value.as_ref()
+    }
+
+    /// Gets the value of the field after the change, if the field exists.
+    pub fn new_value(&self) -> Option<&Json> {
+        self.new_value.as_ref()
+    }
+}
+
+/// Compares two instances of configuration [`Options`](crate::Options) and returns the fields that
+/// changed.
+///
+/// # Arguments
+///
+/// * `old` - The options before the change
+/// * `new` - The options after the change
+///
+/// # Remarks
+///
+/// The options are compared by their serialized values. Nested structures are compared field by
+/// field so that only the innermost changed fields are reported. The changed values are reported
+/// as they are, so compare [`Redacted`](crate::Redacted) views of the options when the changes
+/// are logged and the options contain secrets.
+pub fn options_diff<T: Serialize + ?Sized>(old: &T, new: &T) -> Vec<FieldChange> {
+    let old = serde_json::to_value(old).unwrap_or(Json::Null);
+    let new = serde_json::to_value(new).unwrap_or(Json::Null);
+    let mut changes = Vec::new();
+
+    compare("", Some(&old), Some(&new), &mut changes);
+    changes
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}:{}", path, key)
+    }
+}
+
+fn compare(path: &str, old: Option<&Json>, new: Option<&Json>, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Some(Json::Object(old)), Some(Json::Object(new))) => {
+            for (key, value) in old {
+                compare(&child(path, key), Some(value), new.get(key), changes);
+            }
+
+            for (key, value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+                compare(&child(path, key), None, Some(value), changes);
+            }
+        }
+        (Some(Json::Array(old)), Some(Json::Array(new))) => {
+            for index in 0..old.len().max(new.len()) {
+                compare(
+                    &child(path, &index.to_string()),
+                    old.get(index),
+                    new.get(index),
+                    changes,
+                );
+            }
+        }
+        _ => {
+            if old != new {
+                changes.push(FieldChange {
+                    path: path.to_owned(),
+                    old_value: old.cloned(),
+                    new_value: new.cloned(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "di")]
+type DiffCallback = dyn Fn(Option<&str>, &[FieldChange]) + Send + Sync;
+
+#[cfg(feature = "di")]
+pub(crate) struct DiffObserver<T> {
+    comparer: OptionsNameComparer,
+    previous: Mutex<HashMap<String, Json>>,
+    callback: Box<DiffCallback>,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(feature = "di")]
+impl<T> DiffObserver<T> {
+    pub(crate) fn new<F>(comparer: OptionsNameComparer, callback: F) -> Self
+    where
+        F: Fn(Option<&str>, &[FieldChange]) + Send + Sync + 'static,
+    {
+        Self {
+            comparer,
+            previous: Default::default(),
+            callback: Box::new(callback),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "di")]
+impl<T: Value + Serialize> OptionsCreateObserver<T> for DiffObserver<T> {
+    fn after_create(
+        &self,
+        name: Option<&str>,
+        _duration: Duration,
+        result: &Result<T, ValidateOptionsResult>,
+    ) {
+        let options = match result {
+            Ok(options) => serde_json::to_value(options).unwrap_or(Json::Null),
+            Err(_) => return,
+        };
+        let key = self
+            .comparer
+            .normalize(name.unwrap_or_default())
+            .into_owned();
+        let previous = self.previous.lock().unwrap().insert(key, options.clone());
+
+        // the first instance has nothing to compare to; options are also created for snapshots,
+        // so instances that did not change are not reported
+        if let Some(previous) = previous {
+            let mut changes = Vec::new();
+
+            compare("", Some(&previous), Some(&options), &mut changes);
+
+            if !changes.is_empty() {
+                trace::modified::<T>(name, &changes);
+                (self.callback)(name, &changes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU16, Ordering};
+    use std::sync::Arc;
+    use tokens::{SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default, Serialize)]
+    struct Endpoint {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Serialize)]
+    struct ServiceOptions {
+        name: String,
+        endpoint: Endpoint,
+        tags: Vec<String>,
+    }
+
+    fn new_options(port: u16, tags: &[&str]) -> ServiceOptions {
+        ServiceOptions {
+            name: "catalog".into(),
+            endpoint: Endpoint {
+                host: "localhost".into(),
+                port,
+            },
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn options_diff_should_return_changed_fields() {
+        // arrange
+        let old = new_options(80, &["web"]);
+        let new = new_options(8080, &["web", "beta"]);
+
+        // act
+        let changes = options_diff(&old, &new);
+
+        // assert
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].path(), "endpoint:port");
+        assert_eq!(changes[0].old_value(), Some(&json!(80)));
+        assert_eq!(changes[0].new_value(), Some(&json!(8080)));
+        assert_eq!(changes[1].path(), "tags:1");
+        assert_eq!(changes[1].old_value(), None);
+        assert_eq!(changes[1].new_value(), Some(&json!("beta")));
+    }
+
+    #[test]
+    fn options_diff_should_return_nothing_for_equal_options() {
+        // arrange
+        let old = new_options(80, &["web"]);
+        let new = new_options(80, &["web"]);
+
+        // act
+        let changes = options_diff(&old, &new);
+
+        // assert
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn on_change_diff_should_report_changed_fields_on_reload() {
+        // arrange
+        let port = Arc::new(AtomicU16::new(80));
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let (value, reported, watched) = (port.clone(), changes.clone(), token.clone());
+        let provider = ServiceCollection::new()
+            .add_options::<Endpoint>()
+            .configure(|o| o.host = "localhost".into())
+            .configure(move |o| o.port = value.load(Ordering::SeqCst))
+            .watch_fn(move || Box::new(watched.clone()))
+            .on_change_diff(move |_, diff| reported.lock().unwrap().extend_from_slice(diff))
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Endpoint>>();
+        let _ = monitor.current_value();
+
+        // act
+        port.store(8080, Ordering::SeqCst);
+        token.notify();
+        let _ = monitor.current_value();
+
+        // assert
+        let changes = changes.lock().unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path(), "port");
+        assert_eq!(changes[0].old_value(), Some(&json!(80)));
+        assert_eq!(changes[0].new_value(), Some(&json!(8080)));
+    }
+}
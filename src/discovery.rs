@@ -0,0 +1,162 @@
+use di::ServiceCollection;
+
+/// Represents configuration [`Options`](crate::Options) discovered at compile time.
+///
+/// # Remarks
+///
+/// Discovered options are declared with the [`register_options`](crate::register_options) macro
+/// and are added to a [collection of services](di::ServiceCollection) using
+/// [`add_discovered_options`](crate::ext::OptionsDiscoveryServiceExtensions::add_discovered_options).
+pub struct DiscoveredOptions {
+    register: fn(&mut ServiceCollection),
+}
+
+impl DiscoveredOptions {
+    /// Initializes new discovered options.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - The function used to register the options
+    pub const fn new(register: fn(&mut ServiceCollection)) -> Self {
+        Self { register }
+    }
+
+    /// Registers the options in the specified collection of services.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [collection of services](di::ServiceCollection) to register the options in
+    pub fn register(&self, services: &mut ServiceCollection) {
+        (self.register)(services)
+    }
+}
+
+inventory::collect!(DiscoveredOptions);
+
+/// Declares configuration [`Options`](crate::Options) that are registered by
+/// [`add_discovered_options`](crate::ext::OptionsDiscoveryServiceExtensions::add_discovered_options).
+///
+/// # Examples
+///
+/// ```ignore
+/// register_options!(MyOptions);
+/// register_options!(MyOtherOptions, name = "Other");
+/// register_options!(MyBoundOptions, section = "My");
+/// ```
+///
+/// # Remarks
+///
+/// Binding options to a configuration `section` requires the **cfg** feature and resolves the
+/// [configuration](config::Configuration) from the [`ServiceProvider`](di::ServiceProvider).
+#[macro_export]
+macro_rules! register_options {
+    (@submit |$services:ident| $body:block) => {
+        const _: () = {
+            fn register($services: &mut $crate::__private::ServiceCollection) $body
+
+            $crate::__private::inventory::submit! {
+                $crate::DiscoveredOptions::new(register)
+            }
+        };
+    };
+    (@derived $options:ty) => {
+        $crate::register_options!(@submit |services| {
+            <$options as $crate::RegisterOptions>::add_to(services);
+        });
+    };
+    ($options:ty) => {
+        $crate::register_options!(@submit |services| {
+            $crate::ext::OptionsServiceExtensions::add_options::<$options>(services);
+        });
+    };
+    ($options:ty, name = $name:expr) => {
+        $crate::register_options!(@submit |services| {
+            $crate::ext::OptionsServiceExtensions::add_named_options::<$options>(services, $name);
+        });
+    };
+    ($options:ty, section = $section:expr) => {
+        $crate::register_options!(@submit |services| {
+            $crate::ext::OptionsConfigurationServiceExtensions::add_options_from_config::<$options>(
+                services, $section,
+            );
+        });
+    };
+}
+
+/// Defines extension methods for the [`ServiceCollection`](di::ServiceCollection) struct.
+pub trait OptionsDiscoveryServiceExtensions {
+    /// Registers all of the configuration options declared with [`register_options`](crate::register_options).
+    fn add_discovered_options(&mut self) -> &mut Self;
+}
+
+impl OptionsDiscoveryServiceExtensions for ServiceCollection {
+    fn add_discovered_options(&mut self) -> &mut Self {
+        for options in inventory::iter::<DiscoveredOptions> {
+            options.register(self);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, Options, OptionsSnapshot};
+    use config::{ext::*, Configuration, ConfigurationBuilder, DefaultConfigurationBuilder};
+    use di::{singleton_factory, Ref};
+    use serde::Deserialize;
+
+    #[derive(Default)]
+    struct DiscoverableOptions {
+        enabled: bool,
+    }
+
+    #[derive(Default)]
+    struct NamedDiscoverableOptions;
+
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all(deserialize = "PascalCase"))]
+    struct BoundDiscoverableOptions {
+        enabled: bool,
+    }
+
+    #[derive(Default, crate::Options)]
+    #[options(name = "Derived", discover)]
+    struct DerivedDiscoverableOptions;
+
+    register_options!(DiscoverableOptions);
+    register_options!(NamedDiscoverableOptions, name = "Test");
+    register_options!(BoundDiscoverableOptions, section = "Test");
+
+    #[test]
+    fn add_discovered_options_should_register_declared_options() {
+        // arrange
+        let config: Ref<dyn Configuration> = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Test:Enabled", "true")])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let provider = ServiceCollection::new()
+            .add(singleton_factory(move |_| config.clone()))
+            .add_discovered_options()
+            .configure_options(|o: &mut DiscoverableOptions| o.enabled = true)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<DiscoverableOptions>>();
+        let named = provider.get::<dyn OptionsSnapshot<NamedDiscoverableOptions>>();
+        let bound = provider.get_required::<dyn Options<BoundDiscoverableOptions>>();
+        let derived = provider.get::<dyn OptionsSnapshot<DerivedDiscoverableOptions>>();
+
+        // assert
+        assert!(options.value().enabled);
+        assert!(named.is_some());
+        assert!(bound.value().enabled);
+        assert!(derived.is_some());
+    }
+}
@@ -0,0 +1,213 @@
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub(crate) type Job = Box<dyn FnOnce() + Send>;
+
+/// Represents how the listeners of monitored [`Options`](crate::Options) are invoked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListenerDispatch {
+    /// Indicates that listeners are invoked one after another on the thread that signaled the
+    /// change, which is the default.
+    Sequential,
+
+    /// Indicates that each listener is invoked on its own thread. The thread that signaled the
+    /// change waits until every listener has returned or, if listeners have a timeout, until the
+    /// timeout elapses.
+    Parallel,
+
+    /// Indicates that listeners are invoked one after another on a dedicated notification thread.
+    /// The thread that signaled the change does not wait for the listeners.
+    Queued,
+}
+
+impl Default for ListenerDispatch {
+    fn default() -> Self {
+        Self::Sequential
+    }
+}
+
+pub(crate) struct Dispatcher {
+    mode: ListenerDispatch,
+    queue: Mutex<Option<Sender<Job>>>,
+}
+
+impl Dispatcher {
+    pub(crate) fn new(mode: ListenerDispatch) -> Self {
+        Self {
+            mode,
+            queue: Default::default(),
+        }
+    }
+
+    pub(crate) fn is_sequential(&self) -> bool {
+        self.mode == ListenerDispatch::Sequential
+    }
+
+    pub(crate) fn dispatch(&self, jobs: Vec<Job>, timeout: Option<Duration>) {
+        match self.mode {
+            ListenerDispatch::Sequential => {
+                for job in jobs {
+                    job()
+                }
+            }
+            ListenerDispatch::Parallel => {
+                let count = jobs.len();
+                let deadline = timeout.map(|t| Instant::now() + t);
+                let (sender, receiver) = channel();
+                let mut panic = None;
+
+                for job in jobs {
+                    let sender = sender.clone();
+
+                    thread::spawn(move || {
+                        let _ = sender.send(catch_unwind(AssertUnwindSafe(job)));
+                    });
+                }
+
+                // every listener runs to completion or is abandoned after the timeout before a
+                // panic is propagated
+                for _ in 0..count {
+                    let result = match deadline {
+                        Some(deadline) => receiver
+                            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                            .map_err(|_| ()),
+                        _ => receiver.recv().map_err(|_| ()),
+                    };
+
+                    match result {
+                        Ok(Ok(())) => {}
+                        Ok(Err(error)) => {
+                            panic.get_or_insert(error);
+                        }
+                        Err(()) => break,
+                    }
+                }
+
+                if let Some(panic) = panic {
+                    resume_unwind(panic);
+                }
+            }
+            ListenerDispatch::Queued => {
+                let mut queue = self.queue.lock().unwrap();
+
+                for job in jobs {
+                    // the notification thread is started on first use and again if a listener
+                    // panicked on it
+                    let job = match queue.as_ref() {
+                        Some(queue) => match queue.send(job) {
+                            Ok(()) => continue,
+                            Err(error) => error.0,
+                        },
+                        _ => job,
+                    };
+                    let (sender, receiver) = channel::<Job>();
+
+                    thread::spawn(move || {
+                        for job in receiver {
+                            job()
+                        }
+                    });
+
+                    let _ = sender.send(job);
+                    *queue = Some(sender);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::mpsc::Receiver;
+    use std::sync::{Arc, Condvar};
+
+    #[derive(Default)]
+    struct Config;
+
+    fn monitor(
+        dispatch: ListenerDispatch,
+    ) -> (di::ServiceProvider, Arc<dyn OptionsMonitor<Config>>) {
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .dispatch_listeners(dispatch)
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        (provider, monitor)
+    }
+
+    fn wait(receiver: &Receiver<()>) -> bool {
+        receiver.recv_timeout(Duration::from_secs(5)).is_ok()
+    }
+
+    #[test]
+    fn parallel_dispatch_should_invoke_listeners_concurrently() {
+        // arrange
+        let (_provider, monitor) = monitor(ListenerDispatch::Parallel);
+        let arrived = Arc::new((Mutex::new(0), Condvar::new()));
+        let (sender, receiver) = channel();
+        let subscriptions: Vec<_> = (0..2)
+            .map(|_| {
+                let (arrived, sender) = (arrived.clone(), Mutex::new(sender.clone()));
+
+                // each listener waits for the other, which only happens if they run concurrently
+                monitor.on_change(Box::new(move |_, _| {
+                    let (count, all) = &*arrived;
+                    let mut count = count.lock().unwrap();
+
+                    *count += 1;
+                    all.notify_all();
+
+                    let (count, _) = all
+                        .wait_timeout_while(count, Duration::from_secs(5), |c| *c < 2)
+                        .unwrap();
+
+                    if *count == 2 {
+                        sender.lock().unwrap().send(()).unwrap();
+                    }
+                }))
+            })
+            .collect();
+
+        // act
+        monitor.reload(None);
+
+        // assert
+        assert!(wait(&receiver) && wait(&receiver));
+        drop(subscriptions);
+    }
+
+    #[test]
+    fn queued_dispatch_should_not_wait_for_listeners() {
+        // arrange
+        let (_provider, monitor) = monitor(ListenerDispatch::Queued);
+        let (release, released) = channel::<()>();
+        let (sender, receiver) = channel();
+        let released = Mutex::new(released);
+        let sender = Mutex::new(sender);
+        let _subscription = monitor.on_change(Box::new(move |_, _| {
+            wait(&released.lock().unwrap());
+            sender.lock().unwrap().send(()).unwrap();
+        }));
+
+        // act
+        monitor.reload(None);
+        monitor.reload(None);
+
+        let notified = receiver.try_recv().is_ok();
+
+        release.send(()).unwrap();
+        release.send(()).unwrap();
+
+        // assert
+        assert!(!notified);
+        assert!(wait(&receiver) && wait(&receiver));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    redact::{mask_values, SensitiveMarker},
+    OptionsMonitor, Redacted, RedactedSerialize, Value,
+};
+use di::{Ref, ServiceProvider};
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde_json::Value as Json;
+
+/// Represents the current values of configuration [`Options`](crate::Options) serialized with
+/// their secrets masked.
+///
+/// # Remarks
+///
+/// Options are included using [`add_to_dump`](crate::OptionsBuilder::add_to_dump). The dump is
+/// resolved as a transient service so that each instance reflects the values held by the
+/// [`OptionsMonitor`](crate::OptionsMonitor) at the time it is resolved, which makes it suitable
+/// for diagnostic endpoints that show the effective configuration.
+pub struct OptionsDump {
+    entries: Vec<OptionsDumpEntry>,
+}
+
+/// Represents the serialized value of a single configuration [`Options`](crate::Options) instance.
+pub struct OptionsDumpEntry {
+    options_type: &'static str,
+    name: Option<String>,
+    value: Json,
+}
+
+impl OptionsDumpEntry {
+    /// Gets the name of the type of options.
+    pub fn options_type(&self) -> &str {
+        self.options_type
+    }
+
+    /// Gets the name of the options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the serialized value of the options.
+    pub fn value(&self) -> &Json {
+        &self.value
+    }
+}
+
+impl OptionsDump {
+    /// Initializes a new options dump.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The [provider](di::ServiceProvider) used to resolve the current options
+    pub fn new(provider: &ServiceProvider) -> Self {
+        let mut entries: Vec<OptionsDumpEntry> = Vec::new();
+
+        for dumper in provider.get_all::<OptionsDumper>() {
+            let options_type = dumper.options_type;
+            let name = dumper.name.as_deref();
+
+            if !entries
+                .iter()
+                .any(|e| e.options_type == options_type && e.name() == name)
+            {
+                entries.push(OptionsDumpEntry {
+                    options_type,
+                    name: dumper.name.clone(),
+                    value: (dumper.dump)(provider, name),
+                });
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Gets the dumped options in the order they were registered.
+    pub fn entries(&self) -> &[OptionsDumpEntry] {
+        &self.entries
+    }
+
+    /// Serializes the dumped options to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Serializes the dumped options to pretty-printed JSON.
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+impl Serialize for OptionsDump {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.entries.len()))?;
+
+        for entry in &self.entries {
+            seq.serialize_element(entry)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl Serialize for OptionsDumpEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OptionsDumpEntry", 3)?;
+        state.serialize_field("type", self.options_type)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
+
+pub(crate) struct OptionsDumper {
+    options_type: &'static str,
+    name: Option<String>,
+    dump: fn(&ServiceProvider, Option<&str>) -> Json,
+}
+
+impl OptionsDumper {
+    pub(crate) fn new<T>(name: Option<&str>) -> Self
+    where
+        T: Value + RedactedSerialize + 'static,
+    {
+        Self {
+            options_type: std::any::type_name::<T>(),
+            name: name.map(|s| s.to_owned()),
+            dump: dump::<T>,
+        }
+    }
+}
+
+fn dump<T>(provider: &ServiceProvider, name: Option<&str>) -> Json
+where
+    T: Value + RedactedSerialize + 'static,
+{
+    let options: Ref<T> = provider.get_required::<dyn OptionsMonitor<T>>().get(name);
+
+    // a value that cannot be serialized is reported rather than failing the entire dump
+    let value = serde_json::to_value(Redacted::new(&*options))
+        .unwrap_or_else(|e| Json::String(e.to_string()));
+
+    if provider.get::<SensitiveMarker<T>>().is_some() {
+        mask_values(value)
+    } else {
+        value
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, RedactedSerialize, RegisterOptions};
+    use di::ServiceCollection;
+
+    #[derive(Default, RedactedSerialize)]
+    struct DbOptions {
+        host: String,
+        #[options(secret)]
+        password: String,
+    }
+
+    #[derive(Default, RedactedSerialize)]
+    struct CacheOptions {
+        ttl: u32,
+    }
+
+    #[derive(Default, crate::Options, RedactedSerialize)]
+    #[options(name = "Derived", dump)]
+    struct DerivedOptions {
+        #[options(secret)]
+        token: String,
+    }
+
+    #[derive(Default, crate::Options, RedactedSerialize)]
+    #[options(dump, sensitive)]
+    struct CredentialOptions {
+        user: String,
+        endpoint: Endpoint,
+    }
+
+    #[derive(Default, serde::Serialize)]
+    struct Endpoint {
+        host: String,
+        port: u16,
+    }
+
+    #[test]
+    fn dump_should_serialize_current_options_with_secrets_masked() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_options::<DbOptions>("Primary")
+            .configure(|o| {
+                o.host = "localhost".into();
+                o.password = "p@ssw0rd".into();
+            })
+            .add_to_dump()
+            .add_to_dump();
+        services
+            .add_options::<CacheOptions>()
+            .configure(|o| o.ttl = 60)
+            .add_to_dump();
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let dump = provider.get_required::<OptionsDump>();
+        let json: Json = serde_json::from_str(&dump.to_json()).unwrap();
+
+        // assert
+        assert_eq!(dump.entries().len(), 2);
+        assert!(dump.entries()[0].options_type().ends_with("DbOptions"));
+        assert_eq!(dump.entries()[0].name(), Some("Primary"));
+        assert_eq!(
+            json[0]["value"],
+            serde_json::json!({ "host": "localhost", "password": "***" })
+        );
+        assert!(dump.entries()[1].options_type().ends_with("CacheOptions"));
+        assert_eq!(json[1]["name"], Json::Null);
+        assert_eq!(json[1]["value"]["ttl"], 60);
+    }
+
+    #[test]
+    fn derived_options_should_be_included_in_dump() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        DerivedOptions::add_to(&mut services);
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let dump = provider.get_required::<OptionsDump>();
+
+        // assert
+        assert_eq!(dump.entries().len(), 1);
+        assert_eq!(dump.entries()[0].name(), Some("Derived"));
+        assert_eq!(dump.entries()[0].value()["token"], "***");
+    }
+
+    #[test]
+    fn dump_should_only_include_field_names_of_sensitive_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        CredentialOptions::add_to(&mut services);
+        services.add_options::<CredentialOptions>().configure(|o| {
+            o.user = "admin".into();
+            o.endpoint.host = "vault.contoso.com".into();
+            o.endpoint.port = 8200;
+        });
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let dump = provider.get_required::<OptionsDump>();
+
+        // assert
+        assert_eq!(
+            dump.entries()[0].value(),
+            &serde_json::json!({ "user": "***", "endpoint": { "host": "***", "port": "***" } })
+        );
+        assert!(!dump.to_json().contains("vault"));
+    }
+}
@@ -0,0 +1,570 @@
+use crate::{ext::*, singleton::Singleton, *};
+use config::{ext::*, ConfigurationBuilder, DefaultConfigurationBuilder};
+use di::ServiceCollection;
+use serde::de::DeserializeOwned;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::read_to_string;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::mem::take;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
+use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+/// Represents a change token source for [`Options`](crate::Options) that are bound to a dotenv
+/// file and environment variables.
+///
+/// # Remarks
+///
+/// Neither dotenv files nor environment variables signal when they change. When the **async**
+/// feature is enabled, a background thread can poll both at a specified interval and signal a
+/// change when either differs. The thread stops after the source is dropped. Every source also
+/// checks for changes when [`refresh_env`](crate::refresh_env) is called.
+pub struct EnvironmentChangeTokenSource<T: Value> {
+    poller: Arc<Poller>,
+    _data: PhantomData<T>,
+}
+
+impl<T: Value> EnvironmentChangeTokenSource<T> {
+    /// Initializes a new environment change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the dotenv file to poll
+    /// * `prefix` - The prefix of the environment variables to poll
+    /// * `interval` - The interval at which changes are polled
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn new(path: impl AsRef<Path>, prefix: impl AsRef<str>, interval: Duration) -> Self {
+        Self::create(
+            Some(path.as_ref().to_path_buf()),
+            prefix.as_ref(),
+            Some(interval),
+        )
+    }
+
+    /// Initializes a new environment change token source for environment variables only.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the environment variables to watch
+    ///
+    /// # Remarks
+    ///
+    /// Changes are only detected when [`refresh_env`](crate::refresh_env) is called.
+    pub fn variables(prefix: impl AsRef<str>) -> Self {
+        Self::create(None, prefix.as_ref(), None)
+    }
+
+    /// Initializes a new environment change token source which polls environment variables only.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the environment variables to poll
+    /// * `interval` - The interval at which changes are polled
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn polling(prefix: impl AsRef<str>, interval: Duration) -> Self {
+        Self::create(None, prefix.as_ref(), Some(interval))
+    }
+
+    fn create(path: Option<PathBuf>, prefix: &str, interval: Option<Duration>) -> Self {
+        let mut poller = Poller {
+            path,
+            prefix: prefix.to_owned(),
+            last: Default::default(),
+            token: Default::default(),
+        };
+
+        poller.last = Mutex::new(poller.fingerprint());
+
+        let poller = Arc::new(poller);
+        let weak = Arc::downgrade(&poller);
+
+        pollers().lock().unwrap().push(weak.clone());
+
+        if let Some(interval) = interval {
+            thread::spawn(move || Poller::run(weak, interval));
+        }
+
+        Self {
+            poller,
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for EnvironmentChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(self.poller.token.lock().unwrap().clone())
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("environment")
+    }
+}
+
+/// Checks every [`EnvironmentChangeTokenSource`] for changes immediately.
+///
+/// # Remarks
+///
+/// This is useful after an application changes its own environment variables or is told that its
+/// environment has changed. Only sources whose variables or dotenv file differ signal a change.
+pub fn refresh_env() {
+    let mut pollers = pollers().lock().unwrap();
+
+    pollers.retain(|poller| poller.strong_count() > 0);
+
+    let pollers: Vec<_> = pollers.iter().filter_map(Weak::upgrade).collect();
+
+    for poller in pollers {
+        poller.check();
+    }
+}
+
+fn pollers() -> &'static Mutex<Vec<Weak<Poller>>> {
+    static POLLERS: Singleton<Mutex<Vec<Weak<Poller>>>> = Singleton::new();
+    POLLERS.get_or_init(Default::default)
+}
+
+struct Poller {
+    path: Option<PathBuf>,
+    prefix: String,
+    last: Mutex<u64>,
+    token: Mutex<SharedChangeToken<SingleChangeToken>>,
+}
+
+impl Poller {
+    fn run(poller: Weak<Self>, interval: Duration) {
+        loop {
+            thread::sleep(interval);
+
+            match poller.upgrade() {
+                Some(poller) => poller.check(),
+                _ => return,
+            }
+        }
+    }
+
+    fn check(&self) {
+        let current = self.fingerprint();
+        let mut last = self.last.lock().unwrap();
+
+        if current != *last {
+            *last = current;
+            drop(last);
+
+            // a token only signals once so it's replaced before it's signaled
+            let token = take(&mut *self.token.lock().unwrap());
+            token.notify();
+        }
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let mut vars: Vec<_> = std::env::vars()
+            .filter(|(key, _)| has_prefix(key, &self.prefix))
+            .collect();
+
+        vars.sort();
+
+        if let Some(path) = &self.path {
+            read_to_string(path).ok().hash(&mut hasher);
+        }
+
+        vars.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+fn has_prefix(key: &str, prefix: &str) -> bool {
+    key.len() >= prefix.len()
+        && key.is_char_boundary(prefix.len())
+        && key[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+// a comment starts with a '#' that follows whitespace; any other '#' is part of the value
+fn strip_comment(value: &str) -> &str {
+    let mut previous = None;
+
+    for (index, c) in value.char_indices() {
+        if c == '#' && previous.map_or(false, char::is_whitespace) {
+            return &value[..index];
+        }
+
+        previous = Some(c);
+    }
+
+    value
+}
+
+fn unquote(value: &str) -> String {
+    let quoted = value.trim_start();
+    let mut chars = quoted.char_indices();
+
+    if let Some((_, quote)) = chars.next().filter(|(_, c)| *c == '"' || *c == '\'') {
+        let mut escaped = false;
+
+        // anything after the closing quote, such as a comment, is ignored
+        for (index, c) in chars {
+            if quote == '"' && c == '\\' && !escaped {
+                escaped = true;
+                continue;
+            }
+
+            if c == quote && !escaped {
+                let inner = &quoted[1..index];
+
+                return if quote == '"' {
+                    inner.replace("\\n", "\n").replace("\\\"", "\"")
+                } else {
+                    inner.to_owned()
+                };
+            }
+
+            escaped = false;
+        }
+    }
+
+    // a value without a closing quote is not quoted
+    strip_comment(value).trim().to_owned()
+}
+
+fn parse_dotenv(content: &str, prefix: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+
+            if has_prefix(key, prefix) {
+                // match the key conventions of environment variables
+                let key = key[prefix.len()..].replace("__", ":");
+                pairs.push((key, unquote(value)));
+            }
+        }
+    }
+
+    pairs
+}
+
+// a failure to load or bind the variables is reported when the options are created
+fn load<T: DeserializeOwned>(path: &Path, prefix: &str, options: &mut T) -> Result<(), String> {
+    let content = read_to_string(path).unwrap_or_default();
+    let dotenv = parse_dotenv(&content, prefix);
+    let configuration = DefaultConfigurationBuilder::new()
+        .add_in_memory(&dotenv)
+        .add_env_vars_with_prefix(prefix)
+        .build()
+        .map_err(|error| format!("{:?}", error))?
+        .as_config();
+
+    bind_config(configuration.as_ref(), options).map_err(|error| error.to_string())
+}
+
+/// Defines extension methods for the [`ServiceCollection`](di::ServiceCollection) struct.
+pub trait OptionsEnvironmentServiceExtensions {
+    /// Registers an options type bound to a dotenv file overlaid with environment variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the dotenv file
+    /// * `prefix` - The prefix that variable names must start with
+    ///
+    /// # Remarks
+    ///
+    /// Only variables which start with the prefix are bound and the prefix is removed from their
+    /// names. A double underscore in a name is a section separator. Names are bound as written,
+    /// which typically means the options use `#[serde(rename_all = "SCREAMING_SNAKE_CASE")]`.
+    /// Environment variables take precedence over the dotenv file, which is optional. Both are read
+    /// each time the options are created and a variable that cannot be bound is reported as a
+    /// failure when the options are created.
+    fn apply_env_layered<T>(
+        &mut self,
+        path: impl AsRef<Path>,
+        prefix: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+
+    /// Registers an options type bound to a dotenv file overlaid with environment variables which
+    /// is reloaded when either changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the dotenv file
+    /// * `prefix` - The prefix that variable names must start with
+    /// * `interval` - The interval at which changes are polled
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    fn apply_env_layered_polling<T>(
+        &mut self,
+        path: impl AsRef<Path>,
+        prefix: impl AsRef<str>,
+        interval: Duration,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static;
+}
+
+impl OptionsEnvironmentServiceExtensions for ServiceCollection {
+    fn apply_env_layered<T>(
+        &mut self,
+        path: impl AsRef<Path>,
+        prefix: impl AsRef<str>,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        let path = path.as_ref().to_path_buf();
+        let prefix = prefix.as_ref().to_owned();
+
+        self.add_options()
+            .configure_result(move |options: &mut T| load(&path, &prefix, options))
+    }
+
+    #[cfg(feature = "async")]
+    fn apply_env_layered_polling<T>(
+        &mut self,
+        path: impl AsRef<Path>,
+        prefix: impl AsRef<str>,
+        interval: Duration,
+    ) -> OptionsBuilder<'_, T>
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        let source = EnvironmentChangeTokenSource::new(path.as_ref(), prefix.as_ref(), interval);
+
+        self.apply_env_layered(path, prefix).watch(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use serde::Deserialize;
+    use std::env::{remove_var, set_var, temp_dir};
+    use std::fs::{remove_file, write};
+
+    #[cfg(feature = "async")]
+    use std::time::Instant;
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "SCREAMING_SNAKE_CASE"))]
+    struct ServerOptions {
+        host: String,
+        port: u16,
+        tls: TlsOptions,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all(deserialize = "SCREAMING_SNAKE_CASE"))]
+    struct TlsOptions {
+        enabled: bool,
+    }
+
+    #[test]
+    fn parse_dotenv_should_return_prefixed_variables() {
+        // arrange
+        let content = r#"
+# comment
+APP_HOST=localhost
+export APP_PORT = 8080 # inline comment
+APP_TLS__ENABLED="true"
+OTHER=ignored
+"#;
+
+        // act
+        let pairs = parse_dotenv(content, "APP_");
+
+        // assert
+        assert_eq!(
+            pairs,
+            vec![
+                ("HOST".to_owned(), "localhost".to_owned()),
+                ("PORT".to_owned(), "8080".to_owned()),
+                ("TLS:ENABLED".to_owned(), "true".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_dotenv_should_only_strip_comments_outside_of_quotes() {
+        // arrange
+        let content = r#"
+APP_QUOTED="value" # comment
+APP_HASH_QUOTED="a # b" # comment
+APP_SINGLE='a # b' # comment
+APP_ESCAPED="say \"hi\"" # comment
+APP_HASH=a#b
+APP_UNQUOTED=a b # comment
+APP_EMPTY= # comment
+"#;
+
+        // act
+        let pairs = parse_dotenv(content, "APP_");
+
+        // assert
+        assert_eq!(
+            pairs,
+            vec![
+                ("QUOTED".to_owned(), "value".to_owned()),
+                ("HASH_QUOTED".to_owned(), "a # b".to_owned()),
+                ("SINGLE".to_owned(), "a # b".to_owned()),
+                ("ESCAPED".to_owned(), "say \"hi\"".to_owned()),
+                ("HASH".to_owned(), "a#b".to_owned()),
+                ("UNQUOTED".to_owned(), "a b".to_owned()),
+                ("EMPTY".to_owned(), "".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_env_layered_should_overlay_environment_variables_on_dotenv_file() {
+        // arrange
+        let path = temp_dir().join("options_env_1.env");
+
+        write(
+            &path,
+            "ENV1_HOST=localhost\nENV1_PORT=80\nENV1_TLS__ENABLED=true\n",
+        )
+        .unwrap();
+        set_var("ENV1_PORT", "8080");
+
+        let provider = ServiceCollection::new()
+            .apply_env_layered::<ServerOptions>(&path, "ENV1_")
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<ServerOptions>>();
+        let value = options.value();
+
+        // assert
+        remove_file(&path).ok();
+        remove_var("ENV1_PORT");
+
+        assert_eq!(&value.host, "localhost");
+        assert_eq!(value.port, 8080);
+        assert!(value.tls.enabled);
+    }
+
+    #[test]
+    fn apply_env_layered_should_report_variable_that_cannot_be_bound() {
+        // arrange
+        let path = temp_dir().join("options_env_5.env");
+
+        set_var("ENV5_PORT", "not a port");
+
+        let provider = ServiceCollection::new()
+            .apply_env_layered::<ServerOptions>(&path, "ENV5_")
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = provider.warm_up_options();
+
+        // assert
+        remove_var("ENV5_PORT");
+
+        assert_eq!(result.unwrap_err().failures().len(), 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn apply_env_layered_polling_should_reload_options_when_environment_changes() {
+        // arrange
+        let path = temp_dir().join("options_env_2.env");
+
+        write(&path, "ENV2_PORT=80\n").unwrap();
+
+        let provider = ServiceCollection::new()
+            .apply_env_layered_polling::<ServerOptions>(&path, "ENV2_", Duration::from_millis(10))
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<ServerOptions>>();
+        let original = monitor.current_value().port;
+        let started = Instant::now();
+
+        // act
+        set_var("ENV2_PORT", "8080");
+
+        while monitor.current_value().port == original && started.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        // the poller would observe the cleanup, so the value is captured first
+        let current = monitor.current_value().port;
+
+        // assert
+        remove_file(&path).ok();
+        remove_var("ENV2_PORT");
+
+        assert_eq!(original, 80);
+        assert_eq!(current, 8080);
+    }
+
+    #[test]
+    fn refresh_env_should_only_signal_when_variables_differ() {
+        // arrange
+        set_var("ENV3_PORT", "80");
+
+        let source = EnvironmentChangeTokenSource::<ServerOptions>::variables("ENV3_");
+        let token = source.token();
+
+        // act
+        refresh_env();
+
+        let unchanged = !token.changed();
+
+        set_var("ENV3_PORT", "8080");
+        refresh_env();
+
+        // assert
+        remove_var("ENV3_PORT");
+
+        assert!(unchanged);
+        assert!(token.changed());
+        assert!(!source.token().changed());
+    }
+
+    #[test]
+    fn reload_on_env_should_reload_options_when_refreshed() {
+        // arrange
+        let path = temp_dir().join("options_env_4.env");
+
+        set_var("ENV4_PORT", "80");
+
+        let provider = ServiceCollection::new()
+            .apply_env_layered::<ServerOptions>(&path, "ENV4_")
+            .reload_on_env("ENV4_")
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<ServerOptions>>();
+        let original = monitor.current_value().port;
+
+        // act
+        set_var("ENV4_PORT", "8080");
+        refresh_env();
+
+        let current = monitor.current_value().port;
+
+        // assert
+        remove_var("ENV4_PORT");
+
+        assert_eq!(original, 80);
+        assert_eq!(current, 8080);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{PostConfigureOptions, Value};
+use std::env::{current_dir, var};
+use std::path::{Path, PathBuf};
+
+type PathField<T> = fn(&mut T) -> &mut PathBuf;
+
+/// Represents a [post-configuration](crate::PostConfigureOptions) that expands the paths in the
+/// fields of configuration [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Each path is expanded after the options are bound so that configuration can be written
+/// independently of the machine it's deployed to:
+///
+/// 1. Environment variables in the form `$NAME` or `${NAME}` are replaced with their values; on
+///    Windows, `%NAME%` is also supported. Variables that are not defined are left unchanged.
+/// 2. A leading `~` is replaced with the home directory of the current user.
+/// 3. A relative path is joined to the base directory, which is the current directory unless
+///    another directory is specified.
+///
+/// Empty paths are left unchanged so that validation can report them.
+pub struct PathExpansion<T> {
+    base: Option<PathBuf>,
+    fields: Vec<PathField<T>>,
+}
+
+impl<T> Default for PathExpansion<T> {
+    fn default() -> Self {
+        Self {
+            base: None,
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl<T> PathExpansion<T> {
+    /// Initializes a new path expansion without any paths.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a path to expand.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The function used to access the path in the options
+    pub fn path(mut self, field: PathField<T>) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Sets the directory relative paths are joined to.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base directory, which may also be expanded
+    pub fn relative_to(mut self, base: impl AsRef<Path>) -> Self {
+        self.base = Some(base.as_ref().to_path_buf());
+        self
+    }
+
+    /// Expands the paths in the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to expand the paths of
+    pub fn expand(&self, options: &mut T) {
+        let base = match &self.base {
+            Some(base) => expand_path(base, None),
+            _ => current_dir().unwrap_or_default(),
+        };
+
+        for field in &self.fields {
+            let path = field(options);
+
+            if !path.as_os_str().is_empty() {
+                *path = expand_path(path, Some(&base));
+            }
+        }
+    }
+}
+
+impl<T: Value> PostConfigureOptions<T> for PathExpansion<T> {
+    fn post_configure(&self, _name: Option<&str>, options: &mut T) {
+        self.expand(options)
+    }
+}
+
+fn expand_path(path: &Path, base: Option<&Path>) -> PathBuf {
+    // a path that is not valid unicode cannot contain anything to expand
+    let path = match path.to_str() {
+        Some(text) => expand_home(&expand_vars(text)),
+        _ => path.to_path_buf(),
+    };
+
+    match base {
+        Some(base) if path.is_relative() => base.join(path),
+        _ => path,
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(std::path::is_separator) => rest,
+        _ => return PathBuf::from(path),
+    };
+    let home = if cfg!(windows) {
+        var("USERPROFILE")
+    } else {
+        var("HOME")
+    };
+
+    match home {
+        Ok(home) => PathBuf::from(format!("{}{}", home, rest)),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn expand_vars(path: &str) -> String {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find(|c| c == '$' || (cfg!(windows) && c == '%')) {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let (name, len) = if let Some(braced) = rest.strip_prefix("${") {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 3),
+                _ => ("", 0),
+            }
+        } else if let Some(quoted) = rest.strip_prefix('%') {
+            match quoted.find('%') {
+                Some(end) => (&quoted[..end], end + 2),
+                _ => ("", 0),
+            }
+        } else {
+            let end = rest[1..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len() - 1);
+            (&rest[1..=end], end + 1)
+        };
+
+        match Some(name)
+            .filter(|n| !n.is_empty())
+            .and_then(|n| var(n).ok())
+        {
+            Some(value) => expanded.push_str(&value),
+            _ => expanded.push_str(&rest[..len.max(1)]),
+        }
+
+        rest = &rest[len.max(1)..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, Options};
+    use di::ServiceCollection;
+    use std::env::set_var;
+
+    #[derive(Default)]
+    struct StorageOptions {
+        data_dir: PathBuf,
+        log_file: PathBuf,
+        cache_dir: PathBuf,
+    }
+
+    #[test]
+    fn expand_vars_should_replace_defined_variables() {
+        // arrange
+        set_var("OPTIONS_EXPAND_TEST", "app");
+
+        // act
+        let paths = [
+            expand_vars("/var/$OPTIONS_EXPAND_TEST/data"),
+            expand_vars("/var/${OPTIONS_EXPAND_TEST}.d"),
+            expand_vars("/var/$OPTIONS_EXPAND_UNDEFINED/$"),
+        ];
+
+        // assert
+        assert_eq!(
+            paths,
+            [
+                "/var/app/data".to_owned(),
+                "/var/app.d".to_owned(),
+                "/var/$OPTIONS_EXPAND_UNDEFINED/$".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_paths_should_expand_paths_after_binding() {
+        // arrange
+        let base = std::env::temp_dir().join("app");
+        let provider = ServiceCollection::new()
+            .add_options::<StorageOptions>()
+            .configure(|o| {
+                o.data_dir = "data".into();
+                o.log_file = "~/app.log".into();
+            })
+            .expand_paths(
+                PathExpansion::<StorageOptions>::new()
+                    .path(|o| &mut o.data_dir)
+                    .path(|o| &mut o.log_file)
+                    .path(|o| &mut o.cache_dir)
+                    .relative_to(&base),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<StorageOptions>>();
+
+        // assert
+        let home = PathBuf::from(var(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).unwrap());
+
+        assert_eq!(options.value().data_dir, base.join("data"));
+        assert_eq!(options.value().log_file, home.join("app.log"));
+        assert!(options.value().cache_dir.as_os_str().is_empty());
+    }
+}
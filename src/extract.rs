@@ -0,0 +1,198 @@
+use crate::{Options, OptionsSnapshot, Ref, Value};
+use axum::extract::FromRequestParts;
+use axum::http::{request::Parts, StatusCode};
+use di::ServiceProvider;
+use std::any::type_name;
+use std::ops::Deref;
+
+/// Represents the rejection of an options extractor.
+pub type OptionsRejection = (StatusCode, String);
+
+fn resolve<S: ?Sized + 'static>(parts: &Parts) -> Result<Ref<S>, OptionsRejection> {
+    let provider = parts.extensions.get::<ServiceProvider>().ok_or_else(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "The request does not have a ServiceProvider extension.".into(),
+        )
+    })?;
+
+    provider.get::<S>().ok_or_else(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!(
+                "No service for type '{}' has been registered.",
+                type_name::<S>()
+            ),
+        )
+    })
+}
+
+/// Represents an extractor of configuration [`Options`](crate::Options) in a request handler.
+///
+/// # Remarks
+///
+/// The options are resolved from the [`ServiceProvider`](di::ServiceProvider) added to the
+/// request as an extension, such as by the `Extension` layer. The request is rejected with
+/// [`INTERNAL_SERVER_ERROR`](StatusCode::INTERNAL_SERVER_ERROR) if the request does not have a
+/// provider or the options are not registered.
+pub struct OptionsExtractor<T: Value>(pub Ref<T>);
+
+impl<T: Value> Deref for OptionsExtractor<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S, T> FromRequestParts<S> for OptionsExtractor<T>
+where
+    S: Send + Sync,
+    T: Value + 'static,
+{
+    type Rejection = OptionsRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let options = resolve::<dyn Options<T>>(parts)?;
+        Ok(Self(options.value()))
+    }
+}
+
+/// Represents an extractor of an [`OptionsSnapshot`](crate::OptionsSnapshot) in a request handler.
+///
+/// # Remarks
+///
+/// The snapshot is resolved from the [`ServiceProvider`](di::ServiceProvider) added to the request
+/// as an extension. A snapshot is scoped, so the provider is typically a
+/// [scope](di::ServiceProvider::create_scope) created for each request, which means every options
+/// retrieved from the snapshot are the same for the lifetime of the request. The request is rejected
+/// with [`INTERNAL_SERVER_ERROR`](StatusCode::INTERNAL_SERVER_ERROR) if the request does not have a
+/// provider or the options are not registered.
+pub struct SnapshotExtractor<T: Value>(pub Ref<dyn OptionsSnapshot<T>>);
+
+impl<T: Value> Deref for SnapshotExtractor<T> {
+    type Target = dyn OptionsSnapshot<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<S, T> FromRequestParts<S> for SnapshotExtractor<T>
+where
+    S: Send + Sync,
+    T: Value + 'static,
+{
+    type Rejection = OptionsRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(resolve::<dyn OptionsSnapshot<T>>(parts)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use axum::http::Request;
+    use di::ServiceCollection;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    #[derive(Default)]
+    struct RateLimitOptions {
+        per_second: usize,
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    fn new_parts(provider: Option<ServiceProvider>) -> Parts {
+        let mut request = Request::new(());
+
+        if let Some(provider) = provider {
+            request.extensions_mut().insert(provider);
+        }
+
+        request.into_parts().0
+    }
+
+    fn new_provider() -> ServiceProvider {
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<RateLimitOptions>()
+            .configure(|o| o.per_second = 10);
+        services
+            .add_named_options::<RateLimitOptions>("Burst")
+            .configure(|o| o.per_second = 100);
+
+        services.build_provider().unwrap()
+    }
+
+    #[test]
+    fn options_extractor_should_resolve_options_from_request_provider() {
+        // arrange
+        let mut parts = new_parts(Some(new_provider()));
+
+        // act
+        let options = block_on(OptionsExtractor::<RateLimitOptions>::from_request_parts(
+            &mut parts,
+            &(),
+        ))
+        .ok()
+        .unwrap();
+
+        // assert
+        assert_eq!(options.per_second, 10);
+    }
+
+    #[test]
+    fn snapshot_extractor_should_resolve_snapshot_from_request_provider() {
+        // arrange
+        let mut parts = new_parts(Some(new_provider().create_scope()));
+
+        // act
+        let snapshot = block_on(SnapshotExtractor::<RateLimitOptions>::from_request_parts(
+            &mut parts,
+            &(),
+        ))
+        .ok()
+        .unwrap();
+
+        // assert
+        assert_eq!(snapshot.get(Some("Burst")).per_second, 100);
+    }
+
+    #[test]
+    fn options_extractor_should_reject_request_without_provider() {
+        // arrange
+        let mut parts = new_parts(None);
+
+        // act
+        let result = block_on(OptionsExtractor::<RateLimitOptions>::from_request_parts(
+            &mut parts,
+            &(),
+        ));
+
+        // assert
+        assert_eq!(result.err().unwrap().0, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -1,4 +1,8 @@
 use crate::*;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "di")]
+use crate::cache::CacheComparer;
 
 /// Defines the behavior of an object that creates configuration [`Options`](crate::Options).
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
@@ -9,6 +13,27 @@ pub trait OptionsFactory<T: Value> {
     ///
     /// * `name` - The optional name of the configuration options to create
     fn create(&self, name: Option<&str>) -> Result<T, ValidateOptionsResult>;
+
+    /// Creates and returns new configuration options starting from an existing instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the configuration options to create
+    /// * `seed` - The instance the configuration options are created from
+    ///
+    /// # Remarks
+    ///
+    /// The configuration pipeline is applied to the provided instance rather than a
+    /// new, default instance, which allows an existing instance to be layered upon.
+    /// The default implementation fails because a factory that does not implement this
+    /// method cannot honor the seed, and silently discarding it would hide the layered values.
+    fn create_from(&self, name: Option<&str>, seed: T) -> Result<T, ValidateOptionsResult> {
+        let _ = (name, seed);
+        Err(ValidateOptionsResult::fail(format!(
+            "the factory for '{}' cannot create options from an existing instance",
+            std::any::type_name::<T>()
+        )))
+    }
 }
 
 /// Represents the default factory used to create configuration [`Options`](crate::Options).
@@ -17,11 +42,13 @@ pub struct DefaultOptionsFactory<T: Value + Default> {
     configurations: Vec<Ref<dyn ConfigureOptions<T>>>,
     post_configurations: Vec<Ref<dyn PostConfigureOptions<T>>>,
     validations: Vec<Ref<dyn ValidateOptions<T>>>,
+    observers: Vec<Ref<dyn OptionsCreateObserver<T>>>,
+    fallback: Option<OptionsFallback>,
+    inheritance: Option<OptionsInheritance>,
+    sensitive: bool,
+    slow_threshold: Option<Duration>,
 }
 
-unsafe impl<T: Send + Sync + Default> Send for DefaultOptionsFactory<T> {}
-unsafe impl<T: Send + Sync + Default> Sync for DefaultOptionsFactory<T> {}
-
 impl<T: Value + Default> DefaultOptionsFactory<T> {
     /// Initializes a new options factory.
     ///
@@ -39,38 +66,440 @@ impl<T: Value + Default> DefaultOptionsFactory<T> {
             configurations,
             post_configurations,
             validations,
+            observers: Vec::new(),
+            fallback: None,
+            inheritance: None,
+            sensitive: false,
+            slow_threshold: None,
         }
     }
-}
 
-impl<T: Value + Default> OptionsFactory<T> for DefaultOptionsFactory<T> {
-    fn create(&self, name: Option<&str>) -> Result<T, ValidateOptionsResult> {
-        let mut options = Default::default();
+    /// Sets the observers notified when options are created.
+    ///
+    /// # Arguments
+    ///
+    /// * `observers` - The [observers](crate::OptionsCreateObserver) notified before and after options are created
+    pub fn with_observers(mut self, observers: Vec<Ref<dyn OptionsCreateObserver<T>>>) -> Self {
+        self.observers = observers;
+        self
+    }
+
+    /// Sets the policy used to resolve the names of options that were never configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `fallback` - The [fallback policy](crate::OptionsFallback) used to resolve names
+    pub fn with_fallback(mut self, fallback: OptionsFallback) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Sets the inheritance between named options.
+    ///
+    /// # Arguments
+    ///
+    /// * `inheritance` - The [inheritance](crate::OptionsInheritance) used to configure options in layers
+    pub fn with_inheritance(mut self, inheritance: OptionsInheritance) -> Self {
+        self.inheritance = Some(inheritance);
+        self
+    }
+
+    /// Indicates that the options are [sensitive](crate::SensitiveOptions).
+    ///
+    /// # Remarks
+    ///
+    /// The failures of sensitive options are masked in log events because validation messages
+    /// might include the values of the options.
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
+    /// Sets the threshold at which the creation of options is considered slow.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The [threshold](crate::SlowOptionsThreshold) after which creation is logged as slow
+    pub fn with_slow_threshold(mut self, threshold: SlowOptionsThreshold) -> Self {
+        self.slow_threshold = Some(threshold.value());
+        self
+    }
+
+    fn build(&self, name: Option<&str>, seed: T) -> Result<T, ValidateOptionsResult> {
+        let mut options = seed;
+        let mut failures = Vec::new();
+        let layers = match &self.inheritance {
+            Some(inheritance) => inheritance.layers(name),
+            _ => vec![name],
+        };
+        // configurations that apply to every name are only applied to the first layer
+        for (index, layer) in layers.iter().enumerate() {
+            let overlay = index > 0;
+
+            for configuration in &self.configurations {
+                if overlay && configuration.applies_to_every_name() {
+                    continue;
+                }
 
-        for configuration in &self.configurations {
-            configuration.configure(name, &mut options);
+                if let Err(failure) = configuration.try_configure(*layer, &mut options) {
+                    failures.push(failure);
+                }
+            }
         }
 
-        for configuration in &self.post_configurations {
-            configuration.post_configure(name, &mut options);
+        for (index, layer) in layers.iter().enumerate() {
+            let overlay = index > 0;
+
+            for configuration in &self.post_configurations {
+                if overlay && configuration.applies_to_every_name() {
+                    continue;
+                }
+
+                if let Err(failure) = configuration.try_post_configure(*layer, &mut options) {
+                    failures.push(failure);
+                }
+            }
         }
 
-        if !self.validations.is_empty() {
-            let mut failures = Vec::new();
+        for (index, layer) in layers.iter().enumerate() {
+            let overlay = index > 0;
 
             for validation in &self.validations {
-                let result = validation.validate(name, &options);
+                if overlay && validation.applies_to_every_name() {
+                    continue;
+                }
+
+                let result = validation.validate(*layer, &options);
 
                 if result.failed() {
                     failures.extend_from_slice(result.failures())
                 }
             }
+        }
+
+        if failures.is_empty() {
+            Ok(options)
+        } else {
+            Err(ValidateOptionsResult::fail_many(failures.iter()))
+        }
+    }
+}
+
+impl<T: Value + Default> OptionsFactory<T> for DefaultOptionsFactory<T> {
+    fn create(&self, name: Option<&str>) -> Result<T, ValidateOptionsResult> {
+        self.create_from(name, Default::default())
+    }
+
+    fn create_from(&self, name: Option<&str>, seed: T) -> Result<T, ValidateOptionsResult> {
+        let _span = trace::creating::<T>(name);
+
+        for observer in &self.observers {
+            observer.before_create(name);
+        }
+
+        let start = Instant::now();
+        let resolved = match &self.fallback {
+            Some(fallback) => fallback.resolve(name),
+            _ => name,
+        };
+        let result = self.build(resolved, seed);
+        let duration = start.elapsed();
+
+        trace::created(name, duration, &result, self.sensitive);
+        meter::created::<T>(name, duration, result.is_err());
+
+        if let Some(threshold) = self.slow_threshold.filter(|t| duration > *t) {
+            trace::slow::<T>(name, duration, threshold);
+        }
+
+        for observer in &self.observers {
+            observer.after_create(name, duration, &result);
+        }
+
+        result
+    }
+}
+
+/// Represents the threshold at which the creation of [`Options`](crate::Options) is considered slow.
+///
+/// # Remarks
+///
+/// When the **tracing** feature is enabled, creating options that takes longer than the threshold is
+/// logged as a warning, which provides visibility into expensive configuration, such as parsing
+/// certificates or compiling regular expressions. The threshold is used by the
+/// [`DefaultOptionsFactory`] of every type of options when it is registered as a singleton.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlowOptionsThreshold(Duration);
+
+impl SlowOptionsThreshold {
+    /// Initializes a new slow options threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The duration after which creating options is considered slow
+    pub fn new(threshold: Duration) -> Self {
+        Self(threshold)
+    }
+
+    /// Gets the duration after which creating options is considered slow.
+    pub fn value(&self) -> Duration {
+        self.0
+    }
+}
+
+impl Default for SlowOptionsThreshold {
+    fn default() -> Self {
+        Self(Duration::from_millis(100))
+    }
+}
+
+#[cfg(feature = "di")]
+pub(crate) struct NamedOptionsFactory<T> {
+    name: String,
+    factory: Ref<dyn OptionsFactory<T>>,
+}
+
+#[cfg(feature = "di")]
+impl<T: Value + 'static> NamedOptionsFactory<T> {
+    pub(crate) fn new(name: &str, factory: Ref<dyn OptionsFactory<T>>) -> Self {
+        Self {
+            name: name.to_owned(),
+            factory,
+        }
+    }
+
+    // names are routed with the same comparer as the cache so that names which match are created by
+    // the same factory
+    pub(crate) fn resolve(provider: &di::ServiceProvider) -> Ref<dyn OptionsFactory<T>> {
+        let named: Vec<_> = provider.get_all::<NamedOptionsFactory<T>>().collect();
+
+        match provider.get::<dyn OptionsFactory<T>>() {
+            Some(default) if named.is_empty() => default,
+            default => Ref::new(RoutedOptionsFactory {
+                default,
+                named,
+                comparer: CacheComparer::<T>::resolve(provider),
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "di")]
+impl<T: Value> OptionsFactory<T> for NamedOptionsFactory<T> {
+    fn create(&self, name: Option<&str>) -> Result<T, ValidateOptionsResult> {
+        self.factory.create(name)
+    }
+
+    fn create_from(&self, name: Option<&str>, seed: T) -> Result<T, ValidateOptionsResult> {
+        self.factory.create_from(name, seed)
+    }
+}
+
+#[cfg(feature = "di")]
+struct RoutedOptionsFactory<T> {
+    default: Option<Ref<dyn OptionsFactory<T>>>,
+    named: Vec<Ref<NamedOptionsFactory<T>>>,
+    comparer: OptionsNameComparer,
+}
+
+#[cfg(feature = "di")]
+impl<T: Value> RoutedOptionsFactory<T> {
+    fn route(&self, name: Option<&str>) -> Result<&dyn OptionsFactory<T>, ValidateOptionsResult> {
+        if let Some(name) = name {
+            if let Some(named) = self
+                .named
+                .iter()
+                .find(|f| self.comparer.equals(&f.name, name))
+            {
+                return Ok(&**named);
+            }
+        }
+
+        match &self.default {
+            Some(default) => Ok(&**default),
+            _ => Err(ValidateOptionsResult::fail(match name {
+                Some(name) => format!("no factory is registered for the options named '{}'", name),
+                _ => "no factory is registered for the default options".to_owned(),
+            })),
+        }
+    }
+}
+
+#[cfg(feature = "di")]
+impl<T: Value> OptionsFactory<T> for RoutedOptionsFactory<T> {
+    fn create(&self, name: Option<&str>) -> Result<T, ValidateOptionsResult> {
+        self.route(name)?.create(name)
+    }
+
+    fn create_from(&self, name: Option<&str>, seed: T) -> Result<T, ValidateOptionsResult> {
+        self.route(name)?.create_from(name, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct TestOptions {
+        enabled: bool,
+        retries: u8,
+    }
 
-            if !failures.is_empty() {
-                return Err(ValidateOptionsResult::fail_many(failures.iter()));
+    struct TestValidation(&'static str);
+
+    impl ValidateOptions<TestOptions> for TestValidation {
+        fn validate(&self, _name: Option<&str>, options: &TestOptions) -> ValidateOptionsResult {
+            if options.enabled {
+                ValidateOptionsResult::success()
+            } else {
+                ValidateOptionsResult::fail(self.0)
             }
         }
+    }
+
+    fn new_factory() -> DefaultOptionsFactory<TestOptions> {
+        let configure: Ref<dyn ConfigureOptions<TestOptions>> =
+            Ref::new(configure(|_, o: &mut TestOptions| o.retries += 1));
+        DefaultOptionsFactory::new(vec![configure], Vec::default(), Vec::default())
+    }
+
+    #[test]
+    fn create_should_configure_default_options() {
+        // arrange
+        let factory = new_factory();
+
+        // act
+        let options = factory.create(None).unwrap();
+
+        // assert
+        assert!(!options.enabled);
+        assert_eq!(options.retries, 1);
+    }
+
+    #[test]
+    fn create_from_should_configure_seed_options() {
+        // arrange
+        let factory = new_factory();
+        let seed = TestOptions {
+            enabled: true,
+            retries: 2,
+        };
+
+        // act
+        let options = factory.create_from(None, seed).unwrap();
+
+        // assert
+        assert!(options.enabled);
+        assert_eq!(options.retries, 3);
+    }
+
+    struct CreateOnlyFactory;
+
+    impl OptionsFactory<TestOptions> for CreateOnlyFactory {
+        fn create(&self, _name: Option<&str>) -> Result<TestOptions, ValidateOptionsResult> {
+            Ok(TestOptions {
+                enabled: false,
+                retries: 7,
+            })
+        }
+    }
+
+    #[test]
+    fn create_from_should_fail_when_not_implemented() {
+        // arrange
+        let factory = CreateOnlyFactory;
+        let seed = TestOptions {
+            enabled: true,
+            retries: 2,
+        };
+
+        // act
+        let result = factory.create_from(None, seed);
+
+        // assert
+        assert!(result
+            .err()
+            .unwrap()
+            .failure_message()
+            .contains("cannot create options from an existing instance"));
+    }
 
-        Ok(options)
+    #[derive(Default)]
+    struct TestObserver {
+        events: Mutex<Vec<String>>,
     }
-}
\ No newline at end of file
+
+    impl OptionsCreateObserver<TestOptions> for TestObserver {
+        fn before_create(&self, name: Option<&str>) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("before {}", name.unwrap_or_default()));
+        }
+
+        fn after_create(
+            &self,
+            name: Option<&str>,
+            _duration: Duration,
+            result: &Result<TestOptions, ValidateOptionsResult>,
+        ) {
+            self.events.lock().unwrap().push(format!(
+                "after {} {}",
+                name.unwrap_or_default(),
+                result.as_ref().unwrap().retries
+            ));
+        }
+    }
+
+    #[test]
+    fn create_should_notify_observers() {
+        // arrange
+        let observer = Ref::new(TestObserver::default());
+        let factory = new_factory().with_observers(vec![observer.clone()]);
+
+        // act
+        let _ = factory.create(Some("Test")).unwrap();
+
+        // assert
+        assert_eq!(
+            &*observer.events.lock().unwrap(),
+            &["before Test", "after Test 1"]
+        );
+    }
+
+    struct FailingConfigure;
+
+    impl ConfigureOptions<TestOptions> for FailingConfigure {
+        fn configure(&self, _name: Option<&str>, _options: &mut TestOptions) {}
+
+        fn try_configure(
+            &self,
+            _name: Option<&str>,
+            _options: &mut TestOptions,
+        ) -> Result<(), String> {
+            Err("Invalid retries".into())
+        }
+    }
+
+    #[test]
+    fn create_should_aggregate_configuration_failures() {
+        // arrange
+        let configure: Ref<dyn ConfigureOptions<TestOptions>> = Ref::new(FailingConfigure);
+        let validate: Ref<dyn ValidateOptions<TestOptions>> =
+            Ref::new(TestValidation("Not enabled"));
+        let factory = DefaultOptionsFactory::new(vec![configure], Vec::default(), vec![validate]);
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failures(),
+            &["Invalid retries", "Not enabled"]
+        );
+    }
+}
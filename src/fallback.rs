@@ -0,0 +1,161 @@
+use crate::OptionsNameComparer;
+
+/// Represents the policy used to resolve the name of configuration [`Options`](crate::Options)
+/// that were never configured.
+///
+/// # Remarks
+///
+/// Rather than creating an instance that is only configured by the actions that apply to every
+/// name, a name that was never configured falls back to a parent name, if it was configured, and
+/// finally to the default name.
+#[derive(Clone, Debug)]
+pub struct OptionsFallback {
+    configured: Vec<String>,
+    parent: Option<String>,
+    comparer: OptionsNameComparer,
+}
+
+impl OptionsFallback {
+    /// Initializes a new options fallback policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `configured` - The names of the configured options
+    /// * `parent` - The optional name of the options to fall back to before the default name
+    pub fn new<I, S>(configured: I, parent: Option<&str>) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            configured: configured
+                .into_iter()
+                .map(|name| name.as_ref().to_owned())
+                .collect(),
+            parent: parent.map(|s| s.to_owned()),
+            comparer: OptionsNameComparer::default(),
+        }
+    }
+
+    /// Sets the comparer used to match configured names.
+    ///
+    /// # Arguments
+    ///
+    /// * `comparer` - The [comparer](crate::OptionsNameComparer) used to match names
+    pub fn with_comparer(mut self, comparer: OptionsNameComparer) -> Self {
+        self.comparer = comparer;
+        self
+    }
+
+    /// Resolves the name of the options to create.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the requested options
+    pub fn resolve<'a>(&'a self, name: Option<&'a str>) -> Option<&'a str> {
+        match name {
+            Some(name) if self.is_configured(name) => Some(name),
+            Some(_) => self
+                .parent
+                .as_deref()
+                .filter(|parent| self.is_configured(parent)),
+            None => None,
+        }
+    }
+
+    fn is_configured(&self, name: &str) -> bool {
+        self.configured
+            .iter()
+            .any(|configured| self.comparer.equals(configured, name))
+    }
+}
+
+#[cfg(feature = "di")]
+pub(crate) struct FallbackPolicy<T> {
+    parent: Option<String>,
+    comparer: OptionsNameComparer,
+    _marker: std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "di")]
+impl<T: 'static> FallbackPolicy<T> {
+    pub(crate) fn new(parent: Option<&str>, comparer: OptionsNameComparer) -> Self {
+        Self {
+            parent: parent.map(|s| s.to_owned()),
+            comparer,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub(crate) fn fallback(&self, provider: &di::ServiceProvider) -> OptionsFallback {
+        let options_type = di::Type::of::<T>();
+        let configured: Vec<_> = provider
+            .get_all::<crate::OptionsRegistration>()
+            .filter(|r| *r.options_type() == options_type)
+            .filter_map(|r| r.name().map(|n| n.to_owned()))
+            .collect();
+
+        OptionsFallback::new(configured, self.parent.as_deref()).with_comparer(self.comparer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsSnapshot};
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct TenantOptions {
+        theme: String,
+        quota: u32,
+    }
+
+    #[test]
+    fn resolve_should_fall_back_to_parent_then_default() {
+        // arrange
+        let fallback = OptionsFallback::new(["Tenant1", "Shared"], Some("Shared"));
+        let orphan = OptionsFallback::new(["Tenant1"], Some("Shared"));
+
+        // act
+        let names = [
+            fallback.resolve(Some("tenant1")),
+            fallback.resolve(Some("Tenant2")),
+            orphan.resolve(Some("Tenant2")),
+            fallback.resolve(None),
+        ];
+
+        // assert
+        assert_eq!(names, [Some("tenant1"), Some("Shared"), None, None]);
+    }
+
+    #[test]
+    fn get_should_create_fallback_options_for_unconfigured_name() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<TenantOptions>()
+            .configure(|o| o.quota = 10)
+            .fallback_to(Some("Shared"));
+        services
+            .add_named_options::<TenantOptions>("Shared")
+            .configure(|o| o.theme = "light".into());
+        services
+            .add_named_options::<TenantOptions>("Tenant1")
+            .configure(|o| o.theme = "dark".into());
+
+        let provider = services.build_provider().unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<TenantOptions>>();
+
+        // act
+        let configured = snapshot.get(Some("Tenant1"));
+        let unconfigured = snapshot.get(Some("Tenant42"));
+
+        // assert
+        assert_eq!(&configured.theme, "dark");
+        assert_eq!(&unconfigured.theme, "light");
+        assert_eq!(unconfigured.quota, 10);
+    }
+}
@@ -0,0 +1,605 @@
+use crate::{OptionsChangeTokenSource, OptionsNameComparer, Value};
+use notify::{Config, Event, RecommendedWatcher, RecursiveMode::NonRecursive, Watcher};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::mem::take;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+/// Represents a change token source for [`Options`](crate::Options) that are notified when one or
+/// more files change.
+///
+/// # Remarks
+///
+/// The files do not need to exist when the source is created. A change is signaled when any of the
+/// files is created, modified, or removed. The files are watched by a single watcher for the
+/// lifetime of the source and notifications always occur on another thread.
+pub struct FileChangeTokenSource<T: Value> {
+    name: Option<String>,
+    watch: Arc<FileWatch>,
+    _data: PhantomData<T>,
+}
+
+impl<T: Value> FileChangeTokenSource<T> {
+    /// Initializes a new file change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being watched
+    /// * `paths` - The paths of the files to watch
+    ///
+    /// # Panics
+    ///
+    /// Panics if the files cannot be watched.
+    pub fn new<I, P>(name: Option<&str>, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        Self::with_watch(name, Arc::new(FileWatch::new(paths)))
+    }
+
+    pub(crate) fn with_watch(name: Option<&str>, watch: Arc<FileWatch>) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            watch,
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for FileChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        self.watch.token()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("file")
+    }
+}
+
+/// Represents a change token source for [`Options`](crate::Options) that are notified when a
+/// mounted Kubernetes ConfigMap or Secret changes.
+///
+/// # Remarks
+///
+/// Kubernetes updates a mounted volume by writing the new content to a new folder and atomically
+/// replacing the `..data` symbolic link that each file links through. Neither the files nor their
+/// links are modified, so the mounted folders are watched instead and a change is signaled when
+/// anything in them changes. Notifications always occur on another thread.
+#[cfg(feature = "k8s")]
+#[cfg_attr(docsrs, doc(cfg(feature = "k8s")))]
+pub struct MountChangeTokenSource<T: Value> {
+    name: Option<String>,
+    watch: Arc<FileWatch>,
+    _data: PhantomData<T>,
+}
+
+#[cfg(feature = "k8s")]
+impl<T: Value> MountChangeTokenSource<T> {
+    /// Initializes a new mount change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being watched
+    /// * `paths` - The paths of the mounted folders to watch
+    ///
+    /// # Panics
+    ///
+    /// Panics if the folders cannot be watched.
+    pub fn new<I, P>(name: Option<&str>, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        Self::with_watch(name, Arc::new(FileWatch::folders(paths)))
+    }
+
+    pub(crate) fn with_watch(name: Option<&str>, watch: Arc<FileWatch>) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            watch,
+            _data: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "k8s")]
+impl<T: Value> OptionsChangeTokenSource<T> for MountChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        self.watch.token()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("mount")
+    }
+}
+
+/// Represents a change token source for [`Options`](crate::Options) that are notified when the
+/// configuration fragment for their name changes in a watched directory.
+///
+/// # Remarks
+///
+/// A file in the directory is the fragment of the options whose name matches the file name without
+/// its extension, such as `eu.json` for the options named `eu`. A change is only signaled for the
+/// name of the file that is created, modified, or removed; the other named options are unaffected.
+/// Unnamed options are the union of every fragment, so a change to any file in the directory is
+/// signaled for them. Notifications always occur on another thread.
+pub struct DirectoryChangeTokenSource<T: Value> {
+    name: Option<String>,
+    watch: Arc<DirectoryWatch>,
+    _data: PhantomData<T>,
+}
+
+impl<T: Value> DirectoryChangeTokenSource<T> {
+    /// Initializes a new directory change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being watched
+    /// * `path` - The path of the directory to watch
+    ///
+    /// # Remarks
+    ///
+    /// File names are compared to the name of the options ignoring case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be watched.
+    pub fn new<P: AsRef<Path>>(name: Option<&str>, path: P) -> Self {
+        Self::with_watch(
+            name,
+            Arc::new(DirectoryWatch::new(path, OptionsNameComparer::default())),
+        )
+    }
+
+    pub(crate) fn with_watch(name: Option<&str>, watch: Arc<DirectoryWatch>) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            watch,
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for DirectoryChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        self.watch.token(self.name.as_deref())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("directory")
+    }
+}
+
+pub(crate) struct FileWatch {
+    changes: Arc<Changes>,
+    _watcher: Mutex<RecommendedWatcher>,
+}
+
+impl FileWatch {
+    pub(crate) fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let files: HashSet<_> = paths.into_iter().map(|p| absolute(p.as_ref())).collect();
+        let folders: HashSet<_> = files
+            .iter()
+            .filter_map(|f| f.parent())
+            .map(Path::to_path_buf)
+            .collect();
+
+        Self::watch(files, HashSet::new(), folders)
+    }
+
+    #[cfg(feature = "k8s")]
+    pub(crate) fn folders<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let folders: HashSet<_> = paths.into_iter().map(|p| absolute(p.as_ref())).collect();
+        Self::watch(HashSet::new(), folders.clone(), folders)
+    }
+
+    fn watch(
+        files: HashSet<PathBuf>,
+        folders: HashSet<PathBuf>,
+        watched: HashSet<PathBuf>,
+    ) -> Self {
+        let changes = Arc::new(Changes {
+            files,
+            folders,
+            token: Default::default(),
+        });
+        let handler = Arc::downgrade(&changes);
+        let mut watcher = RecommendedWatcher::new(
+            move |event: notify::Result<Event>| Changes::on_event(&handler, event),
+            Config::default(),
+        )
+        .unwrap_or_else(|e| panic!("{}", e));
+
+        // watch the folders rather than the files so that files which do
+        // not exist yet or are replaced rather than modified are observed
+        for folder in watched.iter().filter(|f| f.exists()) {
+            watcher
+                .watch(folder, NonRecursive)
+                .unwrap_or_else(|e| panic!("{}", e));
+        }
+
+        Self {
+            changes,
+            _watcher: Mutex::new(watcher),
+        }
+    }
+
+    pub(crate) fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(self.changes.token.lock().unwrap().clone())
+    }
+}
+
+struct Changes {
+    files: HashSet<PathBuf>,
+    folders: HashSet<PathBuf>,
+    token: Mutex<SharedChangeToken<SingleChangeToken>>,
+}
+
+impl Changes {
+    fn on_event(changes: &Weak<Self>, event: notify::Result<Event>) {
+        let changes = match (changes.upgrade(), event) {
+            (Some(changes), Ok(event)) if changes.matches(&event) => changes,
+            _ => return,
+        };
+
+        // a token only signals once so it's replaced before it's signaled
+        let token = take(&mut *changes.token.lock().unwrap());
+        token.notify();
+    }
+
+    fn matches(&self, event: &Event) -> bool {
+        let kind = event.kind;
+        let changed = kind.is_modify() || kind.is_create() || kind.is_remove();
+
+        (changed || event.need_rescan())
+            && (event.paths.is_empty() || event.paths.iter().any(|p| self.contains(p)))
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.files.contains(path)
+            || path
+                .parent()
+                .map(|folder| self.folders.contains(folder))
+                .unwrap_or_default()
+    }
+}
+
+pub(crate) struct DirectoryWatch {
+    fragments: Arc<Fragments>,
+    _watcher: Mutex<RecommendedWatcher>,
+}
+
+impl DirectoryWatch {
+    pub(crate) fn new<P: AsRef<Path>>(path: P, comparer: OptionsNameComparer) -> Self {
+        let folder = absolute(path.as_ref());
+        let fragments = Arc::new(Fragments {
+            folder: folder.clone(),
+            comparer,
+            tokens: Default::default(),
+        });
+        let handler = Arc::downgrade(&fragments);
+        let mut watcher = RecommendedWatcher::new(
+            move |event: notify::Result<Event>| Fragments::on_event(&handler, event),
+            Config::default(),
+        )
+        .unwrap_or_else(|e| panic!("{}", e));
+
+        if folder.exists() {
+            watcher
+                .watch(&folder, NonRecursive)
+                .unwrap_or_else(|e| panic!("{}", e));
+        }
+
+        Self {
+            fragments,
+            _watcher: Mutex::new(watcher),
+        }
+    }
+
+    pub(crate) fn token(&self, name: Option<&str>) -> Box<dyn ChangeToken> {
+        let mut tokens = self.fragments.tokens.lock().unwrap();
+        let index = match tokens.iter().position(|(n, _)| n.as_deref() == name) {
+            Some(index) => index,
+            _ => {
+                tokens.push((name.map(|s| s.to_owned()), Default::default()));
+                tokens.len() - 1
+            }
+        };
+
+        Box::new(tokens[index].1.clone())
+    }
+}
+
+type FragmentToken = (Option<String>, SharedChangeToken<SingleChangeToken>);
+
+struct Fragments {
+    folder: PathBuf,
+    comparer: OptionsNameComparer,
+    tokens: Mutex<Vec<FragmentToken>>,
+}
+
+impl Fragments {
+    fn on_event(fragments: &Weak<Self>, event: notify::Result<Event>) {
+        let (fragments, event) = match (fragments.upgrade(), event) {
+            (Some(fragments), Ok(event)) => (fragments, event),
+            _ => return,
+        };
+        let kind = event.kind;
+
+        if !(kind.is_modify() || kind.is_create() || kind.is_remove() || event.need_rescan()) {
+            return;
+        }
+
+        let everything = event.paths.is_empty() || event.need_rescan();
+        let names: Vec<_> = event
+            .paths
+            .iter()
+            .filter(|p| p.parent() == Some(fragments.folder.as_path()))
+            .filter_map(|p| p.file_stem())
+            .map(|n| n.to_string_lossy().into_owned())
+            .collect();
+
+        if !everything && names.is_empty() {
+            return;
+        }
+
+        // tokens only signal once so they're replaced before they're signaled
+        let changed: Vec<_> = fragments
+            .tokens
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|(name, _)| match name {
+                Some(name) => {
+                    everything || names.iter().any(|n| fragments.comparer.equals(n, name))
+                }
+                _ => true,
+            })
+            .map(|(_, token)| take(token))
+            .collect();
+
+        for token in changed {
+            token.notify();
+        }
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor, Ref};
+    use di::ServiceCollection;
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, read_to_string, remove_dir_all, write};
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[derive(Default)]
+    struct Certificate {
+        pem: String,
+    }
+
+    #[test]
+    fn token_should_change_when_watched_file_changes() {
+        // arrange
+        let folder = temp_dir().join("options_file_watch_1");
+        let path = folder.join("tls.pem");
+
+        create_dir_all(&folder).unwrap();
+
+        let source = FileChangeTokenSource::<Certificate>::new(None, [&path]);
+        let token = source.token();
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let _registration = token.register(
+            Box::new(move |_| sender.lock().unwrap().send(()).unwrap()),
+            None,
+        );
+
+        // act
+        write(folder.join("other.pem"), "other").unwrap();
+        write(&path, "certificate").unwrap();
+
+        let changed = receiver.recv_timeout(Duration::from_secs(5)).is_ok();
+
+        // assert
+        remove_dir_all(&folder).ok();
+
+        assert!(changed);
+        assert!(token.changed());
+        assert!(!source.token().changed());
+    }
+
+    #[test]
+    fn watch_files_should_reload_options_when_file_changes() {
+        // arrange
+        let folder = temp_dir().join("options_file_watch_2");
+        let path = folder.join("tls.pem");
+
+        create_dir_all(&folder).unwrap();
+        write(&path, "original").unwrap();
+
+        let file = path.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<Certificate>()
+            .configure(move |o| o.pem = read_to_string(&file).unwrap_or_default())
+            .watch_files([&path])
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Certificate>>();
+        let original = monitor.current_value();
+        let started = Instant::now();
+
+        // act
+        write(&path, "renewed").unwrap();
+
+        while monitor.current_value().pem != "renewed" && started.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let current = monitor.current_value();
+
+        // assert
+        remove_dir_all(&folder).ok();
+
+        assert_eq!(&original.pem, "original");
+        assert_eq!(&current.pem, "renewed");
+    }
+
+    #[test]
+    fn directory_token_should_only_change_for_matching_fragment() {
+        // arrange
+        let folder = temp_dir().join("options_file_watch_4");
+
+        remove_dir_all(&folder).ok();
+        create_dir_all(&folder).unwrap();
+
+        let watch = Arc::new(DirectoryWatch::new(&folder, OptionsNameComparer::default()));
+        let eu = DirectoryChangeTokenSource::<Certificate>::with_watch(Some("EU"), watch.clone());
+        let us = DirectoryChangeTokenSource::<Certificate>::with_watch(Some("US"), watch.clone());
+        let all = DirectoryChangeTokenSource::<Certificate>::with_watch(None, watch);
+        let (eu_token, us_token, all_token) = (eu.token(), us.token(), all.token());
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let _registration = eu_token.register(
+            Box::new(move |_| sender.lock().unwrap().send(()).unwrap()),
+            None,
+        );
+
+        // act
+        write(folder.join("eu.json"), "{}").unwrap();
+
+        let changed = receiver.recv_timeout(Duration::from_secs(5)).is_ok();
+
+        // assert
+        remove_dir_all(&folder).ok();
+
+        assert!(changed);
+        assert!(eu_token.changed());
+        assert!(all_token.changed());
+        assert!(!us_token.changed());
+    }
+
+    #[test]
+    fn watch_directory_should_reload_options_with_matching_name() {
+        // arrange
+        let folder = temp_dir().join("options_file_watch_5");
+
+        remove_dir_all(&folder).ok();
+        create_dir_all(&folder).unwrap();
+
+        let fragments = folder.clone();
+        let provider = ServiceCollection::new()
+            .add_named_options_many::<Certificate>(["eu", "us"])
+            .configure(move |o| {
+                o.pem = read_to_string(fragments.join("eu.pem")).unwrap_or_default()
+            })
+            .watch_directory(&folder)
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Certificate>>();
+        let eu = monitor.get(Some("eu"));
+        let us = monitor.get(Some("us"));
+        let started = Instant::now();
+
+        // act
+        write(folder.join("eu.pem"), "renewed").unwrap();
+
+        while monitor.get(Some("eu")).pem != "renewed" && started.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let (current_eu, current_us) = (monitor.get(Some("eu")), monitor.get(Some("us")));
+
+        // assert
+        remove_dir_all(&folder).ok();
+
+        assert_eq!(&eu.pem, "");
+        assert_eq!(&current_eu.pem, "renewed");
+        assert!(Ref::ptr_eq(&us, &current_us));
+    }
+
+    #[cfg(all(unix, feature = "k8s"))]
+    #[test]
+    fn watch_mounts_should_reload_options_when_mounted_data_is_swapped() {
+        // arrange
+        use std::fs::rename;
+        use std::os::unix::fs::symlink;
+
+        let folder = temp_dir().join("options_file_watch_3");
+        let path = folder.join("tls.pem");
+
+        remove_dir_all(&folder).ok();
+        create_dir_all(folder.join("..2024_01")).unwrap();
+        write(folder.join("..2024_01").join("tls.pem"), "original").unwrap();
+        symlink("..2024_01", folder.join("..data")).unwrap();
+        symlink("..data/tls.pem", &path).unwrap();
+
+        let file = path.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<Certificate>()
+            .configure(move |o| o.pem = read_to_string(&file).unwrap_or_default())
+            .watch_mounts([&folder])
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Certificate>>();
+        let original = monitor.current_value();
+        let started = Instant::now();
+
+        // act
+        create_dir_all(folder.join("..2024_02")).unwrap();
+        write(folder.join("..2024_02").join("tls.pem"), "renewed").unwrap();
+        symlink("..2024_02", folder.join("..data_tmp")).unwrap();
+        rename(folder.join("..data_tmp"), folder.join("..data")).unwrap();
+
+        while monitor.current_value().pem != "renewed" && started.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let current = monitor.current_value();
+
+        // assert
+        remove_dir_all(&folder).ok();
+
+        assert_eq!(&original.pem, "original");
+        assert_eq!(&current.pem, "renewed");
+    }
+}
@@ -0,0 +1,182 @@
+use crate::monitor::{alive, subscribe, Callback, EventCallback, Generations};
+use crate::sync::RwLock;
+use crate::{ChangeEvent, OptionsMonitor, OptionsSnapshot, Ref, Subscription, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Represents [`Options`](crate::Options) with fixed values for a set of names.
+///
+/// # Remarks
+///
+/// Fixed options are primarily intended for testing services that depend on an
+/// [`OptionsSnapshot`](crate::OptionsSnapshot) or [`OptionsMonitor`](crate::OptionsMonitor)
+/// without building a service provider. The default, unnamed options use an empty name.
+/// Requesting options with a name that has no value panics.
+pub struct FixedOptions<T: Value> {
+    values: Mutex<HashMap<String, Ref<T>>>,
+    listeners: RwLock<Vec<Weak<Callback<T>>>>,
+    events: RwLock<Vec<Weak<EventCallback<T>>>>,
+    generations: Generations,
+}
+
+impl<T: Value> FixedOptions<T> {
+    fn new(values: HashMap<String, Ref<T>>) -> Self {
+        Self {
+            values: Mutex::new(values),
+            listeners: Default::default(),
+            events: Default::default(),
+            generations: Default::default(),
+        }
+    }
+
+    /// Changes the value of the options with the specified name and notifies listeners.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to change
+    /// * `value` - The new value of the options
+    pub fn trigger_change(&self, name: Option<&str>, value: T) {
+        let value = Ref::new(value);
+        let previous = self
+            .values
+            .lock()
+            .unwrap()
+            .insert(name.unwrap_or_default().to_owned(), value.clone());
+        let generation = self.generations.next(name);
+        let callbacks = alive(&self.listeners);
+        let events = alive(&self.events);
+
+        for callback in callbacks {
+            callback(name, value.clone());
+        }
+
+        if events.is_empty() {
+            return;
+        }
+
+        let mut event = ChangeEvent::new(name.map(|n| n.to_owned()), value)
+            .with_source("trigger")
+            .with_generation(generation);
+
+        if let Some(previous) = previous {
+            event = event.with_previous(previous);
+        }
+
+        for callback in events {
+            callback(&event);
+        }
+    }
+}
+
+impl<T: Value> OptionsSnapshot<T> for FixedOptions<T> {
+    fn get(&self, name: Option<&str>) -> Ref<T> {
+        let name = name.unwrap_or_default();
+
+        match self.values.lock().unwrap().get(name) {
+            Some(value) => value.clone(),
+            _ => panic!("No options are configured with the name '{}'.", name),
+        }
+    }
+}
+
+impl<T: Value> OptionsMonitor<T> for FixedOptions<T> {
+    fn get(&self, name: Option<&str>) -> Ref<T> {
+        OptionsSnapshot::get(self, name)
+    }
+
+    fn on_change(&self, listener: Box<Callback<T>>) -> Subscription<T> {
+        let mut listeners = self.listeners.write().unwrap();
+
+        listeners.retain(|l| l.strong_count() > 0);
+
+        let listener: Arc<Callback<T>> = Arc::from(listener);
+
+        listeners.push(Arc::downgrade(&listener));
+        Subscription::new(listener)
+    }
+
+    fn on_change_event(&self, listener: Box<EventCallback<T>>) -> Subscription<T> {
+        subscribe(&self.events, listener)
+    }
+
+    fn current_generation(&self, name: Option<&str>) -> u64 {
+        self.generations.get(name)
+    }
+}
+
+/// Creates [fixed options](FixedOptions) with a value for a single name.
+///
+/// # Arguments
+///
+/// * `name` - The name of the options, which is empty for the default options
+/// * `value` - The value of the options
+pub fn create_named<T: Value>(name: &str, value: T) -> FixedOptions<T> {
+    create_map([(name, value)])
+}
+
+/// Creates [fixed options](FixedOptions) with a value for each name.
+///
+/// # Arguments
+///
+/// * `values` - The names and values of the options; an empty name is used for the default options
+pub fn create_map<T, N, I>(values: I) -> FixedOptions<T>
+where
+    T: Value,
+    N: AsRef<str>,
+    I: IntoIterator<Item = (N, T)>,
+{
+    FixedOptions::new(
+        values
+            .into_iter()
+            .map(|(name, value)| (name.as_ref().to_owned(), Ref::new(value)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    struct TestOptions {
+        retries: u8,
+    }
+
+    #[test]
+    fn create_map_should_serve_values_by_name() {
+        // arrange
+        let options = create_map([
+            ("", TestOptions { retries: 1 }),
+            ("Fast", TestOptions { retries: 0 }),
+        ]);
+
+        // act
+        let default = OptionsSnapshot::get(&options, None);
+        let fast = OptionsSnapshot::get(&options, Some("Fast"));
+
+        // assert
+        assert_eq!(default.retries, 1);
+        assert_eq!(fast.retries, 0);
+    }
+
+    #[test]
+    fn trigger_change_should_notify_listeners() {
+        // arrange
+        let options = create_named("Http", TestOptions { retries: 1 });
+        let previous = Arc::new(AtomicU64::default());
+        let observed = previous.clone();
+        let _subscription = options.on_change_event(Box::new(move |event| {
+            let retries = event.previous().unwrap().retries;
+            observed.store(retries as u64, Ordering::SeqCst);
+        }));
+
+        // act
+        options.trigger_change(Some("Http"), TestOptions { retries: 5 });
+
+        // assert
+        assert_eq!(OptionsMonitor::get(&options, Some("Http")).retries, 5);
+        assert_eq!(options.current_generation(Some("Http")), 1);
+        assert_eq!(previous.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::ValidateOptionsResult;
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+type Key = (TypeId, Option<String>);
+
+/// Represents the health of a component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HealthStatus {
+    /// Indicates the component is healthy.
+    Healthy,
+
+    /// Indicates the component is functioning, but not as expected.
+    Degraded,
+
+    /// Indicates the component is not functioning.
+    Unhealthy,
+}
+
+impl Default for HealthStatus {
+    fn default() -> Self {
+        Self::Healthy
+    }
+}
+
+/// Represents the health of a single instance of configuration [`Options`](crate::Options).
+#[derive(Clone, Debug)]
+pub struct HealthEntry {
+    type_name: &'static str,
+    name: Option<String>,
+    status: HealthStatus,
+    failures: Vec<String>,
+    timestamp: SystemTime,
+}
+
+impl HealthEntry {
+    /// Gets the name of the type of options.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Gets the name of the options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the health of the options.
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    /// Gets the failures of the most recent rebuild of the options.
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+
+    /// Gets the time at which the options failed to rebuild.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+/// Represents the result of a [health check](HealthCheck).
+#[derive(Clone, Debug, Default)]
+pub struct HealthReport {
+    status: HealthStatus,
+    entries: Vec<HealthEntry>,
+}
+
+impl HealthReport {
+    /// Gets the overall health, which is the worst health of any entry.
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    /// Gets a value indicating whether the report is healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.status == HealthStatus::Healthy
+    }
+
+    /// Gets the entries that are not healthy.
+    pub fn entries(&self) -> &[HealthEntry] {
+        &self.entries
+    }
+}
+
+/// Defines the behavior of a health check.
+///
+/// # Remarks
+///
+/// The trait is intentionally small so that it can be adapted to a health checking framework or
+/// exposed by an endpoint with only a few lines.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait HealthCheck {
+    /// Checks the current health.
+    fn check(&self) -> HealthReport;
+}
+
+/// Represents a [health check](HealthCheck) for the validation of configuration
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Every [`DefaultOptionsMonitor`](crate::DefaultOptionsMonitor) that reports to the health check
+/// records the result of the most recent rebuild of its options. The health check is
+/// [degraded](HealthStatus::Degraded) when options failed to rebuild, but the last valid options
+/// are still used, and [unhealthy](HealthStatus::Unhealthy) when options failed to rebuild and no
+/// valid options remain. Options become healthy again when they are successfully rebuilt.
+#[derive(Clone, Default)]
+pub struct OptionsHealthCheck {
+    entries: Arc<Mutex<HashMap<Key, HealthEntry>>>,
+}
+
+impl OptionsHealthCheck {
+    /// Initializes a new health check for options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn succeeded<T: 'static>(&self, name: Option<&str>) {
+        self.entries
+            .lock()
+            .unwrap()
+            .remove(&(TypeId::of::<T>(), name.map(|n| n.to_owned())));
+    }
+
+    pub(crate) fn failed<T: 'static>(
+        &self,
+        name: Option<&str>,
+        error: &ValidateOptionsResult,
+        kept: bool,
+    ) {
+        let name = name.map(|n| n.to_owned());
+        let entry = HealthEntry {
+            type_name: type_name::<T>(),
+            name: name.clone(),
+            status: if kept {
+                HealthStatus::Degraded
+            } else {
+                HealthStatus::Unhealthy
+            },
+            failures: error.failures().to_vec(),
+            timestamp: SystemTime::now(),
+        };
+
+        self.entries
+            .lock()
+            .unwrap()
+            .insert((TypeId::of::<T>(), name), entry);
+    }
+}
+
+impl HealthCheck for OptionsHealthCheck {
+    fn check(&self) -> HealthReport {
+        let mut entries: Vec<_> = self.entries.lock().unwrap().values().cloned().collect();
+
+        entries.sort_by(|a, b| (a.type_name, &a.name).cmp(&(b.type_name, &b.name)));
+
+        HealthReport {
+            status: entries.iter().map(|e| e.status).max().unwrap_or_default(),
+            entries,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor, RetryPolicy};
+    use di::ServiceCollection;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct PoolOptions {
+        size: u8,
+    }
+
+    #[test]
+    fn check_should_report_options_that_failed_to_rebuild() {
+        // arrange
+        let size = Arc::new(AtomicU8::new(1));
+        let value = size.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<PoolOptions>()
+            .configure(move |o| o.size = value.load(Ordering::SeqCst))
+            .validate(|o| o.size > 0, "The size must be positive.")
+            .retry_on_failure(RetryPolicy::new(0, Duration::ZERO), |_, _| {})
+            .build_provider()
+            .unwrap();
+        let health = provider.get_required::<OptionsHealthCheck>();
+        let monitor = provider.get_required::<dyn OptionsMonitor<PoolOptions>>();
+
+        // act
+        size.store(0, Ordering::SeqCst);
+        monitor.reload(None);
+
+        let unhealthy = health.check();
+
+        size.store(2, Ordering::SeqCst);
+        monitor.reload(None);
+
+        let healthy = health.check();
+
+        // assert
+        assert_eq!(unhealthy.status(), HealthStatus::Unhealthy);
+        assert!(unhealthy.entries()[0].type_name().ends_with("PoolOptions"));
+        assert_eq!(
+            unhealthy.entries()[0].failures(),
+            ["The size must be positive."]
+        );
+        assert!(healthy.is_healthy());
+        assert!(healthy.entries().is_empty());
+    }
+
+    #[test]
+    fn check_should_be_degraded_when_last_valid_options_are_kept() {
+        // arrange
+        let size = Arc::new(AtomicU8::new(1));
+        let value = size.clone();
+        let provider = ServiceCollection::new()
+            .add_named_options::<PoolOptions>("Primary")
+            .configure(move |o| o.size = value.load(Ordering::SeqCst))
+            .validate(|o| o.size > 0, "The size must be positive.")
+            .retry_on_failure(RetryPolicy::new(0, Duration::ZERO), |_, _| {})
+            .build_provider()
+            .unwrap();
+        let health = provider.get_required::<OptionsHealthCheck>();
+        let monitor = provider.get_required::<dyn OptionsMonitor<PoolOptions>>();
+        let _ = monitor.get(Some("Primary"));
+
+        // act
+        size.store(0, Ordering::SeqCst);
+        monitor.reload(Some("Primary"));
+
+        // assert
+        let report = health.check();
+        assert_eq!(report.status(), HealthStatus::Degraded);
+        assert_eq!(report.entries()[0].name(), Some("Primary"));
+        assert_eq!(monitor.get(Some("Primary")).size, 1);
+    }
+}
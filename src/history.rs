@@ -0,0 +1,379 @@
+use crate::{
+    OptionsCache, OptionsMonitorCache, OptionsNameComparer, Ref, ValidateOptionsResult, Value,
+};
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::mem::take;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tokens::{SharedChangeToken, SingleChangeToken};
+
+#[cfg(feature = "di")]
+use crate::OptionsChangeTokenSource;
+
+#[cfg(feature = "di")]
+use tokens::ChangeToken;
+
+/// Represents a version of configuration [`Options`](crate::Options) retained by an
+/// [`OptionsHistory`].
+pub struct OptionsVersion<T> {
+    version: u64,
+    timestamp: SystemTime,
+    value: Ref<T>,
+}
+
+impl<T> OptionsVersion<T> {
+    /// Gets the version number, which increases each time the options are created.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Gets the time the options were created.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Gets the options.
+    pub fn value(&self) -> Ref<T> {
+        self.value.clone()
+    }
+}
+
+impl<T> Clone for OptionsVersion<T> {
+    fn clone(&self) -> Self {
+        Self {
+            version: self.version,
+            timestamp: self.timestamp,
+            value: self.value.clone(),
+        }
+    }
+}
+
+struct Versions<T> {
+    last: u64,
+    retained: VecDeque<OptionsVersion<T>>,
+}
+
+/// Represents the history of the configuration [`Options`](crate::Options) held by an
+/// [`OptionsMonitor`](crate::OptionsMonitor).
+///
+/// # Remarks
+///
+/// The history is registered using [`keep_history`](crate::OptionsBuilder::keep_history) and
+/// serves as the [`OptionsMonitorCache`](crate::OptionsMonitorCache) for the options, which retains
+/// the most recently created instances of each name so that a bad configuration change can be
+/// quickly undone.
+pub struct OptionsHistory<T> {
+    capacity: usize,
+    comparer: OptionsNameComparer,
+    cache: OptionsCache<T>,
+    versions: Mutex<HashMap<String, Versions<T>>>,
+    seeds: Mutex<HashMap<String, Ref<T>>>,
+    tokens: Mutex<HashMap<String, SharedChangeToken<SingleChangeToken>>>,
+}
+
+impl<T> OptionsHistory<T> {
+    /// Initializes a new options history.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of versions retained for each name
+    pub fn new(capacity: usize) -> Self {
+        Self::with_comparer(capacity, OptionsNameComparer::Exact)
+    }
+
+    /// Initializes a new options history with a name comparer.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of versions retained for each name
+    /// * `comparer` - The [comparer](crate::OptionsNameComparer) used to normalize names
+    pub fn with_comparer(capacity: usize, comparer: OptionsNameComparer) -> Self {
+        Self {
+            capacity,
+            comparer,
+            cache: OptionsCache::with_comparer(comparer),
+            versions: Default::default(),
+            seeds: Default::default(),
+            tokens: Default::default(),
+        }
+    }
+
+    /// Gets the retained versions of the options with the specified name from oldest to newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    pub fn versions(&self, name: Option<&str>) -> Vec<OptionsVersion<T>> {
+        self.versions
+            .lock()
+            .unwrap()
+            .get(self.key(name).as_str())
+            .map(|v| v.retained.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Rolls back the options with the specified name to a retained version.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    /// * `version` - The number of the [version](OptionsVersion) to roll back to
+    ///
+    /// # Remarks
+    ///
+    /// The version replaces the cached options and listeners of the
+    /// [`OptionsMonitor`](crate::OptionsMonitor) are notified as if the options changed. Rolling
+    /// back does not add a version and the options are created from their sources again the next
+    /// time the sources change. The return value indicates whether the version is retained.
+    pub fn rollback(&self, name: Option<&str>, version: u64) -> bool {
+        let key = self.key(name);
+        let value = match self.versions.lock().unwrap().get(&key).and_then(|v| {
+            v.retained
+                .iter()
+                .find(|v| v.version == version)
+                .map(|v| v.value.clone())
+        }) {
+            Some(value) => value,
+            None => return false,
+        };
+
+        self.seeds.lock().unwrap().insert(key.clone(), value);
+
+        // a token only signals once so it's replaced before it's signaled
+        let token = self.tokens.lock().unwrap().get_mut(&key).map(take);
+
+        if let Some(token) = token {
+            token.notify();
+        }
+
+        // seed the cache directly if nothing is monitoring the options
+        if let Some(value) = self.seeds.lock().unwrap().remove(&key) {
+            self.cache.replace(name, value);
+        }
+
+        true
+    }
+
+    #[cfg(feature = "di")]
+    pub(crate) fn token(&self, name: Option<&str>) -> Box<dyn ChangeToken> {
+        Box::new(
+            self.tokens
+                .lock()
+                .unwrap()
+                .entry(self.key(name))
+                .or_default()
+                .clone(),
+        )
+    }
+
+    fn key(&self, name: Option<&str>) -> String {
+        self.comparer
+            .normalize(name.unwrap_or_default())
+            .into_owned()
+    }
+
+    fn record(&self, name: Option<&str>, value: Ref<T>) {
+        let mut versions = self.versions.lock().unwrap();
+        let versions = versions.entry(self.key(name)).or_insert_with(|| Versions {
+            last: 0,
+            retained: VecDeque::new(),
+        });
+
+        versions.last += 1;
+        versions.retained.push_back(OptionsVersion {
+            version: versions.last,
+            timestamp: SystemTime::now(),
+            value,
+        });
+
+        while versions.retained.len() > self.capacity {
+            versions.retained.pop_front();
+        }
+    }
+}
+
+impl<T: Value> OptionsMonitorCache<T> for OptionsHistory<T> {
+    fn get_or_add(&self, name: Option<&str>, create_options: &dyn Fn(Option<&str>) -> T) -> Ref<T> {
+        let created = Cell::new(false);
+        let options = self.cache.get_or_add(name, &|n| {
+            created.set(true);
+            create_options(n)
+        });
+
+        if created.get() {
+            self.record(name, options.clone());
+        }
+
+        options
+    }
+
+    fn try_get_or_add(
+        &self,
+        name: Option<&str>,
+        create_options: &dyn Fn(Option<&str>) -> Result<T, ValidateOptionsResult>,
+    ) -> Result<Ref<T>, ValidateOptionsResult> {
+        let created = Cell::new(false);
+        let options = self.cache.try_get_or_add(name, &|n| {
+            created.set(true);
+            create_options(n)
+        })?;
+
+        if created.get() {
+            self.record(name, options.clone());
+        }
+
+        Ok(options)
+    }
+
+    fn try_add(&self, name: Option<&str>, options: T) -> bool {
+        let added = self.cache.try_add(name, options);
+
+        if added {
+            if let Some(options) = self.cache.get(name) {
+                self.record(name, options);
+            }
+        }
+
+        added
+    }
+
+    fn try_get(&self, name: Option<&str>) -> Option<Ref<T>> {
+        self.cache.get(name)
+    }
+
+    fn try_remove(&self, name: Option<&str>) -> bool {
+        let removed = self.cache.try_remove(name);
+
+        // a pending rollback replaces the options rather than removing them
+        if let Some(value) = self.seeds.lock().unwrap().remove(&self.key(name)) {
+            self.cache.replace(name, value);
+        }
+
+        removed
+    }
+
+    fn try_replace(&self, name: Option<&str>, options: T) -> bool {
+        // a pending rollback replaces the options rather than the rebuilt options
+        if let Some(value) = self.seeds.lock().unwrap().remove(&self.key(name)) {
+            self.cache.replace(name, value);
+            return false;
+        }
+
+        let options = Ref::new(options);
+
+        self.cache.replace(name, options.clone());
+        self.record(name, options);
+        true
+    }
+
+    fn clear(&self) {
+        self.cache.clear()
+    }
+}
+
+#[cfg(feature = "di")]
+pub(crate) struct HistoryChangeTokenSource<T> {
+    name: Option<String>,
+    history: Ref<OptionsHistory<T>>,
+}
+
+#[cfg(feature = "di")]
+impl<T> HistoryChangeTokenSource<T> {
+    pub(crate) fn new(name: Option<&str>, history: Ref<OptionsHistory<T>>) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            history,
+        }
+    }
+}
+
+#[cfg(feature = "di")]
+impl<T: Value> OptionsChangeTokenSource<T> for HistoryChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        self.history.token(self.name.as_deref())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("history")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct Config {
+        retries: u8,
+    }
+
+    #[test]
+    fn history_should_retain_most_recent_versions() {
+        // arrange
+        let history = OptionsHistory::<Config>::new(2);
+
+        // act
+        for retries in 1..=3 {
+            history.try_remove(None);
+            history.get_or_add(None, &|_| Config { retries });
+        }
+
+        // assert
+        let versions = history.versions(None);
+
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version(), 2);
+        assert_eq!(versions[0].value().retries, 2);
+        assert_eq!(versions[1].version(), 3);
+        assert!(history.versions(Some("Other")).is_empty());
+    }
+
+    #[test]
+    fn rollback_should_restore_version_and_notify_listeners() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let (value, watched) = (retries.clone(), token.clone());
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .configure(move |o| o.retries = value.load(Ordering::SeqCst))
+            .watch_fn(move || Box::new(watched.clone()))
+            .keep_history(5)
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let history = provider.get_required::<OptionsHistory<Config>>();
+        let notified = Arc::new(AtomicU8::default());
+        let counter = notified.clone();
+        let _subscription = monitor.on_change(Box::new(move |_, o: Ref<Config>| {
+            counter.store(o.retries, Ordering::SeqCst)
+        }));
+        let original = monitor.current_value().retries;
+
+        retries.store(9, Ordering::SeqCst);
+        token.notify();
+
+        let changed = monitor.current_value().retries;
+
+        // act
+        let rolled_back = history.rollback(None, 1);
+
+        // assert
+        assert_eq!((original, changed), (1, 9));
+        assert!(rolled_back);
+        assert!(!history.rollback(None, 99));
+        assert_eq!(monitor.current_value().retries, 1);
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+        assert_eq!(history.versions(None).len(), 2);
+    }
+}
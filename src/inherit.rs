@@ -0,0 +1,203 @@
+use crate::OptionsNameComparer;
+
+/// Represents the inheritance between named configuration [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// A named instance that inherits from another is configured in layers. The layer of the parent is
+/// applied first, followed by the layer of the child. The configurations that apply to every name
+/// are only applied once, as part of the first layer, so that the layer of the parent is not
+/// overwritten by them.
+#[derive(Clone, Debug, Default)]
+pub struct OptionsInheritance {
+    links: Vec<(String, String, OptionsNameComparer)>,
+}
+
+impl OptionsInheritance {
+    /// Initializes a new, empty options inheritance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares that named options inherit from other named options.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the inheriting options
+    /// * `parent` - The name of the options inherited from
+    /// * `comparer` - The [comparer](crate::OptionsNameComparer) used to match the name
+    pub fn inherit(mut self, name: &str, parent: &str, comparer: OptionsNameComparer) -> Self {
+        self.links
+            .push((name.to_owned(), parent.to_owned(), comparer));
+        self
+    }
+
+    /// Gets a value indicating whether there is no inheritance.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Gets the name of the options that named options inherit from, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the options
+    pub fn parent_of(&self, name: &str) -> Option<&str> {
+        self.link_of(name).map(|(_, parent, _)| parent.as_str())
+    }
+
+    /// Gets the layers of the options with the specified name, starting with the most distant
+    /// ancestor and ending with the name itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    ///
+    /// # Remarks
+    ///
+    /// A cycle in the inheritance ends the layers at the first name that would be repeated.
+    pub fn layers<'a>(&'a self, name: Option<&'a str>) -> Vec<Option<&'a str>> {
+        let mut layers = vec![name];
+        let mut current = name;
+
+        while let Some((_, parent, comparer)) = current.and_then(|n| self.link_of(n)) {
+            if layers
+                .iter()
+                .flatten()
+                .any(|layer| comparer.equals(layer, parent))
+            {
+                break;
+            }
+
+            layers.push(Some(parent));
+            current = Some(parent);
+        }
+
+        layers.reverse();
+        layers
+    }
+}
+
+impl OptionsInheritance {
+    fn link_of(&self, name: &str) -> Option<&(String, String, OptionsNameComparer)> {
+        self.links
+            .iter()
+            .find(|(child, _, comparer)| comparer.equals(child, name))
+    }
+}
+
+#[cfg(feature = "di")]
+pub(crate) struct Inherits<T> {
+    name: String,
+    parent: String,
+    comparer: OptionsNameComparer,
+    _marker: std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "di")]
+impl<T: 'static> Inherits<T> {
+    pub(crate) fn new(name: &str, parent: &str, comparer: OptionsNameComparer) -> Self {
+        Self {
+            name: name.to_owned(),
+            parent: parent.to_owned(),
+            comparer,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub(crate) fn resolve(provider: &di::ServiceProvider) -> OptionsInheritance {
+        provider
+            .get_all::<Self>()
+            .fold(OptionsInheritance::new(), |inheritance, link| {
+                inheritance.inherit(&link.name, &link.parent, link.comparer)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(feature = "di")]
+    use crate::{ext::*, OptionsSnapshot};
+
+    #[cfg(feature = "di")]
+    use di::ServiceCollection;
+
+    #[cfg(feature = "di")]
+    #[derive(Default)]
+    struct RegionOptions {
+        color: String,
+        quota: u32,
+        title: String,
+    }
+
+    #[test]
+    fn layers_should_start_with_most_distant_ancestor() {
+        // arrange
+        let comparer = OptionsNameComparer::default();
+        let inheritance = OptionsInheritance::new()
+            .inherit("EU", "Base", comparer)
+            .inherit("DE", "eu", comparer)
+            .inherit("A", "B", comparer)
+            .inherit("B", "A", comparer);
+
+        // act
+        let layers = [
+            inheritance.layers(Some("de")),
+            inheritance.layers(Some("Base")),
+            inheritance.layers(Some("A")),
+            inheritance.layers(None),
+        ];
+
+        // assert
+        assert_eq!(
+            layers,
+            [
+                vec![Some("Base"), Some("eu"), Some("de")],
+                vec![Some("Base")],
+                vec![Some("B"), Some("A")],
+                vec![None],
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "di")]
+    fn get_should_configure_inherited_options_in_layers() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<RegionOptions>()
+            .configure(|o| o.color = "blue".into());
+        services
+            .add_named_options::<RegionOptions>("Base")
+            .configure(|o| {
+                o.color = "red".into();
+                o.quota = 10;
+            });
+        services
+            .add_named_options::<RegionOptions>("EU")
+            .inherits("Base")
+            .configure(|o| o.title = "Europe".into());
+
+        let provider = services.build_provider().unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<RegionOptions>>();
+
+        // act
+        let eu = snapshot.get(Some("EU"));
+        let base = snapshot.get(Some("Base"));
+
+        // assert
+        assert_eq!(
+            (&eu.color[..], eu.quota, &eu.title[..]),
+            ("red", 10, "Europe")
+        );
+        assert_eq!(
+            (&base.color[..], base.quota, &base.title[..]),
+            ("red", 10, "")
+        );
+    }
+}
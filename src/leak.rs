@@ -0,0 +1,145 @@
+use crate::trace;
+use std::panic::Location;
+use std::sync::{Arc, Mutex};
+
+type StrongCount = dyn Fn() -> usize + Send + Sync;
+
+/// Represents the subscriptions registered at a single call site.
+#[derive(Clone, Debug)]
+pub struct SubscriptionSite {
+    location: &'static Location<'static>,
+    live: usize,
+    dead_on_arrival: usize,
+}
+
+impl SubscriptionSite {
+    /// Gets the location of the call that registered the subscriptions.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Gets the number of subscriptions that are still alive.
+    pub fn live(&self) -> usize {
+        self.live
+    }
+
+    /// Gets the number of subscriptions that were dropped before they were ever notified.
+    ///
+    /// # Remarks
+    ///
+    /// A subscription that is dead on arrival is typically the result of discarding the returned
+    /// [`Subscription`](crate::Subscription), such as `let _ = monitor.on_change(..)`, which
+    /// unsubscribes the listener immediately.
+    pub fn dead_on_arrival(&self) -> usize {
+        self.dead_on_arrival
+    }
+}
+
+/// Represents a report of the subscriptions to an [`OptionsMonitor`](crate::OptionsMonitor).
+#[derive(Clone, Debug, Default)]
+pub struct SubscriptionReport {
+    sites: Vec<SubscriptionSite>,
+}
+
+impl SubscriptionReport {
+    /// Gets the call sites of every tracked subscription.
+    pub fn sites(&self) -> &[SubscriptionSite] {
+        &self.sites
+    }
+
+    /// Gets the call sites with subscriptions that were dead on arrival.
+    pub fn dead_on_arrival(&self) -> impl Iterator<Item = &SubscriptionSite> {
+        self.sites.iter().filter(|s| s.dead_on_arrival > 0)
+    }
+
+    /// Gets the call sites with more live subscriptions than the specified threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The maximum number of live subscriptions expected from a single call site
+    ///
+    /// # Remarks
+    ///
+    /// Subscriptions that accumulate without bound from the same call site usually indicate that a
+    /// new subscription is registered each time something happens, such as a request, while the
+    /// previous subscriptions are never dropped.
+    pub fn accumulating(&self, threshold: usize) -> impl Iterator<Item = &SubscriptionSite> {
+        self.sites.iter().filter(move |s| s.live > threshold)
+    }
+}
+
+struct Registration {
+    location: &'static Location<'static>,
+    strong_count: Box<StrongCount>,
+    notified: bool,
+    reported: bool,
+}
+
+// tracks the call site of every subscription so that subscriptions that are dead on arrival or that
+// accumulate without bound can be reported. registrations are only discarded once they have been
+// notified and dropped, which is the expected lifecycle of a subscription.
+#[derive(Default)]
+pub(crate) struct LeakDetector(Mutex<Vec<Registration>>);
+
+impl LeakDetector {
+    pub(crate) fn track<T, C>(&self, location: &'static Location<'static>, listener: &Arc<C>)
+    where
+        C: ?Sized + Send + Sync + 'static,
+    {
+        let listener = Arc::downgrade(listener);
+        let mut registrations = self.0.lock().unwrap();
+
+        registrations.retain(|r| !r.notified || (r.strong_count)() > 0);
+
+        for registration in registrations.iter_mut() {
+            if !registration.reported && (registration.strong_count)() == 0 {
+                registration.reported = true;
+                trace::dead_on_arrival::<T>(registration.location);
+            }
+        }
+
+        registrations.push(Registration {
+            location,
+            strong_count: Box::new(move || listener.strong_count()),
+            notified: false,
+            reported: false,
+        });
+    }
+
+    pub(crate) fn notified(&self) {
+        for registration in self.0.lock().unwrap().iter_mut() {
+            if (registration.strong_count)() > 0 {
+                registration.notified = true;
+            }
+        }
+    }
+
+    pub(crate) fn report(&self) -> SubscriptionReport {
+        let mut sites: Vec<SubscriptionSite> = Vec::new();
+
+        for registration in self.0.lock().unwrap().iter() {
+            let index = match sites
+                .iter()
+                .position(|s| s.location == registration.location)
+            {
+                Some(index) => index,
+                _ => {
+                    sites.push(SubscriptionSite {
+                        location: registration.location,
+                        live: 0,
+                        dead_on_arrival: 0,
+                    });
+                    sites.len() - 1
+                }
+            };
+
+            if (registration.strong_count)() > 0 {
+                sites[index].live += 1;
+            } else if !registration.notified {
+                sites[index].dead_on_arrival += 1;
+            }
+        }
+
+        SubscriptionReport { sites }
+    }
+}
@@ -2,7 +2,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 /// Represents the type alias for an options reference.
-#[cfg(not(all(feature = "di", feature = "async")))]
+#[cfg(not(feature = "async"))]
 pub type Ref<T> = std::rc::Rc<T>;
 
 /// Represents the type alias for an options reference.
@@ -30,15 +30,52 @@ pub trait Value: Sized + Send + Sync {}
 #[cfg(feature = "async")]
 impl<T: Send + Sync> Value for T {}
 
+mod any_monitor;
+mod args;
+mod audit;
 mod cache;
+mod combine;
+mod comparer;
 mod configure;
+mod confined;
+mod describe;
+mod expand;
 mod factory;
+mod fallback;
+mod fixed;
+mod health;
+mod history;
+mod inherit;
 mod manager;
+mod messages;
+mod meter;
 mod monitor;
+mod observer;
 mod option;
+mod otel;
+mod redact;
+mod retry;
+mod secret;
+mod select;
+mod service;
+mod singleton;
 mod snapshot;
+mod sync;
 mod token;
+mod trace;
+mod transaction;
 mod validate;
+mod variant;
+mod watchdog;
+
+#[cfg(feature = "async")]
+mod async_monitor;
+
+#[cfg(feature = "async")]
+mod async_options;
+
+#[cfg(feature = "async")]
+mod dispatch;
 
 #[cfg(feature = "di")]
 mod di_ext;
@@ -46,33 +83,294 @@ mod di_ext;
 #[cfg(feature = "di")]
 mod builder;
 
+#[cfg(feature = "di")]
+mod verify;
+
+#[cfg(feature = "di")]
+mod warmup;
+
+#[cfg(feature = "di")]
+mod tenant;
+
+#[cfg(feature = "cfg")]
+mod bind;
+
 #[cfg(feature = "cfg")]
 mod cfg_ext;
 
+#[cfg(feature = "cfg")]
+mod cli;
+
+#[cfg(feature = "cfg")]
+mod deprecate;
+
+#[cfg(feature = "cfg")]
+mod lint;
+
+#[cfg(feature = "cfg")]
+mod migrate;
+
+#[cfg(feature = "cfg")]
+mod view;
+
+#[cfg(feature = "env")]
+mod env_ext;
+
+#[cfg(feature = "clap")]
+mod clap_ext;
+
+#[cfg(feature = "dump")]
+mod bundle;
+
+#[cfg(feature = "dump")]
+mod catalog;
+
+#[cfg(feature = "diff")]
+mod diff;
+
+#[cfg(feature = "dump")]
+mod dump;
+
+#[cfg(feature = "fs-watch")]
+mod file_watch;
+
+#[cfg(all(unix, feature = "signal"))]
+mod signal;
+
+#[cfg(feature = "http-push")]
+mod push;
+
+#[cfg(feature = "axum")]
+mod extract;
+
+#[cfg(feature = "tokio")]
+mod broadcast;
+
+#[cfg(feature = "bus")]
+mod bus;
+
+#[cfg(feature = "debug")]
+mod leak;
+
+#[cfg(feature = "json-schema")]
+mod schema;
+
+#[cfg(feature = "writable")]
+mod writable;
+
+#[cfg(feature = "discovery")]
+mod discovery;
+
+#[cfg(feature = "di")]
+mod register;
+
+pub use any_monitor::{AnyChangeEvent, AnyOptionsMonitor, AnySubscription};
+pub use args::*;
+pub use audit::{OptionsAuditEntry, OptionsFactoryAudit};
 pub use cache::*;
+pub use combine::{CombinedMonitor, CombinedSubscription};
+pub use comparer::*;
 pub use configure::*;
+pub use describe::*;
+pub use expand::PathExpansion;
 pub use factory::*;
+pub use fallback::OptionsFallback;
+pub use fixed::{create_map, create_named, FixedOptions};
+pub use health::{HealthCheck, HealthEntry, HealthReport, HealthStatus, OptionsHealthCheck};
+pub use history::{OptionsHistory, OptionsVersion};
+pub use inherit::OptionsInheritance;
 pub use manager::*;
+pub use messages::{DefaultOptionsMessages, OptionsMessages};
 pub use monitor::*;
+pub use observer::*;
 pub use option::*;
+pub use redact::*;
+pub use retry::RetryPolicy;
+pub use secret::*;
+pub use select::SelectedOptionsMonitor;
+pub use service::*;
 pub use snapshot::*;
 pub use token::*;
+pub use transaction::ReloadTransaction;
 pub use validate::*;
+pub use variant::*;
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use async_monitor::*;
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use async_options::*;
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use dispatch::ListenerDispatch;
 
 #[cfg(feature = "di")]
 #[cfg_attr(docsrs, doc(cfg(feature = "di")))]
 pub use builder::*;
 
+#[cfg(feature = "di")]
+#[cfg_attr(docsrs, doc(cfg(feature = "di")))]
+pub use verify::*;
+
+#[cfg(feature = "di")]
+#[cfg_attr(docsrs, doc(cfg(feature = "di")))]
+pub use tenant::{TenantContext, TenantOptions};
+
+#[cfg(feature = "di")]
+#[cfg_attr(docsrs, doc(cfg(feature = "di")))]
+pub use register::*;
+
+#[cfg(feature = "discovery")]
+#[cfg_attr(docsrs, doc(cfg(feature = "discovery")))]
+pub use discovery::DiscoveredOptions;
+
+#[cfg(feature = "env")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+pub use env_ext::{refresh_env, EnvironmentChangeTokenSource};
+
+#[cfg(feature = "cfg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+pub use bind::BindOptions;
+
+#[cfg(feature = "cfg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+pub use cli::index_repeated_args;
+
+#[cfg(feature = "cfg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+pub use deprecate::DeprecatedKey;
+
+#[cfg(feature = "cfg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+pub use lint::{lint, LintEntry, LintReport};
+
+#[cfg(feature = "cfg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
+pub use migrate::{ConfigurationMigration, MigrateOptions};
+
+#[cfg(feature = "dump")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+pub use bundle::{export_snapshot, import_snapshot, OptionsBundle, OptionsBundleEntry};
+
+#[cfg(feature = "dump")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+pub use catalog::{options_catalog, OptionsCatalog, OptionsCatalogEntry};
+
+#[cfg(feature = "diff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diff")))]
+pub use diff::{options_diff, FieldChange};
+
+#[cfg(feature = "dump")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dump")))]
+pub use dump::{OptionsDump, OptionsDumpEntry};
+
+#[cfg(feature = "fs-watch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fs-watch")))]
+pub use file_watch::{DirectoryChangeTokenSource, FileChangeTokenSource};
+
+#[cfg(feature = "k8s")]
+#[cfg_attr(docsrs, doc(cfg(feature = "k8s")))]
+pub use file_watch::MountChangeTokenSource;
+
+#[cfg(all(unix, feature = "signal"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "signal"))))]
+pub use signal::{ReloadSignal, SignalChangeTokenSource};
+
+#[cfg(feature = "http-push")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-push")))]
+pub use push::{HttpPushTokenSource, HttpPushTrigger};
+
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub use extract::{OptionsExtractor, OptionsRejection, SnapshotExtractor};
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use broadcast::OptionsMonitorExt;
+
+#[cfg(all(feature = "tokio", feature = "cfg"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "tokio", feature = "cfg"))))]
+pub use broadcast::ConfigurationReloadBroadcast;
+
+#[cfg(feature = "bus")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bus")))]
+pub use bus::{BusChangeTokenSource, BusHandler, BusSubscriber, BusSubscription};
+
+#[cfg(feature = "debug")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debug")))]
+pub use leak::{SubscriptionReport, SubscriptionSite};
+
+#[cfg(feature = "json-schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+pub use schema::JsonSchema;
+
+#[cfg(feature = "writable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "writable")))]
+pub use writable::{JsonFileOptionsStore, OptionsStore, WritableOptions};
+
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+pub use writable::TomlFileOptionsStore;
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use options_macros::{DescribeOptions, Options, RedactedDebug, RedactedSerialize};
+
+#[cfg(feature = "derive")]
+extern crate self as options;
+
+#[cfg(feature = "di")]
+#[doc(hidden)]
+pub mod __private {
+    pub use di::ServiceCollection;
+
+    #[cfg(feature = "discovery")]
+    pub use inventory;
+
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
+/// Contains composable [validation rules](crate::ValidationRule) for the fields of options.
+pub mod rules;
+
+/// Contains ready-made [validation rules](crate::ValidationRule) for common options.
+pub mod validators;
+
+/// Contains utilities for testing options.
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
 /// Contains options extension methods.
 #[cfg(any(feature = "di", feature = "cfg"))]
 pub mod ext {
     use super::*;
-    
+
     #[cfg(feature = "di")]
     #[cfg_attr(docsrs, doc(cfg(feature = "di")))]
     pub use di_ext::*;
 
+    #[cfg(feature = "di")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "di")))]
+    pub use warmup::OptionsServiceProviderExtensions;
+
     #[cfg(feature = "cfg")]
     #[cfg_attr(docsrs, doc(cfg(feature = "cfg")))]
     pub use cfg_ext::*;
+
+    #[cfg(feature = "env")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+    pub use env_ext::OptionsEnvironmentServiceExtensions;
+
+    #[cfg(feature = "clap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+    pub use clap_ext::OptionsCommandLineServiceExtensions;
+
+    #[cfg(feature = "discovery")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "discovery")))]
+    pub use discovery::OptionsDiscoveryServiceExtensions;
 }
@@ -0,0 +1,311 @@
+use crate::{
+    cfg_ext::OverlaySection, cli::CommandLineSection, migrate::OptionsBinder, verify::instance_of,
+    ValidateOptions, ValidateOptionsResult, Value,
+};
+use config::{ext::*, Configuration};
+use di::{singleton_factory, Ref, ServiceCollection, ServiceProvider, Type};
+use serde::de::DeserializeOwned;
+
+/// Represents the result of [linting](lint) configuration [`Options`](crate::Options).
+pub struct LintReport {
+    entries: Vec<LintEntry>,
+}
+
+/// Represents the result of linting a single configuration [`Options`](crate::Options) instance in
+/// a [`LintReport`].
+pub struct LintEntry {
+    options_type: String,
+    name: Option<String>,
+    section: Option<String>,
+    result: ValidateOptionsResult,
+}
+
+impl LintEntry {
+    /// Gets the name of the type of options.
+    pub fn options_type(&self) -> &str {
+        &self.options_type
+    }
+
+    /// Gets the name of the options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the key of the configuration section the options are bound to, if any.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Gets the result of binding and validating the options.
+    pub fn result(&self) -> &ValidateOptionsResult {
+        &self.result
+    }
+
+    fn describe(&self) -> String {
+        match self.name() {
+            Some(name) => format!("'{}' with the name '{}'", self.options_type, name),
+            _ => format!("'{}'", self.options_type),
+        }
+    }
+}
+
+impl LintReport {
+    /// Gets the linted options in the order they were registered.
+    pub fn entries(&self) -> &[LintEntry] {
+        &self.entries
+    }
+
+    /// Gets a value indicating whether all of the options were bound and validated successfully.
+    pub fn succeeded(&self) -> bool {
+        self.entries.iter().all(|e| !e.result.failed())
+    }
+
+    /// Converts the report into a result that contains every failure.
+    pub fn into_result(self) -> Result<(), ValidateOptionsResult> {
+        let failures: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.result.failed())
+            .map(|e| format!("{}: {}", e.describe(), e.result.failure_message()))
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidateOptionsResult::fail_many(failures.iter()))
+        }
+    }
+}
+
+pub(crate) struct OptionsLinter {
+    options_type: Type,
+    name: Option<String>,
+    key: Option<String>,
+    overlay: Option<String>,
+    command_line: bool,
+    lint: fn(&OptionsLinter, &ServiceProvider, &dyn Configuration) -> ValidateOptionsResult,
+}
+
+impl OptionsLinter {
+    pub(crate) fn new<T>(name: Option<&str>, key: Option<&str>) -> Self
+    where
+        T: Value + Default + DeserializeOwned + 'static,
+    {
+        Self {
+            options_type: Type::of::<T>(),
+            name: name.map(|s| s.to_owned()),
+            key: key.map(|s| s.to_owned()),
+            overlay: None,
+            command_line: false,
+            lint: lint_options::<T>,
+        }
+    }
+
+    pub(crate) fn with_overlay(mut self, overlay: &str) -> Self {
+        self.overlay = Some(overlay.to_owned());
+        self
+    }
+
+    pub(crate) fn for_command_line(mut self) -> Self {
+        self.command_line = true;
+        self
+    }
+}
+
+pub(crate) fn remove_linters<T: 'static>(services: &mut ServiceCollection) {
+    let options_type = Type::of::<T>();
+
+    for i in (0..services.len()).rev() {
+        let matched = instance_of::<OptionsLinter>(&services[i])
+            .map(|l| l.options_type == options_type)
+            .unwrap_or_default();
+
+        if matched {
+            services.remove(i);
+        }
+    }
+}
+
+fn lint_options<T>(
+    linter: &OptionsLinter,
+    provider: &ServiceProvider,
+    configuration: &dyn Configuration,
+) -> ValidateOptionsResult
+where
+    T: Value + Default + DeserializeOwned + 'static,
+{
+    let mut options = T::default();
+    let name = linter.name.as_deref();
+    let binder = OptionsBinder::<T>::new(provider);
+    let bound = match &linter.key {
+        Some(key) => {
+            let section = configuration.section(key);
+
+            if !section.exists() {
+                Ok(())
+            } else if let Some(overlay) = &linter.overlay {
+                let overlay = section.section(overlay);
+                let section = OverlaySection::new(section, overlay);
+                binder.bind(&section, name, &mut options)
+            } else if linter.command_line {
+                binder.bind(&CommandLineSection::new(section), name, &mut options)
+            } else {
+                binder.bind(section.deref(), name, &mut options)
+            }
+        }
+        _ => binder.bind(configuration, name, &mut options),
+    };
+
+    if let Err(error) = bound {
+        return ValidateOptionsResult::fail(format!(
+            "the configuration cannot be bound: {}",
+            error
+        ));
+    }
+
+    let failures: Vec<_> = provider
+        .get_all::<dyn ValidateOptions<T>>()
+        .map(|validation| validation.validate(name, &options))
+        .filter(ValidateOptionsResult::failed)
+        .flat_map(|result| result.failures().to_vec())
+        .collect();
+
+    if failures.is_empty() {
+        ValidateOptionsResult::success()
+    } else {
+        ValidateOptionsResult::fail_many(failures.iter())
+    }
+}
+
+/// Binds and validates the configuration [`Options`](crate::Options) registered in a
+/// [collection of services](di::ServiceCollection) against a configuration.
+///
+/// # Arguments
+///
+/// * `configuration` - The [configuration](config::Configuration) to lint
+/// * `registrations` - The [collection of services](di::ServiceCollection) the options are registered in
+///
+/// # Remarks
+///
+/// Every type and name of options bound to configuration is bound to the specified configuration
+/// instead and validated by its registered [validations](crate::ValidateOptions). Nothing is
+/// resolved from the application, which allows a configuration to be rejected before it is
+/// deployed. Only binding and validation are applied; configure and post-configure actions are not.
+pub fn lint(
+    configuration: Ref<dyn Configuration>,
+    registrations: &ServiceCollection,
+) -> LintReport {
+    let linters: Vec<_> = registrations
+        .iter()
+        .filter_map(instance_of::<OptionsLinter>)
+        .collect();
+    let mut services = ServiceCollection::new();
+
+    for descriptor in registrations.iter() {
+        services.add(descriptor.clone());
+    }
+
+    let config = configuration.clone();
+
+    services.replace(singleton_factory(move |_| config.clone()));
+
+    let provider = services.build_provider();
+    let entries = linters
+        .iter()
+        .map(|linter| LintEntry {
+            options_type: linter.options_type.name().to_owned(),
+            name: linter.name.clone(),
+            section: linter.key.clone(),
+            result: match &provider {
+                Ok(provider) => (linter.lint)(linter, provider, configuration.as_ref()),
+                Err(error) => ValidateOptionsResult::fail(error.to_string()),
+            },
+        })
+        .collect();
+
+    LintReport { entries }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use config::{ConfigurationBuilder, DefaultConfigurationBuilder};
+    use serde::Deserialize;
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "PascalCase"))]
+    struct DbOptions {
+        host: String,
+        port: u16,
+    }
+
+    fn new_config(values: &[(&str, &str)]) -> Ref<dyn Configuration> {
+        Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(values)
+                .build()
+                .unwrap()
+                .as_config(),
+        )
+    }
+
+    fn new_services() -> ServiceCollection {
+        let mut services = ServiceCollection::new();
+
+        services
+            .apply_config_at::<DbOptions>(new_config(&[("Db:Host", "localhost")]), "Db")
+            .validate(|o| o.port > 0, "A port is required.");
+        services
+            .add_named_options_from_config::<DbOptions>("Replica", "Replica")
+            .validate(|o| !o.host.is_empty(), "A host is required.");
+
+        services
+    }
+
+    #[test]
+    fn lint_should_succeed_for_valid_configuration() {
+        // arrange
+        let services = new_services();
+        let config = new_config(&[
+            ("Db:Host", "db.contoso.com"),
+            ("Db:Port", "5432"),
+            ("Replica:Host", "replica.contoso.com"),
+            ("Replica:Port", "5432"),
+        ]);
+
+        // act
+        let report = lint(config, &services);
+
+        // assert
+        assert_eq!(report.entries().len(), 2);
+        assert!(report.succeeded());
+    }
+
+    #[test]
+    fn lint_should_report_every_invalid_options() {
+        // arrange
+        let services = new_services();
+        let config = new_config(&[
+            ("Db:Host", "db.contoso.com"),
+            ("Db:Port", "NaN"),
+            ("Replica:Port", "5432"),
+        ]);
+
+        // act
+        let report = lint(config, &services);
+
+        // assert
+        let entries = report.entries();
+
+        assert_eq!(entries[0].section(), Some("Db"));
+        assert!(entries[0]
+            .result()
+            .failure_message()
+            .starts_with("the configuration cannot be bound"));
+        assert_eq!(entries[1].name(), Some("Replica"));
+        assert_eq!(entries[1].result().failure_message(), "A host is required.");
+        assert!(report.into_result().is_err());
+    }
+}
@@ -2,6 +2,9 @@ use crate::{
     Options, OptionsCache, OptionsFactory, OptionsMonitorCache, OptionsSnapshot, Ref, Value,
 };
 
+#[cfg(feature = "di")]
+use crate::{OptionsNameComparer, ValidateOptionsResult};
+
 /// Represents an object that manages [`Options`](crate::Options) and [option snapshots](crate::OptionsSnapshot).
 pub struct OptionsManager<T: Value> {
     factory: Ref<dyn OptionsFactory<T>>,
@@ -20,10 +23,23 @@ impl<T: Value> OptionsManager<T> {
             cache: Default::default(),
         }
     }
-}
 
-unsafe impl<T: Send + Sync> Send for OptionsManager<T> {}
-unsafe impl<T: Send + Sync> Sync for OptionsManager<T> {}
+    #[cfg(feature = "di")]
+    pub(crate) fn with_comparer(
+        factory: Ref<dyn OptionsFactory<T>>,
+        comparer: OptionsNameComparer,
+    ) -> Self {
+        Self {
+            factory,
+            cache: OptionsCache::with_comparer(comparer),
+        }
+    }
+
+    #[cfg(feature = "di")]
+    pub(crate) fn try_get(&self, name: Option<&str>) -> Result<Ref<T>, ValidateOptionsResult> {
+        self.cache.try_get_or_add(name, &|n| self.factory.create(n))
+    }
+}
 
 impl<T: Value> Options<T> for OptionsManager<T> {
     fn value(&self) -> Ref<T> {
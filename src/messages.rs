@@ -0,0 +1,103 @@
+/// Defines the behavior of something that provides the default messages reported for
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Every method has a default English message. An application can localize or standardize the
+/// wording by registering a `dyn OptionsMessages` singleton that overrides the messages it cares
+/// about. Messages that are specified explicitly, such as the failure message of a validation, are
+/// always used as is.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait OptionsMessages {
+    /// Gets the message used when validation fails without a failure message.
+    fn validation_failed(&self) -> String {
+        String::from("A validation error has occurred.")
+    }
+
+    /// Gets the message used when a required setting is missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the missing setting
+    fn missing_setting(&self, field: &str) -> String {
+        format!("missing required setting `{}`", field)
+    }
+
+    /// Gets the message used when a configuration value cannot be bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The path of the configuration key
+    /// * `error` - The error reported when the value was bound
+    fn invalid_value(&self, key: &str, error: &str) -> String {
+        format!("invalid value for configuration key '{}': {}", key, error)
+    }
+}
+
+/// Represents the default, English [messages](OptionsMessages) for [`Options`](crate::Options).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultOptionsMessages;
+
+impl OptionsMessages for DefaultOptionsMessages {}
+
+#[cfg(feature = "di")]
+pub(crate) fn messages(provider: &di::ServiceProvider) -> crate::Ref<dyn OptionsMessages> {
+    match provider.get::<dyn OptionsMessages>() {
+        Some(messages) => messages,
+        _ => crate::Ref::new(DefaultOptionsMessages),
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsFactory};
+    use di::{singleton, Ref, ServiceCollection};
+
+    #[derive(Default)]
+    struct AppOptions {
+        api_key: Option<String>,
+        workers: u8,
+    }
+
+    struct FrenchMessages;
+
+    impl OptionsMessages for FrenchMessages {
+        fn validation_failed(&self) -> String {
+            String::from("Une erreur de validation s'est produite.")
+        }
+
+        fn missing_setting(&self, field: &str) -> String {
+            format!("paramètre obligatoire manquant `{}`", field)
+        }
+    }
+
+    #[test]
+    fn validate_should_use_registered_messages() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn OptionsMessages, FrenchMessages>()
+                    .from(|_| Ref::new(FrenchMessages)),
+            )
+            .add_options::<AppOptions>()
+            .validate(|o| o.workers > 0, "")
+            .require(|o| o.api_key.is_some(), "api_key")
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<dyn OptionsFactory<AppOptions>>();
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failures(),
+            [
+                "Une erreur de validation s'est produite.",
+                "paramètre obligatoire manquant `api_key`",
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+// the creation and reloading of options are measured through the 'metrics' facade when the 'metrics'
+// feature is enabled and compile away to nothing otherwise. every metric is labeled with the type
+// and name of the options, using the same 'options' and 'name' keys as the fields of trace events.
+
+#![cfg_attr(not(feature = "metrics"), allow(clippy::extra_unused_type_parameters))]
+
+use std::time::Duration;
+
+pub(crate) fn created<T>(name: Option<&str>, duration: Duration, failed: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let options = std::any::type_name::<T>();
+        let name = name.unwrap_or_default().to_owned();
+
+        metrics::histogram!(
+            "options_creation_duration_seconds",
+            "options" => options,
+            "name" => name.clone()
+        )
+        .record(duration.as_secs_f64());
+
+        if failed {
+            metrics::counter!(
+                "options_validation_failures_total",
+                "options" => options,
+                "name" => name
+            )
+            .increment(1);
+        }
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = (name, duration, failed);
+}
+
+pub(crate) fn reloaded<T>(name: Option<&str>) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "options_reloads_total",
+        "options" => std::any::type_name::<T>(),
+        "name" => name.unwrap_or_default().to_owned()
+    )
+    .increment(1);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = name;
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+
+    use crate::*;
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+    use std::sync::{Arc, Mutex};
+    use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+    // the recorder is only used by the thread running the test, which isolates concurrent tests
+    #[derive(Clone, Default)]
+    struct TestRecorder(Arc<Mutex<Vec<(String, f64)>>>);
+
+    struct Measurement(String, Arc<Mutex<Vec<(String, f64)>>>);
+
+    impl CounterFn for Measurement {
+        fn increment(&self, value: u64) {
+            self.1.lock().unwrap().push((self.0.clone(), value as f64));
+        }
+
+        fn absolute(&self, value: u64) {
+            self.increment(value)
+        }
+    }
+
+    impl HistogramFn for Measurement {
+        fn record(&self, value: f64) {
+            self.1.lock().unwrap().push((self.0.clone(), value));
+        }
+    }
+
+    impl TestRecorder {
+        // measurements are identified by their name and the name of the options
+        fn measure(&self, key: &Key) -> Arc<Measurement> {
+            let name = key.labels().find(|l| l.key() == "name").unwrap().value();
+            let id = format!("{}{{name={}}}", key.name(), name);
+            Arc::new(Measurement(id, self.0.clone()))
+        }
+
+        fn names(&self) -> Vec<String> {
+            self.0.lock().unwrap().iter().map(|m| m.0.clone()).collect()
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.measure(key))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(self.measure(key))
+        }
+    }
+
+    #[derive(Default)]
+    struct MeteredOptions {
+        valid: bool,
+    }
+
+    struct Valid;
+
+    impl ValidateOptions<MeteredOptions> for Valid {
+        fn validate(&self, _: Option<&str>, options: &MeteredOptions) -> ValidateOptionsResult {
+            if options.valid {
+                ValidateOptionsResult::success()
+            } else {
+                ValidateOptionsResult::fail("invalid")
+            }
+        }
+    }
+
+    struct TokenSource(SharedChangeToken<SingleChangeToken>);
+
+    impl OptionsChangeTokenSource<MeteredOptions> for TokenSource {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.0.clone())
+        }
+    }
+
+    fn new_factory() -> DefaultOptionsFactory<MeteredOptions> {
+        let configure: Ref<dyn ConfigureOptions<MeteredOptions>> =
+            Ref::new(configure(|name, o: &mut MeteredOptions| {
+                o.valid = name == Some("Valid")
+            }));
+        let validation: Ref<dyn ValidateOptions<MeteredOptions>> = Ref::new(Valid);
+
+        DefaultOptionsFactory::new(vec![configure], Vec::default(), vec![validation])
+    }
+
+    #[test]
+    fn create_should_record_duration_and_validation_failures() {
+        // arrange
+        let recorder = TestRecorder::default();
+        let factory = new_factory();
+
+        // act
+        metrics::with_local_recorder(&recorder, || {
+            assert!(factory.create(Some("Valid")).is_ok());
+            assert!(factory.create(Some("Invalid")).is_err());
+        });
+
+        // assert
+        assert_eq!(
+            recorder.names(),
+            vec![
+                "options_creation_duration_seconds{name=Valid}",
+                "options_creation_duration_seconds{name=Invalid}",
+                "options_validation_failures_total{name=Invalid}",
+            ]
+        );
+    }
+
+    #[test]
+    fn change_should_count_reload() {
+        // arrange
+        let recorder = TestRecorder::default();
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let source: Ref<dyn OptionsChangeTokenSource<MeteredOptions>> =
+            Ref::new(TokenSource(token.clone()));
+        let factory: Ref<dyn OptionsFactory<MeteredOptions>> = Ref::new(new_factory());
+        let monitor =
+            DefaultOptionsMonitor::new(Ref::new(OptionsCache::default()), vec![source], factory);
+
+        // act
+        metrics::with_local_recorder(&recorder, || token.notify());
+
+        // assert
+        drop(monitor);
+        assert_eq!(recorder.names(), vec!["options_reloads_total{name=}"]);
+    }
+}
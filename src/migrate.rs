@@ -0,0 +1,307 @@
+use crate::{
+    cfg_ext::bind_options,
+    deprecate::{deprecate, Deprecation},
+    messages::messages,
+    view::{flatten, is_under, ConfigurationView},
+    OptionsMessages, Value,
+};
+use config::{ext::Error, Configuration};
+use di::{Ref, ServiceProvider};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Represents the values of the configuration bound to [`Options`](crate::Options) while they are
+/// being [migrated](MigrateOptions).
+///
+/// # Remarks
+///
+/// Keys are relative to the configuration section the options are bound to and are compared
+/// without case sensitivity. Changes only affect how the options are bound; the configuration
+/// itself is never modified.
+pub struct ConfigurationMigration {
+    entries: Vec<(String, config::Value)>,
+    changed: bool,
+}
+
+impl ConfigurationMigration {
+    pub(crate) fn new(entries: Vec<(String, config::Value)>) -> Self {
+        Self {
+            entries,
+            changed: false,
+        }
+    }
+
+    /// Gets the keys of every configured value.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(k, _)| k.as_str())
+    }
+
+    /// Gets the configured value with the specified key, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Gets a value indicating whether the specified key or any key beneath it is configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value or section
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.iter().any(|(k, _)| is_under(k, key))
+    }
+
+    /// Sets the value with the specified key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value
+    /// * `value` - The value to set
+    pub fn set<S: AsRef<str>>(&mut self, key: &str, value: S) {
+        let value = config::Value::new(value.as_ref().to_owned());
+
+        match self
+            .entries
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        {
+            Some(entry) => entry.1 = value,
+            _ => self.entries.push((key.to_owned(), value)),
+        }
+
+        self.changed = true;
+    }
+
+    /// Removes the specified key and every key beneath it.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value or section to remove
+    pub fn remove(&mut self, key: &str) {
+        let count = self.entries.len();
+
+        self.entries.retain(|(k, _)| !is_under(k, key));
+        self.changed |= self.entries.len() != count;
+    }
+
+    /// Moves the specified key and every key beneath it to a new key.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The key of the value or section to move
+    /// * `to` - The key the value or section is moved to
+    ///
+    /// # Remarks
+    ///
+    /// The values that are already configured beneath the new key take precedence over the values
+    /// that are moved, which are discarded.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        if !self.contains(from) {
+            return;
+        }
+
+        if self.contains(to) {
+            self.remove(from);
+            return;
+        }
+
+        for (key, _) in self.entries.iter_mut().filter(|(k, _)| is_under(k, from)) {
+            *key = format!("{}{}", to, &key[from.len()..]);
+        }
+
+        self.changed = true;
+    }
+
+    pub(crate) fn changed(&self) -> bool {
+        self.changed
+    }
+
+    pub(crate) fn into_view(self) -> ConfigurationView {
+        ConfigurationView::new(self.entries)
+    }
+}
+
+/// Defines the behavior of something that migrates the shape of the configuration bound to
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Migrations run before the options are bound to configuration, which allows the shape of the
+/// options to change, such as renaming a section or moving a key, without breaking existing
+/// configuration.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait MigrateOptions<T> {
+    /// Migrates the configuration bound to the corresponding options.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being bound
+    /// * `configuration` - The [configuration](ConfigurationMigration) to migrate
+    fn migrate(&self, name: Option<&str>, configuration: &mut ConfigurationMigration);
+}
+
+pub(crate) struct OptionsBinder<T: Value> {
+    deprecations: Vec<Ref<Deprecation<T>>>,
+    migrations: Vec<Ref<dyn MigrateOptions<T>>>,
+    messages: Ref<dyn OptionsMessages>,
+}
+
+impl<T: Value + DeserializeOwned + 'static> OptionsBinder<T> {
+    pub(crate) fn new(provider: &ServiceProvider) -> Self {
+        Self {
+            deprecations: provider.get_all::<Deprecation<T>>().collect(),
+            migrations: provider.get_all::<dyn MigrateOptions<T>>().collect(),
+            messages: messages(provider),
+        }
+    }
+
+    pub(crate) fn bind(
+        &self,
+        configuration: &dyn Configuration,
+        name: Option<&str>,
+        options: &mut T,
+    ) -> Result<(), Error> {
+        if self.deprecations.is_empty() && self.migrations.is_empty() {
+            return bind_options(configuration, options, self.messages.deref());
+        }
+
+        let path = configuration
+            .as_section()
+            .map(|s| s.path().to_owned())
+            .unwrap_or_default();
+        let mut migration = ConfigurationMigration::new(flatten(configuration));
+
+        // deprecated keys are reported as they are configured, before they are migrated
+        deprecate(&self.deprecations, &path, name, &mut migration);
+
+        for migrator in &self.migrations {
+            migrator.migrate(name, &mut migration);
+        }
+
+        if migration.changed() {
+            bind_options(&migration.into_view(), options, self.messages.deref())
+        } else {
+            bind_options(configuration, options, self.messages.deref())
+        }
+    }
+}
+
+pub(crate) struct _Migrate<T, F> {
+    action: F,
+    _options: PhantomData<T>,
+}
+
+impl<T, F> _Migrate<T, F>
+where
+    F: Fn(Option<&str>, &mut ConfigurationMigration),
+{
+    pub(crate) fn new(action: F) -> Self {
+        Self {
+            action,
+            _options: PhantomData,
+        }
+    }
+}
+
+impl<T, F> MigrateOptions<T> for _Migrate<T, F>
+where
+    T: Value,
+    F: Fn(Option<&str>, &mut ConfigurationMigration) + Value,
+{
+    fn migrate(&self, name: Option<&str>, configuration: &mut ConfigurationMigration) {
+        (self.action)(name, configuration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, Options};
+    use config::{ext::*, ConfigurationBuilder, DefaultConfigurationBuilder};
+    use di::ServiceCollection;
+    use serde::Deserialize;
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "PascalCase"))]
+    struct AppOptions {
+        db: DbOptions,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all(deserialize = "PascalCase"))]
+    struct DbOptions {
+        host: String,
+        command_timeout: u32,
+        pooling: bool,
+    }
+
+    fn entry(key: &str, value: &str) -> (String, config::Value) {
+        (key.to_owned(), config::Value::new(value.to_owned()))
+    }
+
+    #[test]
+    fn rename_should_keep_values_configured_beneath_new_key() {
+        // arrange
+        let mut migration = ConfigurationMigration::new(vec![
+            entry("Old:Host", "old.contoso.com"),
+            entry("Old:Port", "1433"),
+            entry("New:Host", "new.contoso.com"),
+            entry("Legacy:Timeout", "30"),
+        ]);
+
+        // act
+        migration.rename("old", "New");
+        migration.rename("Legacy:Timeout", "Timeout");
+
+        // assert
+        assert_eq!(migration.get("new:host"), Some("new.contoso.com"));
+        assert!(!migration.contains("Old"));
+        assert!(!migration.contains("New:Port"));
+        assert_eq!(migration.get("Timeout"), Some("30"));
+        assert!(migration.changed());
+    }
+
+    #[test]
+    fn migrate_should_transform_configuration_before_binding() {
+        // arrange
+        let config: Ref<dyn Configuration> = Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[
+                    ("Database:Host", "db.contoso.com"),
+                    ("Database:Timeout", "30"),
+                ])
+                .build()
+                .unwrap()
+                .as_config(),
+        );
+        let provider = ServiceCollection::new()
+            .apply_config::<AppOptions>(config)
+            .migrate(|cfg| {
+                cfg.rename("Database", "Db");
+                cfg.rename("Db:Timeout", "Db:CommandTimeout");
+            })
+            .migrate(|cfg| {
+                if !cfg.contains("Db:Pooling") {
+                    cfg.set("Db:Pooling", "true");
+                }
+            })
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<AppOptions>>();
+
+        // assert
+        assert_eq!(&options.value().db.host, "db.contoso.com");
+        assert_eq!(options.value().db.command_timeout, 30);
+        assert!(options.value().db.pooling);
+    }
+}
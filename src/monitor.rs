@@ -1,23 +1,181 @@
-use crate::{OptionsChangeTokenSource, OptionsFactory, OptionsMonitorCache, Ref, Value};
-use std::ops::Deref;
-use std::sync::{Arc, RwLock, Weak};
+use crate::confined::{deliver, Confined};
+use crate::sync::{Mutex, RwLock};
+use crate::token::{register_next, TokenRegistration};
+use crate::transaction::{Participant, Staged};
+use crate::watchdog::ListenerWatch;
+use crate::{
+    meter, otel, trace, AnyOptionsMonitor, OptionsChangeTokenSource, OptionsFactory,
+    OptionsHealthCheck, OptionsMonitorCache, OptionsNameComparer, Ref, ReloadTransaction,
+    RetryPolicy, ValidateOptionsResult, Value,
+};
+use std::any::type_name;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "debug")]
+use crate::leak::{LeakDetector, SubscriptionReport};
+
+#[cfg(feature = "di")]
+use crate::watchdog::TimeoutHandler;
+
+#[cfg(feature = "async")]
+use crate::{
+    dispatch::{Dispatcher, Job},
+    ListenerDispatch,
+};
+
+#[cfg(feature = "tokio")]
+use crate::{broadcast::Broadcast, OptionsMonitorExt};
+
+#[cfg(feature = "tokio")]
+use tokio::sync::broadcast::Sender;
+
+pub(crate) type Callback<T> = dyn Fn(Option<&str>, Ref<T>) + Send + Sync;
+pub(crate) type EventCallback<T> = dyn Fn(&ChangeEvent<T>) + Send + Sync;
+
+pub(crate) type ErrorHandler = dyn Fn(Option<&str>, &ValidateOptionsResult) + Send + Sync;
+
+type Equals<T> = fn(&T, &T) -> bool;
+
+#[cfg(feature = "di")]
+pub(crate) enum MonitorPolicy<T> {
+    SuppressUnchanged(Equals<T>),
+    Retry(RetryPolicy, Arc<ErrorHandler>),
+    Enlist(ReloadTransaction, Vec<Option<String>>),
+    #[cfg(feature = "async")]
+    Dispatch(ListenerDispatch),
+    Timeout(Duration, Arc<TimeoutHandler>),
+}
 
 /// Represents a change subscription.
 ///
 /// # Remarks
 ///
 /// When the subscription is dropped, the underlying callback is unsubscribed.
-pub struct Subscription<T: Value>(Arc<dyn Fn(Option<&str>, Ref<T>) + Send + Sync>);
+pub struct Subscription<T: Value>(#[allow(dead_code)] Listener<T>);
+
+#[allow(dead_code)]
+enum Listener<T: Value> {
+    Callback(Arc<Callback<T>>),
+    Event(Arc<EventCallback<T>>),
+}
 
 impl<T: Value> Subscription<T> {
     /// Initializes a new change token registration.
-    pub fn new(callback: Arc<dyn Fn(Option<&str>, Ref<T>) + Send + Sync>) -> Self {
-        Self(callback)
+    pub fn new(callback: Arc<Callback<T>>) -> Self {
+        Self(Listener::Callback(callback))
+    }
+
+    /// Initializes a new change event registration.
+    pub fn for_event(callback: Arc<EventCallback<T>>) -> Self {
+        Self(Listener::Event(callback))
+    }
+}
+
+/// Represents a change to monitored [`Options`](crate::Options).
+pub struct ChangeEvent<T: Value> {
+    name: Option<String>,
+    value: Ref<T>,
+    previous: Option<Ref<T>>,
+    source: Option<String>,
+    generation: u64,
+    timestamp: SystemTime,
+}
+
+impl<T: Value> ChangeEvent<T> {
+    /// Initializes a new change event.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options that changed
+    /// * `value` - The changed options
+    pub fn new(name: Option<String>, value: Ref<T>) -> Self {
+        Self {
+            name,
+            value,
+            previous: None,
+            source: None,
+            generation: 0,
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Sets the options before the change.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The options that were replaced
+    pub fn with_previous(mut self, previous: Ref<T>) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
+    /// Sets the name of the source which triggered the change.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [name](crate::OptionsChangeTokenSource::source_name) of the source
+    pub fn with_source<S: Into<String>>(mut self, source: S) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the generation of the changed options.
+    ///
+    /// # Arguments
+    ///
+    /// * `generation` - The [generation](OptionsMonitor::current_generation) of the options
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    /// Gets the name of the options that changed, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the changed options.
+    pub fn value(&self) -> Ref<T> {
+        self.value.clone()
+    }
+
+    /// Gets the options before the change, if they are known.
+    pub fn previous(&self) -> Option<Ref<T>> {
+        self.previous.clone()
+    }
+
+    /// Gets the name of the source which triggered the change, if any.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Gets the [generation](OptionsMonitor::current_generation) of the changed options.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Gets the time the change occurred.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
     }
 }
 
-unsafe impl<T: Send + Sync> Send for Subscription<T> {}
-unsafe impl<T: Send + Sync> Sync for Subscription<T> {}
+impl<T: Value> Clone for ChangeEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            value: self.value.clone(),
+            previous: self.previous.clone(),
+            source: self.source.clone(),
+            generation: self.generation,
+            timestamp: self.timestamp,
+        }
+    }
+}
 
 /// Defines the behavior for notifications when [`Options`](crate::Options) instances change.
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
@@ -44,16 +202,160 @@ pub trait OptionsMonitor<T: Value> {
     ///
     /// A change subscription for the specified options. When the subscription is dropped, no further
     /// notifications will be propagated.
-    fn on_change(
-        &self,
-        listener: Box<dyn Fn(Option<&str>, Ref<T>) + Send + Sync>,
-    ) -> Subscription<T>;
+    #[cfg_attr(feature = "debug", track_caller)]
+    fn on_change(&self, listener: Box<Callback<T>>) -> Subscription<T>;
+
+    /// Registers a callback function to be invoked with a [`ChangeEvent`] when the configured instance
+    /// with the given name changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - The callback function to invoke
+    ///
+    /// # Returns
+    ///
+    /// A change subscription for the specified options. When the subscription is dropped, no further
+    /// notifications will be propagated.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation only provides the name of the options and the changed options.
+    #[cfg_attr(feature = "debug", track_caller)]
+    fn on_change_event(&self, listener: Box<EventCallback<T>>) -> Subscription<T>
+    where
+        T: 'static,
+    {
+        self.on_change(Box::new(move |name, value| {
+            listener(&ChangeEvent::new(name.map(|n| n.to_owned()), value))
+        }))
+    }
+
+    /// Reloads the configured instance with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name associated with the options.
+    ///
+    /// # Remarks
+    ///
+    /// The cached instance is invalidated, the options are created again, and listeners are notified
+    /// as if a source signaled a change. This is useful after changing state that the options are
+    /// created from, but which has no source of change tokens. The default implementation does
+    /// nothing.
+    fn reload(&self, name: Option<&str>) {
+        let _ = name;
+    }
+
+    /// Returns the generation of the configured instance with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name associated with the options.
+    ///
+    /// # Remarks
+    ///
+    /// The generation starts at zero and increases each time the configured instance changes, which
+    /// allows artifacts derived from the options to be checked for staleness without holding the
+    /// options. The default implementation always returns zero, which indicates that generations are
+    /// not tracked.
+    fn current_generation(&self, name: Option<&str>) -> u64 {
+        let _ = name;
+        0
+    }
+
+    /// Returns the error of the most recent failed rebuild of the configured instance with the given
+    /// name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name associated with the options.
+    ///
+    /// # Remarks
+    ///
+    /// When options fail to rebuild, but the last valid options are kept, the error explains why
+    /// the current options are stale. The error is cleared when the options are successfully
+    /// rebuilt. The default implementation always returns nothing, which indicates that errors are
+    /// not tracked.
+    fn last_error(&self, name: Option<&str>) -> Option<ValidateOptionsResult> {
+        let _ = name;
+        None
+    }
+}
+
+pub(crate) fn alive<C: ?Sized>(listeners: &RwLock<Vec<Weak<C>>>) -> Vec<Arc<C>> {
+    listeners
+        .read()
+        .unwrap()
+        .iter()
+        .filter_map(|c| c.upgrade())
+        .collect()
+}
+
+pub(crate) fn subscribe<T: Value>(
+    listeners: &RwLock<Vec<Weak<EventCallback<T>>>>,
+    listener: Box<EventCallback<T>>,
+) -> Subscription<T> {
+    let mut listeners = listeners.write().unwrap();
+
+    listeners.retain(|l| l.strong_count() > 0);
+
+    let listener: Arc<EventCallback<T>> = Arc::from(listener);
+
+    listeners.push(Arc::downgrade(&listener));
+    Subscription::for_event(listener)
+}
+
+// generations are counted per name, where names which are equal according to the comparer share a
+// count
+#[derive(Default)]
+pub(crate) struct Generations {
+    comparer: OptionsNameComparer,
+    counts: Mutex<HashMap<String, u64>>,
+}
+
+impl Generations {
+    pub(crate) fn new(comparer: OptionsNameComparer) -> Self {
+        Self {
+            comparer,
+            counts: Default::default(),
+        }
+    }
+
+    pub(crate) fn get(&self, name: Option<&str>) -> u64 {
+        self.counts
+            .lock()
+            .unwrap()
+            .get(self.comparer.normalize(name.unwrap_or_default()).as_ref())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn next(&self, name: Option<&str>) -> u64 {
+        let mut counts = self.counts.lock().unwrap();
+        let generation = counts
+            .entry(
+                self.comparer
+                    .normalize(name.unwrap_or_default())
+                    .into_owned(),
+            )
+            .or_default();
+
+        *generation += 1;
+        *generation
+    }
 }
 
 /// Represents the default implementation for notifications when option instances change.
+///
+/// # Remarks
+///
+/// A change is processed, and listeners are invoked, on the thread that signaled it. Unless the
+/// **async** feature is enabled, options and their services are not required to be thread-safe and
+/// stay on the thread that created the monitor. A change signaled from another thread is then
+/// processed on that thread the next time the monitor is used.
 pub struct DefaultOptionsMonitor<T: Value> {
     tracker: Arc<ChangeTracker<T>>,
-    _subscriptions: Vec<Box<dyn tokens::Subscription>>,
+    _watches: Vec<Arc<Watch<T>>>,
 }
 
 impl<T: Value + 'static> DefaultOptionsMonitor<T> {
@@ -64,69 +366,392 @@ impl<T: Value + 'static> DefaultOptionsMonitor<T> {
     /// * `cache` - The [cache](crate::OptionsMonitorCache) used for monitored options
     /// * `sources` - The [source tokens](crate::OptionsChangeTokenSource) used to track option changes
     /// * `factory` - The [factory](crate::OptionsFactory) used to create new options
+    ///
+    /// # Remarks
+    ///
+    /// The generations and errors of names which are equal according to the default
+    /// [comparer](crate::OptionsNameComparer) are shared, which matches a default
+    /// [`OptionsCache`](crate::OptionsCache).
     pub fn new(
         cache: Ref<dyn OptionsMonitorCache<T>>,
         sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
         factory: Ref<dyn OptionsFactory<T>>,
     ) -> Self {
-        let tracker = Arc::new(ChangeTracker::new(cache, factory));
-        let mut subscriptions = Vec::new();
-
-        // SAFETY: the following is not guaranteed to be safe unless 'async' is enabled
-        for source in sources {
-            let producer = Producer::new(source.clone());
-            let consumer = tracker.clone();
-            let state = source.name().map(|n| Arc::new(n.to_owned()));
-            let subscription: Box<dyn tokens::Subscription> = Box::new(tokens::on_change(
-                move || producer.token(),
-                move |state| {
-                    if let Some(name) = state {
-                        consumer.on_change(Some(name.as_str()));
-                    } else {
-                        consumer.on_change(None);
-                    };
-                },
-                state,
-            ));
-            subscriptions.push(subscription);
+        Self::create(cache, sources, factory, None, Default::default())
+    }
+
+    /// Initializes a new default options monitor that suppresses changes which do not change the
+    /// options.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The [cache](crate::OptionsMonitorCache) used for monitored options
+    /// * `sources` - The [source tokens](crate::OptionsChangeTokenSource) used to track option changes
+    /// * `factory` - The [factory](crate::OptionsFactory) used to create new options
+    ///
+    /// # Remarks
+    ///
+    /// When a change is signaled, the options are created again and compared to the cached
+    /// options. If they are equal, the cached options are kept and listeners are not notified.
+    pub fn with_equality(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+    ) -> Self
+    where
+        T: PartialEq,
+    {
+        Self::create(cache, sources, factory, Some(T::eq), Default::default())
+    }
+
+    /// Retries creating options that failed to reload.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The [policy](crate::RetryPolicy) used to retry
+    /// * `on_error` - The function invoked with the failure when every retry failed
+    ///
+    /// # Remarks
+    ///
+    /// Without a retry policy, options which fail to reload are removed from the cache and the
+    /// failure occurs the next time they are retrieved. With a retry policy, the cached options are
+    /// kept and listeners are not notified until the options are successfully rebuilt. A retry
+    /// occurs on a background thread once its delay elapses. Unless the **async** feature is
+    /// enabled, the retry is processed the next time the monitor is used.
+    pub fn with_retry<F>(self, policy: RetryPolicy, on_error: F) -> Self
+    where
+        F: Fn(Option<&str>, &ValidateOptionsResult) + Send + Sync + 'static,
+    {
+        *self.tracker.retry.lock().unwrap() = Some(Arc::new(Retry {
+            policy,
+            on_error: Box::new(on_error),
+        }));
+        self
+    }
+
+    /// Enlists the options in a [transaction](crate::ReloadTransaction) that reloads them together
+    /// with other options.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The [transaction](crate::ReloadTransaction) to enlist in
+    /// * `name` - The optional name of the options to reload
+    pub fn enlist(self, transaction: &ReloadTransaction, name: Option<&str>) -> Self {
+        let participant: Ref<dyn Participant> = Ref::new(Enlisted {
+            tracker: Arc::downgrade(&self.tracker),
+            name: name.map(|n| n.to_owned()),
+        });
+
+        transaction.enlist(participant);
+        self
+    }
+
+    /// Sets how the listeners of the options are invoked.
+    ///
+    /// # Arguments
+    ///
+    /// * `dispatch` - The [dispatch](crate::ListenerDispatch) used to invoke listeners
+    ///
+    /// # Remarks
+    ///
+    /// Listeners are invoked sequentially on the thread that signaled the change by default, so a
+    /// slow listener delays every listener after it as well as the source of the change.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn with_dispatch(self, dispatch: ListenerDispatch) -> Self {
+        *self.tracker.dispatcher.lock().unwrap() = Some(Arc::new(Dispatcher::new(dispatch)));
+        self
+    }
+
+    /// Reports listeners that take longer than a timeout to return.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The duration after which a listener is considered hung
+    /// * `on_timeout` - The function invoked with the name of the options and the elapsed time
+    ///
+    /// # Remarks
+    ///
+    /// A listener that exceeds the timeout is reported while it is still running, which makes a
+    /// listener that never returns visible. The listener is not interrupted and it is reported at
+    /// most once per change. When the **tracing** feature is enabled, the listener is also logged as a
+    /// warning. With [parallel dispatch](crate::ListenerDispatch::Parallel), the thread that
+    /// signaled the change stops waiting for listeners once the timeout elapses.
+    pub fn with_listener_timeout<F>(self, timeout: Duration, on_timeout: F) -> Self
+    where
+        F: Fn(Option<&str>, Duration) + Send + Sync + 'static,
+    {
+        *self.tracker.watch.lock().unwrap() =
+            Some(ListenerWatch::new(timeout, Arc::new(on_timeout)));
+        self
+    }
+
+    /// Publishes the changes to the options to a [monitor](crate::AnyOptionsMonitor) for options of
+    /// any type.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - The [monitor](crate::AnyOptionsMonitor) to publish changes to
+    pub fn publish_to(self, monitor: &AnyOptionsMonitor) -> Self {
+        *self.tracker.any.lock().unwrap() = Some(monitor.clone());
+        self
+    }
+
+    /// Reports the result of rebuilding the options to a [health check](crate::OptionsHealthCheck).
+    ///
+    /// # Arguments
+    ///
+    /// * `health` - The [health check](crate::OptionsHealthCheck) to report to
+    ///
+    /// # Remarks
+    ///
+    /// A failure is only reported when the options are rebuilt as soon as they change, which occurs
+    /// when they are retried, when unchanged options are suppressed, or when they are enlisted in a
+    /// transaction. Otherwise, the options are created when they are next retrieved.
+    pub fn report_to(self, health: &OptionsHealthCheck) -> Self {
+        *self.tracker.health.lock().unwrap() = Some(health.clone());
+        self
+    }
+
+    #[cfg(feature = "di")]
+    pub(crate) fn with_policies<I>(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+        policies: I,
+        comparer: OptionsNameComparer,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Ref<MonitorPolicy<T>>>,
+    {
+        let policies: Vec<_> = policies.into_iter().collect();
+        let equals = policies.iter().find_map(|p| match **p {
+            MonitorPolicy::SuppressUnchanged(equals) => Some(equals),
+            _ => None,
+        });
+        let mut monitor = Self::create(cache, sources, factory, equals, comparer);
+
+        for policy in &policies {
+            match &**policy {
+                MonitorPolicy::Retry(policy, on_error) => {
+                    let on_error = on_error.clone();
+                    monitor = monitor.with_retry(policy.clone(), move |n, e| on_error(n, e));
+                }
+                MonitorPolicy::Enlist(transaction, names) => {
+                    for name in names {
+                        monitor = monitor.enlist(transaction, name.as_deref());
+                    }
+                }
+                #[cfg(feature = "async")]
+                MonitorPolicy::Dispatch(dispatch) => monitor = monitor.with_dispatch(*dispatch),
+                MonitorPolicy::Timeout(timeout, on_timeout) => {
+                    let on_timeout = on_timeout.clone();
+                    monitor = monitor.with_listener_timeout(*timeout, move |n, e| on_timeout(n, e));
+                }
+                _ => {}
+            }
         }
 
+        monitor
+    }
+
+    // changes signaled on other threads are processed before the monitor is used
+    fn tracker(&self) -> &ChangeTracker<T> {
+        deliver();
+        &self.tracker
+    }
+
+    fn create(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+        equals: Option<Equals<T>>,
+        comparer: OptionsNameComparer,
+    ) -> Self {
+        let tracker = ChangeTracker::new(cache, factory, equals, comparer);
+        let watches = sources
+            .into_iter()
+            .map(|source| Watch::new(source, tracker.clone()))
+            .collect();
+
         Self {
             tracker,
-            _subscriptions: subscriptions,
+            _watches: watches,
         }
     }
 }
 
-unsafe impl<T: Send + Sync> Send for DefaultOptionsMonitor<T> {}
-unsafe impl<T: Send + Sync> Sync for DefaultOptionsMonitor<T> {}
+impl<T: Value + 'static> DefaultOptionsMonitor<T> {
+    /// Gets a report of the subscriptions to the monitor.
+    ///
+    /// # Remarks
+    ///
+    /// Every subscription is tracked with the location of the call that registered it, which can be
+    /// used to find subscriptions that were dropped before they were ever notified or that
+    /// accumulate without bound. Subscriptions that are dead on arrival are also logged as warnings
+    /// when the **tracing** feature is enabled.
+    #[cfg(feature = "debug")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug")))]
+    pub fn subscriptions(&self) -> SubscriptionReport {
+        self.tracker.detector.report()
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl<T: Value + 'static> OptionsMonitorExt<T> for DefaultOptionsMonitor<T> {
+    fn broadcast(&self) -> Sender<ChangeEvent<T>> {
+        self.tracker.broadcast.sender()
+    }
+}
 
-impl<T: Value> OptionsMonitor<T> for DefaultOptionsMonitor<T> {
+impl<T: Value + 'static> OptionsMonitor<T> for DefaultOptionsMonitor<T> {
     fn get(&self, name: Option<&str>) -> Ref<T> {
-        self.tracker.get(name)
+        self.tracker().get(name)
     }
 
-    fn on_change(
-        &self,
-        listener: Box<dyn Fn(Option<&str>, Ref<T>) + Send + Sync>,
-    ) -> Subscription<T> {
-        self.tracker.add(listener)
+    fn on_change(&self, listener: Box<Callback<T>>) -> Subscription<T> {
+        self.tracker().add(listener)
+    }
+
+    fn on_change_event(&self, listener: Box<EventCallback<T>>) -> Subscription<T> {
+        let tracker = self.tracker();
+        let subscription = subscribe(&tracker.events, listener);
+
+        #[cfg(feature = "debug")]
+        if let Listener::Event(listener) = &subscription.0 {
+            tracker
+                .detector
+                .track::<T, _>(std::panic::Location::caller(), listener);
+        }
+
+        subscription
+    }
+
+    fn reload(&self, name: Option<&str>) {
+        self.tracker().reload(name);
+    }
+
+    fn current_generation(&self, name: Option<&str>) -> u64 {
+        self.tracker().generations.get(name)
+    }
+
+    fn last_error(&self, name: Option<&str>) -> Option<ValidateOptionsResult> {
+        let tracker = self.tracker();
+        let errors = tracker.errors.lock().unwrap();
+        errors.get(tracker.key(name).as_ref()).cloned()
+    }
+}
+
+struct Watch<T: Value> {
+    name: Option<String>,
+    source: Ref<dyn OptionsChangeTokenSource<T>>,
+    tracker: Arc<ChangeTracker<T>>,
+    me: Confined<Weak<Self>>,
+    registration: Mutex<Option<TokenRegistration>>,
+}
+
+impl<T: Value + 'static> Watch<T> {
+    fn new(
+        source: Ref<dyn OptionsChangeTokenSource<T>>,
+        tracker: Arc<ChangeTracker<T>>,
+    ) -> Arc<Self> {
+        let watch = Arc::new_cyclic(|me: &Weak<Self>| Self {
+            name: source.name().map(|n| n.to_owned()),
+            source,
+            tracker,
+            me: Confined::new(me.clone()),
+            registration: Default::default(),
+        });
+
+        if watch.register() {
+            watch.changed();
+        }
+
+        watch
+    }
+
+    fn register(&self) -> bool {
+        let watch = self.me.clone();
+        let (registration, missed) = register_next(&*self.source, move || {
+            watch.post(|watch| {
+                if let Some(watch) = watch.upgrade() {
+                    watch.changed();
+                }
+            })
+        });
+
+        // only keep the registration if a token that hasn't already changed was acquired;
+        // otherwise, the old token and registration are immediately dropped
+        if registration.is_some() {
+            *self.registration.lock().unwrap() = registration;
+        }
+
+        missed
+    }
+
+    fn changed(&self) {
+        // the next token is registered before the change is processed so that a change which
+        // occurs while processing is observed. a missed change is also observed because the
+        // options are rebuilt after the latest token was acquired
+        self.register();
+        self.tracker
+            .on_change(self.name.as_deref(), self.source.source_name());
     }
 }
 
 struct ChangeTracker<T: Value> {
     cache: Ref<dyn OptionsMonitorCache<T>>,
     factory: Ref<dyn OptionsFactory<T>>,
-    listeners: RwLock<Vec<Weak<dyn Fn(Option<&str>, Ref<T>) + Send + Sync>>>,
+    equals: Option<Equals<T>>,
+    retry: Mutex<Option<Arc<Retry>>>,
+    retries: Arc<RetrySignal<T>>,
+    comparer: OptionsNameComparer,
+    attempts: Mutex<HashMap<String, u32>>,
+    errors: Mutex<HashMap<String, ValidateOptionsResult>>,
+    generations: Generations,
+    any: Mutex<Option<AnyOptionsMonitor>>,
+    health: Mutex<Option<OptionsHealthCheck>>,
+    watch: Mutex<Option<ListenerWatch>>,
+    #[cfg(feature = "async")]
+    dispatcher: Mutex<Option<Arc<Dispatcher>>>,
+    #[cfg(feature = "tokio")]
+    broadcast: Broadcast<T>,
+    listeners: RwLock<Vec<Weak<Callback<T>>>>,
+    events: RwLock<Vec<Weak<EventCallback<T>>>>,
+    #[cfg(feature = "debug")]
+    detector: LeakDetector,
 }
 
-impl<T: Value> ChangeTracker<T> {
-    fn new(cache: Ref<dyn OptionsMonitorCache<T>>, factory: Ref<dyn OptionsFactory<T>>) -> Self {
-        Self {
+impl<T: Value + 'static> ChangeTracker<T> {
+    fn new(
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+        equals: Option<Equals<T>>,
+        comparer: OptionsNameComparer,
+    ) -> Arc<Self> {
+        Arc::new_cyclic(|me: &Weak<Self>| Self {
             cache,
             factory,
+            equals,
+            retry: Default::default(),
+            retries: Arc::new(RetrySignal::new(me.clone())),
+            comparer,
+            attempts: Default::default(),
+            errors: Default::default(),
+            generations: Generations::new(comparer),
+            any: Default::default(),
+            health: Default::default(),
+            watch: Default::default(),
+            #[cfg(feature = "async")]
+            dispatcher: Default::default(),
+            #[cfg(feature = "tokio")]
+            broadcast: Default::default(),
             listeners: Default::default(),
-        }
+            events: Default::default(),
+            #[cfg(feature = "debug")]
+            detector: Default::default(),
+        })
     }
 
     fn get(&self, name: Option<&str>) -> Ref<T> {
@@ -134,65 +759,412 @@ impl<T: Value> ChangeTracker<T> {
             .get_or_add(name, &|n| self.factory.create(n).unwrap())
     }
 
-    fn add(&self, listener: Box<dyn Fn(Option<&str>, Ref<T>) + Send + Sync>) -> Subscription<T> {
+    // errors and attempts are keyed like the cache so that names which are equal share them
+    fn key<'a>(&self, name: Option<&'a str>) -> Cow<'a, str> {
+        self.comparer.normalize(name.unwrap_or_default())
+    }
+
+    #[cfg_attr(feature = "debug", track_caller)]
+    fn add(&self, listener: Box<Callback<T>>) -> Subscription<T> {
         let mut listeners = self.listeners.write().unwrap();
 
-        // writes are much infrequent and we already need to escalate
-        // to a write-lock, so do the trimming of any dead callbacks now
-        for i in (0..listeners.len()).rev() {
-            if listeners[i].upgrade().is_none() {
-                listeners.remove(i);
+        // writes are much infrequent and we already need to escalate
+        // to a write-lock, so do the trimming of any dead callbacks now
+        for i in (0..listeners.len()).rev() {
+            if listeners[i].upgrade().is_none() {
+                listeners.remove(i);
+            }
+        }
+
+        let source: Arc<Callback<T>> = Arc::from(listener);
+
+        listeners.push(Arc::downgrade(&source));
+
+        #[cfg(feature = "debug")]
+        self.detector
+            .track::<T, _>(std::panic::Location::caller(), &source);
+
+        Subscription::new(source)
+    }
+
+    fn reload(&self, name: Option<&str>) {
+        // an explicit reload is never suppressed
+        self.notify(name, None, None);
+    }
+
+    fn on_change(&self, name: Option<&str>, source: Option<&str>) {
+        self.notify(name, source, self.equals);
+    }
+
+    fn retry(&self, name: Option<&str>) {
+        self.notify(name, Some("retry"), self.equals);
+    }
+
+    fn notify(&self, name: Option<&str>, source: Option<&str>, equals: Option<Equals<T>>) {
+        let _span = trace::changing::<T>(name, source);
+
+        meter::reloaded::<T>(name);
+
+        let previous = self.cache.try_get(name);
+        let retry = self.retry.lock().unwrap().clone();
+
+        if equals.is_none() && retry.is_none() {
+            // without a policy, the options are created when they are next retrieved
+            self.cache.try_remove(name);
+            otel::rebuilt::<T>(name, source, otel::INVALIDATED);
+        } else {
+            match self.rebuild(name, equals) {
+                Ok(true) => {
+                    self.succeeded(name);
+                    otel::rebuilt::<T>(name, source, otel::SUCCEEDED);
+                }
+                Ok(false) => {
+                    self.record_success(name);
+                    trace::unchanged::<T>(name);
+                    otel::rebuilt::<T>(name, source, otel::UNCHANGED);
+                    return;
+                }
+                Err(error) => match retry {
+                    Some(retry) => return self.failed(name, source, &retry, error),
+                    None => {
+                        self.record_failure(name, &error, false);
+                        self.cache.try_remove(name);
+                        otel::rebuilt::<T>(name, source, otel::FAILED);
+                    }
+                },
+            }
+        }
+
+        self.publish(name, source, previous);
+    }
+
+    fn publish(&self, name: Option<&str>, source: Option<&str>, previous: Option<Ref<T>>) {
+        // acquire a read-lock and capture any callbacks that are still alive.
+        // do NOT invoke the callback with the read-lock held. the callback might
+        // register a new callback on the same token which will result in a deadlock.
+        // invoking the callbacks after the read-lock is released ensures that won't happen.
+        let callbacks = alive(&self.listeners);
+        let events = alive(&self.events);
+        let generation = self.generations.next(name);
+        trace::changed::<T>(name);
+
+        #[cfg(feature = "debug")]
+        self.detector.notified();
+
+        let watch = self.watch.lock().unwrap().clone();
+
+        #[cfg(feature = "tokio")]
+        if let Some(sender) = self.broadcast.active() {
+            // there is an error only when every receiver was dropped since it was checked
+            let _ = sender.send(self.event(name, source, previous.clone(), generation));
+        }
+
+        #[cfg(feature = "async")]
+        if let Some(dispatcher) = self.dispatcher() {
+            let timeout = watch.as_ref().map(ListenerWatch::timeout);
+            let jobs = self.jobs(name, source, previous, generation, callbacks, events);
+
+            self.publish_any(name, source, generation);
+            return dispatcher.dispatch(guard::<T>(jobs, watch, name), timeout);
+        }
+
+        for callback in callbacks {
+            let value = self.get(name);
+            let _guard = watch.as_ref().map(|w| w.start(type_name::<T>(), name));
+            callback(name, value);
+        }
+
+        self.publish_any(name, source, generation);
+
+        if events.is_empty() {
+            return;
+        }
+
+        let event = self.event(name, source, previous, generation);
+
+        for callback in events {
+            let _guard = watch.as_ref().map(|w| w.start(type_name::<T>(), name));
+            callback(&event);
+        }
+    }
+
+    fn event(
+        &self,
+        name: Option<&str>,
+        source: Option<&str>,
+        previous: Option<Ref<T>>,
+        generation: u64,
+    ) -> ChangeEvent<T> {
+        let mut event = ChangeEvent::new(name.map(|n| n.to_owned()), self.get(name))
+            .with_generation(generation);
+
+        if let Some(previous) = previous {
+            event = event.with_previous(previous);
+        }
+
+        if let Some(source) = source {
+            event = event.with_source(source);
+        }
+
+        event
+    }
+
+    #[cfg(feature = "async")]
+    fn dispatcher(&self) -> Option<Arc<Dispatcher>> {
+        self.dispatcher
+            .lock()
+            .unwrap()
+            .clone()
+            .filter(|d| !d.is_sequential())
+    }
+
+    #[cfg(feature = "async")]
+    fn jobs(
+        &self,
+        name: Option<&str>,
+        source: Option<&str>,
+        previous: Option<Ref<T>>,
+        generation: u64,
+        callbacks: Vec<Arc<Callback<T>>>,
+        events: Vec<Arc<EventCallback<T>>>,
+    ) -> Vec<Job> {
+        let mut jobs = Vec::<Job>::with_capacity(callbacks.len() + events.len());
+
+        if callbacks.is_empty() && events.is_empty() {
+            return jobs;
+        }
+
+        // the options are resolved before they are dispatched so that every listener observes
+        // the same instance
+        let value = self.get(name);
+
+        for callback in callbacks {
+            let name = name.map(|n| n.to_owned());
+            let value = value.clone();
+            jobs.push(Box::new(move || callback(name.as_deref(), value)));
+        }
+
+        if !events.is_empty() {
+            let event = self.event(name, source, previous, generation);
+
+            for callback in events {
+                let event = event.clone();
+                jobs.push(Box::new(move || callback(&event)));
+            }
+        }
+
+        jobs
+    }
+
+    fn publish_any(&self, name: Option<&str>, source: Option<&str>, generation: u64) {
+        let any = self.any.lock().unwrap().clone();
+
+        if let Some(any) = any {
+            any.publish::<T>(name, source, generation);
+        }
+    }
+
+    fn rebuild(
+        &self,
+        name: Option<&str>,
+        equals: Option<Equals<T>>,
+    ) -> Result<bool, ValidateOptionsResult> {
+        let options = self.factory.create(name)?;
+
+        if let (Some(equals), Some(current)) = (equals, self.cache.try_get(name)) {
+            if equals(&current, &options) {
+                return Ok(false);
+            }
+        }
+
+        self.cache.try_replace(name, options);
+        Ok(true)
+    }
+
+    fn succeeded(&self, name: Option<&str>) {
+        self.record_success(name);
+        self.attempts
+            .lock()
+            .unwrap()
+            .remove(self.key(name).as_ref());
+    }
+
+    fn record_success(&self, name: Option<&str>) {
+        self.errors.lock().unwrap().remove(self.key(name).as_ref());
+
+        if let Some(health) = &*self.health.lock().unwrap() {
+            health.succeeded::<T>(name);
+        }
+    }
+
+    fn record_failure(&self, name: Option<&str>, error: &ValidateOptionsResult, kept: bool) {
+        self.errors
+            .lock()
+            .unwrap()
+            .insert(self.key(name).into_owned(), error.clone());
+
+        if let Some(health) = &*self.health.lock().unwrap() {
+            health.failed::<T>(name, error, kept);
+        }
+    }
+
+    fn failed(
+        &self,
+        name: Option<&str>,
+        source: Option<&str>,
+        retry: &Retry,
+        error: ValidateOptionsResult,
+    ) {
+        // the cached options are kept, if there are any
+        let kept = self.cache.try_get(name).is_some();
+        self.record_failure(name, &error, kept);
+
+        let attempt = {
+            let mut attempts = self.attempts.lock().unwrap();
+            let attempt = attempts.entry(self.key(name).into_owned()).or_default();
+
+            *attempt += 1;
+            *attempt
+        };
+
+        // the cached options are kept until they are successfully rebuilt
+        if attempt <= retry.policy.max_retries() {
+            trace::retrying::<T>(name, attempt);
+            otel::rebuilt::<T>(name, source, otel::RETRYING);
+            self.retries
+                .schedule(name.map(|n| n.to_owned()), retry.policy.delay(attempt));
+        } else {
+            self.attempts
+                .lock()
+                .unwrap()
+                .remove(self.key(name).as_ref());
+            otel::rebuilt::<T>(name, source, otel::FAILED);
+            (retry.on_error)(name, &error);
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+fn guard<T>(jobs: Vec<Job>, watch: Option<ListenerWatch>, name: Option<&str>) -> Vec<Job> {
+    let watch = match watch {
+        Some(watch) => watch,
+        _ => return jobs,
+    };
+
+    // a dispatched listener is watched from when it starts rather than when it is dispatched
+    jobs.into_iter()
+        .map(|job| {
+            let watch = watch.clone();
+            let name = name.map(|n| n.to_owned());
+            let job: Job = Box::new(move || {
+                let _guard = watch.start(type_name::<T>(), name.as_deref());
+                job()
+            });
+            job
+        })
+        .collect()
+}
+
+struct Enlisted<T: Value> {
+    tracker: Weak<ChangeTracker<T>>,
+    name: Option<String>,
+}
+
+impl<T: Value + 'static> Participant for Enlisted<T> {
+    fn is_alive(&self) -> bool {
+        self.tracker.strong_count() > 0
+    }
+
+    fn prepare(&self) -> Result<Option<Box<dyn Staged>>, ValidateOptionsResult> {
+        let tracker = match self.tracker.upgrade() {
+            Some(tracker) => tracker,
+            _ => return Ok(None),
+        };
+        let name = self.name.as_deref();
+        let options = match tracker.factory.create(name) {
+            Ok(options) => options,
+            Err(error) => {
+                // the transaction keeps the current options of every participant
+                let kept = tracker.cache.try_get(name).is_some();
+                tracker.record_failure(name, &error, kept);
+                return Err(error);
+            }
+        };
+
+        if let (Some(equals), Some(current)) = (tracker.equals, tracker.cache.try_get(name)) {
+            if equals(&current, &options) {
+                tracker.record_success(name);
+                trace::unchanged::<T>(name);
+                return Ok(None);
             }
         }
 
-        let source: Arc<dyn Fn(Option<&str>, Ref<T>) + Send + Sync> = Arc::from(listener);
-
-        listeners.push(Arc::downgrade(&source));
-        Subscription::new(source)
+        Ok(Some(Box::new(StagedOptions {
+            tracker,
+            name: self.name.clone(),
+            options: Some(options),
+            previous: None,
+        })))
     }
+}
 
-    fn on_change(&self, name: Option<&str>) {
-        // acquire a read-lock and capture any callbacks that are still alive.
-        // do NOT invoke the callback with the read-lock held. the callback might
-        // register a new callback on the same token which will result in a deadlock.
-        // invoking the callbacks after the read-lock is released ensures that won't happen.
-        let callbacks: Vec<_> = self
-            .listeners
-            .read()
-            .unwrap()
-            .iter()
-            .filter_map(|c| c.upgrade())
-            .collect();
+struct StagedOptions<T: Value> {
+    tracker: Arc<ChangeTracker<T>>,
+    name: Option<String>,
+    options: Option<T>,
+    previous: Option<Ref<T>>,
+}
 
-        self.cache.try_remove(name);
+impl<T: Value + 'static> Staged for StagedOptions<T> {
+    fn swap(&mut self) {
+        let name = self.name.as_deref();
 
-        for callback in callbacks {
-            callback(name, self.get(name));
+        if let Some(options) = self.options.take() {
+            self.previous = self.tracker.cache.try_get(name);
+            self.tracker.cache.try_replace(name, options);
+            self.tracker.record_success(name);
         }
     }
+
+    fn notify(self: Box<Self>) {
+        self.tracker
+            .publish(self.name.as_deref(), Some("transaction"), self.previous);
+    }
 }
 
-unsafe impl<T: Value> Send for ChangeTracker<T> {}
-unsafe impl<T: Value> Sync for ChangeTracker<T> {}
+struct Retry {
+    policy: RetryPolicy,
+    on_error: Box<ErrorHandler>,
+}
 
-struct Producer<T: Value>(Ref<dyn OptionsChangeTokenSource<T>>);
+struct RetrySignal<T: Value> {
+    tracker: Confined<Weak<ChangeTracker<T>>>,
+}
 
-impl<T: Value> Producer<T> {
-    fn new(source: Ref<dyn OptionsChangeTokenSource<T>>) -> Self {
-        Self(source)
+impl<T: Value + 'static> RetrySignal<T> {
+    fn new(tracker: Weak<ChangeTracker<T>>) -> Self {
+        Self {
+            tracker: Confined::new(tracker),
+        }
     }
-}
 
-impl<T: Value> Deref for Producer<T> {
-    type Target = dyn OptionsChangeTokenSource<T>;
+    fn schedule(self: &Arc<Self>, name: Option<String>, delay: Duration) {
+        let signal = self.clone();
 
-    fn deref(&self) -> &Self::Target {
-        self.0.deref()
+        thread::spawn(move || {
+            thread::sleep(delay);
+            signal.notify(name);
+        });
     }
-}
 
-unsafe impl<T: Value> Send for Producer<T> {}
-unsafe impl<T: Value> Sync for Producer<T> {}
+    fn notify(&self, name: Option<String>) {
+        self.tracker.post(move |tracker| {
+            if let Some(tracker) = tracker.upgrade() {
+                tracker.retry(name.as_deref());
+            }
+        });
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -205,7 +1177,7 @@ mod tests {
     };
     use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
 
-    #[derive(Default)]
+    #[derive(Default, PartialEq)]
     struct Config {
         retries: u8,
     }
@@ -250,6 +1222,26 @@ mod tests {
         }
     }
 
+    struct SharedSetup(Arc<AtomicU8>);
+
+    impl ConfigureOptions<Config> for SharedSetup {
+        fn configure(&self, _name: Option<&str>, options: &mut Config) {
+            options.retries = self.0.load(Ordering::SeqCst);
+        }
+    }
+
+    struct FailWhen(Arc<AtomicBool>);
+
+    impl ValidateOptions<Config> for FailWhen {
+        fn validate(&self, _name: Option<&str>, _options: &Config) -> ValidateOptionsResult {
+            if self.0.load(Ordering::SeqCst) {
+                ValidateOptionsResult::fail("the configuration is incomplete")
+            } else {
+                ValidateOptionsResult::success()
+            }
+        }
+    }
+
     #[derive(Default)]
     struct ConfigSource {
         token: SharedChangeToken<SingleChangeToken>,
@@ -267,6 +1259,56 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct ReloadSource {
+        token: Mutex<SharedChangeToken<SingleChangeToken>>,
+    }
+
+    impl ReloadSource {
+        fn reload(&self) {
+            // a token only signals once so it's replaced before it's signaled
+            let token = std::mem::take(&mut *self.token.lock().unwrap());
+            token.notify()
+        }
+    }
+
+    impl OptionsChangeTokenSource<Config> for ReloadSource {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.token.lock().unwrap().clone())
+        }
+        fn source_name(&self) -> Option<&str> {
+            Some("reload")
+        }
+    }
+
+    #[derive(Default)]
+    struct RacySource {
+        token: Mutex<SharedChangeToken<SingleChangeToken>>,
+        race: AtomicBool,
+    }
+
+    impl RacySource {
+        fn reload(&self, race: bool) {
+            self.race.store(race, Ordering::SeqCst);
+            let token = std::mem::take(&mut *self.token.lock().unwrap());
+            token.notify()
+        }
+    }
+
+    impl OptionsChangeTokenSource<Config> for RacySource {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            let token = self.token.lock().unwrap().clone();
+
+            // simulate a change signaled before the callback is registered
+            if self.race.swap(false, Ordering::SeqCst) {
+                let raced = std::mem::take(&mut *self.token.lock().unwrap());
+                raced.notify();
+            }
+
+            Box::new(token)
+        }
+    }
+
     struct Foo {
         monitor: Ref<dyn OptionsMonitor<Config>>,
         _sub: Subscription<Config>,
@@ -295,7 +1337,7 @@ mod tests {
                 self.state.reset();
             }
 
-            self.retries.borrow().clone()
+            *self.retries.borrow()
         }
     }
 
@@ -325,4 +1367,400 @@ mod tests {
         assert_eq!(initial, 1);
         assert_eq!(foo.retries(), 2);
     }
+
+    #[test]
+    fn monitored_options_should_update_when_source_changes_on_another_thread() {
+        // arrange
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(ConfigSetup::default());
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(ConfigSource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory);
+        let initial = monitor.current_value().retries;
+        let token = source.token.clone();
+
+        // act
+        std::thread::spawn(move || token.notify()).join().unwrap();
+
+        // assert
+        assert_eq!(initial, 1);
+        assert_eq!(monitor.current_value().retries, 2);
+    }
+
+    #[test]
+    fn listener_should_be_invoked_when_source_changes_on_another_thread() {
+        // arrange
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(ConfigSetup::default());
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(ConfigSource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory);
+        let notified = Arc::new(AtomicU8::default());
+        let counter = notified.clone();
+        let _sub = monitor.on_change(Box::new(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        let token = source.token.clone();
+
+        // act
+        std::thread::spawn(move || token.notify()).join().unwrap();
+
+        let _ = monitor.current_value();
+
+        // assert
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(not(feature = "async"))]
+    #[test]
+    fn change_signaled_on_another_thread_should_be_processed_on_owning_thread() {
+        // arrange
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(ConfigSetup::default());
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(ConfigSource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory);
+        let threads = Arc::new(Mutex::new(Vec::new()));
+        let observed = threads.clone();
+        let _sub = monitor.on_change(Box::new(move |_, _| {
+            observed.lock().unwrap().push(std::thread::current().id());
+        }));
+        let token = source.token.clone();
+
+        // act
+        std::thread::spawn(move || token.notify()).join().unwrap();
+
+        let pending = threads.lock().unwrap().len();
+        let generation = monitor.current_generation(None);
+
+        // assert
+        assert_eq!(pending, 0);
+        assert_eq!(generation, 1);
+        assert_eq!(*threads.lock().unwrap(), [std::thread::current().id()]);
+    }
+
+    #[test]
+    fn monitor_with_equality_should_suppress_unchanged_options() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(SharedSetup(retries.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(ReloadSource::default());
+        let monitor = DefaultOptionsMonitor::with_equality(cache, vec![source.clone()], factory);
+        let notified = Arc::new(AtomicU8::default());
+        let counter = notified.clone();
+        let _sub = monitor.on_change(Box::new(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        let original = monitor.current_value();
+
+        // act
+        source.reload();
+
+        let unchanged = monitor.current_value();
+
+        retries.store(2, Ordering::SeqCst);
+        source.reload();
+
+        // assert
+        assert!(Ref::ptr_eq(&original, &unchanged));
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+        assert_eq!(monitor.current_value().retries, 2);
+        assert_eq!(monitor.current_generation(None), 1);
+    }
+
+    #[test]
+    fn on_change_event_should_describe_change() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(SharedSetup(retries.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(ReloadSource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = events.clone();
+        let _sub = monitor.on_change_event(Box::new(move |event: &ChangeEvent<Config>| {
+            received.lock().unwrap().push((
+                event.name().map(|n| n.to_owned()),
+                event.value().retries,
+                event.previous().map(|o| o.retries),
+                event.source().map(|s| s.to_owned()),
+                event.generation(),
+            ))
+        }));
+        let _ = monitor.current_value();
+
+        // act
+        retries.store(2, Ordering::SeqCst);
+        source.reload();
+
+        // assert
+        let events = events.lock().unwrap();
+
+        assert_eq!(*events, [(None, 2, Some(1), Some("reload".to_owned()), 1)]);
+    }
+
+    #[test]
+    fn monitor_should_not_miss_change_signaled_before_registration() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(SharedSetup(retries.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(RacySource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory);
+        let original = monitor.current_value().retries;
+
+        retries.store(2, Ordering::SeqCst);
+        source.reload(true);
+
+        let raced = monitor.current_value().retries;
+
+        // act
+        retries.store(3, Ordering::SeqCst);
+        source.reload(false);
+
+        // assert
+        assert_eq!((original, raced), (1, 2));
+        assert_eq!(monitor.current_value().retries, 3);
+    }
+
+    #[test]
+    fn reload_should_rebuild_options_and_notify_listeners() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(SharedSetup(retries.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            Vec::default(),
+        ));
+        let monitor = DefaultOptionsMonitor::with_equality(cache, Vec::default(), factory);
+        let notified = Arc::new(AtomicU8::default());
+        let counter = notified.clone();
+        let _sub = monitor.on_change(Box::new(move |_, o: Ref<Config>| {
+            counter.store(o.retries, Ordering::SeqCst)
+        }));
+        let original = monitor.current_value().retries;
+
+        // act
+        monitor.reload(None);
+
+        let unchanged = notified.load(Ordering::SeqCst);
+
+        retries.store(2, Ordering::SeqCst);
+        monitor.reload(None);
+
+        // assert
+        assert_eq!((original, unchanged), (1, 1));
+        assert_eq!(notified.load(Ordering::SeqCst), 2);
+        assert_eq!(monitor.current_value().retries, 2);
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..400 {
+            if condition() {
+                return;
+            }
+
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn monitor_with_retry_should_keep_options_until_reload_succeeds() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let failing = Arc::new(AtomicBool::new(false));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(SharedSetup(retries.clone()));
+        let validation: Ref<dyn ValidateOptions<Config>> = Ref::new(FailWhen(failing.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            vec![validation],
+        ));
+        let source = Ref::new(ReloadSource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory)
+            .with_retry(RetryPolicy::new(5, Duration::from_millis(10)), |_, _| {});
+        let notified = Arc::new(AtomicU8::default());
+        let counter = notified.clone();
+        let _sub = monitor.on_change(Box::new(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        let _ = monitor.current_value();
+
+        // act
+        failing.store(true, Ordering::SeqCst);
+        retries.store(2, Ordering::SeqCst);
+        source.reload();
+
+        let kept = monitor.current_value().retries;
+
+        failing.store(false, Ordering::SeqCst);
+        wait_until(|| monitor.current_value().retries == 2);
+
+        // assert
+        assert_eq!(kept, 1);
+        assert_eq!(monitor.current_value().retries, 2);
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn monitor_with_retry_should_report_failure_when_retries_are_exhausted() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let failing = Arc::new(AtomicBool::new(false));
+        let errors = Arc::new(AtomicU8::default());
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let setup = Ref::new(SharedSetup(retries.clone()));
+        let validation: Ref<dyn ValidateOptions<Config>> = Ref::new(FailWhen(failing.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            vec![setup],
+            Vec::default(),
+            vec![validation],
+        ));
+        let source = Ref::new(ReloadSource::default());
+        let counter = errors.clone();
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory).with_retry(
+            RetryPolicy::new(2, Duration::from_millis(1)),
+            move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+        let _ = monitor.current_value();
+
+        // act
+        failing.store(true, Ordering::SeqCst);
+        retries.store(2, Ordering::SeqCst);
+        source.reload();
+        wait_until(|| {
+            let _ = monitor.current_value();
+            errors.load(Ordering::SeqCst) > 0
+        });
+
+        // assert
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+        assert_eq!(monitor.current_value().retries, 1);
+    }
+
+    #[test]
+    fn last_error_should_explain_stale_options_until_reload_succeeds() {
+        // arrange
+        let failing = Arc::new(AtomicBool::new(false));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let validation: Ref<dyn ValidateOptions<Config>> = Ref::new(FailWhen(failing.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            Vec::default(),
+            Vec::default(),
+            vec![validation],
+        ));
+        let monitor = DefaultOptionsMonitor::new(cache, Vec::default(), factory)
+            .with_retry(RetryPolicy::new(0, Duration::ZERO), |_, _| {});
+        let _ = monitor.current_value();
+
+        // act
+        failing.store(true, Ordering::SeqCst);
+        monitor.reload(None);
+
+        let stale = monitor.last_error(None);
+        let other = monitor.last_error(Some("Other"));
+
+        failing.store(false, Ordering::SeqCst);
+        monitor.reload(None);
+
+        // assert
+        assert_eq!(
+            stale.unwrap().failure_message(),
+            "the configuration is incomplete"
+        );
+        assert!(other.is_none());
+        assert!(monitor.last_error(None).is_none());
+    }
+
+    #[test]
+    fn last_error_and_generation_should_match_names_like_the_cache() {
+        // arrange
+        let failing = Arc::new(AtomicBool::new(false));
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let validation: Ref<dyn ValidateOptions<Config>> = Ref::new(FailWhen(failing.clone()));
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            Vec::default(),
+            Vec::default(),
+            vec![validation],
+        ));
+        let monitor = DefaultOptionsMonitor::new(cache, Vec::default(), factory)
+            .with_retry(RetryPolicy::new(0, Duration::ZERO), |_, _| {});
+        let _ = monitor.get(Some("Primary"));
+
+        // act
+        monitor.reload(Some("primary"));
+        failing.store(true, Ordering::SeqCst);
+        monitor.reload(Some("PRIMARY"));
+
+        // assert
+        assert_eq!(monitor.current_generation(Some("Primary")), 1);
+        assert!(monitor.last_error(Some("Primary")).is_some());
+        assert!(monitor.last_error(Some("Other")).is_none());
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn subscriptions_should_report_dead_on_arrival_listeners() {
+        // arrange
+        let cache = Ref::new(OptionsCache::<Config>::default());
+        let factory = Ref::new(DefaultOptionsFactory::new(
+            Vec::default(),
+            Vec::default(),
+            Vec::default(),
+        ));
+        let source = Ref::new(ReloadSource::default());
+        let monitor = DefaultOptionsMonitor::new(cache, vec![source.clone()], factory);
+        let _sub = monitor.on_change(Box::new(|_, _| {}));
+        let subscriptions: Vec<_> = (0..3)
+            .map(|_| monitor.on_change_event(Box::new(|_| {})))
+            .collect();
+
+        // act
+        let _ = monitor.on_change(Box::new(|_, _| {}));
+        let report = monitor.subscriptions();
+
+        // assert
+        let dead: Vec<_> = report.dead_on_arrival().collect();
+        let accumulating: Vec<_> = report.accumulating(2).collect();
+
+        assert_eq!(report.sites().len(), 3);
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].location().file(), file!());
+        assert_eq!(dead[0].live(), 0);
+        assert_eq!(accumulating.len(), 1);
+        assert_eq!(accumulating[0].live(), subscriptions.len());
+    }
 }
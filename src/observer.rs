@@ -0,0 +1,36 @@
+use crate::{ValidateOptionsResult, Value};
+use std::time::Duration;
+
+/// Defines the behavior of an object that observes the creation of configuration [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Observers are notified by the [`DefaultOptionsFactory`](crate::DefaultOptionsFactory) before
+/// and after each instance is created, which is useful for instrumentation and auditing.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait OptionsCreateObserver<T: Value> {
+    /// Occurs before configuration options are created.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the configuration options being created
+    fn before_create(&self, name: Option<&str>) {
+        let _ = name;
+    }
+
+    /// Occurs after configuration options have been created.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the configuration options that were created
+    /// * `duration` - The amount of time it took to create the configuration options
+    /// * `result` - The result of creating the configuration options
+    fn after_create(
+        &self,
+        name: Option<&str>,
+        duration: Duration,
+        result: &Result<T, ValidateOptionsResult>,
+    ) {
+        let _ = (name, duration, result);
+    }
+}
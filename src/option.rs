@@ -1,4 +1,5 @@
 use crate::{Ref, Value};
+use std::marker::PhantomData;
 
 /// Defines the behavior to retrieve configured options.
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
@@ -24,5 +25,197 @@ impl<T: Value> Options<T> for OptionsWrapper<T> {
     }
 }
 
-unsafe impl<T: Send + Sync> Send for OptionsWrapper<T> {}
-unsafe impl<T: Send + Sync> Sync for OptionsWrapper<T> {}
\ No newline at end of file
+/// Creates a wrapper around an existing value to return itself as [`Options`](Options).
+///
+/// # Arguments
+///
+/// * `existing` - The existing options value to wrap.
+///
+/// # Remarks
+///
+/// The value is shared rather than copied, which makes it convenient to provide a value returned
+/// by [`edit`](OptionsExt::edit) to a component that depends on [`Options`](Options).
+pub fn create_from<T: Value>(existing: Ref<T>) -> impl Options<T> {
+    OptionsWrapper(existing)
+}
+
+impl<T: Value> Options<T> for Ref<T> {
+    fn value(&self) -> Ref<T> {
+        self.clone()
+    }
+}
+
+/// Creates [`Options`](Options) that are provided by a function.
+///
+/// # Arguments
+///
+/// * `value` - The function that provides the options value
+///
+/// # Remarks
+///
+/// The function is called each time the value is requested, which allows a simple call site or
+/// test to provide options without a service provider.
+pub fn from_fn<T, F>(value: F) -> impl Options<T>
+where
+    T: Value,
+    F: Fn() -> Ref<T> + Value,
+{
+    OptionsFn {
+        value,
+        _options: PhantomData,
+    }
+}
+
+struct OptionsFn<T, F> {
+    value: F,
+    _options: PhantomData<T>,
+}
+
+impl<T, F> Options<T> for OptionsFn<T, F>
+where
+    T: Value,
+    F: Fn() -> Ref<T> + Value,
+{
+    fn value(&self) -> Ref<T> {
+        (self.value)()
+    }
+}
+
+/// Defines extension methods for [`Options`](Options).
+pub trait OptionsExt<T: Value> {
+    /// Gets a copy of the configured value with a mutation applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `mutation` - The function applied to the copy of the configured value
+    ///
+    /// # Remarks
+    ///
+    /// The configured value is cloned and never changed, which is useful in tests that require the
+    /// same options with only a few fields changed.
+    fn edit<F: FnOnce(&mut T)>(&self, mutation: F) -> Ref<T>
+    where
+        T: Clone;
+
+    /// Gets a value derived from the configured value.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - The function that derives the value from the configured value
+    ///
+    /// # Remarks
+    ///
+    /// The configured value is released as soon as the derived value is returned, which is useful
+    /// when only a single field is needed.
+    fn map<U, F: FnOnce(&T) -> U>(&self, selector: F) -> U;
+
+    /// Attempts to take the configured value.
+    ///
+    /// # Remarks
+    ///
+    /// The value is only taken when nothing else holds it, such as a cache; otherwise, the shared
+    /// value is returned as an error.
+    fn try_unwrap(&self) -> Result<T, Ref<T>>;
+
+    /// Takes the configured value if nothing else holds it or gets a copy of it.
+    fn unwrap_or_clone(&self) -> T
+    where
+        T: Clone;
+}
+
+impl<T: Value, O: Options<T> + ?Sized> OptionsExt<T> for O {
+    fn edit<F: FnOnce(&mut T)>(&self, mutation: F) -> Ref<T>
+    where
+        T: Clone,
+    {
+        let mut options = T::clone(&self.value());
+        mutation(&mut options);
+        Ref::new(options)
+    }
+
+    fn map<U, F: FnOnce(&T) -> U>(&self, selector: F) -> U {
+        selector(&self.value())
+    }
+
+    fn try_unwrap(&self) -> Result<T, Ref<T>> {
+        Ref::try_unwrap(self.value())
+    }
+
+    fn unwrap_or_clone(&self) -> T
+    where
+        T: Clone,
+    {
+        self.try_unwrap()
+            .unwrap_or_else(|options| T::clone(&options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct TestOptions {
+        enabled: bool,
+        retries: u8,
+    }
+
+    #[test]
+    fn edit_should_return_changed_copy_of_options() {
+        // arrange
+        let options = create(TestOptions {
+            enabled: true,
+            retries: 3,
+        });
+
+        // act
+        let edited = create_from(options.edit(|o| o.retries = 5));
+
+        // assert
+        assert!(edited.value().enabled);
+        assert_eq!(edited.value().retries, 5);
+        assert_eq!(options.value().retries, 3);
+    }
+
+    #[test]
+    fn ref_and_function_should_satisfy_options() {
+        // arrange
+        let value = Ref::new(TestOptions {
+            enabled: true,
+            retries: 3,
+        });
+        let shared = value.clone();
+        let options: Vec<Ref<dyn Options<TestOptions>>> = vec![
+            Ref::new(value.clone()),
+            Ref::new(from_fn(move || shared.clone())),
+        ];
+
+        // act
+        let values: Vec<_> = options.iter().map(|o| o.value()).collect();
+
+        // assert
+        assert!(values.iter().all(|v| Ref::ptr_eq(v, &value)));
+    }
+
+    #[test]
+    fn try_unwrap_should_only_take_value_that_is_not_shared() {
+        // arrange
+        let shared = create(TestOptions {
+            enabled: true,
+            retries: 3,
+        });
+        let unique = from_fn(|| Ref::new(TestOptions::default()));
+
+        // act
+        let retries = shared.map(|o| o.retries);
+        let taken = unique.try_unwrap();
+        let copied = shared.unwrap_or_clone();
+
+        // assert
+        assert_eq!(retries, 3);
+        assert!(taken.is_ok());
+        assert!(shared.try_unwrap().is_err());
+        assert!(copied.enabled);
+    }
+}
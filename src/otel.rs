@@ -0,0 +1,122 @@
+// the outcome of every change processed by an options monitor is recorded as an OpenTelemetry event
+// when the 'otel' feature is enabled and compiles away to nothing otherwise. the event is added to a
+// span created by the global tracer provider, which is a child of the active span, if there is one.
+
+#![cfg_attr(not(feature = "otel"), allow(clippy::extra_unused_type_parameters))]
+
+// the change marked the options stale; they are created when they are next retrieved
+pub(crate) const INVALIDATED: &str = "invalidated";
+
+// the options were rebuilt and replaced the cached options
+pub(crate) const SUCCEEDED: &str = "succeeded";
+
+// the options were rebuilt, but are equal to the cached options
+pub(crate) const UNCHANGED: &str = "unchanged";
+
+// the options could not be rebuilt and the change will be retried
+pub(crate) const RETRYING: &str = "retrying";
+
+// the options could not be rebuilt
+pub(crate) const FAILED: &str = "failed";
+
+#[cfg(feature = "otel")]
+const SCOPE: &str = "more_options";
+
+pub(crate) fn rebuilt<T>(name: Option<&str>, source: Option<&str>, outcome: &'static str) {
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::{Span, Tracer};
+        use opentelemetry::{global, KeyValue};
+
+        let mut span = global::tracer(SCOPE).start("options.change");
+
+        span.add_event(
+            "options.rebuild",
+            vec![
+                KeyValue::new("options.type", std::any::type_name::<T>()),
+                KeyValue::new("options.name", name.unwrap_or_default().to_owned()),
+                KeyValue::new("options.source", source.unwrap_or_default().to_owned()),
+                KeyValue::new("options.outcome", outcome),
+            ],
+        );
+        span.end();
+    }
+
+    #[cfg(not(feature = "otel"))]
+    let _ = (name, source, outcome);
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+
+    use crate::*;
+    use opentelemetry::{global, Value};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+    use std::collections::HashMap;
+    use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default)]
+    struct TracedOptions;
+
+    struct TokenSource(SharedChangeToken<SingleChangeToken>);
+
+    impl OptionsChangeTokenSource<TracedOptions> for TokenSource {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.0.clone())
+        }
+
+        fn source_name(&self) -> Option<&str> {
+            Some("Traced")
+        }
+    }
+
+    fn new_monitor(
+        token: &SharedChangeToken<SingleChangeToken>,
+    ) -> DefaultOptionsMonitor<TracedOptions> {
+        let source: Ref<dyn OptionsChangeTokenSource<TracedOptions>> =
+            Ref::new(TokenSource(token.clone()));
+        let factory: Ref<dyn OptionsFactory<TracedOptions>> =
+            Ref::new(DefaultOptionsFactory::new(vec![], vec![], vec![]));
+
+        DefaultOptionsMonitor::new(Ref::new(OptionsCache::default()), vec![source], factory)
+    }
+
+    #[test]
+    fn change_should_record_rebuild_event() {
+        // arrange
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let token = SharedChangeToken::<SingleChangeToken>::default();
+        let monitor = new_monitor(&token);
+
+        global::set_tracer_provider(provider);
+
+        // act
+        token.notify();
+
+        // assert
+        let spans = exporter.get_finished_spans().unwrap();
+        let event = spans
+            .iter()
+            .flat_map(|s| s.events.iter())
+            .find(|e| {
+                e.attributes.iter().any(|a| {
+                    a.key.as_str() == "options.type" && a.value.as_str().ends_with("TracedOptions")
+                })
+            })
+            .unwrap();
+        let attributes: HashMap<_, _> = event
+            .attributes
+            .iter()
+            .map(|a| (a.key.as_str(), a.value.clone()))
+            .collect();
+
+        drop(monitor);
+        assert_eq!(event.name, "options.rebuild");
+        assert_eq!(attributes["options.name"], Value::from(""));
+        assert_eq!(attributes["options.source"], Value::from("Traced"));
+        assert_eq!(attributes["options.outcome"], Value::from("invalidated"));
+    }
+}
@@ -0,0 +1,417 @@
+// a push is an authorized POST to a configured path. the listener is intentionally tiny; it only
+// understands enough HTTP/1.1 to read the request line and headers, and it discards any body. each
+// connection is answered on its own thread so a slow client cannot delay the others. applications
+// which already host an HTTP server should call HttpPushTrigger::trigger from their own route.
+
+use crate::{OptionsChangeTokenSource, Value};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Result, Write};
+use std::marker::PhantomData;
+use std::mem::take;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
+use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+const MAX_HEADER_LEN: u64 = 8 * 1024;
+const MAX_BODY_LEN: u64 = 64 * 1024;
+const MAX_CONNECTIONS: usize = 16;
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct PushWatch {
+    token: Mutex<SharedChangeToken<SingleChangeToken>>,
+    listener: Option<SocketAddr>,
+}
+
+impl PushWatch {
+    fn notify(&self) {
+        // a token only signals once so it's replaced before it's signaled
+        let token = take(&mut *self.token.lock().unwrap());
+        token.notify();
+    }
+}
+
+impl Drop for PushWatch {
+    fn drop(&mut self) {
+        // the listener blocks until a connection is accepted, so it's woken to observe that the
+        // watch was dropped
+        if let Some(mut addr) = self.listener {
+            if addr.ip().is_unspecified() {
+                addr.set_ip(match addr.ip() {
+                    IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                });
+            }
+
+            let _ = TcpStream::connect_timeout(&addr, TIMEOUT);
+        }
+    }
+}
+
+/// Represents a trigger used to push changes to [`Options`](crate::Options), such as from an
+/// external configuration service.
+///
+/// # Remarks
+///
+/// A trigger can be pushed from the route of an existing HTTP server by calling
+/// [`trigger`](HttpPushTrigger::trigger) or it can [listen](HttpPushTrigger::listen) for pushes
+/// itself. Every [`HttpPushTokenSource`] created from the trigger, or any of its clones, is
+/// notified when a change is pushed.
+#[derive(Clone, Default)]
+pub struct HttpPushTrigger {
+    watch: Arc<PushWatch>,
+    local_addr: Option<SocketAddr>,
+}
+
+impl HttpPushTrigger {
+    /// Initializes a new HTTP push trigger without a listener.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Initializes a new HTTP push trigger that listens for pushes.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to listen on, such as `127.0.0.1:9090`
+    /// * `path` - The path a change is pushed to, such as `/config/changed`
+    /// * `secret` - The secret shared with the configuration service that authorizes a push
+    ///
+    /// # Remarks
+    ///
+    /// A change is pushed by sending a `POST` request to the path with an `Authorization: Bearer`
+    /// header that contains the secret, which is answered with `204 No Content`. A request without
+    /// the secret is answered with `401 Unauthorized` and any body is ignored. Each connection is
+    /// answered on its own thread and is closed if the request is not received within one second.
+    /// The listener runs on a background thread until every clone of the trigger and every source
+    /// created from it is dropped. An error is returned if the secret is empty.
+    pub fn listen<A: ToSocketAddrs>(addr: A, path: &str, secret: &str) -> Result<Self> {
+        if secret.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The secret that authorizes a push cannot be empty.",
+            ));
+        }
+
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let watch = Arc::new(PushWatch {
+            token: Default::default(),
+            listener: Some(local_addr),
+        });
+        let route = Arc::new(Route {
+            path: path.to_owned(),
+            secret: secret.to_owned(),
+        });
+        let weak = Arc::downgrade(&watch);
+
+        thread::spawn(move || accept(listener, weak, route));
+
+        Ok(Self {
+            watch,
+            local_addr: Some(local_addr),
+        })
+    }
+
+    /// Gets the local address of the listener, if any.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Pushes a change to every source created from the trigger.
+    pub fn trigger(&self) {
+        self.watch.notify()
+    }
+}
+
+/// Represents a change token source for [`Options`](crate::Options) that are notified when a
+/// change is pushed to an [`HttpPushTrigger`].
+pub struct HttpPushTokenSource<T: Value> {
+    name: Option<String>,
+    watch: Arc<PushWatch>,
+    _data: PhantomData<T>,
+}
+
+impl<T: Value> HttpPushTokenSource<T> {
+    /// Initializes a new HTTP push change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being watched
+    /// * `trigger` - The [trigger](HttpPushTrigger) changes are pushed to
+    pub fn new(name: Option<&str>, trigger: &HttpPushTrigger) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            watch: trigger.watch.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for HttpPushTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(self.watch.token.lock().unwrap().clone())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("push")
+    }
+}
+
+struct Route {
+    path: String,
+    secret: String,
+}
+
+impl Route {
+    fn authorized(&self, authorization: &str) -> bool {
+        let token = match authorization.split_once(' ') {
+            Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => token.trim(),
+            _ => return false,
+        };
+
+        // every byte is compared so that the time taken doesn't reveal how much of the secret
+        // matched
+        token.len() == self.secret.len()
+            && token
+                .bytes()
+                .zip(self.secret.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+fn accept(listener: TcpListener, watch: Weak<PushWatch>, route: Arc<Route>) {
+    let connections = Arc::new(AtomicUsize::default());
+
+    for stream in listener.incoming() {
+        let watch = match watch.upgrade() {
+            Some(watch) => watch,
+            _ => return,
+        };
+        let stream = match stream {
+            Ok(stream) => stream,
+            _ => continue,
+        };
+
+        // excess connections are closed rather than queued behind slow clients
+        if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            connections.fetch_sub(1, Ordering::SeqCst);
+            continue;
+        }
+
+        let route = route.clone();
+        let connections = connections.clone();
+
+        thread::spawn(move || {
+            // a client that misbehaves only affects its own request
+            let _ = respond(stream, &watch, &route);
+            connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+fn respond(stream: TcpStream, watch: &PushWatch, route: &Route) -> Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_HEADER_LEN));
+    let mut line = String::new();
+
+    reader.read_line(&mut line)?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let target = parts.next().unwrap_or_default();
+    let target = target.split('?').next().unwrap_or_default().to_owned();
+    let mut content_length = 0;
+    let mut authorized = false;
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim();
+
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or_default();
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorized = route.authorized(value.trim());
+            }
+        }
+    }
+
+    // the header limit no longer applies once the body is reached
+    reader.get_mut().set_limit(MAX_BODY_LEN);
+    std::io::copy(
+        &mut (&mut reader).take(content_length.min(MAX_BODY_LEN)),
+        &mut std::io::sink(),
+    )?;
+
+    let status = if target != route.path {
+        "404 Not Found"
+    } else if method != "POST" {
+        "405 Method Not Allowed"
+    } else if !authorized {
+        "401 Unauthorized"
+    } else {
+        watch.notify();
+        "204 No Content"
+    };
+
+    let mut stream = stream;
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::atomic::AtomicU8;
+    use std::time::Instant;
+
+    #[derive(Default)]
+    struct Config {
+        generation: u8,
+    }
+
+    fn send(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+
+        stream.write_all(request.as_bytes()).unwrap();
+        stream.read_to_string(&mut response).unwrap();
+        response.lines().next().unwrap_or_default().to_owned()
+    }
+
+    #[test]
+    fn token_should_change_when_change_is_pushed_to_listener() {
+        // arrange
+        let trigger = HttpPushTrigger::listen("127.0.0.1:0", "/config/changed", "s3cr3t").unwrap();
+        let addr = trigger.local_addr().unwrap();
+        let source = HttpPushTokenSource::<Config>::new(None, &trigger);
+        let token = source.token();
+        let changes = Arc::new(AtomicUsize::default());
+        let counter = changes.clone();
+        let _registration = token.register(
+            Box::new(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+            None,
+        );
+
+        // act
+        let not_found = send(addr, "POST /other HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        let not_allowed = send(
+            addr,
+            "GET /config/changed HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        );
+        let unauthorized = send(
+            addr,
+            "POST /config/changed HTTP/1.1\r\nAuthorization: Bearer guess\r\n\r\n",
+        );
+        let pushed = send(
+            addr,
+            "POST /config/changed?v=2 HTTP/1.1\r\nHost: localhost\r\n\
+             Authorization: Bearer s3cr3t\r\nContent-Length: 2\r\n\r\n{}",
+        );
+
+        // assert
+        assert_eq!(not_found, "HTTP/1.1 404 Not Found");
+        assert_eq!(not_allowed, "HTTP/1.1 405 Method Not Allowed");
+        assert_eq!(unauthorized, "HTTP/1.1 401 Unauthorized");
+        assert_eq!(pushed, "HTTP/1.1 204 No Content");
+        assert_eq!(changes.load(Ordering::SeqCst), 1);
+        assert!(token.changed());
+        assert!(!source.token().changed());
+    }
+
+    #[test]
+    fn listener_should_answer_push_while_another_client_is_idle() {
+        // arrange
+        let trigger = HttpPushTrigger::listen("127.0.0.1:0", "/config/changed", "s3cr3t").unwrap();
+        let addr = trigger.local_addr().unwrap();
+        let idle = TcpStream::connect(addr).unwrap();
+
+        // act
+        let pushed = send(
+            addr,
+            "POST /config/changed HTTP/1.1\r\nAuthorization: Bearer s3cr3t\r\n\r\n",
+        );
+
+        // assert
+        assert_eq!(pushed, "HTTP/1.1 204 No Content");
+        drop(idle);
+    }
+
+    #[test]
+    fn listen_should_require_secret() {
+        // arrange
+        let addr = "127.0.0.1:0";
+
+        // act
+        let result = HttpPushTrigger::listen(addr, "/config/changed", "");
+
+        // assert
+        assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn listener_should_stop_when_trigger_is_dropped() {
+        // arrange
+        let trigger = HttpPushTrigger::listen("127.0.0.1:0", "/config/changed", "s3cr3t").unwrap();
+        let addr = trigger.local_addr().unwrap();
+        let started = Instant::now();
+
+        // act
+        drop(trigger);
+
+        while TcpStream::connect(addr).is_ok() && started.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        // assert
+        assert!(TcpStream::connect(addr).is_err());
+    }
+
+    #[test]
+    fn reload_on_push_should_reload_options() {
+        // arrange
+        let trigger = HttpPushTrigger::new();
+        let generation = Arc::new(AtomicU8::default());
+        let counter = generation.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .configure(move |o| o.generation = counter.fetch_add(1, Ordering::SeqCst) + 1)
+            .reload_on_push(&trigger)
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let original = monitor.current_value().generation;
+
+        // act
+        trigger.trigger();
+
+        // assert
+        assert_eq!(original, 1);
+        assert_eq!(monitor.current_value().generation, 2);
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt::{Debug, Formatter, Result as FormatResult};
+
+/// Represents the text displayed in place of a secret value.
+pub const REDACTED: &str = "***";
+
+/// Defines the behavior of configuration options that can be formatted with their secrets masked.
+///
+/// # Remarks
+///
+/// This trait is typically implemented using `#[derive(RedactedDebug)]`, which masks every field
+/// marked with `#[options(secret)]`.
+pub trait RedactedDebug {
+    /// Formats the value using the given formatter with all secrets masked.
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - The [formatter](std::fmt::Formatter) to write to
+    fn fmt_redacted(&self, formatter: &mut Formatter<'_>) -> FormatResult;
+
+    /// Gets a view of the value whose [`Debug`](std::fmt::Debug) output masks all secrets.
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+/// Defines the behavior of configuration options that can be serialized with their secrets masked.
+///
+/// # Remarks
+///
+/// This trait is typically implemented using `#[derive(RedactedSerialize)]`, which masks every
+/// field marked with `#[options(secret)]`.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub trait RedactedSerialize {
+    /// Serializes the value using the given serializer with all secrets masked.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - The [serializer](serde::Serializer) to write to
+    fn serialize_redacted<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Represents a view of a value with all secrets masked.
+///
+/// # Remarks
+///
+/// The view implements [`Debug`](std::fmt::Debug) when the value implements [`RedactedDebug`] and
+/// [`Serialize`](serde::Serialize) when the value implements `RedactedSerialize`, which makes it
+/// suitable for logging, health endpoints, and validation failure messages.
+pub struct Redacted<'a, T: ?Sized>(&'a T);
+
+impl<'a, T: ?Sized> Redacted<'a, T> {
+    /// Initializes a new redacted view.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to mask the secrets of
+    pub fn new(value: &'a T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: RedactedDebug + ?Sized> Debug for Redacted<'_, T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        self.0.fmt_redacted(formatter)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: RedactedSerialize + ?Sized> serde::Serialize for Redacted<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_redacted(serializer)
+    }
+}
+
+/// Defines a marker for configuration options whose values must never be included in diagnostics.
+///
+/// # Remarks
+///
+/// Masking secrets with [`RedactedDebug`] or `RedactedSerialize` relies on every secret being
+/// marked. Options which are marked sensitive using [`sensitive`](crate::OptionsBuilder::sensitive)
+/// are additionally reported by the crate's own log events, dumps, catalogs, and support bundles
+/// without any of their values; only the names of their fields are included. This trait is typically
+/// implemented using `#[options(sensitive)]` with `#[derive(Options)]`.
+pub trait SensitiveOptions {}
+
+#[cfg(feature = "di")]
+pub(crate) struct SensitiveMarker<T>(std::marker::PhantomData<T>);
+
+#[cfg(feature = "di")]
+impl<T: SensitiveOptions> SensitiveMarker<T> {
+    pub(crate) fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+// the values of sensitive options are masked while keeping the names of their fields
+#[cfg(feature = "dump")]
+pub(crate) fn mask_values(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, mask_values(value)))
+                .collect(),
+        ),
+        _ => serde_json::Value::String(REDACTED.to_owned()),
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+
+    use super::*;
+    use crate::{RedactedDebug, RedactedSerialize};
+
+    #[derive(RedactedDebug, RedactedSerialize)]
+    struct DbOptions {
+        host: String,
+        #[options(secret)]
+        password: String,
+    }
+
+    fn new_options() -> DbOptions {
+        DbOptions {
+            host: "localhost".into(),
+            password: "p@ssw0rd".into(),
+        }
+    }
+
+    #[test]
+    fn redacted_debug_should_mask_secrets() {
+        // arrange
+        let options = new_options();
+
+        // act
+        let text = format!("{:?}", options.redacted());
+
+        // assert
+        assert_eq!(text, r#"DbOptions { host: "localhost", password: *** }"#);
+    }
+
+    #[test]
+    fn redacted_serialize_should_mask_secrets() {
+        // arrange
+        let options = new_options();
+
+        // act
+        let json = serde_json::to_string(&Redacted::new(&options)).unwrap();
+
+        // assert
+        assert_eq!(json, r#"{"host":"localhost","password":"***"}"#);
+    }
+}
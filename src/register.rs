@@ -0,0 +1,138 @@
+use di::ServiceCollection;
+
+/// Defines the behavior of configuration [`Options`](crate::Options) that register themselves.
+///
+/// # Remarks
+///
+/// This trait is typically implemented using `#[derive(Options)]`, which allows library authors to
+/// ship options types that applications register with a single call.
+pub trait RegisterOptions {
+    /// Registers the options and all of their associated services.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [collection of services](di::ServiceCollection) to register the options in
+    fn add_to(services: &mut ServiceCollection);
+
+    /// Gets the default name of the options, if any.
+    fn name() -> Option<&'static str> {
+        None
+    }
+
+    /// Gets the key of the configuration section the options are bound to, if any.
+    fn section() -> Option<&'static str> {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+
+    use super::*;
+    use crate::{
+        ext::*, ConfigureOptions, Options, OptionsSnapshot, ValidateOptions, ValidateOptionsResult,
+    };
+    use config::{ext::*, Configuration, ConfigurationBuilder, DefaultConfigurationBuilder};
+    use di::{singleton_factory, Ref};
+    use serde::Deserialize;
+
+    #[derive(Default, crate::Options)]
+    #[options(configurer = TestConfigurer, validator = TestValidation)]
+    struct TestOptions {
+        retries: u8,
+    }
+
+    #[derive(Default, Deserialize, crate::Options)]
+    #[serde(rename_all(deserialize = "PascalCase"))]
+    #[options(name = "Primary", section = "Db")]
+    struct DbOptions {
+        pool_size: usize,
+    }
+
+    #[derive(Default)]
+    struct TestConfigurer;
+
+    impl ConfigureOptions<TestOptions> for TestConfigurer {
+        fn configure(&self, _name: Option<&str>, options: &mut TestOptions) {
+            options.retries = 3;
+        }
+    }
+
+    #[derive(Default)]
+    struct TestValidation;
+
+    impl ValidateOptions<TestOptions> for TestValidation {
+        fn validate(&self, _name: Option<&str>, options: &TestOptions) -> ValidateOptionsResult {
+            if options.retries > 0 {
+                ValidateOptionsResult::success()
+            } else {
+                ValidateOptionsResult::fail("Retries must be greater than zero")
+            }
+        }
+    }
+
+    #[test]
+    fn add_to_should_register_derived_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        TestOptions::add_to(&mut services);
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let options = provider.get_required::<dyn Options<TestOptions>>();
+
+        // assert
+        assert_eq!(options.value().retries, 3);
+    }
+
+    fn new_config() -> Ref<dyn Configuration> {
+        Ref::from(
+            DefaultConfigurationBuilder::new()
+                .add_in_memory(&[("Db:PoolSize", "10")])
+                .build()
+                .unwrap()
+                .as_config(),
+        )
+    }
+
+    #[test]
+    fn add_to_should_bind_declared_section_with_declared_name() {
+        // arrange
+        let config = new_config();
+        let mut services = ServiceCollection::new();
+
+        services.add(singleton_factory(move |_| config.clone()));
+        DbOptions::add_to(&mut services);
+
+        let provider = services.build_provider().unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<DbOptions>>();
+
+        // act
+        let options = snapshot.get(Some("Primary"));
+
+        // assert
+        assert_eq!(options.pool_size, 10);
+    }
+
+    #[test]
+    fn apply_declared_config_should_bind_declared_section_with_declared_name() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .apply_declared_config::<DbOptions>(new_config())
+            .build_provider()
+            .unwrap();
+        let snapshot = provider.get_required::<dyn OptionsSnapshot<DbOptions>>();
+
+        // act
+        let named = snapshot.get(Some("Primary"));
+        let unnamed = snapshot.get(None);
+
+        // assert
+        assert_eq!(DbOptions::name(), Some("Primary"));
+        assert_eq!(DbOptions::section(), Some("Db"));
+        assert_eq!(named.pool_size, 10);
+        assert_eq!(unnamed.pool_size, 0);
+    }
+}
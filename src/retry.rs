@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+/// Represents the policy used to retry creating monitored [`Options`](crate::Options) that failed
+/// to reload.
+///
+/// # Remarks
+///
+/// The delay before each retry grows exponentially from the initial delay by the multiplier until
+/// it reaches the maximum delay.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+}
+
+impl RetryPolicy {
+    /// Initializes a new retry policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - The maximum number of retries after a failed reload
+    /// * `initial_delay` - The delay before the first retry
+    pub fn new(max_retries: u32, initial_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_delay,
+            max_delay: Duration::from_secs(60),
+            multiplier: 2,
+        }
+    }
+
+    /// Sets the maximum delay between retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_delay` - The maximum delay between retries, which defaults to 60 seconds
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the multiplier applied to the delay after each retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `multiplier` - The multiplier applied to the delay, which defaults to 2
+    pub fn with_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Gets the maximum number of retries after a failed reload.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Gets the delay before the specified retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The one-based number of the retry
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn delay_should_grow_exponentially_up_to_max_delay() {
+        // arrange
+        let policy =
+            RetryPolicy::new(5, Duration::from_millis(100)).with_max_delay(Duration::from_secs(1));
+
+        // act
+        let delays: Vec<_> = (1..=5).map(|attempt| policy.delay(attempt)).collect();
+
+        // assert
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000].map(Duration::from_millis)
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{
+    DefaultOptionsMessages, OptionsMessages, ValidateOptions, ValidateOptionsResult,
+    ValidationRule, Value,
+};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+type Check<V> = dyn Fn(&str, &V) -> Option<String> + Send + Sync;
+
+/// Represents a composable [validation rule](crate::ValidationRule) for a single field of
+/// configuration [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// Every constraint of the rule is checked and each failure is qualified with the name of the
+/// field. A rule can be registered using
+/// [`validate_with_rule`](crate::OptionsBuilder::validate_with_rule) or directly as
+/// [`ValidateOptions`](crate::ValidateOptions), in which case it applies to every name of the
+/// options.
+pub struct FieldRule<T, V: ?Sized, F> {
+    field: String,
+    value: F,
+    checks: Vec<Box<Check<V>>>,
+    _options: PhantomData<T>,
+}
+
+/// Creates a new rule for a field.
+///
+/// # Arguments
+///
+/// * `field` - The name of the field used in failure messages
+/// * `value` - The function used to get the value of the field
+///
+/// # Example
+///
+/// ```
+/// use options::rules::rule;
+///
+/// struct AppOptions {
+///     name: String,
+/// }
+///
+/// let rule = rule("name", |o: &AppOptions| &o.name).non_empty().max_len(64);
+/// ```
+pub fn rule<T, V, F>(field: &str, value: F) -> FieldRule<T, V, F>
+where
+    V: ?Sized,
+    F: Fn(&T) -> &V,
+{
+    FieldRule {
+        field: field.to_owned(),
+        value,
+        checks: Vec::new(),
+        _options: PhantomData,
+    }
+}
+
+impl<T, V: ?Sized, F> FieldRule<T, V, F> {
+    fn and<C>(mut self, check: C) -> Self
+    where
+        C: Fn(&str, &V) -> Option<String> + Send + Sync + 'static,
+    {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Requires the field to satisfy a predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The predicate the value of the field must satisfy
+    /// * `failure` - The failure message, which is prefixed with the name of the field
+    pub fn must<P>(self, predicate: P, failure: &str) -> Self
+    where
+        P: Fn(&V) -> bool + Send + Sync + 'static,
+    {
+        let failure = failure.to_owned();
+
+        self.and(move |field, value| {
+            if predicate(value) {
+                None
+            } else {
+                Some(format!("'{}' {}", field, failure))
+            }
+        })
+    }
+}
+
+impl<T, V, F> FieldRule<T, V, F>
+where
+    V: AsRef<str> + ?Sized,
+{
+    /// Requires the field to not be empty.
+    pub fn non_empty(self) -> Self {
+        self.and(|field, value| {
+            if value.as_ref().is_empty() {
+                Some(format!("'{}' must not be empty.", field))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Requires the field to have a minimum number of characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum number of characters
+    pub fn min_len(self, min: usize) -> Self {
+        self.and(move |field, value| {
+            if value.as_ref().chars().count() < min {
+                Some(format!("'{}' must be at least {} characters.", field, min))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Requires the field to have a maximum number of characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of characters
+    pub fn max_len(self, max: usize) -> Self {
+        self.and(move |field, value| {
+            if value.as_ref().chars().count() > max {
+                Some(format!("'{}' must be at most {} characters.", field, max))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Requires the number of characters in the field to be within a range.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The inclusive range of allowed lengths
+    pub fn length(self, range: RangeInclusive<usize>) -> Self {
+        self.and(move |field, value| {
+            if range.contains(&value.as_ref().chars().count()) {
+                None
+            } else {
+                Some(format!(
+                    "'{}' must be between {} and {} characters.",
+                    field,
+                    range.start(),
+                    range.end()
+                ))
+            }
+        })
+    }
+
+    /// Requires the field to match a pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The function used to match the value of the field
+    /// * `description` - The description of the pattern used in the failure message, such as
+    ///   `"a lowercase identifier"`
+    ///
+    /// # Remarks
+    ///
+    /// The pattern is a function rather than a regular expression so that any matcher can be used,
+    /// including a compiled regular expression.
+    pub fn matches<P>(self, pattern: P, description: &str) -> Self
+    where
+        P: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        let description = description.to_owned();
+
+        self.and(move |field, value| {
+            if pattern(value.as_ref()) {
+                None
+            } else {
+                Some(format!("'{}' must be {}.", field, description))
+            }
+        })
+    }
+
+    /// Requires the field to be one of the specified values.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The allowed values, which are compared with case sensitivity
+    pub fn one_of(self, values: &[&str]) -> Self {
+        let values: Vec<_> = values.iter().map(|v| (*v).to_owned()).collect();
+
+        self.and(move |field, value| {
+            if values.iter().any(|v| v == value.as_ref()) {
+                None
+            } else {
+                Some(format!(
+                    "'{}' must be one of: {}.",
+                    field,
+                    values.join(", ")
+                ))
+            }
+        })
+    }
+}
+
+impl<T, V, F> FieldRule<T, Option<V>, F> {
+    /// Requires the field to have a value.
+    pub fn required(self) -> Self {
+        self.and(|field, value| {
+            if value.is_none() {
+                Some(DefaultOptionsMessages.missing_setting(field))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<T, V, F> FieldRule<T, V, F>
+where
+    V: PartialOrd + Display + Send + Sync + 'static,
+{
+    /// Requires the field to be within a range.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The inclusive range of allowed values
+    pub fn range(self, range: RangeInclusive<V>) -> Self {
+        self.and(move |field, value| {
+            if range.contains(value) {
+                None
+            } else {
+                Some(format!(
+                    "'{}' must be between {} and {}.",
+                    field,
+                    range.start(),
+                    range.end()
+                ))
+            }
+        })
+    }
+}
+
+impl<T, V, F> ValidationRule<T> for FieldRule<T, V, F>
+where
+    T: Value,
+    V: ?Sized,
+    F: Fn(&T) -> &V + Value,
+{
+    fn check(&self, options: &T) -> ValidateOptionsResult {
+        let value = (self.value)(options);
+        let failures: Vec<_> = self
+            .checks
+            .iter()
+            .filter_map(|check| check(&self.field, value))
+            .collect();
+
+        if failures.is_empty() {
+            ValidateOptionsResult::success()
+        } else {
+            ValidateOptionsResult::fail_many(failures.iter())
+        }
+    }
+}
+
+impl<T, V, F> ValidateOptions<T> for FieldRule<T, V, F>
+where
+    T: Value,
+    V: ?Sized,
+    F: Fn(&T) -> &V + Value,
+{
+    fn validate(&self, _name: Option<&str>, options: &T) -> ValidateOptionsResult {
+        self.check(options)
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsFactory};
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct AppOptions {
+        api_key: Option<String>,
+        name: String,
+        level: String,
+        workers: u8,
+    }
+
+    #[test]
+    fn rule_should_report_every_failed_constraint() {
+        // arrange
+        let rule = rule("name", |o: &AppOptions| &o.name)
+            .non_empty()
+            .min_len(3)
+            .matches(|s| s.chars().all(|c| c.is_ascii_lowercase()), "lowercase");
+        let options = AppOptions::default();
+
+        // act
+        let result = rule.check(&options);
+
+        // assert
+        assert_eq!(
+            result.failures(),
+            [
+                "'name' must not be empty.",
+                "'name' must be at least 3 characters.",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_with_rule_should_validate_fields() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<AppOptions>()
+            .configure(|o| {
+                o.name = "MyApp".into();
+                o.level = "verbose".into();
+                o.workers = 0;
+            })
+            .validate_with_rule(rule("name", |o: &AppOptions| &o.name).length(1..=64))
+            .validate_with_rule(
+                rule("level", |o: &AppOptions| &o.level).one_of(&["debug", "info", "warn"]),
+            )
+            .validate_with_rule(rule("workers", |o: &AppOptions| &o.workers).range(1..=16))
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<dyn OptionsFactory<AppOptions>>();
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failures(),
+            [
+                "'level' must be one of: debug, info, warn.",
+                "'workers' must be between 1 and 16.",
+            ]
+        );
+    }
+
+    #[test]
+    fn require_should_report_missing_settings() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<AppOptions>()
+            .require(|o| o.api_key.is_some(), "api_key")
+            .validate_with_rule(rule("api_key", |o: &AppOptions| &o.api_key).required())
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<dyn OptionsFactory<AppOptions>>();
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failures(),
+            [
+                "missing required setting `api_key`",
+                "missing required setting `api_key`",
+            ]
+        );
+    }
+}
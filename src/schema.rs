@@ -0,0 +1,539 @@
+use crate::{ValidateOptionsResult, ValidationRule, Value};
+use serde::Serialize;
+use serde_json::{Map, Value as Json};
+use std::fs;
+use std::path::Path;
+
+/// Represents a JSON Schema used to [validate](crate::ValidationRule) configuration
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// The options are serialized to JSON and checked against the schema. The following keywords
+/// are supported: `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+/// `items`, `minItems`, `maxItems`, `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`,
+/// `minLength`, and `maxLength`. The annotations `$schema`, `$id`, `$comment`, `title`,
+/// `description`, `default`, `examples`, `deprecated`, `readOnly`, and `writeOnly` are allowed, but
+/// have no effect. A schema with any other keyword, or with a keyword whose value is malformed, is
+/// rejected when it is parsed rather than partially enforced. Failures are reported with the path of
+/// the value, such as `servers[0].port`, and never include the configured value.
+#[derive(Clone, Debug)]
+pub struct JsonSchema(Json);
+
+impl JsonSchema {
+    /// Parses a JSON Schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The JSON text of the schema
+    pub fn parse(schema: &str) -> Result<Self, String> {
+        match serde_json::from_str::<Json>(schema) {
+            Ok(schema) => {
+                verify(&schema, "#")?;
+                Ok(Self(schema))
+            }
+            Err(error) => Err(format!("The JSON Schema is invalid: {}", error)),
+        }
+    }
+
+    /// Loads a JSON Schema from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the schema file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(schema) => Self::parse(&schema),
+            Err(error) => Err(format!(
+                "The JSON Schema '{}' could not be read: {}",
+                path.display(),
+                error
+            )),
+        }
+    }
+
+    /// Validates a JSON value against the schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to validate
+    pub fn check_value(&self, value: &Json) -> ValidateOptionsResult {
+        let mut failures = Vec::new();
+
+        check(&self.0, value, "", &mut failures);
+
+        if failures.is_empty() {
+            ValidateOptionsResult::success()
+        } else {
+            ValidateOptionsResult::fail_many(failures.iter())
+        }
+    }
+}
+
+impl<T: Value + Serialize> ValidationRule<T> for JsonSchema {
+    fn check(&self, options: &T) -> ValidateOptionsResult {
+        match serde_json::to_value(options) {
+            Ok(value) => self.check_value(&value),
+            Err(error) => ValidateOptionsResult::fail(format!(
+                "The options could not be serialized to JSON: {}",
+                error
+            )),
+        }
+    }
+}
+
+type Satisfies = fn(f64, f64) -> bool;
+
+// keywords which only describe a schema and never affect validation
+const ANNOTATIONS: [&str; 10] = [
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+const TYPES: [&str; 7] = [
+    "null", "boolean", "object", "array", "string", "number", "integer",
+];
+
+// every keyword is verified when the schema is parsed so that a keyword which is not supported is
+// never silently ignored
+fn verify(schema: &Json, at: &str) -> Result<(), String> {
+    let schema = match schema {
+        Json::Object(schema) => schema,
+        Json::Bool(_) => return Ok(()),
+        _ => {
+            return Err(format!(
+                "The JSON Schema at '{}' must be an object or a boolean.",
+                at
+            ))
+        }
+    };
+
+    for (keyword, value) in schema {
+        let at = format!("{}/{}", at, keyword);
+        let valid = match keyword.as_str() {
+            "type" => match value {
+                Json::String(name) => TYPES.contains(&name.as_str()),
+                Json::Array(names) => names
+                    .iter()
+                    .all(|n| n.as_str().map(|n| TYPES.contains(&n)).unwrap_or_default()),
+                _ => false,
+            },
+            "enum" => value.is_array(),
+            "required" => value
+                .as_array()
+                .map(|names| names.iter().all(Json::is_string))
+                .unwrap_or_default(),
+            "properties" => match value {
+                Json::Object(properties) => {
+                    for (name, property) in properties {
+                        verify(property, &format!("{}/{}", at, name))?;
+                    }
+                    true
+                }
+                _ => false,
+            },
+            "additionalProperties" | "items" => {
+                verify(value, &at)?;
+                true
+            }
+            "minItems" | "maxItems" | "minLength" | "maxLength" => value.is_u64(),
+            "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => value.is_number(),
+            "const" => true,
+            keyword if ANNOTATIONS.contains(&keyword) => true,
+            _ => {
+                return Err(format!(
+                    "The JSON Schema keyword '{}' is not supported.",
+                    at
+                ))
+            }
+        };
+
+        if !valid {
+            return Err(format!(
+                "The value of the JSON Schema keyword '{}' is invalid.",
+                at
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn check(schema: &Json, value: &Json, path: &str, failures: &mut Vec<String>) {
+    let schema = match schema {
+        Json::Object(schema) => schema,
+        Json::Bool(false) => return failures.push(format!("'{}' is not allowed.", display(path))),
+        // a schema is verified when it's parsed, so the only other schema is true, which allows
+        // any value
+        _ => return,
+    };
+
+    if !check_type(schema, value, path, failures) {
+        return;
+    }
+
+    if let Some(Json::Array(values)) = schema.get("enum") {
+        if !values.contains(value) {
+            failures.push(format!(
+                "'{}' must be one of: {}.",
+                display(path),
+                values
+                    .iter()
+                    .map(Json::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            failures.push(format!("'{}' must be {}.", display(path), expected));
+        }
+    }
+
+    match value {
+        Json::Object(object) => check_object(schema, object, path, failures),
+        Json::Array(items) => check_array(schema, items, path, failures),
+        Json::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                check_number(schema, number, path, failures)
+            }
+        }
+        Json::String(text) => check_string(schema, text, path, failures),
+        Json::Null | Json::Bool(_) => {}
+    }
+}
+
+fn check_type(
+    schema: &Map<String, Json>,
+    value: &Json,
+    path: &str,
+    failures: &mut Vec<String>,
+) -> bool {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Json::String(name)) => vec![name],
+        Some(Json::Array(names)) => names.iter().filter_map(Json::as_str).collect(),
+        // a schema is verified when it's parsed, so the type is only missing, which allows any type
+        _ => return true,
+    };
+
+    if types.iter().any(|name| is_type(name, value)) {
+        true
+    } else {
+        failures.push(format!(
+            "'{}' must be of type {}.",
+            display(path),
+            types.join(" or ")
+        ));
+        false
+    }
+}
+
+fn is_type(name: &str, value: &Json) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().map(|n| n.fract() == 0.0).unwrap_or_default()
+        }
+        _ => false,
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Json>,
+    object: &Map<String, Json>,
+    path: &str,
+    failures: &mut Vec<String>,
+) {
+    if let Some(Json::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Json::as_str) {
+            if !object.contains_key(name) {
+                failures.push(format!("'{}' is required.", join(path, name)));
+            }
+        }
+    }
+
+    let properties = match schema.get("properties") {
+        Some(Json::Object(properties)) => Some(properties),
+        _ => None,
+    };
+
+    for (name, value) in object {
+        let path = join(path, name);
+
+        match properties.and_then(|p| p.get(name)) {
+            Some(property) => check(property, value, &path, failures),
+            _ => {
+                if let Some(additional) = schema.get("additionalProperties") {
+                    check(additional, value, &path, failures);
+                }
+            }
+        }
+    }
+}
+
+fn check_array(schema: &Map<String, Json>, items: &[Json], path: &str, failures: &mut Vec<String>) {
+    if let Some(min) = schema.get("minItems").and_then(Json::as_u64) {
+        if (items.len() as u64) < min {
+            failures.push(format!(
+                "'{}' must have at least {} items.",
+                display(path),
+                min
+            ));
+        }
+    }
+
+    if let Some(max) = schema.get("maxItems").and_then(Json::as_u64) {
+        if (items.len() as u64) > max {
+            failures.push(format!(
+                "'{}' must have at most {} items.",
+                display(path),
+                max
+            ));
+        }
+    }
+
+    if let Some(item) = schema.get("items") {
+        for (index, value) in items.iter().enumerate() {
+            check(item, value, &format!("{}[{}]", path, index), failures);
+        }
+    }
+}
+
+fn check_number(schema: &Map<String, Json>, number: f64, path: &str, failures: &mut Vec<String>) {
+    let limits: [(&str, Satisfies, &str); 4] = [
+        ("minimum", |n, l| n >= l, "at least"),
+        ("maximum", |n, l| n <= l, "at most"),
+        ("exclusiveMinimum", |n, l| n > l, "greater than"),
+        ("exclusiveMaximum", |n, l| n < l, "less than"),
+    ];
+
+    for (keyword, satisfies, description) in limits.iter() {
+        if let Some(limit) = schema.get(*keyword) {
+            if let Some(value) = limit.as_f64() {
+                if !satisfies(number, value) {
+                    failures.push(format!(
+                        "'{}' must be {} {}.",
+                        display(path),
+                        description,
+                        limit
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn check_string(schema: &Map<String, Json>, text: &str, path: &str, failures: &mut Vec<String>) {
+    let len = text.chars().count() as u64;
+
+    if let Some(min) = schema.get("minLength").and_then(Json::as_u64) {
+        if len < min {
+            failures.push(format!(
+                "'{}' must be at least {} characters.",
+                display(path),
+                min
+            ));
+        }
+    }
+
+    if let Some(max) = schema.get("maxLength").and_then(Json::as_u64) {
+        if len > max {
+            failures.push(format!(
+                "'{}' must be at most {} characters.",
+                display(path),
+                max
+            ));
+        }
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn display(path: &str) -> &str {
+    if path.is_empty() {
+        "options"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsFactory};
+    use di::ServiceCollection;
+
+    #[derive(Default, Serialize)]
+    struct Server {
+        host: String,
+        port: u32,
+    }
+
+    #[derive(Default, Serialize)]
+    struct ServerOptions {
+        mode: String,
+        servers: Vec<Server>,
+    }
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["mode", "servers"],
+        "properties": {
+            "mode": { "enum": ["primary", "replica"] },
+            "servers": {
+                "type": "array",
+                "minItems": 1,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "host": { "type": "string", "minLength": 1 },
+                        "port": { "type": "integer", "minimum": 1, "maximum": 65535 }
+                    }
+                }
+            }
+        },
+        "additionalProperties": false
+    }"#;
+
+    #[test]
+    fn check_value_should_report_path_of_every_failure() {
+        // arrange
+        let schema = JsonSchema::parse(SCHEMA).unwrap();
+        let value = serde_json::json!({
+            "servers": [{ "host": "", "port": 70000 }, { "host": "db", "port": "5432" }],
+            "debug": true
+        });
+
+        // act
+        let result = schema.check_value(&value);
+
+        // assert
+        assert_eq!(
+            result.failures(),
+            [
+                "'mode' is required.",
+                "'debug' is not allowed.",
+                "'servers[0].host' must be at least 1 characters.",
+                "'servers[0].port' must be at most 65535.",
+                "'servers[1].port' must be of type integer.",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_should_reject_unsupported_keyword() {
+        // arrange
+        let schema = r#"{ "properties": { "host": { "type": "string", "pattern": "^db" } } }"#;
+
+        // act
+        let result = JsonSchema::parse(schema);
+
+        // assert
+        assert_eq!(
+            result.unwrap_err(),
+            "The JSON Schema keyword '#/properties/host/pattern' is not supported."
+        );
+    }
+
+    #[test]
+    fn parse_should_reject_malformed_keyword() {
+        // arrange
+        let schema = r#"{ "items": { "type": "text" } }"#;
+
+        // act
+        let result = JsonSchema::parse(schema);
+
+        // assert
+        assert_eq!(
+            result.unwrap_err(),
+            "The value of the JSON Schema keyword '#/items/type' is invalid."
+        );
+    }
+
+    #[test]
+    fn parse_should_allow_annotations() {
+        // arrange
+        let schema = r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Server",
+            "properties": { "port": { "description": "The port", "default": 80 } }
+        }"#;
+
+        // act
+        let result = JsonSchema::parse(schema);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_json_schema_should_return_error_for_unsupported_schema() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        // act
+        let result = services
+            .add_options::<ServerOptions>()
+            .validate_json_schema(r#"{ "oneOf": [] }"#);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap(),
+            "The JSON Schema keyword '#/oneOf' is not supported."
+        );
+    }
+
+    #[test]
+    fn validate_json_schema_should_validate_options() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<ServerOptions>()
+            .configure(|o| {
+                o.mode = "standby".into();
+                o.servers.push(Server {
+                    host: "db".into(),
+                    port: 5432,
+                });
+            })
+            .validate_json_schema(SCHEMA)
+            .unwrap()
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<dyn OptionsFactory<ServerOptions>>();
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failures(),
+            ["'mode' must be one of: \"primary\", \"replica\"."]
+        );
+    }
+}
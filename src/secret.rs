@@ -0,0 +1,277 @@
+use std::fs::read_to_string;
+use std::path::{Component, Path, PathBuf};
+use tokens::{ChangeToken, NeverChangeToken};
+
+/// Defines the behavior of a source of secrets, such as a secret manager, used to configure
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// This trait is independent of any particular secret manager. The crate only provides the
+/// [`DirectorySecretSource`] and does not provide a client for any secret manager. An adapter for a
+/// secret manager such as HashiCorp Vault or AWS Secrets Manager is implemented over the client the
+/// application already uses and signals the [change token](tokens::ChangeToken) when secrets are
+/// rotated.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait SecretSource {
+    /// Gets the value of the secret with the specified key, if it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the secret to retrieve
+    fn secret(&self, key: &str) -> Option<String>;
+
+    /// Creates and returns a [`ChangeToken`](tokens::ChangeToken) that signals when secrets are
+    /// rotated.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation never signals a change.
+    fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(NeverChangeToken::new())
+    }
+}
+
+type ApplySecret<T> = fn(&mut T, String);
+
+/// Represents a mapping of secrets to the fields of configuration [`Options`](crate::Options).
+pub struct SecretMapping<T> {
+    entries: Vec<(String, ApplySecret<T>)>,
+}
+
+impl<T> Default for SecretMapping<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> SecretMapping<T> {
+    /// Initializes a new, empty secret mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a secret to a field of the options.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the secret
+    /// * `apply` - The function used to apply the value of the secret to the options
+    pub fn map<K: AsRef<str>>(mut self, key: K, apply: ApplySecret<T>) -> Self {
+        self.entries.push((key.as_ref().to_owned(), apply));
+        self
+    }
+
+    /// Applies the mapped secrets to the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [source](SecretSource) of the secrets
+    /// * `options` - The options to apply the secrets to
+    ///
+    /// # Remarks
+    ///
+    /// A secret that does not exist is skipped and leaves the corresponding field unchanged, which
+    /// allows validation to report it.
+    pub fn apply(&self, source: &dyn SecretSource, options: &mut T) {
+        for (key, apply) in &self.entries {
+            if let Some(value) = source.secret(key) {
+                apply(options, value);
+            }
+        }
+    }
+}
+
+/// Represents a [source of secrets](SecretSource) stored as files in a directory.
+///
+/// # Remarks
+///
+/// Each secret is stored in a file whose name is the key of the secret, which is how Docker and
+/// Kubernetes mount secrets, such as in `/run/secrets`. Trailing line breaks are removed. The
+/// files are read each time the options are created. A key that is not a single file name, such as
+/// one that contains a path separator or is `..`, does not exist, so a secret can never be read
+/// from outside of the directory.
+pub struct DirectorySecretSource {
+    path: PathBuf,
+}
+
+impl DirectorySecretSource {
+    /// Initializes a new directory secret source.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the directory that contains the secrets
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl SecretSource for DirectorySecretSource {
+    fn secret(&self, key: &str) -> Option<String> {
+        let mut components = Path::new(key).components();
+
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(file)), None) if file == key => {}
+            _ => return None,
+        }
+
+        read_to_string(self.path.join(key))
+            .ok()
+            .map(|value| value.trim_end_matches(&['\r', '\n'][..]).to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::collections::HashMap;
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::sync::{Arc, Mutex};
+    use tokens::{SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default)]
+    struct DbOptions {
+        user: String,
+        password: String,
+    }
+
+    #[test]
+    fn apply_should_set_mapped_secrets() {
+        // arrange
+        struct Secrets(HashMap<&'static str, &'static str>);
+
+        impl SecretSource for Secrets {
+            fn secret(&self, key: &str) -> Option<String> {
+                self.0.get(key).map(|v| v.to_string())
+            }
+        }
+
+        let source = Secrets(HashMap::from([("db/password", "p@ssw0rd")]));
+        let mapping = SecretMapping::new()
+            .map("db/user", |o: &mut DbOptions, v| o.user = v)
+            .map("db/password", |o, v| o.password = v);
+        let mut options = DbOptions {
+            user: "admin".into(),
+            ..Default::default()
+        };
+
+        // act
+        mapping.apply(&source, &mut options);
+
+        // assert
+        assert_eq!(&options.user, "admin");
+        assert_eq!(&options.password, "p@ssw0rd");
+    }
+
+    #[test]
+    fn directory_secret_source_should_read_secret_files() {
+        // arrange
+        let folder = temp_dir().join("options_secrets_1");
+
+        create_dir_all(&folder).unwrap();
+        write(folder.join("db-password"), "p@ssw0rd\n").unwrap();
+
+        let source = DirectorySecretSource::new(&folder);
+
+        // act
+        let password = source.secret("db-password");
+        let missing = source.secret("api-key");
+
+        // assert
+        remove_dir_all(&folder).ok();
+
+        assert_eq!(password.as_deref(), Some("p@ssw0rd"));
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn directory_secret_source_should_not_read_files_outside_of_directory() {
+        // arrange
+        let parent = temp_dir().join("options_secrets_2");
+        let folder = parent.join("secrets");
+
+        create_dir_all(folder.join("nested")).unwrap();
+        write(parent.join("outside"), "outside").unwrap();
+        write(folder.join("nested").join("inside"), "inside").unwrap();
+
+        let source = DirectorySecretSource::new(&folder);
+        let outside = parent.join("outside");
+
+        // act
+        let secrets = [
+            source.secret("../outside"),
+            source.secret(".."),
+            source.secret("."),
+            source.secret(""),
+            source.secret("nested/inside"),
+            source.secret("nested/"),
+            source.secret(outside.to_str().unwrap()),
+        ];
+
+        // assert
+        remove_dir_all(&parent).ok();
+
+        assert!(secrets.iter().all(Option::is_none));
+    }
+
+    #[derive(Clone, Default)]
+    struct Vault {
+        password: Arc<Mutex<String>>,
+        token: Arc<Mutex<SharedChangeToken<SingleChangeToken>>>,
+    }
+
+    impl Vault {
+        fn rotate(&self, password: &str) {
+            *self.password.lock().unwrap() = password.into();
+            let token = std::mem::take(&mut *self.token.lock().unwrap());
+            token.notify();
+        }
+    }
+
+    impl SecretSource for Vault {
+        fn secret(&self, key: &str) -> Option<String> {
+            match key {
+                "db/password" => Some(self.password.lock().unwrap().clone()),
+                _ => None,
+            }
+        }
+
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.token.lock().unwrap().clone())
+        }
+    }
+
+    #[test]
+    fn configure_secrets_should_reload_options_when_secrets_are_rotated() {
+        // arrange
+        let vault = Vault::default();
+        let provider = ServiceCollection::new()
+            .add_options::<DbOptions>()
+            .configure_secrets(
+                vault.clone(),
+                SecretMapping::new().map("db/password", |o: &mut DbOptions, v| o.password = v),
+            )
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<DbOptions>>();
+
+        vault.rotate("original");
+
+        let original = monitor.current_value();
+
+        // act
+        vault.rotate("rotated");
+
+        // assert
+        assert_eq!(&original.password, "original");
+        assert_eq!(&monitor.current_value().password, "rotated");
+    }
+}
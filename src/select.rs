@@ -0,0 +1,302 @@
+use crate::confined::Confined;
+use crate::monitor::{alive, subscribe, Callback, EventCallback, Generations};
+use crate::sync::{Mutex, RwLock};
+use crate::{
+    ChangeEvent, OptionsMonitor, OptionsNameComparer, Ref, Subscription, ValidateOptionsResult,
+    Value,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+
+type Selector<T, U> = dyn Fn(&T) -> U + Send + Sync;
+
+impl<T: Value + 'static> dyn OptionsMonitor<T> {
+    /// Creates a monitor for a projection of the monitored [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - The function used to project the options
+    ///
+    /// # Remarks
+    ///
+    /// Listeners of the projection are only notified when the projected value of the options
+    /// changes, which keeps components that depend on part of the options from reacting to
+    /// unrelated changes.
+    pub fn select<U, F>(self: Ref<Self>, selector: F) -> SelectedOptionsMonitor<T, U>
+    where
+        U: Value + PartialEq + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        SelectedOptionsMonitor::new(self, selector)
+    }
+}
+
+/// Represents an [`OptionsMonitor`](crate::OptionsMonitor) for a projection of monitored
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// A projection is created using the `select` method of an
+/// [`OptionsMonitor`](crate::OptionsMonitor). The projected value of each name is compared to the
+/// value last observed, and listeners are only notified when it is not equal.
+pub struct SelectedOptionsMonitor<T: Value, U: Value> {
+    source: Ref<dyn OptionsMonitor<T>>,
+    projection: Arc<Projection<T, U>>,
+    _subscription: Subscription<T>,
+}
+
+struct Projection<T, U: Value> {
+    selector: Box<Selector<T, U>>,
+    comparer: OptionsNameComparer,
+    values: Mutex<HashMap<String, Ref<U>>>,
+    generations: Generations,
+    listeners: RwLock<Vec<Weak<Callback<U>>>>,
+    events: RwLock<Vec<Weak<EventCallback<U>>>>,
+}
+
+impl<T: Value + 'static, U: Value + PartialEq + 'static> SelectedOptionsMonitor<T, U> {
+    /// Initializes a new selected options monitor.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [monitor](crate::OptionsMonitor) of the options to project
+    /// * `selector` - The function used to project the options
+    ///
+    /// # Remarks
+    ///
+    /// The values of names which are equal according to the default
+    /// [comparer](crate::OptionsNameComparer) are shared.
+    pub fn new<F>(source: Ref<dyn OptionsMonitor<T>>, selector: F) -> Self
+    where
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        Self::with_comparer(source, selector, OptionsNameComparer::default())
+    }
+
+    /// Initializes a new selected options monitor with a name comparer.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [monitor](crate::OptionsMonitor) of the options to project
+    /// * `selector` - The function used to project the options
+    /// * `comparer` - The [comparer](crate::OptionsNameComparer) used to match names, which should
+    ///   be the comparer of the source
+    pub fn with_comparer<F>(
+        source: Ref<dyn OptionsMonitor<T>>,
+        selector: F,
+        comparer: OptionsNameComparer,
+    ) -> Self
+    where
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let projection = Arc::new(Projection {
+            selector: Box::new(selector),
+            comparer,
+            values: Default::default(),
+            generations: Generations::new(comparer),
+            listeners: Default::default(),
+            events: Default::default(),
+        });
+        let weak = Confined::new(Arc::downgrade(&projection));
+        let subscription = source.on_change_event(Box::new(move |event: &ChangeEvent<T>| {
+            if let Some(projection) = weak.get().and_then(|p| p.upgrade()) {
+                projection.changed(event);
+            }
+        }));
+
+        Self {
+            source,
+            projection,
+            _subscription: subscription,
+        }
+    }
+}
+
+impl<T: Value, U: Value + PartialEq> Projection<T, U> {
+    fn changed(&self, change: &ChangeEvent<T>) {
+        let name = change.name();
+        let value = (self.selector)(&change.value());
+        let key = self
+            .comparer
+            .normalize(name.unwrap_or_default())
+            .into_owned();
+        let (value, previous, generation) = {
+            let mut values = self.values.lock().unwrap();
+            let previous = values.get(&key).cloned();
+
+            // without a previous value, the change cannot be compared so it's reported
+            if previous.as_ref().map(|v| **v == value).unwrap_or_default() {
+                return;
+            }
+
+            let value = Ref::new(value);
+            values.insert(key, value.clone());
+            (value, previous, self.generations.next(name))
+        };
+
+        // do NOT invoke the callbacks with the lock held. a callback might register a new callback
+        let callbacks = alive(&self.listeners);
+        let events = alive(&self.events);
+
+        for callback in callbacks {
+            callback(name, value.clone());
+        }
+
+        if events.is_empty() {
+            return;
+        }
+
+        let mut event =
+            ChangeEvent::new(name.map(|n| n.to_owned()), value).with_generation(generation);
+
+        if let Some(previous) = previous {
+            event = event.with_previous(previous);
+        }
+
+        if let Some(source) = change.source() {
+            event = event.with_source(source);
+        }
+
+        for callback in events {
+            callback(&event);
+        }
+    }
+}
+
+impl<T, U> OptionsMonitor<U> for SelectedOptionsMonitor<T, U>
+where
+    T: Value + 'static,
+    U: Value + PartialEq + 'static,
+{
+    fn get(&self, name: Option<&str>) -> Ref<U> {
+        let value = (self.projection.selector)(&self.source.get(name));
+        let mut values = self.projection.values.lock().unwrap();
+        let key = self.projection.comparer.normalize(name.unwrap_or_default());
+
+        if let Some(current) = values.get(key.as_ref()) {
+            if **current == value {
+                return Ref::clone(current);
+            }
+
+            // the value last observed is only replaced when listeners are notified
+            return Ref::new(value);
+        }
+
+        let value = Ref::new(value);
+        values.insert(key.into_owned(), value.clone());
+        value
+    }
+
+    fn on_change(&self, listener: Box<Callback<U>>) -> Subscription<U> {
+        let mut listeners = self.projection.listeners.write().unwrap();
+
+        listeners.retain(|l| l.strong_count() > 0);
+
+        let listener: Arc<Callback<U>> = Arc::from(listener);
+
+        listeners.push(Arc::downgrade(&listener));
+        Subscription::new(listener)
+    }
+
+    fn on_change_event(&self, listener: Box<EventCallback<U>>) -> Subscription<U> {
+        subscribe(&self.projection.events, listener)
+    }
+
+    fn reload(&self, name: Option<&str>) {
+        self.source.reload(name)
+    }
+
+    fn current_generation(&self, name: Option<&str>) -> u64 {
+        self.projection.generations.get(name)
+    }
+
+    fn last_error(&self, name: Option<&str>) -> Option<ValidateOptionsResult> {
+        self.source.last_error(name)
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::ServiceCollection;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use tokens::{SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default)]
+    struct Config {
+        retries: u8,
+        timeout: u8,
+    }
+
+    #[test]
+    fn selected_monitor_should_only_notify_when_projection_changes() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let timeout = Arc::new(AtomicU8::new(30));
+        let token = Arc::new(Mutex::new(SharedChangeToken::<SingleChangeToken>::default()));
+        let (r, t, watched) = (retries.clone(), timeout.clone(), token.clone());
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .configure(move |o| {
+                o.retries = r.load(Ordering::SeqCst);
+                o.timeout = t.load(Ordering::SeqCst);
+            })
+            .watch_fn(move || Box::new(watched.lock().unwrap().clone()))
+            .build_provider()
+            .unwrap();
+        let monitor = provider
+            .get_required::<dyn OptionsMonitor<Config>>()
+            .select(|o| o.retries);
+        let notified = Arc::new(AtomicU8::default());
+        let counter = notified.clone();
+        let _subscription = monitor.on_change(Box::new(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        let original = *monitor.current_value();
+        let notify = || {
+            let token = std::mem::take(&mut *token.lock().unwrap());
+            token.notify();
+        };
+
+        // act
+        timeout.store(60, Ordering::SeqCst);
+        notify();
+
+        let unrelated = notified.load(Ordering::SeqCst);
+
+        retries.store(3, Ordering::SeqCst);
+        notify();
+
+        // assert
+        assert_eq!(original, 1);
+        assert_eq!(unrelated, 0);
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+        assert_eq!(*monitor.current_value(), 3);
+        assert_eq!(monitor.current_generation(None), 1);
+    }
+
+    #[test]
+    fn selected_monitor_should_match_names_like_the_cache() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let r = retries.clone();
+        let provider = ServiceCollection::new()
+            .add_named_options::<Config>("Primary")
+            .configure(move |o| o.retries = r.load(Ordering::SeqCst))
+            .build_provider()
+            .unwrap();
+        let source = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let monitor = source.clone().select(|o| o.retries);
+        let _ = monitor.get(Some("Primary"));
+
+        // act
+        retries.store(3, Ordering::SeqCst);
+        source.reload(Some("PRIMARY"));
+
+        // assert
+        assert_eq!(*monitor.get(Some("primary")), 3);
+        assert_eq!(monitor.current_generation(Some("Primary")), 1);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::monitor::Callback;
+use crate::{
+    OptionsFactory, OptionsMonitor, OptionsMonitorCache, Ref, Subscription, ValidateOptionsResult,
+    Value,
+};
+
+/// Represents a service which provides access to configuration [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// The service combines the behaviors of [`Options`](crate::Options),
+/// [`OptionsSnapshot`](crate::OptionsSnapshot), and [`OptionsMonitor`](crate::OptionsMonitor) so that
+/// consumers can change how options are accessed without changing what they depend on.
+pub struct OptionsService<T: Value> {
+    monitor: Ref<dyn OptionsMonitor<T>>,
+    cache: Ref<dyn OptionsMonitorCache<T>>,
+    factory: Ref<dyn OptionsFactory<T>>,
+}
+
+impl<T: Value> OptionsService<T> {
+    /// Initializes a new options service.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - The [monitor](crate::OptionsMonitor) used to track options changes
+    /// * `cache` - The [cache](crate::OptionsMonitorCache) shared with the monitor
+    /// * `factory` - The [factory](crate::OptionsFactory) used to create new options
+    pub fn new(
+        monitor: Ref<dyn OptionsMonitor<T>>,
+        cache: Ref<dyn OptionsMonitorCache<T>>,
+        factory: Ref<dyn OptionsFactory<T>>,
+    ) -> Self {
+        Self {
+            monitor,
+            cache,
+            factory,
+        }
+    }
+
+    /// Gets the current configured value with the default options name.
+    pub fn value(&self) -> Ref<T> {
+        self.monitor.current_value()
+    }
+
+    /// Gets the current configured value with the specified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to retrieve
+    pub fn get(&self, name: Option<&str>) -> Ref<T> {
+        self.monitor.get(name)
+    }
+
+    /// Attempts to get the current configured value with the default options name.
+    pub fn try_value(&self) -> Result<Ref<T>, ValidateOptionsResult> {
+        self.try_get(None)
+    }
+
+    /// Attempts to get the current configured value with the specified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to retrieve
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [`get`](OptionsService::get), a failure to create the options is returned rather than
+    /// causing a panic.
+    pub fn try_get(&self, name: Option<&str>) -> Result<Ref<T>, ValidateOptionsResult> {
+        self.cache.try_get_or_add(name, &|n| self.factory.create(n))
+    }
+
+    /// Registers a callback function to be invoked when the configured instance with the given name changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - The callback function to invoke
+    pub fn on_change(&self, listener: Box<Callback<T>>) -> Subscription<T> {
+        self.monitor.on_change(listener)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+
+    #[derive(Default)]
+    struct TestOptions {
+        retries: u8,
+    }
+
+    struct TestValidation;
+
+    impl ValidateOptions<TestOptions> for TestValidation {
+        fn validate(&self, _name: Option<&str>, options: &TestOptions) -> ValidateOptionsResult {
+            if options.retries > 0 {
+                ValidateOptionsResult::success()
+            } else {
+                ValidateOptionsResult::fail("Retries must be greater than zero")
+            }
+        }
+    }
+
+    fn new_service(retries: u8) -> OptionsService<TestOptions> {
+        let cache: Ref<dyn OptionsMonitorCache<TestOptions>> = Ref::new(OptionsCache::default());
+        let configure: Ref<dyn ConfigureOptions<TestOptions>> =
+            Ref::new(configure(move |_, o: &mut TestOptions| o.retries = retries));
+        let validate: Ref<dyn ValidateOptions<TestOptions>> = Ref::new(TestValidation);
+        let factory: Ref<dyn OptionsFactory<TestOptions>> = Ref::new(DefaultOptionsFactory::new(
+            vec![configure],
+            Vec::default(),
+            vec![validate],
+        ));
+        let monitor = Ref::new(DefaultOptionsMonitor::new(
+            cache.clone(),
+            Vec::default(),
+            factory.clone(),
+        ));
+
+        OptionsService::new(monitor, cache, factory)
+    }
+
+    #[test]
+    fn try_value_should_return_configured_options() {
+        // arrange
+        let service = new_service(3);
+
+        // act
+        let options = service.try_value().unwrap();
+
+        // assert
+        assert_eq!(options.retries, 3);
+        assert!(Ref::ptr_eq(&options, &service.value()));
+    }
+
+    #[test]
+    fn try_get_should_return_failure_for_invalid_options() {
+        // arrange
+        let service = new_service(0);
+
+        // act
+        let result = service.try_get(Some("Test"));
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failure_message(),
+            "Retries must be greater than zero"
+        );
+    }
+}
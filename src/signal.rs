@@ -0,0 +1,302 @@
+// signal handlers may only perform async-signal-safe operations, so the handler writes the signal
+// number to a pipe and a single background thread reads the pipe and signals the change tokens of
+// every source watching that signal. this is commonly known as the self-pipe trick.
+
+use crate::{singleton::Singleton, OptionsChangeTokenSource, Value};
+use std::collections::HashSet;
+use std::io::{Error, ErrorKind};
+use std::marker::PhantomData;
+use std::mem::take;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use tokens::{ChangeToken, SharedChangeToken, SingleChangeToken};
+
+/// Represents a Unix signal used to reload [`Options`](crate::Options).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReloadSignal {
+    /// Indicates the hangup signal (`SIGHUP`), which conventionally asks a daemon to re-read its
+    /// configuration.
+    Hangup,
+
+    /// Indicates the first user-defined signal (`SIGUSR1`).
+    User1,
+
+    /// Indicates the second user-defined signal (`SIGUSR2`).
+    User2,
+}
+
+impl ReloadSignal {
+    fn number(self) -> c_int {
+        match self {
+            Self::Hangup => libc::SIGHUP,
+            Self::User1 => libc::SIGUSR1,
+            Self::User2 => libc::SIGUSR2,
+        }
+    }
+}
+
+/// Represents a change token source for [`Options`](crate::Options) that are notified when the
+/// process receives a Unix signal.
+///
+/// # Remarks
+///
+/// A handler is installed for each signal the first time it is watched and replaces any existing
+/// handler for the lifetime of the process. Notifications always occur on another thread.
+pub struct SignalChangeTokenSource<T: Value> {
+    name: Option<String>,
+    watch: Arc<SignalWatch>,
+    _data: PhantomData<T>,
+}
+
+impl<T: Value> SignalChangeTokenSource<T> {
+    /// Initializes a new signal change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being watched
+    /// * `signals` - The [signals](ReloadSignal) to watch
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signal handler cannot be installed.
+    pub fn new(name: Option<&str>, signals: &[ReloadSignal]) -> Self {
+        Self::with_watch(name, SignalWatch::new(signals))
+    }
+
+    pub(crate) fn with_watch(name: Option<&str>, watch: Arc<SignalWatch>) -> Self {
+        Self {
+            name: name.map(|s| s.to_owned()),
+            watch,
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for SignalChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(self.watch.token.lock().unwrap().clone())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn source_name(&self) -> Option<&str> {
+        Some("signal")
+    }
+}
+
+pub(crate) struct SignalWatch {
+    signals: Vec<c_int>,
+    token: Mutex<SharedChangeToken<SingleChangeToken>>,
+}
+
+impl SignalWatch {
+    pub(crate) fn new(signals: &[ReloadSignal]) -> Arc<Self> {
+        let watch = Arc::new(Self {
+            signals: signals.iter().map(|s| s.number()).collect(),
+            token: Default::default(),
+        });
+
+        dispatcher().subscribe(&watch);
+        watch
+    }
+
+    fn notify(&self) {
+        // a token only signals once so it's replaced before it's signaled
+        let token = take(&mut *self.token.lock().unwrap());
+        token.notify();
+    }
+}
+
+struct Dispatcher {
+    reader: c_int,
+    installed: Mutex<HashSet<c_int>>,
+    watches: Mutex<Vec<Weak<SignalWatch>>>,
+}
+
+static WRITER: AtomicI32 = AtomicI32::new(-1);
+
+fn dispatcher() -> &'static Dispatcher {
+    static DISPATCHER: Singleton<Dispatcher> = Singleton::new();
+    DISPATCHER.get_or_start(Dispatcher::new, |dispatcher| {
+        thread::spawn(move || dispatcher.run());
+    })
+}
+
+extern "C" fn handler(signal: c_int) {
+    let signal = signal as u8;
+
+    // SAFETY: write is async-signal-safe. if the pipe is full, the signal is dropped, but changes
+    // are already pending for every watch
+    unsafe {
+        libc::write(
+            WRITER.load(Ordering::SeqCst),
+            &signal as *const u8 as *const c_void,
+            1,
+        );
+    }
+}
+
+fn install(signal: c_int) {
+    // SAFETY: the handler only performs async-signal-safe operations
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+
+        action.sa_sigaction = handler as extern "C" fn(c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        if libc::sigaction(signal, &action, ptr::null_mut()) != 0 {
+            panic!("{}", Error::last_os_error());
+        }
+    }
+}
+
+impl Dispatcher {
+    fn new() -> Self {
+        let mut fds: [c_int; 2] = [-1; 2];
+
+        // SAFETY: the file descriptors are only used by the signal handler and the dispatch thread
+        unsafe {
+            if libc::pipe(fds.as_mut_ptr()) != 0 {
+                panic!("{}", Error::last_os_error());
+            }
+
+            libc::fcntl(fds[0], libc::F_SETFD, libc::FD_CLOEXEC);
+            libc::fcntl(fds[1], libc::F_SETFD, libc::FD_CLOEXEC);
+
+            // the handler must never block
+            libc::fcntl(fds[1], libc::F_SETFL, libc::O_NONBLOCK);
+        }
+
+        WRITER.store(fds[1], Ordering::SeqCst);
+
+        Self {
+            reader: fds[0],
+            installed: Default::default(),
+            watches: Default::default(),
+        }
+    }
+
+    fn subscribe(&self, watch: &Arc<SignalWatch>) {
+        let mut installed = self.installed.lock().unwrap();
+
+        for signal in &watch.signals {
+            if installed.insert(*signal) {
+                install(*signal);
+            }
+        }
+
+        let mut watches = self.watches.lock().unwrap();
+
+        watches.retain(|w| w.strong_count() > 0);
+        watches.push(Arc::downgrade(watch));
+    }
+
+    fn run(&self) {
+        let mut signal = 0u8;
+
+        loop {
+            // SAFETY: the buffer is a single byte
+            let read = unsafe { libc::read(self.reader, &mut signal as *mut u8 as *mut c_void, 1) };
+
+            if read == 1 {
+                self.notify(signal as c_int);
+            } else if read < 0 && Error::last_os_error().kind() == ErrorKind::Interrupted {
+                continue;
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn notify(&self, signal: c_int) {
+        // do NOT signal with the lock held. a callback might watch the signal again
+        let watches: Vec<_> = self
+            .watches
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|w| w.signals.contains(&signal))
+            .collect();
+
+        for watch in watches {
+            watch.notify();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::atomic::AtomicU8;
+    use std::sync::mpsc::channel;
+    use std::time::{Duration, Instant};
+
+    #[derive(Default)]
+    struct Config {
+        generation: u8,
+    }
+
+    #[test]
+    fn token_should_change_when_watched_signal_is_received() {
+        // arrange
+        let source = SignalChangeTokenSource::<Config>::new(None, &[ReloadSignal::User1]);
+        let token = source.token();
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let _registration = token.register(
+            Box::new(move |_| sender.lock().unwrap().send(()).unwrap()),
+            None,
+        );
+
+        // act
+        // SAFETY: a handler is installed for the signal
+        unsafe { libc::raise(libc::SIGUSR1) };
+
+        let changed = receiver.recv_timeout(Duration::from_secs(5)).is_ok();
+
+        // assert
+        assert!(changed);
+        assert!(token.changed());
+        assert!(!source.token().changed());
+    }
+
+    #[test]
+    fn reload_on_sighup_should_reload_options() {
+        // arrange
+        let generation = Arc::new(AtomicU8::default());
+        let counter = generation.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .configure(move |o| o.generation = counter.fetch_add(1, Ordering::SeqCst) + 1)
+            .reload_on_sighup()
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let original = monitor.current_value().generation;
+        let started = Instant::now();
+
+        // act
+        // SAFETY: a handler is installed for the signal
+        unsafe { libc::raise(libc::SIGHUP) };
+
+        while monitor.current_value().generation == original
+            && started.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        // assert
+        assert_eq!(original, 1);
+        assert!(monitor.current_value().generation > original);
+    }
+}
@@ -0,0 +1,73 @@
+// a few services, such as the listener watchdog, live for the whole process. the minimum supported
+// rust version predates OnceLock and a const Mutex::new, so the value is created once, leaked, and
+// published through an atomic pointer. this is the only place that dereferences it.
+
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Once;
+
+pub(crate) struct Singleton<T> {
+    init: Once,
+    value: AtomicPtr<T>,
+}
+
+impl<T: Send + Sync + 'static> Singleton<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            init: Once::new(),
+            value: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    #[cfg_attr(all(feature = "async", not(feature = "env")), allow(dead_code))]
+    pub(crate) fn get_or_init(&'static self, create: impl FnOnce() -> T) -> &'static T {
+        self.get_or_start(create, |_| {})
+    }
+
+    // start is invoked once, after the value is created, which allows a thread that borrows the
+    // value for the rest of the process to be spawned
+    pub(crate) fn get_or_start(
+        &'static self,
+        create: impl FnOnce() -> T,
+        start: impl FnOnce(&'static T),
+    ) -> &'static T {
+        self.init.call_once(|| {
+            let value: &'static T = Box::leak(Box::new(create()));
+
+            self.value
+                .store(value as *const T as *mut T, Ordering::Release);
+            start(value);
+        });
+
+        // SAFETY: call_once does not return until the value is stored, the value is never
+        // written again, and it is leaked so it lives for the rest of the process
+        unsafe { &*self.value.load(Ordering::Acquire) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn get_or_init_should_create_value_once() {
+        // arrange
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static VALUE: Singleton<AtomicUsize> = Singleton::new();
+        let create = || {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            AtomicUsize::new(42)
+        };
+
+        // act
+        let first = VALUE.get_or_init(create);
+        let second = VALUE.get_or_init(create);
+
+        // assert
+        assert!(ptr::eq(first, second));
+        assert_eq!(first.load(Ordering::SeqCst), 42);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,133 @@
+// the locks shared by the options cache and the monitors that notify it are taken from 'loom' when
+// the 'loom' feature is enabled, which allows a model to explore every interleaving of them. loom
+// has neither Weak nor Arc::new_cyclic, so reference counting always uses the standard library.
+// the models only run with the 'loom' feature and other tests only run without it:
+//
+// cargo test --release --lib --features loom sync::tests
+
+#[cfg(feature = "loom")]
+pub(crate) use loom::sync::{Mutex, RwLock};
+
+#[cfg(not(feature = "loom"))]
+pub(crate) use std::sync::{Mutex, RwLock};
+
+#[cfg(all(test, feature = "loom"))]
+mod tests {
+
+    use crate::*;
+    use loom::sync::atomic::{AtomicUsize, Ordering};
+    use loom::thread;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    struct Version(usize);
+
+    // creates options from the latest version of their source, which is changed before a reload
+    struct VersionFactory(Arc<AtomicUsize>);
+
+    impl OptionsFactory<Version> for VersionFactory {
+        fn create(&self, _name: Option<&str>) -> Result<Version, ValidateOptionsResult> {
+            Ok(Version(self.0.load(Ordering::SeqCst)))
+        }
+    }
+
+    // creates options whose version is the number of times they have been created
+    struct CountingFactory(Arc<AtomicUsize>);
+
+    impl OptionsFactory<Version> for CountingFactory {
+        fn create(&self, _name: Option<&str>) -> Result<Version, ValidateOptionsResult> {
+            Ok(Version(self.0.fetch_add(1, Ordering::SeqCst) + 1))
+        }
+    }
+
+    fn new_monitor(version: &Arc<AtomicUsize>) -> Arc<DefaultOptionsMonitor<Version>> {
+        Arc::new(DefaultOptionsMonitor::new(
+            Ref::new(OptionsCache::default()),
+            Vec::new(),
+            Ref::new(VersionFactory(version.clone())),
+        ))
+    }
+
+    #[test]
+    fn notification_racing_try_remove_should_not_cache_stale_options() {
+        loom::model(|| {
+            // arrange
+            let version = Arc::new(AtomicUsize::new(0));
+            let monitor = new_monitor(&version);
+            let reader = monitor.clone();
+
+            // act
+            let get = thread::spawn(move || {
+                reader.get(None);
+            });
+
+            version.store(1, Ordering::SeqCst);
+            monitor.reload(None);
+            get.join().unwrap();
+
+            // assert
+            assert_eq!(monitor.get(None).0, 1);
+        });
+    }
+
+    #[test]
+    fn reload_racing_get_should_not_create_options_again() {
+        loom::model(|| {
+            // arrange
+            let created = Arc::new(AtomicUsize::new(0));
+            // options are only rebuilt rather than invalidated with a retry policy
+            let monitor = Arc::new(
+                DefaultOptionsMonitor::new(
+                    Ref::new(OptionsCache::default()),
+                    Vec::new(),
+                    Ref::new(CountingFactory(created.clone())),
+                )
+                .with_retry(RetryPolicy::new(0, Duration::ZERO), |_, _| {}),
+            );
+            let reader = monitor.clone();
+
+            monitor.get(None);
+
+            // act
+            let get = thread::spawn(move || reader.get(None).0);
+
+            monitor.reload(None);
+
+            let read = get.join().unwrap();
+
+            // assert
+            assert!(read <= 2);
+            assert_eq!(created.load(Ordering::SeqCst), 2);
+        });
+    }
+
+    #[test]
+    fn registration_racing_notification_should_observe_latest_options() {
+        loom::model(|| {
+            // arrange
+            let version = Arc::new(AtomicUsize::new(0));
+            let calls = Arc::new(AtomicUsize::new(0));
+            let monitor = new_monitor(&version);
+            let subscriber = monitor.clone();
+            let counter = calls.clone();
+
+            // act
+            let register = thread::spawn(move || {
+                subscriber.on_change(Box::new(move |_, options: Ref<Version>| {
+                    assert_eq!(options.0, 1);
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }))
+            });
+
+            version.store(1, Ordering::SeqCst);
+            monitor.reload(None);
+
+            let subscription = register.join().unwrap();
+
+            // assert
+            assert!(calls.load(Ordering::SeqCst) <= 1);
+            assert_eq!(monitor.get(None).0, 1);
+            drop(subscription);
+        });
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{ConfigureOptions, OptionsMonitor, Ref, Value};
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+const PREFIX: &str = "tenant:";
+
+/// Represents the ambient tenant of the current scope.
+///
+/// # Remarks
+///
+/// The context is a scoped service. The tenant is typically set once at the start of a scope, such
+/// as by middleware that identifies the tenant of a request, and can then be used by any service
+/// resolved from the same scope.
+#[derive(Debug, Default)]
+pub struct TenantContext(Mutex<Option<String>>);
+
+impl TenantContext {
+    /// Initializes a new tenant context.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant` - The optional identifier of the tenant
+    pub fn new(tenant: Option<&str>) -> Self {
+        Self(Mutex::new(tenant.map(|s| s.to_owned())))
+    }
+
+    /// Gets the identifier of the current tenant, if any.
+    pub fn tenant(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Sets the current tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant` - The optional identifier of the tenant
+    pub fn set_tenant(&self, tenant: Option<&str>) {
+        *self.0.lock().unwrap() = tenant.map(|s| s.to_owned());
+    }
+
+    /// Gets the name of the [`Options`](crate::Options) for a tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant` - The identifier of the tenant
+    ///
+    /// # Remarks
+    ///
+    /// The name is prefixed so that tenants never collide with other named options. The name can be
+    /// used to register options for a specific tenant, such as `add_named_options`.
+    pub fn options_name(tenant: &str) -> String {
+        format!("{}{}", PREFIX, tenant)
+    }
+
+    /// Gets the tenant that the name of the [`Options`](crate::Options) refers to, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options
+    pub fn tenant_of(name: Option<&str>) -> Option<&str> {
+        name.and_then(|n| n.strip_prefix(PREFIX))
+    }
+}
+
+/// Represents the configuration [`Options`](crate::Options) of the tenant in the current scope.
+///
+/// # Remarks
+///
+/// The options are resolved from the [`OptionsMonitor`](crate::OptionsMonitor) using the name of
+/// the tenant in the scoped [`TenantContext`]. The options of each tenant are cached and reloaded
+/// independently. If the scope has no tenant, the unnamed options are used.
+pub struct TenantOptions<T: Value> {
+    context: Ref<TenantContext>,
+    monitor: Ref<dyn OptionsMonitor<T>>,
+}
+
+impl<T: Value> TenantOptions<T> {
+    /// Initializes new tenant options.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The [context](TenantContext) of the current tenant
+    /// * `monitor` - The [monitor](crate::OptionsMonitor) used to resolve options
+    pub fn new(context: Ref<TenantContext>, monitor: Ref<dyn OptionsMonitor<T>>) -> Self {
+        Self { context, monitor }
+    }
+
+    /// Gets the identifier of the current tenant, if any.
+    pub fn tenant(&self) -> Option<String> {
+        self.context.tenant()
+    }
+
+    /// Gets the configuration options for the current tenant.
+    pub fn get(&self) -> Ref<T> {
+        let name = self.name();
+        self.monitor.get(name.as_deref())
+    }
+
+    /// Invalidates the cached options of the current tenant.
+    ///
+    /// # Remarks
+    ///
+    /// The options are created again and listeners are notified as if a source signaled a change.
+    pub fn invalidate(&self) {
+        let name = self.name();
+        self.monitor.reload(name.as_deref());
+    }
+
+    /// Invalidates the cached options of a tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant` - The identifier of the tenant to invalidate
+    pub fn invalidate_tenant(&self, tenant: &str) {
+        self.monitor
+            .reload(Some(&TenantContext::options_name(tenant)));
+    }
+
+    fn name(&self) -> Option<String> {
+        self.context
+            .tenant()
+            .map(|tenant| TenantContext::options_name(&tenant))
+    }
+}
+
+pub(crate) struct TenantConfigure<T, F> {
+    setup: F,
+    _marker: PhantomData<T>,
+}
+
+impl<T, F: Fn(&str, &mut T)> TenantConfigure<T, F> {
+    pub(crate) fn new(setup: F) -> Self {
+        Self {
+            setup,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Value, F: Fn(&str, &mut T) + Value> ConfigureOptions<T> for TenantConfigure<T, F> {
+    fn configure(&self, name: Option<&str>, options: &mut T) {
+        if let Some(tenant) = TenantContext::tenant_of(name) {
+            (self.setup)(tenant, options)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct BrandOptions {
+        tenant: String,
+        color: String,
+    }
+
+    #[test]
+    fn get_should_resolve_options_for_tenant_of_scope() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<BrandOptions>()
+            .configure(|o| o.color = "blue".into())
+            .configure_tenant(|tenant, o| o.tenant = tenant.into())
+            .per_tenant();
+        services
+            .add_named_options::<BrandOptions>(TenantContext::options_name("fabrikam"))
+            .configure(|o| o.color = "green".into());
+
+        let provider = services.build_provider().unwrap();
+        let contoso = provider.create_scope();
+        let fabrikam = provider.create_scope();
+
+        contoso
+            .get_required::<TenantContext>()
+            .set_tenant(Some("contoso"));
+        fabrikam
+            .get_required::<TenantContext>()
+            .set_tenant(Some("fabrikam"));
+
+        // act
+        let first = contoso.get_required::<TenantOptions<BrandOptions>>().get();
+        let second = fabrikam.get_required::<TenantOptions<BrandOptions>>().get();
+        let none = provider
+            .create_scope()
+            .get_required::<TenantOptions<BrandOptions>>()
+            .get();
+
+        // assert
+        assert_eq!((&first.tenant[..], &first.color[..]), ("contoso", "blue"));
+        assert_eq!(
+            (&second.tenant[..], &second.color[..]),
+            ("fabrikam", "green")
+        );
+        assert_eq!((&none.tenant[..], &none.color[..]), ("", "blue"));
+    }
+
+    #[test]
+    fn invalidate_should_only_reload_options_of_tenant() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<BrandOptions>()
+            .per_tenant()
+            .build_provider()
+            .unwrap();
+        let scope = provider.create_scope();
+        let context = scope.get_required::<TenantContext>();
+        let options = scope.get_required::<TenantOptions<BrandOptions>>();
+
+        context.set_tenant(Some("fabrikam"));
+
+        let other = options.get();
+
+        context.set_tenant(Some("contoso"));
+
+        let original = options.get();
+
+        // act
+        options.invalidate();
+
+        let current = options.get();
+
+        context.set_tenant(Some("fabrikam"));
+
+        let unaffected = options.get();
+
+        // assert
+        assert!(!Ref::ptr_eq(&original, &current));
+        assert!(Ref::ptr_eq(&other, &unaffected));
+    }
+}
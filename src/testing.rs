@@ -0,0 +1,272 @@
+use crate::{ValidateOptions, ValidateOptionsResult, Value};
+use std::any::Any;
+use std::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[cfg(feature = "proptest")]
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    test_runner::{Config, TestCaseError, TestRunner},
+};
+
+/// Checks a validator against generated options.
+///
+/// # Arguments
+///
+/// * `validator` - The [validator](crate::ValidateOptions) to check
+/// * `cases` - The number of options to generate
+/// * `generate` - The function used to generate the options for each case, which receives the
+///   index of the case
+/// * `invariant` - The invariant the result of every validation must satisfy
+///
+/// # Remarks
+///
+/// A validator must be total; it must report a result for any options it's given, no matter how
+/// they are configured. Each generated instance is validated and the check panics with the failing
+/// case if the validator panics or its result does not satisfy the invariant. The generator can
+/// be any source of options. Options which implement `Arbitrary` can also be checked with
+/// `check_arbitrary_validator` when the **proptest** feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// use options::{testing::check_validator, ValidateOptions, ValidateOptionsResult};
+///
+/// #[derive(Debug)]
+/// struct PageOptions {
+///     size: u32,
+/// }
+///
+/// struct PageValidator;
+///
+/// impl ValidateOptions<PageOptions> for PageValidator {
+///     fn validate(&self, _name: Option<&str>, options: &PageOptions) -> ValidateOptionsResult {
+///         if options.size > 0 {
+///             ValidateOptionsResult::success()
+///         } else {
+///             ValidateOptionsResult::fail("The page size must be greater than zero.")
+///         }
+///     }
+/// }
+///
+/// check_validator(
+///     &PageValidator,
+///     100,
+///     |i| PageOptions { size: i as u32 },
+///     |options, result| result.succeeded() == (options.size > 0),
+/// );
+/// ```
+pub fn check_validator<T, V, G, I>(validator: &V, cases: usize, mut generate: G, invariant: I)
+where
+    T: Value + Debug,
+    V: ValidateOptions<T> + ?Sized,
+    G: FnMut(usize) -> T,
+    I: Fn(&T, &ValidateOptionsResult) -> bool,
+{
+    for case in 0..cases {
+        let options = generate(case);
+        let result = catch_unwind(AssertUnwindSafe(|| validator.validate(None, &options)));
+
+        match result {
+            Ok(result) => {
+                if !invariant(&options, &result) {
+                    panic!(
+                        "The validation of case {} did not satisfy the invariant.\nOptions: {:?}\nResult: {:?}",
+                        case, options, result
+                    );
+                }
+            }
+            Err(error) => {
+                panic!(
+                    "The validation of case {} panicked: {}\nOptions: {:?}",
+                    case,
+                    panic_message(&*error),
+                    options
+                );
+            }
+        }
+    }
+}
+
+/// Checks a validator against options generated by `proptest`.
+///
+/// # Arguments
+///
+/// * `validator` - The [validator](crate::ValidateOptions) to check
+/// * `cases` - The number of options to generate
+/// * `invariant` - The invariant the result of every validation must satisfy
+///
+/// # Remarks
+///
+/// The options are generated by the `Arbitrary` strategy of the options type. When the validator
+/// panics or its result does not satisfy the invariant, the failing options are shrunk to the
+/// smallest options which still fail and the check panics with them.
+///
+/// # Example
+///
+/// ```
+/// use options::{testing::check_arbitrary_validator, ValidateOptions, ValidateOptionsResult};
+/// use proptest::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct PageOptions {
+///     size: u32,
+/// }
+///
+/// impl Arbitrary for PageOptions {
+///     type Parameters = ();
+///     type Strategy = BoxedStrategy<Self>;
+///
+///     fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+///         any::<u32>().prop_map(|size| PageOptions { size }).boxed()
+///     }
+/// }
+///
+/// struct PageValidator;
+///
+/// impl ValidateOptions<PageOptions> for PageValidator {
+///     fn validate(&self, _name: Option<&str>, options: &PageOptions) -> ValidateOptionsResult {
+///         if options.size > 0 {
+///             ValidateOptionsResult::success()
+///         } else {
+///             ValidateOptionsResult::fail("The page size must be greater than zero.")
+///         }
+///     }
+/// }
+///
+/// check_arbitrary_validator(&PageValidator, 100, |options: &PageOptions, result| {
+///     result.succeeded() == (options.size > 0)
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub fn check_arbitrary_validator<T, V, I>(validator: &V, cases: u32, invariant: I)
+where
+    T: Value + Debug + Arbitrary,
+    V: ValidateOptions<T> + ?Sized,
+    I: Fn(&T, &ValidateOptionsResult) -> bool,
+{
+    // failures are reported by the check rather than persisted for the next run
+    let mut runner = TestRunner::new(Config {
+        cases,
+        failure_persistence: None,
+        ..Config::default()
+    });
+    let outcome = runner.run(&any::<T>(), |options| {
+        match catch_unwind(AssertUnwindSafe(|| validator.validate(None, &options))) {
+            Ok(result) if invariant(&options, &result) => Ok(()),
+            Ok(result) => Err(TestCaseError::fail(format!(
+                "The validation did not satisfy the invariant.\nResult: {:?}",
+                result
+            ))),
+            Err(error) => Err(TestCaseError::fail(format!(
+                "The validation panicked: {}",
+                panic_message(&*error)
+            ))),
+        }
+    });
+
+    if let Err(error) = outcome {
+        panic!("{}", error);
+    }
+}
+
+fn panic_message(error: &(dyn Any + Send)) -> String {
+    error
+        .downcast_ref::<&str>()
+        .map(|m| (*m).to_owned())
+        .or_else(|| error.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct RangeOptions {
+        min: u8,
+        max: u8,
+    }
+
+    struct RangeValidator;
+
+    impl ValidateOptions<RangeOptions> for RangeValidator {
+        fn validate(&self, _name: Option<&str>, options: &RangeOptions) -> ValidateOptionsResult {
+            assert!(options.min <= options.max, "min must not exceed max");
+
+            if options.max - options.min < 10 {
+                ValidateOptionsResult::success()
+            } else {
+                ValidateOptionsResult::fail("The range is too large.")
+            }
+        }
+    }
+
+    #[test]
+    fn check_validator_should_pass_when_invariant_holds() {
+        // arrange
+        let generate = |i: usize| RangeOptions {
+            min: 0,
+            max: i as u8,
+        };
+
+        // act
+        check_validator(&RangeValidator, 50, generate, |o, r| {
+            r.succeeded() == (o.max < 10)
+        });
+    }
+
+    #[cfg(feature = "proptest")]
+    impl Arbitrary for RangeOptions {
+        type Parameters = ();
+        type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            use proptest::strategy::Strategy;
+
+            (any::<u8>(), any::<u8>())
+                .prop_map(|(a, b)| RangeOptions {
+                    min: a.min(b),
+                    max: a.max(b),
+                })
+                .boxed()
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn check_arbitrary_validator_should_pass_when_invariant_holds() {
+        // arrange
+        let invariant =
+            |o: &RangeOptions, r: &ValidateOptionsResult| r.succeeded() == (o.max - o.min < 10);
+
+        // act
+        check_arbitrary_validator(&RangeValidator, 64, invariant);
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    #[should_panic(expected = "minimal failing input: RangeOptions")]
+    fn check_arbitrary_validator_should_panic_with_shrunk_options() {
+        // arrange
+        let invariant = |_: &RangeOptions, r: &ValidateOptionsResult| r.succeeded();
+
+        // act
+        check_arbitrary_validator(&RangeValidator, 256, invariant);
+    }
+
+    #[test]
+    #[should_panic(expected = "The validation of case 1 panicked: min must not exceed max")]
+    fn check_validator_should_panic_when_validator_panics() {
+        // arrange
+        let generate = |i: usize| RangeOptions {
+            min: i as u8,
+            max: 0,
+        };
+
+        // act
+        check_validator(&RangeValidator, 2, generate, |_, _| true);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::Value;
-use tokens::ChangeToken;
+use crate::{Ref, Value};
+use std::marker::PhantomData;
+use tokens::{ChangeToken, CompositeChangeToken, Registration};
+
+// the number of tokens acquired from a source before its tokens are assumed to never change again
+const MAX_ACQUIRE_ATTEMPTS: usize = 3;
 
 /// Used to fetch [`ChangeToken`](tokens::ChangeToken) used for tracking options changes.
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
@@ -12,4 +16,227 @@ pub trait OptionsChangeTokenSource<T: Value> {
     fn name(&self) -> Option<&str> {
         None
     }
+
+    /// Gets the name of the source which triggers changes, if any.
+    ///
+    /// # Remarks
+    ///
+    /// The name is provided to [change events](crate::ChangeEvent) to describe what triggered a change.
+    fn source_name(&self) -> Option<&str> {
+        None
+    }
+}
+
+pub(crate) type TokenRegistration = (Box<dyn ChangeToken>, Registration);
+
+/// Registers a callback for the next change signaled by a source of change tokens.
+///
+/// # Remarks
+///
+/// A token which changes at most once never invokes a callback registered after it changed, which
+/// happens when a change is signaled between acquiring the token and registering the callback. A
+/// new token is acquired when that happens. The returned flag indicates whether a change was
+/// missed, which the caller must process because the callback will not be invoked for it. No
+/// registration is returned if the source only produces tokens which already changed.
+pub(crate) fn register_next<T, F>(
+    source: &dyn OptionsChangeTokenSource<T>,
+    callback: F,
+) -> (Option<TokenRegistration>, bool)
+where
+    T: Value,
+    F: Fn() + Clone + Send + Sync + 'static,
+{
+    for attempt in 0..MAX_ACQUIRE_ATTEMPTS {
+        let token = source.token();
+        let notify = callback.clone();
+        let registration = token.register(Box::new(move |_| notify()), None);
+
+        if !token.changed() || token.must_poll() {
+            return (Some((token, registration)), attempt > 0);
+        }
+    }
+
+    (None, false)
+}
+
+impl<T: Value + 'static> dyn OptionsChangeTokenSource<T> {
+    /// Creates a source of change tokens from a function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being changed
+    /// * `token` - The function used to create a [change token](tokens::ChangeToken)
+    pub fn from_fn<F>(name: Option<&str>, token: F) -> impl OptionsChangeTokenSource<T>
+    where
+        F: Fn() -> Box<dyn ChangeToken> + Value + 'static,
+    {
+        FnChangeTokenSource::new(name.map(|n| n.to_owned()), Ref::new(token))
+    }
+}
+
+pub(crate) struct FnChangeTokenSource<T, F> {
+    name: Option<String>,
+    token: Ref<F>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, F> FnChangeTokenSource<T, F> {
+    pub(crate) fn new(name: Option<String>, token: Ref<F>) -> Self {
+        Self {
+            name,
+            token,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, F> OptionsChangeTokenSource<T> for FnChangeTokenSource<T, F>
+where
+    T: Value,
+    F: Fn() -> Box<dyn ChangeToken> + Value,
+{
+    fn token(&self) -> Box<dyn ChangeToken> {
+        (self.token)()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// Represents a source of change tokens which merges multiple sources of change tokens.
+///
+/// # Remarks
+///
+/// A change signaled by any of the merged sources is a change to the composite source, which
+/// allows one type of [`Options`](crate::Options) to react to several triggers, such as a file, a
+/// signal, and a manual reload, with a single registration.
+pub struct CompositeChangeTokenSource<T: Value> {
+    name: Option<String>,
+    sources: Vec<Ref<dyn OptionsChangeTokenSource<T>>>,
+}
+
+impl<T: Value> CompositeChangeTokenSource<T> {
+    /// Initializes a new composite change token source.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options being changed
+    pub fn new(name: Option<&str>) -> Self {
+        Self {
+            name: name.map(|n| n.to_owned()),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Merges the specified source of change tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The [source](OptionsChangeTokenSource) of change tokens to merge
+    pub fn with<S: OptionsChangeTokenSource<T> + 'static>(mut self, source: S) -> Self {
+        self.sources.push(Ref::new(source));
+        self
+    }
+}
+
+impl<T: Value> OptionsChangeTokenSource<T> for CompositeChangeTokenSource<T> {
+    fn token(&self) -> Box<dyn ChangeToken> {
+        Box::new(CompositeChangeToken::new(
+            self.sources.iter().map(|source| source.token()),
+        ))
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tokens::{SharedChangeToken, SingleChangeToken};
+
+    #[derive(Default)]
+    struct Config {
+        retries: u8,
+    }
+
+    #[derive(Clone, Default)]
+    struct Trigger(Arc<Mutex<SharedChangeToken<SingleChangeToken>>>);
+
+    impl Trigger {
+        fn token(&self) -> Box<dyn ChangeToken> {
+            Box::new(self.0.lock().unwrap().clone())
+        }
+
+        fn fire(&self) {
+            // a token only signals once so it's replaced before it's signaled
+            let token = std::mem::take(&mut *self.0.lock().unwrap());
+            token.notify()
+        }
+    }
+
+    #[test]
+    fn from_fn_should_create_named_source() {
+        // arrange
+        let trigger = Trigger::default();
+        let watched = trigger.clone();
+
+        // act
+        let source =
+            <dyn OptionsChangeTokenSource<Config>>::from_fn(Some("Test"), move || watched.token());
+
+        // assert
+        let token = source.token();
+
+        trigger.fire();
+
+        assert_eq!(source.name(), Some("Test"));
+        assert!(token.changed());
+    }
+
+    #[test]
+    fn composite_source_should_reload_when_any_source_changes() {
+        // arrange
+        let retries = Arc::new(AtomicU8::new(1));
+        let (first, second) = (Trigger::default(), Trigger::default());
+        let (a, b) = (first.clone(), second.clone());
+        let value = retries.clone();
+        let source = CompositeChangeTokenSource::new(None)
+            .with(<dyn OptionsChangeTokenSource<Config>>::from_fn(
+                None,
+                move || a.token(),
+            ))
+            .with(<dyn OptionsChangeTokenSource<Config>>::from_fn(
+                None,
+                move || b.token(),
+            ));
+        let provider = ServiceCollection::new()
+            .add_options::<Config>()
+            .configure(move |o| o.retries = value.load(Ordering::SeqCst))
+            .watch(source)
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let original = monitor.current_value().retries;
+
+        // act
+        retries.store(2, Ordering::SeqCst);
+        first.fire();
+
+        let changed = monitor.current_value().retries;
+
+        retries.store(3, Ordering::SeqCst);
+        second.fire();
+
+        // assert
+        assert_eq!((original, changed), (1, 2));
+        assert_eq!(monitor.current_value().retries, 3);
+    }
 }
@@ -0,0 +1,563 @@
+// lifecycle events and spans are emitted through 'tracing' when the 'tracing' feature is enabled and
+// compile away to nothing otherwise. every event and span uses the 'more_options' target and carries
+// the options type and name as the 'options' and 'name' fields, so they can be filtered, routed, and
+// correlated by any subscriber, including per type of options with a field filter.
+
+#![cfg_attr(not(feature = "tracing"), allow(clippy::extra_unused_type_parameters))]
+
+use crate::ValidateOptionsResult;
+use std::time::Duration;
+
+#[cfg(feature = "tracing")]
+use std::any::type_name;
+
+#[cfg(feature = "tracing")]
+const TARGET: &str = "more_options";
+
+// a span that is exited when dropped
+#[cfg(feature = "tracing")]
+pub(crate) struct Span(#[allow(dead_code)] tracing::span::EnteredSpan);
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Span;
+
+pub(crate) fn creating<T>(name: Option<&str>) -> Span {
+    #[cfg(feature = "tracing")]
+    let span = Span(
+        tracing::debug_span!(
+            target: TARGET,
+            "options.create",
+            options = type_name::<T>(),
+            name = name.unwrap_or_default()
+        )
+        .entered(),
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let span = {
+        let _ = name;
+        Span
+    };
+
+    span
+}
+
+pub(crate) fn changing<T>(name: Option<&str>, source: Option<&str>) -> Span {
+    #[cfg(feature = "tracing")]
+    let span = Span(
+        tracing::info_span!(
+            target: TARGET,
+            "options.change",
+            options = type_name::<T>(),
+            name = name.unwrap_or_default(),
+            source = source.unwrap_or_default()
+        )
+        .entered(),
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let span = {
+        let _ = (name, source);
+        Span
+    };
+
+    span
+}
+
+pub(crate) fn created<T>(
+    name: Option<&str>,
+    duration: Duration,
+    result: &Result<T, ValidateOptionsResult>,
+    sensitive: bool,
+) {
+    #[cfg(feature = "tracing")]
+    {
+        let options = type_name::<T>();
+        let name = name.unwrap_or_default();
+        let duration_ms = duration.as_secs_f64() * 1000.0;
+
+        match result {
+            Ok(_) => tracing::debug!(
+                target: TARGET,
+                options,
+                name,
+                duration_ms,
+                "options created"
+            ),
+            Err(failure) => {
+                // validation messages might include the values of sensitive options
+                let failure = if sensitive {
+                    crate::REDACTED.to_owned()
+                } else {
+                    failure.failure_message()
+                };
+
+                tracing::error!(
+                    target: TARGET,
+                    options,
+                    name,
+                    duration_ms,
+                    failure = failure.as_str(),
+                    "options validation failed"
+                )
+            }
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (name, duration, result, sensitive);
+}
+
+pub(crate) fn slow<T>(name: Option<&str>, duration: Duration, threshold: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: TARGET,
+        options = type_name::<T>(),
+        name = name.unwrap_or_default(),
+        duration_ms = duration.as_secs_f64() * 1000.0,
+        threshold_ms = threshold.as_secs_f64() * 1000.0,
+        "options creation was slow"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (name, duration, threshold);
+}
+
+#[cfg(feature = "debug")]
+pub(crate) fn dead_on_arrival<T>(location: &std::panic::Location<'_>) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: TARGET,
+        options = type_name::<T>(),
+        location = %location,
+        "options subscription dropped before it was notified"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = location;
+}
+
+pub(crate) fn invalidated<T>(name: Option<&str>) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: TARGET,
+        options = type_name::<T>(),
+        name = name.unwrap_or_default(),
+        "options cache invalidated"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = name;
+}
+
+pub(crate) fn cleared<T>() {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: TARGET,
+        options = type_name::<T>(),
+        "options cache cleared"
+    );
+}
+
+pub(crate) fn changed<T>(name: Option<&str>) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        target: TARGET,
+        options = type_name::<T>(),
+        name = name.unwrap_or_default(),
+        "options changed"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = name;
+}
+
+pub(crate) fn retrying<T>(name: Option<&str>, attempt: u32) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: TARGET,
+        options = type_name::<T>(),
+        name = name.unwrap_or_default(),
+        attempt,
+        "options reload failed; retrying"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (name, attempt);
+}
+
+pub(crate) fn unchanged<T>(name: Option<&str>) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: TARGET,
+        options = type_name::<T>(),
+        name = name.unwrap_or_default(),
+        "options change suppressed"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = name;
+}
+
+pub(crate) fn listener_timeout(
+    options: &str,
+    name: Option<&str>,
+    elapsed: Duration,
+    timeout: Duration,
+) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: TARGET,
+        options,
+        name = name.unwrap_or_default(),
+        elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+        timeout_ms = timeout.as_secs_f64() * 1000.0,
+        "options listener exceeded its timeout"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (options, name, elapsed, timeout);
+}
+
+#[cfg(all(feature = "diff", feature = "di"))]
+pub(crate) fn modified<T>(name: Option<&str>, changes: &[crate::FieldChange]) {
+    #[cfg(feature = "tracing")]
+    {
+        // only the paths are logged because the values might be secrets
+        let fields = changes
+            .iter()
+            .map(|change| change.path())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        tracing::info!(
+            target: TARGET,
+            options = type_name::<T>(),
+            name = name.unwrap_or_default(),
+            fields = fields.as_str(),
+            "options fields changed"
+        );
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (name, changes);
+}
+
+#[cfg(feature = "cfg")]
+pub(crate) fn deprecated<T>(name: Option<&str>, key: &str, replacement: Option<&str>) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: TARGET,
+        options = type_name::<T>(),
+        name = name.unwrap_or_default(),
+        key,
+        replacement = replacement.unwrap_or_default(),
+        "deprecated configuration key"
+    );
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = (name, key, replacement);
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+
+    use crate::*;
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Capture {
+        target: String,
+        message: String,
+        options: String,
+        name: String,
+        source: String,
+        failure: String,
+        span: String,
+    }
+
+    impl Visit for Capture {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            match field.name() {
+                "options" => self.options = value.to_owned(),
+                "name" => self.name = value.to_owned(),
+                "source" => self.source = value.to_owned(),
+                "failure" => self.failure = value.to_owned(),
+                _ => {}
+            }
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            if field.name() == "message" {
+                self.message = format!("{:?}", value);
+            }
+        }
+    }
+
+    // the subscriber is only the default of the thread running the test, which isolates
+    // concurrent tests
+    #[derive(Clone, Default)]
+    struct TestSubscriber {
+        next: Arc<AtomicU64>,
+        spans: Arc<Mutex<HashMap<u64, Capture>>>,
+        entered: Arc<Mutex<Vec<u64>>>,
+        events: Arc<Mutex<Vec<Capture>>>,
+    }
+
+    impl TestSubscriber {
+        fn events(&self, options: &str) -> Vec<Capture> {
+            self.events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.options.ends_with(options))
+                .cloned()
+                .collect()
+        }
+
+        fn messages(&self, options: &str) -> Vec<(String, String)> {
+            self.events(options)
+                .into_iter()
+                .map(|e| (e.message, e.name))
+                .collect()
+        }
+    }
+
+    impl Subscriber for TestSubscriber {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == "more_options"
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut capture = Capture {
+                target: span.metadata().target().to_owned(),
+                message: span.metadata().name().to_owned(),
+                ..Default::default()
+            };
+
+            span.record(&mut capture);
+            self.spans.lock().unwrap().insert(id, capture);
+            Id::from_u64(id)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut capture = Capture {
+                target: event.metadata().target().to_owned(),
+                ..Default::default()
+            };
+
+            event.record(&mut capture);
+
+            if let Some(id) = self.entered.lock().unwrap().last() {
+                capture.span = self.spans.lock().unwrap()[id].message.clone();
+            }
+
+            self.events.lock().unwrap().push(capture);
+        }
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _span: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+    }
+
+    fn traced<F: FnOnce()>(action: F) -> TestSubscriber {
+        let subscriber = TestSubscriber::default();
+        tracing::subscriber::with_default(subscriber.clone(), action);
+        subscriber
+    }
+
+    #[derive(Default)]
+    struct TracedOptions;
+
+    #[derive(Default)]
+    struct CredentialOptions {
+        password: String,
+    }
+
+    impl SensitiveOptions for CredentialOptions {}
+
+    struct WeakPassword;
+
+    impl ValidateOptions<CredentialOptions> for WeakPassword {
+        fn validate(&self, _: Option<&str>, options: &CredentialOptions) -> ValidateOptionsResult {
+            ValidateOptionsResult::fail(format!("'{}' is too weak", options.password))
+        }
+    }
+
+    #[test]
+    fn option_lifecycle_should_emit_events() {
+        // arrange
+        let cache = OptionsCache::<TracedOptions>::default();
+        let factory = DefaultOptionsFactory::<TracedOptions>::new(
+            Vec::default(),
+            Vec::default(),
+            Vec::default(),
+        );
+
+        // act
+        let subscriber = traced(|| {
+            cache.get_or_add(Some("Test"), &|n| factory.create(n).unwrap());
+            cache.try_remove(Some("Test"));
+        });
+
+        // assert
+        assert_eq!(
+            subscriber.messages("TracedOptions"),
+            vec![
+                ("options created".to_owned(), "Test".to_owned()),
+                ("options cache invalidated".to_owned(), "Test".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn creation_should_be_traced_in_span() {
+        // arrange
+        let factory = DefaultOptionsFactory::<TracedOptions>::new(
+            Vec::default(),
+            Vec::default(),
+            Vec::default(),
+        );
+
+        // act
+        let subscriber = traced(|| {
+            factory.create(Some("Test")).unwrap();
+        });
+
+        // assert
+        let spans: Vec<_> = subscriber.spans.lock().unwrap().values().cloned().collect();
+        let events = subscriber.events("TracedOptions");
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].message, "options.create");
+        assert_eq!(spans[0].name, "Test");
+        assert_eq!(events[0].span, "options.create");
+    }
+
+    #[test]
+    fn events_and_spans_should_use_the_same_target() {
+        // arrange
+        let token = tokens::SharedChangeToken::<tokens::SingleChangeToken>::default();
+        let source: Ref<dyn OptionsChangeTokenSource<TracedOptions>> =
+            Ref::new(TokenSource(token.clone()));
+        let factory: Ref<dyn OptionsFactory<TracedOptions>> =
+            Ref::new(DefaultOptionsFactory::<TracedOptions>::new(
+                Vec::default(),
+                Vec::default(),
+                Vec::default(),
+            ));
+        let monitor =
+            DefaultOptionsMonitor::new(Ref::new(OptionsCache::default()), vec![source], factory);
+
+        // act
+        let subscriber = traced(|| {
+            let _ = monitor.current_value();
+            token.notify();
+        });
+
+        // assert
+        let spans: Vec<_> = subscriber.spans.lock().unwrap().values().cloned().collect();
+        let changed: Vec<_> = subscriber
+            .events("TracedOptions")
+            .into_iter()
+            .filter(|e| e.message == "options changed")
+            .collect();
+
+        assert!(spans.iter().any(|s| s.message == "options.change"));
+        assert!(spans.iter().all(|s| s.target == "more_options"));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].span, "options.change");
+        assert!(subscriber
+            .events("TracedOptions")
+            .iter()
+            .all(|e| e.target == "more_options"));
+    }
+
+    struct TokenSource(tokens::SharedChangeToken<tokens::SingleChangeToken>);
+
+    impl OptionsChangeTokenSource<TracedOptions> for TokenSource {
+        fn token(&self) -> Box<dyn tokens::ChangeToken> {
+            Box::new(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn validation_failure_of_sensitive_options_should_be_masked() {
+        // arrange
+        let validation: Ref<dyn ValidateOptions<CredentialOptions>> = Ref::new(WeakPassword);
+        let factory = DefaultOptionsFactory::<CredentialOptions>::new(
+            Vec::default(),
+            Vec::default(),
+            vec![validation],
+        )
+        .sensitive();
+        let mut result = Ok(CredentialOptions::default());
+
+        // act
+        let subscriber = traced(|| {
+            result = factory.create_from(
+                None,
+                CredentialOptions {
+                    password: "hunter2".into(),
+                },
+            );
+        });
+
+        // assert
+        let failures: Vec<_> = subscriber
+            .events("CredentialOptions")
+            .into_iter()
+            .map(|e| (e.target, e.failure))
+            .collect();
+
+        assert!(result.is_err());
+        assert_eq!(
+            failures,
+            vec![("more_options".to_owned(), REDACTED.to_owned())]
+        );
+    }
+
+    #[test]
+    fn slow_creation_should_emit_event() {
+        // arrange
+        let configure: Ref<dyn ConfigureOptions<TracedOptions>> = Ref::new(configure(|_, _| {
+            std::thread::sleep(Duration::from_millis(5))
+        }));
+        let factory = DefaultOptionsFactory::<TracedOptions>::new(
+            vec![configure],
+            Vec::default(),
+            Vec::default(),
+        )
+        .with_slow_threshold(SlowOptionsThreshold::new(Duration::from_millis(1)));
+
+        // act
+        let subscriber = traced(|| {
+            factory.create(Some("Slow")).unwrap();
+        });
+
+        // assert
+        assert_eq!(
+            subscriber.messages("TracedOptions"),
+            vec![
+                ("options created".to_owned(), "Slow".to_owned()),
+                ("options creation was slow".to_owned(), "Slow".to_owned()),
+            ]
+        );
+    }
+}
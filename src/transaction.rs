@@ -0,0 +1,196 @@
+use crate::{Ref, ValidateOptionsResult};
+use std::sync::Mutex;
+
+// a participant is a monitored instance of options. reloading is split into phases so that every
+// participant is created before any of them is swapped and every participant is swapped before
+// any listener is notified.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub(crate) trait Participant {
+    fn is_alive(&self) -> bool;
+
+    // returns nothing if the participant is no longer alive or its options did not change
+    fn prepare(&self) -> Result<Option<Box<dyn Staged>>, ValidateOptionsResult>;
+}
+
+pub(crate) trait Staged {
+    fn swap(&mut self);
+
+    fn notify(self: Box<Self>);
+}
+
+#[derive(Default)]
+struct TransactionState {
+    participants: Mutex<Vec<Ref<dyn Participant>>>,
+    commit: Mutex<()>,
+}
+
+/// Represents a transaction that reloads several configuration [`Options`](crate::Options)
+/// together.
+///
+/// # Remarks
+///
+/// Options that must agree with each other, such as the paths of a certificate and its key, can be
+/// enlisted in the same transaction. When the transaction is committed, the options of every
+/// participant are created before any of them are replaced. If any of them fail, none of them are
+/// replaced. Listeners are only notified after all of the options have been replaced, so a listener
+/// never observes the new options of one participant alongside the old options of another.
+///
+/// Enlisted options should not also be reloaded by a source of change tokens for the same change;
+/// instead, the transaction is committed when the change occurs.
+#[derive(Clone, Default)]
+pub struct ReloadTransaction(Ref<TransactionState>);
+
+impl ReloadTransaction {
+    /// Initializes a new reload transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reloads the options of every participant in the transaction.
+    ///
+    /// # Remarks
+    ///
+    /// The failures of every participant that cannot be created are returned and the current
+    /// options of all the participants are kept. Participants whose options suppress unchanged
+    /// values are not replaced nor notified if their options are unchanged.
+    pub fn commit(&self) -> Result<(), ValidateOptionsResult> {
+        let commit = self.0.commit.lock().unwrap();
+
+        // the participants are copied so that options created during the commit can be enlisted
+        let participants = {
+            let mut participants = self.0.participants.lock().unwrap();
+            participants.retain(|p| p.is_alive());
+            participants.clone()
+        };
+        let mut staged = Vec::with_capacity(participants.len());
+        let mut failures = Vec::new();
+
+        for participant in &participants {
+            match participant.prepare() {
+                Ok(Some(options)) => staged.push(options),
+                Ok(None) => {}
+                Err(error) => failures.extend_from_slice(error.failures()),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(ValidateOptionsResult::fail_many(failures.iter()));
+        }
+
+        for options in &mut staged {
+            options.swap();
+        }
+
+        // listeners are notified without the lock so that they can commit the transaction again
+        drop(commit);
+
+        for options in staged {
+            options.notify();
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn enlist(&self, participant: Ref<dyn Participant>) {
+        self.0.participants.lock().unwrap().push(participant);
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::cell::RefCell;
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct CertOptions {
+        path: String,
+    }
+
+    #[derive(Default)]
+    struct KeyOptions {
+        path: String,
+    }
+
+    thread_local! {
+        static KEY: RefCell<Option<Ref<dyn OptionsMonitor<KeyOptions>>>> =
+            const { RefCell::new(None) };
+    }
+
+    fn new_provider(
+        transaction: &ReloadTransaction,
+        version: &Arc<Mutex<String>>,
+    ) -> di::ServiceProvider {
+        let mut services = ServiceCollection::new();
+        let cert = version.clone();
+        let key = version.clone();
+
+        services
+            .add_options::<CertOptions>()
+            .configure(move |o| o.path = format!("cert-{}.pem", cert.lock().unwrap()))
+            .reload_in(transaction);
+        services
+            .add_options::<KeyOptions>()
+            .configure(move |o| o.path = format!("key-{}.pem", key.lock().unwrap()))
+            .validate(|o| !o.path.contains("bad"), "Invalid key.")
+            .reload_in(transaction);
+
+        services.build_provider().unwrap()
+    }
+
+    #[test]
+    fn commit_should_replace_every_participant_before_notifying_listeners() {
+        // arrange
+        let transaction = ReloadTransaction::new();
+        let version = Arc::new(Mutex::new(String::from("v1")));
+        let provider = new_provider(&transaction, &version);
+        let cert = provider.get_required::<dyn OptionsMonitor<CertOptions>>();
+        let key = provider.get_required::<dyn OptionsMonitor<KeyOptions>>();
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let paths = observed.clone();
+        let _subscription = cert.on_change(Box::new(move |_, cert| {
+            let key = KEY.with(|k| k.borrow().as_ref().unwrap().current_value());
+            paths
+                .lock()
+                .unwrap()
+                .push((cert.path.clone(), key.path.clone()));
+        }));
+
+        KEY.with(|k| *k.borrow_mut() = Some(key.clone()));
+        *version.lock().unwrap() = "v2".into();
+
+        // act
+        transaction.commit().unwrap();
+
+        // assert
+        assert_eq!(
+            *observed.lock().unwrap(),
+            [("cert-v2.pem".to_owned(), "key-v2.pem".to_owned())]
+        );
+        KEY.with(|k| k.borrow_mut().take());
+    }
+
+    #[test]
+    fn commit_should_keep_every_participant_when_any_fails() {
+        // arrange
+        let transaction = ReloadTransaction::new();
+        let version = Arc::new(Mutex::new(String::from("v1")));
+        let provider = new_provider(&transaction, &version);
+        let cert = provider.get_required::<dyn OptionsMonitor<CertOptions>>();
+        let key = provider.get_required::<dyn OptionsMonitor<KeyOptions>>();
+        let original = (cert.current_value(), key.current_value());
+
+        *version.lock().unwrap() = "bad".into();
+
+        // act
+        let result = transaction.commit();
+
+        // assert
+        assert_eq!(result.unwrap_err().failures(), ["Invalid key."]);
+        assert!(Ref::ptr_eq(&original.0, &cert.current_value()));
+        assert!(Ref::ptr_eq(&original.1, &key.current_value()));
+    }
+}
@@ -90,6 +90,7 @@ impl Display for ValidateOptionsResult {
 }
 
 /// Defines the behavior of an object that validates configuration options.
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
 pub trait ValidateOptions<T> {
     /// Validates named options or all options if no name is specified.
     ///
@@ -98,6 +99,34 @@ pub trait ValidateOptions<T> {
     /// * `name` - The optional name of the options to validate
     /// * `options` - The options to validate
     fn validate(&self, name: Option<&str>, options: &T) -> ValidateOptionsResult;
+
+    /// Gets a value indicating whether the validation applies to every name.
+    ///
+    /// # Remarks
+    ///
+    /// A validation that applies to every name is only applied to the first layer of
+    /// [inherited](crate::OptionsInheritance) options so that the layer of the parent is not
+    /// overwritten. The default implementation returns `false`.
+    fn applies_to_every_name(&self) -> bool {
+        false
+    }
+}
+
+/// Defines the behavior of a reusable rule used to validate configuration options.
+///
+/// # Remarks
+///
+/// Unlike [`ValidateOptions`], a rule is independent of the name of the options, which allows it to
+/// be shared between types and names of options. A rule is registered using
+/// [`validate_with_rule`](crate::OptionsBuilder::validate_with_rule).
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait ValidationRule<T> {
+    /// Checks the options against the rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to check
+    fn check(&self, options: &T) -> ValidateOptionsResult;
 }
 
 #[cfg(test)]
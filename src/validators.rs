@@ -0,0 +1,197 @@
+use crate::{ValidateOptionsResult, ValidationRule, Value};
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+
+struct FieldRule<T, F> {
+    check: F,
+    _options: PhantomData<T>,
+}
+
+impl<T, F> ValidationRule<T> for FieldRule<T, F>
+where
+    T: Value,
+    F: Fn(&T) -> Option<String> + Value,
+{
+    fn check(&self, options: &T) -> ValidateOptionsResult {
+        match (self.check)(options) {
+            Some(failure) => ValidateOptionsResult::fail(failure),
+            _ => ValidateOptionsResult::success(),
+        }
+    }
+}
+
+fn field_rule<T, F>(check: F) -> impl ValidationRule<T>
+where
+    T: Value,
+    F: Fn(&T) -> Option<String> + Value,
+{
+    FieldRule {
+        check,
+        _options: PhantomData,
+    }
+}
+
+/// Creates a rule that requires a field to be an absolute URL.
+///
+/// # Arguments
+///
+/// * `field` - The name of the field used in the failure message
+/// * `value` - The function used to get the value of the field
+///
+/// # Remarks
+///
+/// An absolute URL has a scheme, such as `https`, followed by `://` and a host. Whitespace is not
+/// allowed anywhere in the URL.
+pub fn url<T, F>(field: &str, value: F) -> impl ValidationRule<T>
+where
+    T: Value,
+    F: Fn(&T) -> &str + Value,
+{
+    let field = field.to_owned();
+
+    field_rule(move |options: &T| {
+        if is_absolute_url(value(options)) {
+            None
+        } else {
+            Some(format!("'{}' must be an absolute URL.", field))
+        }
+    })
+}
+
+/// Creates a rule that requires a field to be a socket address, such as `127.0.0.1:8080`.
+///
+/// # Arguments
+///
+/// * `field` - The name of the field used in the failure message
+/// * `value` - The function used to get the value of the field
+///
+/// # Remarks
+///
+/// The address must be an IP address and a port; host names are not resolved.
+pub fn socket_addr<T, F>(field: &str, value: F) -> impl ValidationRule<T>
+where
+    T: Value,
+    F: Fn(&T) -> &str + Value,
+{
+    let field = field.to_owned();
+
+    field_rule(
+        move |options: &T| match value(options).parse::<SocketAddr>() {
+            Ok(_) => None,
+            _ => Some(format!(
+                "'{}' must be a socket address, such as 127.0.0.1:8080.",
+                field
+            )),
+        },
+    )
+}
+
+/// Creates a rule that requires a field to be a port within a range.
+///
+/// # Arguments
+///
+/// * `field` - The name of the field used in the failure message
+/// * `value` - The function used to get the value of the field
+/// * `range` - The inclusive range of allowed ports
+pub fn port_range<T, F>(field: &str, value: F, range: RangeInclusive<u16>) -> impl ValidationRule<T>
+where
+    T: Value,
+    F: Fn(&T) -> u16 + Value,
+{
+    let field = field.to_owned();
+
+    field_rule(move |options: &T| {
+        if range.contains(&value(options)) {
+            None
+        } else {
+            Some(format!(
+                "'{}' must be a port between {} and {}.",
+                field,
+                range.start(),
+                range.end()
+            ))
+        }
+    })
+}
+
+fn is_absolute_url(url: &str) -> bool {
+    let (scheme, rest) = match url.split_once("://") {
+        Some(parts) => parts,
+        _ => return false,
+    };
+    let host = rest.split(['/', '?', '#']).next();
+    let mut chars = scheme.chars();
+
+    chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic())
+        .unwrap_or_default()
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        && host.map(|h| !h.is_empty()).unwrap_or_default()
+        && !url.chars().any(char::is_whitespace)
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsFactory};
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct ServerOptions {
+        endpoint: String,
+        bind: String,
+        port: u16,
+    }
+
+    #[test]
+    fn is_absolute_url_should_require_scheme_and_host() {
+        // arrange
+        let urls = [
+            "https://contoso.com/api?v=1",
+            "amqp+ssl://broker:5671",
+            "contoso.com",
+            "https:///api",
+            "1http://contoso.com",
+            "https://contoso .com",
+        ];
+
+        // act
+        let valid: Vec<_> = urls.iter().map(|u| is_absolute_url(u)).collect();
+
+        // assert
+        assert_eq!(valid, [true, true, false, false, false, false]);
+    }
+
+    #[test]
+    fn validate_with_rule_should_report_every_failed_rule() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_options::<ServerOptions>()
+            .configure(|o| {
+                o.endpoint = "contoso.com".into();
+                o.bind = "0.0.0.0:8080".into();
+                o.port = 80;
+            })
+            .validate_with_rule(url("endpoint", |o: &ServerOptions| &o.endpoint))
+            .validate_with_rule(socket_addr("bind", |o: &ServerOptions| &o.bind))
+            .validate_with_rule(port_range("port", |o: &ServerOptions| o.port, 1024..=65535))
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<dyn OptionsFactory<ServerOptions>>();
+
+        // act
+        let result = factory.create(None);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failures(),
+            [
+                "'endpoint' must be an absolute URL.",
+                "'port' must be a port between 1024 and 65535.",
+            ]
+        );
+    }
+}
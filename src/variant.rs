@@ -0,0 +1,299 @@
+use crate::{OptionsMonitor, Ref, Value};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+impl<T: Value + 'static> dyn OptionsMonitor<T> {
+    /// Creates a resolver that selects among named variants of the monitored
+    /// [`Options`](crate::Options).
+    ///
+    /// # Arguments
+    ///
+    /// * `variants` - The [variants](Variant) to select from
+    pub fn variants<I>(self: Ref<Self>, variants: I) -> VariantOptionsResolver<T>
+    where
+        I: IntoIterator<Item = Variant>,
+    {
+        VariantOptionsResolver::new(self, variants)
+    }
+}
+
+/// Represents a named instance of configuration [`Options`](crate::Options) allocated to a
+/// percentage of requests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    name: String,
+    percentage: u8,
+}
+
+impl Variant {
+    /// Initializes a new variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the options used by the variant
+    /// * `percentage` - The percentage, from 0 to 100, allocated to the variant
+    pub fn new<S: AsRef<str>>(name: S, percentage: u8) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            percentage: percentage.min(100),
+        }
+    }
+
+    /// Gets the name of the options used by the variant.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the percentage allocated to the variant.
+    pub fn percentage(&self) -> u8 {
+        self.percentage
+    }
+}
+
+/// Defines the behavior of a strategy used to allocate requests to a [`Variant`].
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait VariantAllocation {
+    /// Allocates a request to a percentile from 0 to 99.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The optional key identifying the context of the request, such as a user
+    fn allocate(&self, context: Option<&str>) -> u8;
+}
+
+/// Represents a [`VariantAllocation`] that allocates each request at random.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PercentageAllocation;
+
+impl VariantAllocation for PercentageAllocation {
+    fn allocate(&self, _context: Option<&str>) -> u8 {
+        // each state is seeded with different keys, which is sufficient for allocation, but is not
+        // suitable for anything that requires a cryptographically secure random number
+        (RandomState::new().build_hasher().finish() % 100) as u8
+    }
+}
+
+/// Represents a [`VariantAllocation`] that always allocates the same context to the same variant.
+///
+/// # Remarks
+///
+/// The key of the context is hashed with a stable algorithm, so a context is allocated to the same
+/// variant across processes as long as the variants do not change. A request without a context is
+/// allocated at random.
+#[derive(Clone, Debug, Default)]
+pub struct StickyAllocation {
+    salt: String,
+}
+
+impl StickyAllocation {
+    /// Initializes a new sticky allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `salt` - The value combined with each context so that independent rollouts allocate the
+    ///   same context differently
+    pub fn new<S: AsRef<str>>(salt: S) -> Self {
+        Self {
+            salt: salt.as_ref().to_owned(),
+        }
+    }
+}
+
+impl VariantAllocation for StickyAllocation {
+    fn allocate(&self, context: Option<&str>) -> u8 {
+        match context {
+            Some(context) => (fnv1a(&[&self.salt, context]) % 100) as u8,
+            _ => PercentageAllocation.allocate(None),
+        }
+    }
+}
+
+// the std hasher is not guaranteed to be stable between releases
+fn fnv1a(segments: &[&str]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+    for byte in segments.iter().flat_map(|s| s.bytes().chain(Some(0))) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Represents a resolver that selects among named variants of configuration
+/// [`Options`](crate::Options).
+///
+/// # Remarks
+///
+/// A resolver is created using the `variants` method of an
+/// [`OptionsMonitor`](crate::OptionsMonitor). Each request is allocated to a percentile and the
+/// variants are assigned consecutive ranges of percentiles in the order they are specified.
+/// Percentiles that are not assigned to any variant resolve the options with the default name,
+/// which makes it possible to gradually roll out a configuration change by increasing the
+/// percentage of a single variant.
+pub struct VariantOptionsResolver<T: Value> {
+    monitor: Ref<dyn OptionsMonitor<T>>,
+    variants: Vec<Variant>,
+    allocation: Box<dyn VariantAllocation>,
+}
+
+impl<T: Value + 'static> VariantOptionsResolver<T> {
+    /// Initializes a new variant options resolver.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - The [monitor](crate::OptionsMonitor) used to resolve the named options
+    /// * `variants` - The [variants](Variant) to select from
+    ///
+    /// # Remarks
+    ///
+    /// Requests are allocated using a [`StickyAllocation`] by default.
+    pub fn new<I>(monitor: Ref<dyn OptionsMonitor<T>>, variants: I) -> Self
+    where
+        I: IntoIterator<Item = Variant>,
+    {
+        Self {
+            monitor,
+            variants: variants.into_iter().collect(),
+            allocation: Box::new(StickyAllocation::default()),
+        }
+    }
+
+    /// Sets the strategy used to allocate requests to variants.
+    ///
+    /// # Arguments
+    ///
+    /// * `allocation` - The [allocation strategy](VariantAllocation) to use
+    pub fn with_allocation<A: VariantAllocation + 'static>(mut self, allocation: A) -> Self {
+        self.allocation = Box::new(allocation);
+        self
+    }
+
+    /// Gets the variants to select from.
+    pub fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+
+    /// Gets the name of the options allocated to a request, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The optional key identifying the context of the request, such as a user
+    pub fn variant(&self, context: Option<&str>) -> Option<&str> {
+        let percentile = self.allocation.allocate(context).min(99) as u32;
+        let mut upper = 0u32;
+
+        for variant in &self.variants {
+            upper += variant.percentage as u32;
+
+            if percentile < upper {
+                return Some(&variant.name);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the options allocated to a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The optional key identifying the context of the request, such as a user
+    pub fn get(&self, context: Option<&str>) -> Ref<T> {
+        self.monitor.get(self.variant(context))
+    }
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::ServiceCollection;
+
+    #[derive(Default)]
+    struct CheckoutOptions {
+        layout: String,
+    }
+
+    struct Fixed(u8);
+
+    impl VariantAllocation for Fixed {
+        fn allocate(&self, _context: Option<&str>) -> u8 {
+            self.0
+        }
+    }
+
+    fn new_monitor() -> Ref<dyn OptionsMonitor<CheckoutOptions>> {
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<CheckoutOptions>()
+            .configure(|o| o.layout = "classic".into());
+        services
+            .add_named_options::<CheckoutOptions>("treatment")
+            .configure(|o| o.layout = "modern".into());
+
+        services
+            .build_provider()
+            .unwrap()
+            .get_required::<dyn OptionsMonitor<CheckoutOptions>>()
+    }
+
+    #[test]
+    fn get_should_resolve_options_for_allocated_variant() {
+        // arrange
+        let variants = || [Variant::new("control", 50), Variant::new("treatment", 20)];
+        let monitor = new_monitor();
+
+        // act
+        let names = [0, 49, 50, 69, 70, 99].map(|percentile| {
+            VariantOptionsResolver::new(monitor.clone(), variants())
+                .with_allocation(Fixed(percentile))
+                .variant(None)
+                .map(|n| n.to_owned())
+        });
+        let treatment = monitor
+            .clone()
+            .variants(variants())
+            .with_allocation(Fixed(60))
+            .get(None);
+        let unallocated = monitor
+            .variants(variants())
+            .with_allocation(Fixed(90))
+            .get(None);
+
+        // assert
+        assert_eq!(
+            names,
+            [
+                Some("control".to_owned()),
+                Some("control".to_owned()),
+                Some("treatment".to_owned()),
+                Some("treatment".to_owned()),
+                None,
+                None,
+            ]
+        );
+        assert_eq!(&treatment.layout, "modern");
+        assert_eq!(&unallocated.layout, "classic");
+    }
+
+    #[test]
+    fn sticky_allocation_should_allocate_context_consistently() {
+        // arrange
+        let allocation = StickyAllocation::new("checkout");
+        let users: Vec<_> = (0..1000).map(|i| format!("user{}", i)).collect();
+
+        // act
+        let first: Vec<_> = users.iter().map(|u| allocation.allocate(Some(u))).collect();
+        let second: Vec<_> = users.iter().map(|u| allocation.allocate(Some(u))).collect();
+
+        // assert
+        let allocated = first.iter().filter(|p| **p < 10).count();
+
+        assert_eq!(first, second);
+        assert!(first.iter().all(|p| *p < 100));
+        assert!((50..150).contains(&allocated));
+    }
+}
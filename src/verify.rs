@@ -0,0 +1,319 @@
+use crate::{OptionsNameComparer, ValidateOptionsResult};
+use di::{Ref, ServiceCollection, ServiceDescriptor, ServiceProvider, Type};
+use std::collections::HashMap;
+
+/// Represents the registration of configuration [`Options`](crate::Options) in a
+/// [collection of services](di::ServiceCollection).
+///
+/// # Remarks
+///
+/// Registrations are recorded by the [`OptionsBuilder`](crate::OptionsBuilder) and the
+/// configuration extensions so that the registered options can be verified before a
+/// [`ServiceProvider`](di::ServiceProvider) is built.
+#[derive(Clone, Debug)]
+pub struct OptionsRegistration {
+    options_type: Type,
+    name: Option<String>,
+    dependencies: Vec<Type>,
+    binding: Option<OptionsBinding>,
+    role: OptionsRole,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum OptionsRole {
+    Options,
+    Configure,
+    PostConfigure,
+    Validate,
+    Factory,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct OptionsBinding {
+    source: usize,
+    key: String,
+}
+
+impl OptionsRegistration {
+    /// Initializes a new options registration.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the registered options
+    pub fn new<T: ?Sized + 'static>(name: Option<&str>) -> Self {
+        Self {
+            options_type: Type::of::<T>(),
+            name: name.map(|s| s.to_owned()),
+            dependencies: Vec::new(),
+            binding: None,
+            role: OptionsRole::Options,
+        }
+    }
+
+    /// Adds a service dependency required by the registration.
+    ///
+    /// # Arguments
+    ///
+    /// * `dependency` - The [type](di::Type) of the required service
+    pub fn depends_on(mut self, dependency: Type) -> Self {
+        if !self.dependencies.contains(&dependency) {
+            self.dependencies.push(dependency);
+        }
+        self
+    }
+
+    #[cfg(feature = "cfg")]
+    pub(crate) fn bound_to(mut self, source: usize, key: &str) -> Self {
+        self.binding = Some(OptionsBinding {
+            source,
+            key: key.to_owned(),
+        });
+        self
+    }
+
+    pub(crate) fn for_role(mut self, role: OptionsRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    pub(crate) fn role(&self) -> OptionsRole {
+        self.role
+    }
+
+    /// Gets the [type](di::Type) of the registered options.
+    pub fn options_type(&self) -> &Type {
+        &self.options_type
+    }
+
+    /// Gets the name of the registered options, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the service dependencies required by the registration.
+    pub fn dependencies(&self) -> &[Type] {
+        &self.dependencies
+    }
+
+    /// Gets the configuration key the registered options are bound to, if any.
+    pub fn binding_key(&self) -> Option<&str> {
+        self.binding.as_ref().map(|b| b.key.as_str())
+    }
+}
+
+// records are registered as existing instances, which can be read without building a provider
+pub(crate) fn instance_of<T: 'static>(descriptor: &ServiceDescriptor) -> Option<Ref<T>> {
+    if descriptor.service_type() == Type::of::<T>() {
+        descriptor
+            .get(&ServiceProvider::default())
+            .downcast_ref::<Ref<T>>()
+            .cloned()
+    } else {
+        None
+    }
+}
+
+fn registration_of(descriptor: &ServiceDescriptor) -> Option<Ref<OptionsRegistration>> {
+    instance_of::<OptionsRegistration>(descriptor)
+}
+
+pub(crate) fn registrations(services: &ServiceCollection) -> Vec<Ref<OptionsRegistration>> {
+    services.iter().filter_map(registration_of).collect()
+}
+
+pub(crate) fn remove_registrations<T: 'static>(
+    services: &mut ServiceCollection,
+    roles: &[OptionsRole],
+) {
+    let options_type = Type::of::<T>();
+
+    for i in (0..services.len()).rev() {
+        let matched = registration_of(&services[i])
+            .map(|r| r.options_type() == options_type && roles.contains(&r.role()))
+            .unwrap_or_default();
+
+        if matched {
+            services.remove(i);
+        }
+    }
+}
+
+fn describe(registration: &OptionsRegistration) -> String {
+    match registration.name() {
+        Some(name) => format!("'{}' with the name '{}'", registration.options_type(), name),
+        _ => format!("'{}'", registration.options_type()),
+    }
+}
+
+/// Verifies the configuration [`Options`](crate::Options) registered in a [collection of services](di::ServiceCollection).
+///
+/// # Arguments
+///
+/// * `services` - The [collection of services](di::ServiceCollection) to verify
+///
+/// # Remarks
+///
+/// Verification reports every service dependency required to configure or validate options that
+/// has not been registered, options that are bound to more than one configuration, and named options
+/// that are created by more than one factory.
+pub fn verify_options(services: &ServiceCollection) -> Result<(), ValidateOptionsResult> {
+    let comparer = OptionsNameComparer::default();
+    let mut failures = Vec::new();
+    let mut bindings = HashMap::<(Type, String), Vec<OptionsBinding>>::new();
+    let mut factories = HashMap::<(Type, String), usize>::new();
+
+    for registration in registrations(services) {
+        let key = || {
+            let name = comparer.normalize(registration.name().unwrap_or_default());
+            (registration.options_type().clone(), name.into_owned())
+        };
+
+        for dependency in registration.dependencies() {
+            if !services.iter().any(|d| d.service_type() == dependency) {
+                failures.push(format!(
+                    "Options {} require the service '{}', which has not been registered.",
+                    describe(&registration),
+                    dependency
+                ));
+            }
+        }
+
+        if let Some(binding) = &registration.binding {
+            let existing = bindings.entry(key()).or_default();
+
+            if existing.is_empty() {
+                existing.push(binding.clone());
+            } else if !existing.contains(binding) {
+                failures.push(format!(
+                    "Options {} are bound to more than one configuration.",
+                    describe(&registration)
+                ));
+                existing.push(binding.clone());
+            }
+        }
+
+        if registration.role() == OptionsRole::Factory {
+            let count = factories.entry(key()).or_default();
+
+            *count += 1;
+
+            if *count == 2 {
+                failures.push(format!(
+                    "Options {} are created by more than one factory.",
+                    describe(&registration)
+                ));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidateOptionsResult::fail_many(failures.iter()))
+    }
+}
+
+/// Verifies the configuration [`Options`](crate::Options) registered in a [collection of services](di::ServiceCollection)
+/// and then builds a [`ServiceProvider`](di::ServiceProvider).
+///
+/// # Arguments
+///
+/// * `services` - The [collection of services](di::ServiceCollection) to build a provider from
+///
+/// # Remarks
+///
+/// Registrations that conflict with one another are otherwise resolved by the order in which they
+/// were registered without any indication. Every failure reported by [`verify_options`] and by the
+/// validation of the collection of services prevents the provider from being built.
+pub fn build_verified_provider(
+    services: &ServiceCollection,
+) -> Result<ServiceProvider, ValidateOptionsResult> {
+    verify_options(services)?;
+    services
+        .build_provider()
+        .map_err(|error| ValidateOptionsResult::fail(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::existing_as_self;
+
+    #[derive(Default)]
+    struct TestOptions {
+        setting: usize,
+    }
+
+    #[derive(Default)]
+    struct TestService;
+
+    #[test]
+    fn verify_options_should_succeed_when_dependencies_are_registered() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_options::<TestOptions>()
+            .configure1(|o, _: Ref<TestService>| o.setting = 1)
+            .add(existing_as_self(TestService));
+
+        // act
+        let result = verify_options(&services);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn verify_options_should_report_missing_dependency() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_options::<TestOptions>("Test")
+            .validate1(|o, _: Ref<TestService>| o.setting > 0, "");
+
+        // act
+        let result = verify_options(&services);
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().failure_message(),
+            format!(
+                "Options '{}' with the name 'Test' require the service '{}', which has not been registered.",
+                Type::of::<TestOptions>(),
+                Type::of::<TestService>()
+            )
+        );
+    }
+
+    #[test]
+    fn build_verified_provider_should_report_conflicting_factories() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let factory = |_: &ServiceProvider| {
+            let factory: Ref<dyn crate::OptionsFactory<TestOptions>> = Ref::new(
+                crate::DefaultOptionsFactory::new(Vec::default(), Vec::default(), Vec::default()),
+            );
+            factory
+        };
+
+        services.add_named_options_with("Test", factory);
+        services.add_named_options_with("test", factory);
+        services.add_named_options_with("Other", factory);
+
+        // act
+        let result = build_verified_provider(&services);
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().failure_message(),
+            format!(
+                "Options '{}' with the name 'test' are created by more than one factory.",
+                Type::of::<TestOptions>()
+            )
+        );
+    }
+}
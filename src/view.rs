@@ -0,0 +1,161 @@
+use config::{Configuration, ConfigurationIterator, ConfigurationPath, ConfigurationSection};
+use di::Ref;
+use std::borrow::Borrow;
+use std::ops::Deref;
+use tokens::{ChangeToken, NeverChangeToken};
+
+const DELIMITER: &str = ":";
+
+// a snapshot of the values of a configuration whose keys can be rewritten before it's bound
+#[derive(Clone)]
+pub(crate) struct ConfigurationView {
+    entries: Ref<Vec<(String, config::Value)>>,
+    key: String,
+    path: String,
+}
+
+pub(crate) fn flatten(configuration: &dyn Configuration) -> Vec<(String, config::Value)> {
+    configuration
+        .iter(Some(ConfigurationPath::Relative))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+impl ConfigurationView {
+    pub(crate) fn new(entries: Vec<(String, config::Value)>) -> Self {
+        Self {
+            entries: Ref::new(entries),
+            key: String::new(),
+            path: String::new(),
+        }
+    }
+
+    fn child(&self, key: &str) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            key: key.rsplit(DELIMITER).next().unwrap_or_default().to_owned(),
+            path: join(&self.path, key),
+        }
+    }
+}
+
+pub(crate) fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}{}{}", path, DELIMITER, key)
+    }
+}
+
+// configuration keys are case-insensitive
+pub(crate) fn is_under(key: &str, path: &str) -> bool {
+    path.is_empty()
+        || key.len() >= path.len()
+            && key.is_char_boundary(path.len())
+            && key[..path.len()].eq_ignore_ascii_case(path)
+            && (key.len() == path.len() || key[path.len()..].starts_with(DELIMITER))
+}
+
+impl Configuration for ConfigurationView {
+    fn get(&self, key: &str) -> Option<config::Value> {
+        let path = join(&self.path, key);
+
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&path))
+            .map(|(_, v)| v.clone())
+    }
+
+    fn section(&self, key: &str) -> Box<dyn ConfigurationSection> {
+        Box::new(self.child(key))
+    }
+
+    fn children(&self) -> Vec<Box<dyn ConfigurationSection>> {
+        let offset = if self.path.is_empty() {
+            0
+        } else {
+            self.path.len() + DELIMITER.len()
+        };
+        let mut keys: Vec<&str> = Vec::new();
+
+        for (key, _) in self.entries.iter() {
+            if key.len() <= self.path.len() || !is_under(key, &self.path) {
+                continue;
+            }
+
+            let child = key[offset..].split(DELIMITER).next().unwrap_or_default();
+
+            if !keys.iter().any(|k| k.eq_ignore_ascii_case(child)) {
+                keys.push(child);
+            }
+        }
+
+        keys.iter()
+            .map(|key| Box::new(self.child(key)) as Box<dyn ConfigurationSection>)
+            .collect()
+    }
+
+    fn reload_token(&self) -> Box<dyn ChangeToken> {
+        Box::new(NeverChangeToken::new())
+    }
+
+    fn as_section(&self) -> Option<&dyn ConfigurationSection> {
+        if self.path.is_empty() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    fn iter(
+        &self,
+        path: Option<ConfigurationPath>,
+    ) -> Box<dyn Iterator<Item = (String, config::Value)>> {
+        Box::new(ConfigurationIterator::new(
+            self,
+            path.unwrap_or(ConfigurationPath::Absolute),
+        ))
+    }
+}
+
+impl ConfigurationSection for ConfigurationView {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn value(&self) -> config::Value {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&self.path))
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default()
+    }
+
+    fn as_config(&self) -> Box<dyn Configuration> {
+        Box::new(self.clone())
+    }
+}
+
+impl<'a> AsRef<dyn Configuration + 'a> for ConfigurationView {
+    fn as_ref(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+impl<'a> Borrow<dyn Configuration + 'a> for ConfigurationView {
+    fn borrow(&self) -> &(dyn Configuration + 'a) {
+        self
+    }
+}
+
+impl Deref for ConfigurationView {
+    type Target = dyn Configuration;
+
+    fn deref(&self) -> &Self::Target {
+        self
+    }
+}
@@ -0,0 +1,186 @@
+use crate::verify::OptionsRole;
+use crate::{
+    OptionsManager, OptionsNameComparer, OptionsRegistration, OptionsService,
+    ValidateOptionsResult, Value,
+};
+use di::{ServiceProvider, Type};
+use std::collections::HashSet;
+
+type Warm = fn(&ServiceProvider, Option<&str>) -> Result<(), ValidateOptionsResult>;
+
+// a warmer is recorded for every type of options so that options can be created without knowing
+// their types. the same type may be recorded more than once.
+pub(crate) struct OptionsWarmer {
+    options_type: Type,
+    warm: Warm,
+}
+
+impl OptionsWarmer {
+    pub(crate) fn new<T: Value + 'static>() -> Self {
+        Self {
+            options_type: Type::of::<T>(),
+            warm: warm::<T>,
+        }
+    }
+}
+
+fn warm<T: Value + 'static>(
+    provider: &ServiceProvider,
+    name: Option<&str>,
+) -> Result<(), ValidateOptionsResult> {
+    // options and snapshots share the manager, but the monitor has its own cache
+    provider.get_required::<OptionsManager<T>>().try_get(name)?;
+    provider.get_required::<OptionsService<T>>().try_get(name)?;
+    Ok(())
+}
+
+/// Defines extension methods for the [`ServiceProvider`](di::ServiceProvider) struct.
+pub trait OptionsServiceProviderExtensions {
+    /// Creates the configuration options with the default name of every registered type.
+    ///
+    /// # Remarks
+    ///
+    /// The options are otherwise created the first time they are retrieved. Creating them when the
+    /// application starts moves the cost of creating them, and the discovery of any failures, to
+    /// startup. Every failure is returned rather than causing a panic.
+    fn warm_up_options(&self) -> Result<(), ValidateOptionsResult>;
+
+    /// Creates the configuration options with the default name and every registered name of every
+    /// registered type.
+    ///
+    /// # Remarks
+    ///
+    /// A name is registered when it is associated with the options, such as with
+    /// [`add_named_options`](crate::ext::OptionsServiceExtensions::add_named_options).
+    fn warm_up_named_options(&self) -> Result<(), ValidateOptionsResult>;
+}
+
+impl OptionsServiceProviderExtensions for ServiceProvider {
+    fn warm_up_options(&self) -> Result<(), ValidateOptionsResult> {
+        warm_up(self, false)
+    }
+
+    fn warm_up_named_options(&self) -> Result<(), ValidateOptionsResult> {
+        warm_up(self, true)
+    }
+}
+
+fn warm_up(provider: &ServiceProvider, named: bool) -> Result<(), ValidateOptionsResult> {
+    let registrations: Vec<_> = if named {
+        provider
+            .get_all::<OptionsRegistration>()
+            .filter(|r| r.role() == OptionsRole::Options && r.name().is_some())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let comparer = OptionsNameComparer::default();
+    let mut warmed = HashSet::new();
+    let mut failures = Vec::new();
+
+    for warmer in provider.get_all::<OptionsWarmer>() {
+        if !warmed.insert(warmer.options_type.clone()) {
+            continue;
+        }
+
+        let names = registrations
+            .iter()
+            .filter(|r| *r.options_type() == warmer.options_type)
+            .map(|r| r.name());
+        let mut seen = HashSet::new();
+
+        for name in std::iter::once(None).chain(names) {
+            if !seen.insert(name.map(|n| comparer.normalize(n).into_owned())) {
+                continue;
+            }
+
+            if let Err(error) = (warmer.warm)(provider, name) {
+                failures.extend(error.failures().iter().map(|failure| match name {
+                    Some(name) => format!(
+                        "Options '{}' with the name '{}' could not be created: {}",
+                        warmer.options_type, name, failure
+                    ),
+                    _ => format!(
+                        "Options '{}' could not be created: {}",
+                        warmer.options_type, failure
+                    ),
+                }));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidateOptionsResult::fail_many(failures.iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ext::*;
+    use di::ServiceCollection;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct PoolOptions {
+        size: usize,
+    }
+
+    #[test]
+    fn warm_up_options_should_create_options_before_they_are_requested() {
+        // arrange
+        let created = Arc::new(AtomicUsize::default());
+        let counter = created.clone();
+        let provider = ServiceCollection::new()
+            .add_options::<PoolOptions>()
+            .configure(move |o| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                o.size = 10;
+            })
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = provider.warm_up_options();
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            provider
+                .get_required::<dyn crate::Options<PoolOptions>>()
+                .value()
+                .size,
+            10
+        );
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn warm_up_named_options_should_report_every_failure() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add_named_options::<PoolOptions>("Primary")
+            .validate(|o| o.size > 0, "The size must be positive.")
+            .build_provider()
+            .unwrap();
+
+        // act
+        let unnamed = provider.warm_up_options();
+        let named = provider.warm_up_named_options();
+
+        // assert
+        assert!(unnamed.is_ok());
+        assert_eq!(
+            named.unwrap_err().failures(),
+            [format!(
+                "Options '{}' with the name 'Primary' could not be created: The size must be positive.",
+                Type::of::<PoolOptions>()
+            )]
+        );
+    }
+}
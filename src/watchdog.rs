@@ -0,0 +1,179 @@
+// listeners are watched by a single thread for the whole process. a listener is registered before
+// it's invoked and unregistered when it returns. the thread sleeps until the earliest deadline and
+// reports any listener that is still running, but it never interrupts the listener.
+
+use crate::{singleton::Singleton, trace};
+use std::mem::take;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub(crate) type TimeoutHandler = dyn Fn(Option<&str>, Duration) + Send + Sync;
+
+#[derive(Clone)]
+pub(crate) struct ListenerWatch {
+    timeout: Duration,
+    on_timeout: Arc<TimeoutHandler>,
+}
+
+impl ListenerWatch {
+    pub(crate) fn new(timeout: Duration, on_timeout: Arc<TimeoutHandler>) -> Self {
+        Self {
+            timeout,
+            on_timeout,
+        }
+    }
+
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub(crate) fn start(&self, options: &'static str, name: Option<&str>) -> WatchGuard {
+        let watchdog = watchdog();
+        let id = watchdog.next.fetch_add(1, Ordering::Relaxed);
+        let started = Instant::now();
+
+        watchdog.entries.lock().unwrap().push(Entry {
+            id,
+            started,
+            deadline: started + self.timeout,
+            timeout: self.timeout,
+            options,
+            name: name.map(|n| n.to_owned()),
+            on_timeout: self.on_timeout.clone(),
+        });
+        watchdog.wake.notify_one();
+        WatchGuard(id)
+    }
+}
+
+pub(crate) struct WatchGuard(u64);
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        // a listener that already exceeded its timeout was removed when it was reported
+        watchdog()
+            .entries
+            .lock()
+            .unwrap()
+            .retain(|e| e.id != self.0);
+    }
+}
+
+struct Entry {
+    id: u64,
+    started: Instant,
+    deadline: Instant,
+    timeout: Duration,
+    options: &'static str,
+    name: Option<String>,
+    on_timeout: Arc<TimeoutHandler>,
+}
+
+#[derive(Default)]
+struct Watchdog {
+    entries: Mutex<Vec<Entry>>,
+    next: AtomicU64,
+    wake: Condvar,
+}
+
+impl Watchdog {
+    fn run(&self) {
+        let mut entries = self.entries.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+            let (expired, pending): (Vec<_>, Vec<_>) = take(&mut *entries)
+                .into_iter()
+                .partition(|e| e.deadline <= now);
+
+            *entries = pending;
+
+            if !expired.is_empty() {
+                // handlers are invoked without the lock so that they can start other listeners
+                drop(entries);
+
+                for entry in expired {
+                    let elapsed = entry.started.elapsed();
+                    let name = entry.name.as_deref();
+
+                    trace::listener_timeout(entry.options, name, elapsed, entry.timeout);
+                    (entry.on_timeout)(name, elapsed);
+                }
+
+                entries = self.entries.lock().unwrap();
+                continue;
+            }
+
+            entries = match entries.iter().map(|e| e.deadline).min() {
+                Some(deadline) => {
+                    self.wake
+                        .wait_timeout(entries, deadline.saturating_duration_since(now))
+                        .unwrap()
+                        .0
+                }
+                _ => self.wake.wait(entries).unwrap(),
+            };
+        }
+    }
+}
+
+fn watchdog() -> &'static Watchdog {
+    static WATCHDOG: Singleton<Watchdog> = Singleton::new();
+    WATCHDOG.get_or_start(Watchdog::default, |watchdog| {
+        thread::spawn(move || watchdog.run());
+    })
+}
+
+#[cfg(all(test, feature = "di"))]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use std::sync::mpsc::channel;
+
+    #[derive(Default)]
+    struct Config;
+
+    #[test]
+    fn listener_timeout_should_report_listener_while_it_is_running() {
+        // arrange
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let provider = ServiceCollection::new()
+            .add_named_options::<Config>("Hung")
+            .listener_timeout(Duration::from_millis(10), move |name, elapsed| {
+                let _ = sender
+                    .lock()
+                    .unwrap()
+                    .send((name.map(|n| n.to_owned()), elapsed));
+            })
+            .build_provider()
+            .unwrap();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Config>>();
+        let reported = Arc::new(Mutex::new(None));
+        let report = reported.clone();
+        let receiver = Mutex::new(receiver);
+
+        // the listener only returns once it has been reported
+        let _subscription = monitor.on_change(Box::new(move |_, _| {
+            let timeout = receiver
+                .lock()
+                .unwrap()
+                .recv_timeout(Duration::from_secs(5))
+                .ok();
+            *report.lock().unwrap() = timeout;
+        }));
+
+        // act
+        monitor.reload(Some("Hung"));
+
+        // assert
+        let (name, elapsed) = reported.lock().unwrap().take().unwrap();
+        assert_eq!(name.as_deref(), Some("Hung"));
+        assert!(elapsed >= Duration::from_millis(10));
+    }
+}
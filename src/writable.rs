@@ -0,0 +1,475 @@
+use crate::{ConfigureOptions, OptionsFactory, ValidateOptions, ValidateOptionsResult, Value};
+use di::Ref;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs::{read_to_string, rename, write};
+use std::io::{ErrorKind, Result as IoResult};
+use std::mem::take;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokens::{SharedChangeToken, SingleChangeToken};
+
+/// Defines the behavior of a store that persists configuration [`Options`](crate::Options).
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait OptionsStore<T> {
+    /// Loads the persisted options, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to load
+    /// * `options` - The options to load into
+    ///
+    /// # Remarks
+    ///
+    /// The options are left unchanged if nothing has been persisted.
+    fn load(&self, name: Option<&str>, options: &mut T) -> IoResult<()>;
+
+    /// Persists the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to save
+    /// * `options` - The options to save
+    fn save(&self, name: Option<&str>, options: &T) -> IoResult<()>;
+}
+
+// the key of the options with the default name in a file store
+const DEFAULT_KEY: &str = "";
+
+fn key(name: Option<&str>) -> &str {
+    name.unwrap_or(DEFAULT_KEY)
+}
+
+fn read(path: &Path) -> IoResult<Option<String>> {
+    match read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+// the file is replaced so that a partially written file is never loaded
+fn replace(path: &Path, content: String) -> IoResult<()> {
+    let mut temp = path.to_path_buf().into_os_string();
+
+    temp.push(".tmp");
+    write(&temp, content)?;
+    rename(&temp, path)
+}
+
+/// Represents a [store](OptionsStore) that persists configuration [`Options`](crate::Options) to a
+/// JSON file.
+///
+/// # Remarks
+///
+/// The file contains an object with a member for each name of the options that have been saved,
+/// where the options with the default name are keyed by an empty string. The loaded options replace
+/// any values configured before the store is applied. Saving options preserves the options saved
+/// with any other name and replaces the file atomically so that a partially written file is never
+/// loaded.
+pub struct JsonFileOptionsStore {
+    path: PathBuf,
+}
+
+impl JsonFileOptionsStore {
+    /// Initializes a new JSON file options store.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the JSON file
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    fn document(&self) -> IoResult<serde_json::Map<String, serde_json::Value>> {
+        match read(&self.path)? {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            _ => Ok(Default::default()),
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> OptionsStore<T> for JsonFileOptionsStore {
+    fn load(&self, name: Option<&str>, options: &mut T) -> IoResult<()> {
+        if let Some(value) = self.document()?.remove(key(name)) {
+            *options = serde_json::from_value(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn save(&self, name: Option<&str>, options: &T) -> IoResult<()> {
+        let mut document = self.document()?;
+
+        document.insert(key(name).to_owned(), serde_json::to_value(options)?);
+        replace(&self.path, serde_json::to_string_pretty(&document)?)
+    }
+}
+
+/// Represents a [store](OptionsStore) that persists configuration [`Options`](crate::Options) to a
+/// TOML file.
+///
+/// # Remarks
+///
+/// The file contains a table for each name of the options that have been saved, where the options
+/// with the default name are keyed by an empty string. The loaded options replace any values
+/// configured before the store is applied. Saving options preserves the options saved with any
+/// other name and replaces the file atomically so that a partially written file is never loaded.
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+pub struct TomlFileOptionsStore {
+    path: PathBuf,
+}
+
+#[cfg(feature = "toml")]
+impl TomlFileOptionsStore {
+    /// Initializes a new TOML file options store.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the TOML file
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    fn document(&self) -> IoResult<toml::Table> {
+        match read(&self.path)? {
+            Some(content) => content.parse().map_err(invalid_data),
+            _ => Ok(Default::default()),
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> std::io::Error {
+    std::io::Error::new(ErrorKind::InvalidData, error)
+}
+
+#[cfg(feature = "toml")]
+impl<T: Serialize + DeserializeOwned> OptionsStore<T> for TomlFileOptionsStore {
+    fn load(&self, name: Option<&str>, options: &mut T) -> IoResult<()> {
+        if let Some(value) = self.document()?.remove(key(name)) {
+            *options = value.try_into().map_err(invalid_data)?;
+        }
+
+        Ok(())
+    }
+
+    fn save(&self, name: Option<&str>, options: &T) -> IoResult<()> {
+        let mut document = self.document()?;
+        let value = toml::Value::try_from(options).map_err(invalid_data)?;
+
+        document.insert(key(name).to_owned(), value);
+        replace(
+            &self.path,
+            toml::to_string_pretty(&document).map_err(invalid_data)?,
+        )
+    }
+}
+
+pub(crate) struct StoreConfigure<T>(Ref<dyn OptionsStore<T>>);
+
+impl<T> StoreConfigure<T> {
+    pub(crate) fn new(store: Ref<dyn OptionsStore<T>>) -> Self {
+        Self(store)
+    }
+}
+
+impl<T> ConfigureOptions<T> for StoreConfigure<T> {
+    fn configure(&self, name: Option<&str>, options: &mut T) {
+        let _ = self.try_configure(name, options);
+    }
+
+    fn try_configure(&self, name: Option<&str>, options: &mut T) -> Result<(), String> {
+        self.0
+            .load(name, options)
+            .map_err(|error| error.to_string())
+    }
+}
+
+/// Represents configuration [`Options`](crate::Options) that can be updated and persisted.
+///
+/// # Remarks
+///
+/// Writable options are registered using [`writable`](crate::OptionsBuilder::writable). Each update
+/// creates the options through the [`OptionsFactory`](crate::OptionsFactory), applies the
+/// mutation, validates the result, persists it to the [store](OptionsStore), and then reloads
+/// monitored options.
+pub struct WritableOptions<T: Value> {
+    factory: Ref<dyn OptionsFactory<T>>,
+    validations: Vec<Ref<dyn ValidateOptions<T>>>,
+    store: Ref<dyn OptionsStore<T>>,
+    token: Arc<Mutex<SharedChangeToken<SingleChangeToken>>>,
+    update: Mutex<()>,
+}
+
+impl<T: Value> WritableOptions<T> {
+    pub(crate) fn new(
+        factory: Ref<dyn OptionsFactory<T>>,
+        validations: Vec<Ref<dyn ValidateOptions<T>>>,
+        store: Ref<dyn OptionsStore<T>>,
+        token: Arc<Mutex<SharedChangeToken<SingleChangeToken>>>,
+    ) -> Self {
+        Self {
+            factory,
+            validations,
+            store,
+            token,
+            update: Mutex::new(()),
+        }
+    }
+
+    /// Updates and persists the options with the default name.
+    ///
+    /// # Arguments
+    ///
+    /// * `mutate` - The function used to change the options
+    pub fn update<F: FnOnce(&mut T)>(&self, mutate: F) -> Result<(), ValidateOptionsResult> {
+        self.update_named(None, mutate)
+    }
+
+    /// Updates and persists the options with the specified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The optional name of the options to update
+    /// * `mutate` - The function used to change the options
+    ///
+    /// # Remarks
+    ///
+    /// Nothing is persisted if the changed options are invalid or cannot be created.
+    pub fn update_named<F: FnOnce(&mut T)>(
+        &self,
+        name: Option<&str>,
+        mutate: F,
+    ) -> Result<(), ValidateOptionsResult> {
+        // concurrent updates are serialized so that no update is lost
+        let _update = self.update.lock().unwrap();
+        let mut options = self.factory.create(name)?;
+
+        mutate(&mut options);
+
+        let mut failures = Vec::new();
+
+        for validation in &self.validations {
+            let result = validation.validate(name, &options);
+
+            if result.failed() {
+                failures.extend_from_slice(result.failures())
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(ValidateOptionsResult::fail_many(failures.iter()));
+        }
+
+        self.store
+            .save(name, &options)
+            .map_err(|error| ValidateOptionsResult::fail(error.to_string()))?;
+
+        // a token only signals once so it's replaced before it's signaled
+        let token = take(&mut *self.token.lock().unwrap());
+        token.notify();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{ext::*, OptionsMonitor};
+    use di::ServiceCollection;
+    use serde::Deserialize;
+    use std::env::temp_dir;
+    use std::fs::remove_file;
+
+    #[derive(Default, Deserialize, Serialize)]
+    struct Settings {
+        theme: String,
+        page_size: u8,
+    }
+
+    fn new_provider(path: &Path) -> di::ServiceProvider {
+        ServiceCollection::new()
+            .add_options::<Settings>()
+            .configure(|o| o.page_size = 10)
+            .writable(JsonFileOptionsStore::new(path))
+            .validate(
+                |o| o.page_size > 0,
+                "The page size must be greater than zero.",
+            )
+            .build_provider()
+            .unwrap()
+    }
+
+    #[test]
+    fn update_should_persist_options_and_reload_monitor() {
+        // arrange
+        let path = temp_dir().join("options_writable_1.json");
+
+        remove_file(&path).ok();
+
+        let provider = new_provider(&path);
+        let writable = provider.get_required::<WritableOptions<Settings>>();
+        let monitor = provider.get_required::<dyn OptionsMonitor<Settings>>();
+        let original = monitor.current_value();
+
+        // act
+        let result = writable.update(|o| o.theme = "dark".into());
+
+        // assert
+        let current = monitor.current_value();
+        let persisted = read_to_string(&path).unwrap_or_default();
+        let reloaded = new_provider(&path)
+            .get_required::<dyn OptionsMonitor<Settings>>()
+            .current_value();
+
+        remove_file(&path).ok();
+
+        assert!(result.is_ok());
+        assert_eq!(&original.theme, "");
+        assert_eq!(&current.theme, "dark");
+        assert_eq!(current.page_size, 10);
+        assert!(persisted.contains("dark"));
+        assert_eq!(&reloaded.theme, "dark");
+    }
+
+    #[test]
+    fn update_should_not_persist_invalid_options() {
+        // arrange
+        let path = temp_dir().join("options_writable_2.json");
+
+        remove_file(&path).ok();
+
+        let provider = new_provider(&path);
+        let writable = provider.get_required::<WritableOptions<Settings>>();
+
+        // act
+        let result = writable.update(|o| o.page_size = 0);
+
+        // assert
+        assert_eq!(
+            result.err().map(|e| e.failure_message()),
+            Some("The page size must be greater than zero.".to_owned())
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn save_should_persist_options_by_name() {
+        // arrange
+        let path = temp_dir().join("options_writable_3.json");
+        let store = JsonFileOptionsStore::new(&path);
+        let mut light = Settings::default();
+        let mut dark = Settings::default();
+
+        remove_file(&path).ok();
+
+        // act
+        store
+            .save(
+                None,
+                &Settings {
+                    theme: "light".into(),
+                    page_size: 10,
+                },
+            )
+            .unwrap();
+        store
+            .save(
+                Some("Admin"),
+                &Settings {
+                    theme: "dark".into(),
+                    page_size: 50,
+                },
+            )
+            .unwrap();
+        store.load(None, &mut light).unwrap();
+        store.load(Some("Admin"), &mut dark).unwrap();
+
+        // assert
+        remove_file(&path).ok();
+        assert_eq!(&light.theme, "light");
+        assert_eq!(light.page_size, 10);
+        assert_eq!(&dark.theme, "dark");
+        assert_eq!(dark.page_size, 50);
+    }
+
+    #[test]
+    fn load_should_not_change_options_when_name_has_not_been_saved() {
+        // arrange
+        let path = temp_dir().join("options_writable_4.json");
+        let store = JsonFileOptionsStore::new(&path);
+        let mut options = Settings {
+            theme: "light".into(),
+            page_size: 10,
+        };
+
+        remove_file(&path).ok();
+        store
+            .save(
+                Some("Admin"),
+                &Settings {
+                    theme: "dark".into(),
+                    page_size: 50,
+                },
+            )
+            .unwrap();
+
+        // act
+        store.load(None, &mut options).unwrap();
+
+        // assert
+        remove_file(&path).ok();
+        assert_eq!(&options.theme, "light");
+        assert_eq!(options.page_size, 10);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_store_should_persist_options_by_name() {
+        // arrange
+        let path = temp_dir().join("options_writable_5.toml");
+        let store = TomlFileOptionsStore::new(&path);
+        let mut light = Settings::default();
+        let mut dark = Settings::default();
+
+        remove_file(&path).ok();
+
+        // act
+        store
+            .save(
+                None,
+                &Settings {
+                    theme: "light".into(),
+                    page_size: 10,
+                },
+            )
+            .unwrap();
+        store
+            .save(
+                Some("Admin"),
+                &Settings {
+                    theme: "dark".into(),
+                    page_size: 50,
+                },
+            )
+            .unwrap();
+        store.load(None, &mut light).unwrap();
+        store.load(Some("Admin"), &mut dark).unwrap();
+
+        // assert
+        let persisted = read_to_string(&path).unwrap_or_default();
+
+        remove_file(&path).ok();
+        assert!(persisted.contains("[Admin]"));
+        assert_eq!(&light.theme, "light");
+        assert_eq!(light.page_size, 10);
+        assert_eq!(&dark.theme, "dark");
+        assert_eq!(dark.page_size, 50);
+    }
+}